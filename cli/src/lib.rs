@@ -0,0 +1,833 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! `abstract-parser` — разбор файлов `.abs`-грамматикой из командной строки.
+
+use chars_parser::minimize::{minimize, Failure};
+use grammar_runtime::{
+    export::Syntax, import::Source, CompileError, GrammarRule, Node, RuntimeGrammar,
+};
+use parser::{
+    debug::{record, ParseEvent},
+    logs::TraceConfig,
+};
+use std::{
+    fmt::Write as _,
+    io::{self, Read, Write},
+    ops::Range,
+    str::FromStr,
+};
+
+pub const USAGE: &str = "\
+usage:
+    abstract-parser parse --grammar <file.abs> [--rule <Rule>] [--format json|sexpr|tree] [input]
+    abstract-parser trace --grammar <file.abs> [--rule <Rule>] [--filter <Rule1,Rule2@a..b>] [input]
+    abstract-parser check <file.abs>
+    abstract-parser fmt [--check] <file.abs>
+    abstract-parser railroad [--out <dir>] <file.abs>
+    abstract-parser export --format ebnf|abnf|pest <file.abs>
+    abstract-parser import --from pest|abnf [--macro] <file>
+    abstract-parser minimize --grammar <file.abs> [--rule <Rule>] [--failure error[:<text>]|panic[:<text>]|depth:<N>] [input]
+
+Без --rule берётся первое правило грамматики, без input (или с `-`) — stdin.
+fmt печатает отформатированную грамматику, с --check — только проверяет, что файл уже отформатирован.
+minimize сокращает вход, пока сбой разбора воспроизводится, и печатает остаток.
+railroad пишет в --out (по умолчанию `railroad`) SVG-диаграмму каждого правила и index.html.
+export печатает грамматику в синтаксисе ISO EBNF, ABNF (RFC 5234) или pest.
+import переводит грамматику pest или ABNF (RFC 5234) в .abs, с --macro — в вызов grammar!.";
+
+/// Вид вывода дерева разбора.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Sexpr,
+    #[default]
+    Tree,
+}
+
+impl FromStr for Format {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "sexpr" => Ok(Self::Sexpr),
+            "tree" => Ok(Self::Tree),
+            v => Err(CliError::Usage(format!("unknown format `{v}`"))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(String),
+}
+
+impl Input {
+    #[inline]
+    fn name(&self) -> &str {
+        match self {
+            Self::Stdin => "<stdin>",
+            Self::File(v) => v,
+        }
+    }
+
+    fn read(&self) -> Result<String, CliError> {
+        match self {
+            Self::Stdin => {
+                let mut v = String::new();
+                io::stdin()
+                    .read_to_string(&mut v)
+                    .map(|_| v)
+                    .map_err(|e| CliError::Failed(format!("<stdin>: {e}")))
+            }
+            Self::File(path) => read(path),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Parse {
+        grammar: String,
+        rule: Option<String>,
+        input: Input,
+        format: Format,
+    },
+    Trace {
+        grammar: String,
+        rule: Option<String>,
+        input: Input,
+        filter: TraceConfig,
+    },
+    Check {
+        grammar: String,
+    },
+    Fmt {
+        grammar: String,
+        /// Не печатать, а завершиться ошибкой, если файл не отформатирован.
+        check: bool,
+    },
+    Minimize {
+        grammar: String,
+        rule: Option<String>,
+        input: Input,
+        failure: Failure,
+    },
+    Railroad {
+        grammar: String,
+        /// Каталог для диаграмм, создаётся при необходимости.
+        out: String,
+    },
+    Export {
+        grammar: String,
+        syntax: Syntax,
+    },
+    Import {
+        /// Грамматика в синтаксисе `source`.
+        grammar: String,
+        source: Source,
+        /// Печатать вызов `grammar!`, а не текст `.abs`.
+        macro_: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliError {
+    /// Неверные аргументы; выводится вместе с [`USAGE`].
+    Usage(String),
+    /// Файл не читается, грамматика или вход не разбираются.
+    Failed(String),
+}
+
+impl CliError {
+    #[inline]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Usage(..) => 2,
+            Self::Failed(..) => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Usage(v) => write!(f, "error: {v}\n\n{USAGE}"),
+            Self::Failed(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl Command {
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut args = args.into_iter();
+        let name = args
+            .next()
+            .ok_or_else(|| CliError::Usage("missing subcommand".into()))?;
+
+        let (mut grammar, mut rule, mut format, mut filter, mut failure, mut out, mut from) =
+            (None, None, None, None, None, None, None);
+        let mut positional = vec![];
+        let (mut check, mut macro_) = (false, false);
+        while let Some(arg) = args.next() {
+            let slot = match arg.as_str() {
+                "--check" if name == "fmt" => {
+                    check = true;
+                    continue;
+                }
+                "--macro" if name == "import" => {
+                    macro_ = true;
+                    continue;
+                }
+                "-g" | "--grammar" => &mut grammar,
+                "-r" | "--rule" => &mut rule,
+                "-f" | "--format" => &mut format,
+                "--filter" => &mut filter,
+                "--failure" => &mut failure,
+                "-o" | "--out" if name == "railroad" => &mut out,
+                "--from" if name == "import" => &mut from,
+                v if v.starts_with("--") => {
+                    return Err(CliError::Usage(format!("unknown option `{v}`")))
+                }
+                _ => {
+                    positional.push(arg);
+                    continue;
+                }
+            };
+            *slot = Some(
+                args.next()
+                    .ok_or_else(|| CliError::Usage(format!("`{arg}` expects a value")))?,
+            );
+        }
+
+        let mut positional = positional.into_iter();
+        // у check, fmt, railroad, export и import грамматика — единственный аргумент
+        let grammar_only = matches!(
+            name.as_str(),
+            "check" | "fmt" | "railroad" | "export" | "import"
+        );
+        let grammar = match grammar.or_else(|| positional.next().filter(|_| grammar_only)) {
+            Some(v) => v,
+            None => return Err(CliError::Usage("missing grammar".into())),
+        };
+        let input = match positional.next() {
+            Some(v) if grammar_only => {
+                return Err(CliError::Usage(format!("unexpected argument `{v}`")))
+            }
+            Some(v) if v != "-" => Input::File(v),
+            _ => Input::Stdin,
+        };
+        if let Some(v) = positional.next() {
+            return Err(CliError::Usage(format!("unexpected argument `{v}`")));
+        }
+
+        Ok(match name.as_str() {
+            "parse" => Self::Parse {
+                grammar,
+                rule,
+                input,
+                format: format
+                    .as_deref()
+                    .map_or(Ok(Format::default()), str::parse)?,
+            },
+            "trace" => Self::Trace {
+                grammar,
+                rule,
+                input,
+                filter: filter
+                    .as_deref()
+                    .map_or(Ok(TraceConfig::all()), str::parse)
+                    .map_err(|e| CliError::Usage(e.0))?,
+            },
+            "check" => Self::Check { grammar },
+            "fmt" => Self::Fmt { grammar, check },
+            "minimize" => Self::Minimize {
+                grammar,
+                rule,
+                input,
+                failure: failure
+                    .as_deref()
+                    .map_or(Ok(Failure::Error(None)), parse_failure)?,
+            },
+            "railroad" => Self::Railroad {
+                grammar,
+                out: out.unwrap_or_else(|| "railroad".into()),
+            },
+            "export" => Self::Export {
+                grammar,
+                syntax: parse_syntax(
+                    format
+                        .as_deref()
+                        .ok_or_else(|| CliError::Usage("missing `--format`".into()))?,
+                )?,
+            },
+            "import" => Self::Import {
+                grammar,
+                source: parse_source(
+                    from.as_deref()
+                        .ok_or_else(|| CliError::Usage("missing `--from`".into()))?,
+                )?,
+                macro_,
+            },
+            v => return Err(CliError::Usage(format!("unknown subcommand `{v}`"))),
+        })
+    }
+
+    /// Выполняет команду; результат пишется в `out`, диагностика возвращается ошибкой.
+    pub fn run(&self, out: &mut impl Write) -> Result<(), CliError> {
+        let io = |e: io::Error| CliError::Failed(e.to_string());
+        match self {
+            Self::Parse {
+                grammar,
+                rule,
+                input,
+                format,
+            } => {
+                let (src, runtime) = load(grammar)?;
+                let rule = compile(grammar, &src, &runtime, rule.as_deref())?;
+                let text = input.read()?;
+                let node = rule.parse(&text).map_err(|e| {
+                    CliError::Failed(diagnostic(input.name(), &text, e.pos..e.pos, &e))
+                })?;
+                writeln!(out, "{}", render(&node, &text, *format)).map_err(io)
+            }
+            Self::Trace {
+                grammar,
+                rule,
+                input,
+                filter,
+            } => {
+                let (src, runtime) = load(grammar)?;
+                let rule = compile(grammar, &src, &runtime, rule.as_deref())?;
+                let text = input.read()?;
+                let (result, events) = record(|| rule.parse(&text));
+                write!(out, "{}", trace(&events, filter)).map_err(io)?;
+                result.map(|_| ()).map_err(|e| {
+                    CliError::Failed(diagnostic(input.name(), &text, e.pos..e.pos, &e))
+                })
+            }
+            Self::Check { grammar: path } => {
+                let (src, grammar) = load(path)?;
+                grammar.check().map_err(|errors| {
+                    CliError::Failed(
+                        errors
+                            .iter()
+                            .map(|e| compile_diagnostic(path, &src, e))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    )
+                })?;
+                writeln!(
+                    out,
+                    "{path}: ok, {} rule(s)",
+                    grammar.grammar().rules().count()
+                )
+                .map_err(io)
+            }
+            Self::Fmt {
+                grammar: path,
+                check,
+            } => {
+                let src = read(path)?;
+                let formatted = grammar_runtime::format(&src).map_err(|e| {
+                    CliError::Failed(diagnostic(path, &src, e.pos..e.pos, &e.message))
+                })?;
+                if !check {
+                    return write!(out, "{formatted}").map_err(io);
+                }
+                match first_difference(&src, &formatted) {
+                    None => writeln!(out, "{path}: ok").map_err(io),
+                    Some(pos) => Err(CliError::Failed(diagnostic(
+                        path,
+                        &src,
+                        pos..pos,
+                        &"file is not formatted, run `abstract-parser fmt`",
+                    ))),
+                }
+            }
+            Self::Minimize {
+                grammar,
+                rule,
+                input,
+                failure,
+            } => {
+                let (src, runtime) = load(grammar)?;
+                let rule = compile(grammar, &src, &runtime, rule.as_deref())?;
+                let text = input.read()?;
+                let minimal = minimize(
+                    &text,
+                    |src| {
+                        failure
+                            .check_parse(|| rule.parse(src).map(|_| ()).map_err(|e| e.to_string()))
+                    },
+                    |src| record(|| rule.parse(src)).1,
+                )
+                .ok_or_else(|| {
+                    CliError::Failed(format!("{}: the failure does not occur", input.name()))
+                })?;
+                write!(out, "{minimal}").map_err(io)
+            }
+            Self::Railroad {
+                grammar: path,
+                out: dir,
+            } => {
+                let (_, grammar) = load(path)?;
+                let title = std::path::Path::new(path)
+                    .file_name()
+                    .map_or(path.as_str(), |v| v.to_str().unwrap_or(path));
+                let files = grammar_runtime::railroad::railroad(grammar.grammar(), title);
+                let failed = |e: io::Error| CliError::Failed(format!("{dir}: {e}"));
+                std::fs::create_dir_all(dir).map_err(failed)?;
+                for (name, text) in &files {
+                    std::fs::write(std::path::Path::new(dir).join(name), text).map_err(failed)?;
+                }
+                writeln!(out, "{dir}: {} file(s)", files.len()).map_err(io)
+            }
+            Self::Export {
+                grammar: path,
+                syntax,
+            } => {
+                let (src, grammar) = load(path)?;
+                let text = grammar_runtime::export::export(&grammar, *syntax)
+                    .map_err(|e| CliError::Failed(compile_diagnostic(path, &src, &e)))?;
+                write!(out, "{text}").map_err(io)
+            }
+            Self::Import {
+                grammar: path,
+                source,
+                macro_,
+            } => {
+                let src = read(path)?;
+                let grammar = grammar_runtime::import::import(&src, *source).map_err(|e| {
+                    CliError::Failed(diagnostic(path, &src, e.pos..e.pos, &e.message))
+                })?;
+                let text = grammar_runtime::format(&grammar.to_string())
+                    .map_err(|e| CliError::Failed(format!("{path}: {e}")))?;
+                if *macro_ {
+                    write!(out, "{}", grammar_runtime::import::grammar_macro(&text)).map_err(io)
+                } else {
+                    write!(out, "{text}").map_err(io)
+                }
+            }
+        }
+    }
+}
+
+fn parse_syntax(v: &str) -> Result<Syntax, CliError> {
+    match v {
+        "ebnf" => Ok(Syntax::Ebnf),
+        "abnf" => Ok(Syntax::Abnf),
+        "pest" => Ok(Syntax::Pest),
+        v => Err(CliError::Usage(format!("unknown export format `{v}`"))),
+    }
+}
+
+fn parse_source(v: &str) -> Result<Source, CliError> {
+    match v {
+        "pest" => Ok(Source::Pest),
+        "abnf" => Ok(Source::Abnf),
+        v => Err(CliError::Usage(format!("unknown import format `{v}`"))),
+    }
+}
+
+/// `error`, `error:<text>`, `panic`, `panic:<text>` или `depth:<N>`.
+fn parse_failure(v: &str) -> Result<Failure, CliError> {
+    let (kind, arg) = match v.split_once(':') {
+        Some((kind, arg)) => (kind, Some(arg.to_string())),
+        None => (v, None),
+    };
+    match (kind, arg) {
+        ("error", arg) => Ok(Failure::Error(arg)),
+        ("panic", arg) => Ok(Failure::Panic(arg)),
+        ("depth", Some(arg)) => arg
+            .parse()
+            .map(Failure::Depth)
+            .map_err(|_| CliError::Usage(format!("invalid depth `{arg}`"))),
+        _ => Err(CliError::Usage(format!("unknown failure `{v}`"))),
+    }
+}
+
+/// Начало первой строки `src`, которая отличается в `formatted`.
+fn first_difference(src: &str, formatted: &str) -> Option<usize> {
+    let mut pos = 0;
+    let mut lines = formatted.split_inclusive('\n');
+    for line in src.split_inclusive('\n') {
+        if lines.next() != Some(line) {
+            return Some(pos);
+        }
+        pos += line.len();
+    }
+    lines.next().map(|_| pos)
+}
+
+fn read(path: &str) -> Result<String, CliError> {
+    std::fs::read_to_string(path).map_err(|e| CliError::Failed(format!("{path}: {e}")))
+}
+
+fn load(path: &str) -> Result<(String, RuntimeGrammar), CliError> {
+    let src = read(path)?;
+    let grammar = RuntimeGrammar::from_abs(&src)
+        .map_err(|e| CliError::Failed(diagnostic(path, &src, e.pos..e.pos, &e.message)))?;
+    Ok((src, grammar))
+}
+
+/// Правило по имени, без имени — первое правило грамматики.
+fn compile<'g>(
+    path: &str,
+    src: &str,
+    grammar: &'g RuntimeGrammar,
+    rule: Option<&str>,
+) -> Result<GrammarRule<'g>, CliError> {
+    let name = match rule {
+        Some(v) => v.to_string(),
+        None => grammar
+            .grammar()
+            .rules()
+            .next()
+            .map(|v| v.name.clone())
+            .ok_or_else(|| CliError::Failed("grammar has no rules".into()))?,
+    };
+    grammar
+        .rule(&name)
+        .map_err(|e| CliError::Failed(compile_diagnostic(path, src, &e)))
+}
+
+fn compile_diagnostic(path: &str, src: &str, error: &CompileError) -> String {
+    match error.span() {
+        Some(span) => diagnostic(path, src, span, error),
+        None => format!("{path}: error: {error}"),
+    }
+}
+
+/// Строка и столбец (с единицы, столбец в символах) байтовой позиции.
+pub fn line_col(src: &str, pos: usize) -> (usize, usize) {
+    let before = &src[..pos.min(src.len())];
+    let line_start = before.rfind('\n').map_or(0, |v| v + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Сообщение `path:line:col: error: ...` со строкой исходника и подчёркнутым диапазоном.
+pub fn diagnostic(
+    path: &str,
+    src: &str,
+    span: Range<usize>,
+    message: &impl std::fmt::Display,
+) -> String {
+    let (line, col) = line_col(src, span.start);
+    let text = src.lines().nth(line - 1).unwrap_or_default();
+    let len = src
+        .get(span.clone())
+        .map_or(0, |v| v.lines().next().unwrap_or_default().chars().count());
+    let gutter = " ".repeat(line.to_string().len());
+    format!(
+        "{path}:{line}:{col}: error: {message}\n{gutter} |\n{line} | {text}\n{gutter} | {}{}",
+        " ".repeat(col - 1),
+        "^".repeat(len.max(1))
+    )
+}
+
+pub fn render(node: &Node, src: &str, format: Format) -> String {
+    let mut out = String::new();
+    match format {
+        Format::Json => json(&mut out, node, src, 0),
+        Format::Sexpr => sexpr(&mut out, node, src, 0),
+        Format::Tree => tree(&mut out, node, src, 0),
+    }
+    out
+}
+
+fn json(out: &mut String, node: &Node, src: &str, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let _ = write!(
+        out,
+        "{{\"rule\": {}, \"span\": [{}, {}]",
+        json_str(&node.rule),
+        node.span.start,
+        node.span.end
+    );
+    if node.children.is_empty() {
+        let _ = write!(out, ", \"text\": {}}}", json_str(node.text(src)));
+        return;
+    }
+    out.push_str(", \"children\": [");
+    for (i, child) in node.children.iter().enumerate() {
+        out.push_str(if i == 0 { "\n" } else { ",\n" });
+        let _ = write!(out, "{indent}  ");
+        json(out, child, src, depth + 1);
+    }
+    let _ = write!(out, "\n{indent}]}}");
+}
+
+fn json_str(v: &str) -> String {
+    let mut out = String::with_capacity(v.len() + 2);
+    out.push('"');
+    for c in v.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn sexpr(out: &mut String, node: &Node, src: &str, depth: usize) {
+    if node.children.is_empty() {
+        let _ = write!(out, "({} {:?})", node.rule, node.text(src));
+        return;
+    }
+    let _ = write!(out, "({}", node.rule);
+    for child in &node.children {
+        let _ = write!(out, "\n{}", "  ".repeat(depth + 1));
+        sexpr(out, child, src, depth + 1);
+    }
+    out.push(')');
+}
+
+fn tree(out: &mut String, node: &Node, src: &str, depth: usize) {
+    let _ = write!(
+        out,
+        "{}{} {}..{}",
+        "  ".repeat(depth),
+        node.rule,
+        node.span.start,
+        node.span.end
+    );
+    if node.children.is_empty() {
+        let _ = write!(out, " {:?}", node.text(src));
+    }
+    for child in &node.children {
+        out.push('\n');
+        tree(out, child, src, depth + 1);
+    }
+}
+
+/// Трасса разбора деревом вызовов: правило, диапазон и исход, по строке на вызов.
+/// Фильтр скрывает строки, но не меняет отступы остальных.
+pub fn trace(events: &[ParseEvent], filter: &TraceConfig) -> String {
+    let mut lines = Vec::<Option<String>>::new();
+    let mut stack = vec![];
+    for event in events {
+        let depth = stack.len();
+        match event {
+            ParseEvent::Enter { .. } => {
+                stack.push(lines.len());
+                lines.push(None);
+            }
+            ParseEvent::Exit {
+                rule,
+                start,
+                end,
+                ok,
+                ..
+            } => {
+                let Some(i) = stack.pop() else { continue };
+                if filter.matches(*start, rule) {
+                    lines[i] = Some(format!(
+                        "{}{rule} {start}..{end} {}",
+                        "  ".repeat(depth - 1),
+                        if *ok { "ok" } else { "fail" }
+                    ));
+                }
+            }
+            ParseEvent::Cached { rule, pos, end, ok } => {
+                if filter.matches(*pos, rule) {
+                    lines.push(Some(format!(
+                        "{}{rule} {pos}..{} {} (cached)",
+                        "  ".repeat(depth),
+                        end.unwrap_or(*pos),
+                        if *ok { "ok" } else { "fail" }
+                    )));
+                }
+            }
+        }
+    }
+    lines.into_iter().flatten().map(|v| v + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(v: &str) -> Result<Command, CliError> {
+        Command::from_args(v.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn from_args() {
+        assert_eq!(
+            args("parse --grammar zpl.abs --rule Grammar input.zpl --format json"),
+            Ok(Command::Parse {
+                grammar: "zpl.abs".into(),
+                rule: Some("Grammar".into()),
+                input: Input::File("input.zpl".into()),
+                format: Format::Json,
+            })
+        );
+        assert_eq!(
+            args("trace -g a.abs --filter Item@0..4 -"),
+            Ok(Command::Trace {
+                grammar: "a.abs".into(),
+                rule: None,
+                input: Input::Stdin,
+                filter: TraceConfig::all().rules(["Item"]).range(0..4),
+            })
+        );
+        assert_eq!(
+            args("fmt a.abs"),
+            Ok(Command::Fmt {
+                grammar: "a.abs".into(),
+                check: false,
+            })
+        );
+        assert_eq!(
+            args("fmt --check a.abs"),
+            Ok(Command::Fmt {
+                grammar: "a.abs".into(),
+                check: true,
+            })
+        );
+        assert!(matches!(
+            args("parse --check -g a.abs"),
+            Err(CliError::Usage(..))
+        ));
+        assert_eq!(
+            args("minimize -g a.abs --failure depth:40 in.txt"),
+            Ok(Command::Minimize {
+                grammar: "a.abs".into(),
+                rule: None,
+                input: Input::File("in.txt".into()),
+                failure: Failure::Depth(40),
+            })
+        );
+        assert_eq!(
+            parse_failure("error:expected X"),
+            Ok(Failure::Error(Some("expected X".into())))
+        );
+        assert!(matches!(
+            args("minimize -g a.abs --failure depth"),
+            Err(CliError::Usage(..))
+        ));
+        assert!(matches!(args("parse in.txt"), Err(CliError::Usage(..))));
+        assert!(matches!(
+            args("check a.abs b.abs"),
+            Err(CliError::Usage(..))
+        ));
+        assert!(matches!(
+            args("parse -g a.abs -f xml"),
+            Err(CliError::Usage(..))
+        ));
+        assert!(matches!(args("run a.abs"), Err(CliError::Usage(..))));
+    }
+
+    #[test]
+    fn diagnostics() {
+        let src = "ab\nc дd\n";
+        assert_eq!(line_col(src, 0), (1, 1));
+        assert_eq!(line_col(src, 3), (2, 1));
+        assert_eq!(line_col(src, 7), (2, 4));
+        assert_eq!(
+            diagnostic("in.txt", src, 5..8, &"expected X"),
+            "in.txt:2:3: error: expected X\n  |\n2 | c дd\n  |   ^^"
+        );
+    }
+
+    #[test]
+    fn formats() {
+        let node = Node {
+            rule: "Pair".into(),
+            span: 0..4,
+            children: vec![
+                Node {
+                    rule: "Key".into(),
+                    span: 0..1,
+                    children: vec![],
+                },
+                Node {
+                    rule: "\"\\\"\"s".into(),
+                    span: 2..4,
+                    children: vec![],
+                },
+            ],
+        };
+        let src = "a=\"\n";
+        assert_eq!(
+            render(&node, src, Format::Json),
+            r#"{"rule": "Pair", "span": [0, 4], "children": [
+  {"rule": "Key", "span": [0, 1], "text": "a"},
+  {"rule": "\"\\\"\"s", "span": [2, 4], "text": "\"\n"}
+]}"#
+        );
+        assert_eq!(
+            render(&node, src, Format::Sexpr),
+            "(Pair\n  (Key \"a\")\n  (\"\\\"\"s \"\\\"\\n\"))"
+        );
+        assert_eq!(
+            render(&node, src, Format::Tree),
+            "Pair 0..4\n  Key 0..1 \"a\"\n  \"\\\"\"s 2..4 \"\\\"\\n\""
+        );
+    }
+
+    #[test]
+    fn trace_tree() {
+        let events = [
+            ParseEvent::Enter {
+                rule: "A".into(),
+                pos: 0,
+            },
+            ParseEvent::Enter {
+                rule: "B".into(),
+                pos: 0,
+            },
+            ParseEvent::Exit {
+                rule: "B".into(),
+                start: 0,
+                end: 2,
+                ok: true,
+                consumed: true,
+            },
+            ParseEvent::Cached {
+                rule: "C".into(),
+                pos: 2,
+                end: None,
+                ok: false,
+            },
+            ParseEvent::Exit {
+                rule: "A".into(),
+                start: 0,
+                end: 2,
+                ok: false,
+                consumed: false,
+            },
+        ];
+        assert_eq!(
+            trace(&events, &TraceConfig::all()),
+            "A 0..2 fail\n  B 0..2 ok\n  C 2..2 fail (cached)\n"
+        );
+        assert_eq!(
+            trace(&events, &TraceConfig::all().rules(["B"])),
+            "  B 0..2 ok\n"
+        );
+    }
+}
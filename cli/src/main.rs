@@ -0,0 +1,27 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use abstract_parser_cli::{CliError, Command, USAGE};
+use std::io;
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if args.is_empty() || args.iter().any(|v| v == "-h" || v == "--help") {
+        println!("{USAGE}");
+        return;
+    }
+    let result = Command::from_args(args).and_then(|cmd| cmd.run(&mut io::stdout().lock()));
+    if let Err(e) = result {
+        eprintln!("{e}");
+        std::process::exit(CliError::exit_code(&e));
+    }
+}
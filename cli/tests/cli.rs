@@ -0,0 +1,222 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+const LIST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/list.abs");
+const BROKEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/broken.abs");
+const JSON: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/json.pest");
+const HTTP: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/http.abnf");
+
+/// Запускает бинарник с `stdin` и возвращает код выхода, stdout и stderr.
+fn run(args: &[&str], stdin: &str) -> (i32, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_abstract-parser"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let out = child.wait_with_output().unwrap();
+    (
+        out.status.code().unwrap(),
+        String::from_utf8(out.stdout).unwrap(),
+        String::from_utf8(out.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn parse() {
+    let (code, out, _) = run(&["parse", "--grammar", LIST, "--rule", "List"], "[a, 1]");
+    assert_eq!(code, 0);
+    assert_eq!(
+        out,
+        r#"List 0..6
+  "\[" 0..1 "["
+  Item 1..2
+    Name 1..2
+      Ident 1..2 "a"
+  Item 4..5
+    Number 4..5
+      Number 4..5 "1"
+  "\]" 5..6 "]"
+"#
+    );
+
+    let (_, out, _) = run(&["parse", "-g", LIST, "-f", "sexpr"], "[b]");
+    assert_eq!(
+        out,
+        "(List\n  (\"\\[\" \"[\")\n  (Item\n    (Name\n      (Ident \"b\")))\n  (\"\\]\" \"]\"))\n"
+    );
+
+    let (_, out, _) = run(&["parse", "-g", LIST, "-f", "json", "-"], "[]");
+    assert_eq!(
+        out,
+        r#"{"rule": "List", "span": [0, 2], "children": [
+  {"rule": "\"\\[\"", "span": [0, 1], "text": "["},
+  {"rule": "\"\\]\"", "span": [1, 2], "text": "]"}
+]}
+"#
+    );
+}
+
+#[test]
+fn parse_error() {
+    let (code, out, err) = run(&["parse", "-g", LIST], "[a,\n 1,]");
+    assert_eq!((code, out.as_str()), (1, ""));
+    assert_eq!(
+        err,
+        "<stdin>:2:4: error: expected one of Number, Ident at byte 7\n  |\n2 |  1,]\n  |    ^\n"
+    );
+}
+
+#[test]
+fn check() {
+    let (code, out, _) = run(&["check", LIST], "");
+    assert_eq!(code, 0);
+    assert!(out.ends_with("list.abs: ok, 2 rule(s)\n"));
+
+    let (code, _, err) = run(&["check", BROKEN], "");
+    assert_eq!(code, 1);
+    assert!(err.contains(
+        "broken.abs:3:5: error: rule `D` expects 0 generic argument(s), found 1\n  |\n3 | C = D<B>\n"
+    ));
+}
+
+#[test]
+fn trace() {
+    let (code, out, _) = run(
+        &["trace", "-g", LIST, "--filter", "Item,Number,Ident"],
+        "[a]",
+    );
+    assert_eq!(code, 0);
+    assert_eq!(
+        out,
+        "    Item 1..2 ok\n      Number 1..1 fail\n      Ident 1..2 ok\n"
+    );
+
+    let (code, out, err) = run(&["trace", "-g", LIST, "--filter", "List"], "[a");
+    assert_eq!(code, 1);
+    assert_eq!(out, "List 0..0 fail\n");
+    assert!(err.starts_with("<stdin>:1:3: error:"));
+}
+
+#[test]
+fn fmt() {
+    let (code, out, _) = run(&["fmt", LIST], "");
+    assert_eq!(code, 0);
+    assert_eq!(out, std::fs::read_to_string(LIST).unwrap());
+}
+
+#[test]
+fn fmt_check() {
+    let (code, out, _) = run(&["fmt", "--check", LIST], "");
+    assert_eq!(code, 0);
+    assert!(out.ends_with("list.abs: ok\n"));
+
+    let path = concat!(env!("CARGO_TARGET_TMPDIR"), "/unformatted.abs");
+    std::fs::write(path, "// [a]\nList=Item  **\",\"\nItem = \"[a-z]\"\n").unwrap();
+    let (code, _, err) = run(&["fmt", "--check", path], "");
+    assert_eq!(code, 1);
+    assert!(err.contains(
+        "unformatted.abs:2:1: error: file is not formatted, run `abstract-parser fmt`\n"
+    ));
+    let (code, out, _) = run(&["fmt", path], "");
+    assert_eq!(
+        (code, out.as_str()),
+        (0, "// [a]\nList = Item ** \",\"\nItem = \"[a-z]\"\n")
+    );
+}
+
+#[test]
+fn minimize() {
+    let input = "[a, 1, b,\n 2, c 3,\n 4, 5, d]";
+    let failure = r#"error:expected one of ",\s*""#;
+    let (code, out, _) = run(&["minimize", "-g", LIST, "--failure", failure], input);
+    assert_eq!((code, out.as_str()), (0, "[a"));
+
+    let (code, _, err) = run(&["minimize", "-g", LIST], "[a, 1]");
+    assert_eq!(code, 1);
+    assert_eq!(err, "<stdin>: the failure does not occur\n");
+}
+
+#[test]
+fn railroad() {
+    let dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/railroad");
+    let (code, out, _) = run(&["railroad", "--out", dir, LIST], "");
+    assert_eq!((code, out), (0, format!("{dir}: 3 file(s)\n")));
+    let list = std::fs::read_to_string(format!("{dir}/List.svg")).unwrap();
+    assert!(list.starts_with("<svg "));
+    assert!(list.contains(r#"<a href="Item.svg">"#));
+    let index = std::fs::read_to_string(format!("{dir}/index.html")).unwrap();
+    assert!(index.contains(r#"<section id="Item">"#));
+    assert!(index.contains(r##"<a href="#Item">"##));
+}
+
+#[test]
+fn export() {
+    let (code, out, _) = run(&["export", "--format", "pest", LIST], "");
+    assert_eq!(code, 0);
+    assert!(out.contains("\nItem = { Number | Ident }\n"));
+
+    let (code, _, err) = run(&["export", LIST], "");
+    assert_eq!(code, 2);
+    assert!(err.starts_with("error: missing `--format`\n"));
+    assert_eq!(run(&["export", "-f", "bnf", LIST], "").0, 2);
+}
+
+#[test]
+fn import() {
+    let (code, out, _) = run(&["import", "--from", "pest", JSON], "");
+    assert_eq!(code, 0);
+    assert!(out.starts_with("//! A JSON grammar\nWhitespace = "));
+    assert!(out.contains("\nValue = Object / Array / String / Number / Boolean / Null\n"));
+
+    let (code, out, _) = run(&["import", "--from", "pest", "--macro", JSON], "");
+    assert_eq!(code, 0);
+    assert!(out.starts_with("grammar! {r#\"\n//! A JSON grammar\n"));
+    assert!(out.ends_with("\n\"#}\n"));
+
+    let (code, out, _) = run(&["import", "--from", "abnf", HTTP], "");
+    assert_eq!(code, 0);
+    assert!(out.contains("\nHttpName = \"HTTP\"s\n"));
+    assert!(out.ends_with("\nSp = \" \"\n"));
+
+    let (code, _, err) = run(&["import", JSON], "");
+    assert_eq!(code, 2);
+    assert!(err.starts_with("error: missing `--from`\n"));
+    assert_eq!(run(&["import", "--from", "yacc", JSON], "").0, 2);
+    let (code, _, err) = run(&["import", "--from", "pest", LIST], "");
+    assert_eq!(code, 1);
+    assert!(err.contains("list.abs:2:8: error: expected `{`"), "{}", err);
+}
+
+#[test]
+fn usage() {
+    let (code, out, _) = run(&["--help"], "");
+    assert_eq!(code, 0);
+    assert!(out.starts_with("usage:"));
+
+    let (code, _, err) = run(&["parse", "in.txt"], "");
+    assert_eq!(code, 2);
+    assert!(err.starts_with("error: missing grammar\n\nusage:"));
+    assert_eq!(run(&["lint", LIST], "").0, 2);
+}
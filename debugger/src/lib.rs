@@ -0,0 +1,492 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(if_let_guard)]
+
+use colored::Colorize;
+pub use parser::debug::ParseEvent;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    Rule(String),
+    Offset(usize),
+}
+
+impl Breakpoint {
+    #[inline]
+    fn hit(&self, event: &ParseEvent) -> bool {
+        match (self, event) {
+            (Self::Rule(name), ParseEvent::Enter { rule, .. }) => name == rule,
+            (
+                Self::Offset(offset),
+                ParseEvent::Enter { pos, .. } | ParseEvent::Cached { pos, .. },
+            ) => offset == pos,
+            _ => false,
+        }
+    }
+}
+
+impl std::str::FromStr for Breakpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "" => Err("expected rule name or @offset".into()),
+            s if let Some(offset) = s.strip_prefix('@') => offset
+                .parse()
+                .map(Self::Offset)
+                .map_err(|e| format!("`{offset}`: {e}")),
+            s => Ok(Self::Rule(s.into())),
+        }
+    }
+}
+
+impl std::fmt::Display for Breakpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rule(name) => write!(f, "{name}"),
+            Self::Offset(offset) => write!(f, "@{offset}"),
+        }
+    }
+}
+
+/// Записанный разбор, по которому можно ходить в обе стороны.
+#[derive(Debug)]
+pub struct Session {
+    pub input: String,
+    pub events: Vec<ParseEvent>,
+    pub breakpoints: Vec<Breakpoint>,
+    current: usize,
+    /// Глубина вложенности события: `Enter` и его `Exit` на одном уровне.
+    levels: Vec<usize>,
+    /// Индекс `Enter` объемлющего правила.
+    parents: Vec<Option<usize>>,
+}
+
+impl Session {
+    pub fn new(input: String, events: Vec<ParseEvent>) -> Self {
+        let (mut levels, mut parents) = (
+            Vec::with_capacity(events.len()),
+            Vec::with_capacity(events.len()),
+        );
+        let mut stack: Vec<usize> = vec![];
+        for (i, event) in events.iter().enumerate() {
+            match event {
+                ParseEvent::Enter { .. } => {
+                    levels.push(stack.len());
+                    parents.push(stack.last().copied());
+                    stack.push(i);
+                }
+                ParseEvent::Exit { .. } => {
+                    stack.pop();
+                    levels.push(stack.len());
+                    parents.push(stack.last().copied());
+                }
+                ParseEvent::Cached { .. } => {
+                    levels.push(stack.len());
+                    parents.push(stack.last().copied());
+                }
+            }
+        }
+        Self {
+            input,
+            events,
+            breakpoints: vec![],
+            current: 0,
+            levels,
+            parents,
+        }
+    }
+
+    #[inline]
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    #[inline]
+    pub fn event(&self) -> Option<&ParseEvent> {
+        self.events.get(self.current)
+    }
+
+    /// Позиция курсора после текущего события.
+    pub fn cursor(&self) -> usize {
+        match self.event() {
+            Some(ParseEvent::Enter { pos, .. }) => *pos,
+            Some(ParseEvent::Exit { end, .. }) => *end,
+            Some(ParseEvent::Cached { pos, end, .. }) => end.unwrap_or(*pos),
+            None => 0,
+        }
+    }
+
+    /// Стек правил от корня до текущего события.
+    pub fn stack(&self) -> Vec<&ParseEvent> {
+        let mut stack = vec![];
+        let mut next = match self.event() {
+            Some(ParseEvent::Enter { .. }) => Some(self.current),
+            Some(_) => self.parents[self.current],
+            None => None,
+        };
+        while let Some(i) = next {
+            stack.push(&self.events[i]);
+            next = self.parents[i];
+        }
+        stack.reverse();
+        stack
+    }
+
+    /// Уже разобранные правила на текущей позиции, т.е. то, что лежит в кэше.
+    /// Одноимённые правила (разные инстансы дженериков) схлопываются до последнего.
+    pub fn cached(&self) -> Vec<&ParseEvent> {
+        let cursor = self.cursor();
+        let mut cached: Vec<&ParseEvent> = vec![];
+        for v in &self.events[..self.current.min(self.events.len())] {
+            if matches!(v, ParseEvent::Exit { start, .. } if *start == cursor) {
+                cached.retain(|c| c.rule() != v.rule());
+                cached.push(v);
+            }
+        }
+        cached
+    }
+
+    #[inline]
+    fn seek(&mut self, to: Option<usize>) -> bool {
+        to.map(|v| self.current = v).is_some()
+    }
+
+    #[inline]
+    fn forward(&self, cond: impl Fn(usize) -> bool) -> Option<usize> {
+        (self.current + 1..self.events.len()).find(|i| cond(*i))
+    }
+
+    #[inline]
+    fn backward(&self, cond: impl Fn(usize) -> bool) -> Option<usize> {
+        (0..self.current).rev().find(|i| cond(*i))
+    }
+
+    pub fn step_into(&mut self) -> bool {
+        self.seek(self.forward(|_| true))
+    }
+
+    pub fn step_over(&mut self) -> bool {
+        let Some(&level) = self.levels.get(self.current) else {
+            return false;
+        };
+        self.seek(self.forward(|i| self.levels[i] <= level))
+    }
+
+    pub fn step_out(&mut self) -> bool {
+        let Some(&level) = self.levels.get(self.current) else {
+            return false;
+        };
+        self.seek(self.forward(|i| self.levels[i] < level))
+    }
+
+    pub fn resume(&mut self) -> bool {
+        self.seek(
+            self.forward(|i| self.is_breakpoint(i))
+                .or(self.events.len().checked_sub(1)),
+        )
+    }
+
+    pub fn back_into(&mut self) -> bool {
+        self.seek(self.backward(|_| true))
+    }
+
+    pub fn back_over(&mut self) -> bool {
+        let Some(&level) = self.levels.get(self.current) else {
+            return false;
+        };
+        self.seek(self.backward(|i| self.levels[i] <= level))
+    }
+
+    pub fn back_out(&mut self) -> bool {
+        let Some(&level) = self.levels.get(self.current) else {
+            return false;
+        };
+        self.seek(self.backward(|i| self.levels[i] < level))
+    }
+
+    pub fn back_resume(&mut self) -> bool {
+        self.seek(
+            self.backward(|i| self.is_breakpoint(i))
+                .or((!self.events.is_empty()).then_some(0)),
+        )
+    }
+
+    #[inline]
+    fn is_breakpoint(&self, i: usize) -> bool {
+        self.breakpoints.iter().any(|v| v.hit(&self.events[i]))
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let Some(event) = self.event() else {
+            return "empty trace\n".into();
+        };
+
+        let _ = writeln!(out, "{}", "stack:".bold());
+        for (depth, v) in self.stack().iter().enumerate() {
+            if let ParseEvent::Enter { rule, pos } = v {
+                let _ = writeln!(out, "  {:w$}{rule} @{pos}", "", w = depth * 2);
+            }
+        }
+
+        let _ = writeln!(
+            out,
+            "{} [{}/{}] {}",
+            "event:".bold(),
+            self.current + 1,
+            self.events.len(),
+            match event {
+                ParseEvent::Enter { rule, pos } => format!("→ {rule} @{pos}"),
+                ParseEvent::Exit {
+                    rule,
+                    start,
+                    end,
+                    ok,
+                    ..
+                } => format!(
+                    "← {rule} @{start}..{end} {}",
+                    if *ok {
+                        "✅Pass".green()
+                    } else {
+                        "❌Fail".red()
+                    }
+                ),
+                ParseEvent::Cached { rule, pos, ok, .. } => format!(
+                    "🔁Cached {rule} @{pos} {}",
+                    if *ok {
+                        "✅Pass".green()
+                    } else {
+                        "❌Fail".red()
+                    }
+                ),
+            }
+        );
+
+        let _ = writeln!(out, "{}", "input:".bold());
+        out.push_str(&self.render_input());
+
+        let cached = self.cached();
+        if !cached.is_empty() {
+            let _ = writeln!(out, "{} @{}", "cached:".bold(), self.cursor());
+            const LIMIT: usize = 16;
+            if cached.len() > LIMIT {
+                let _ = writeln!(out, "  … ещё {}", cached.len() - LIMIT);
+            }
+            for v in &cached[cached.len().saturating_sub(LIMIT)..] {
+                if let ParseEvent::Exit { rule, end, ok, .. } = v {
+                    let _ = writeln!(
+                        out,
+                        "  {rule} {}",
+                        if *ok {
+                            format!("..{end}").green()
+                        } else {
+                            "❌Fail".red()
+                        }
+                    );
+                }
+            }
+        }
+        out
+    }
+
+    /// Строка входа с подсвеченным курсором, у успешного выхода подсвечивается весь span.
+    pub fn render_input(&self) -> String {
+        let cursor = self.cursor().min(self.input.len());
+        let start = match self.event() {
+            Some(ParseEvent::Exit {
+                start, ok: true, ..
+            }) => (*start).min(cursor),
+            _ => cursor,
+        };
+        let line_start = self.input[..start].rfind('\n').map_or(0, |v| v + 1);
+        let line_end = self.input[cursor..]
+            .find('\n')
+            .map_or(self.input.len(), |v| cursor + v);
+        let line = self.input[..start].matches('\n').count() + 1;
+        let column = self.input[line_start..start].chars().count() + 1;
+
+        let (head, span, tail) = (
+            &self.input[line_start..start],
+            &self.input[start..cursor],
+            &self.input[cursor..line_end],
+        );
+        let mut tail = tail.chars();
+        let under = tail
+            .next()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "⏎".into());
+        format!(
+            "{line:>4}:{column:<3} {head}{}{}{}\n",
+            span.on_blue(),
+            under.black().on_yellow(),
+            tail.as_str()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        use ParseEvent::*;
+        Session::new(
+            "ab".into(),
+            vec![
+                Enter {
+                    rule: "Root".into(),
+                    pos: 0,
+                },
+                Enter {
+                    rule: "A".into(),
+                    pos: 0,
+                },
+                Exit {
+                    rule: "A".into(),
+                    start: 0,
+                    end: 1,
+                    ok: true,
+                    consumed: true,
+                },
+                Enter {
+                    rule: "B".into(),
+                    pos: 1,
+                },
+                Exit {
+                    rule: "B".into(),
+                    start: 1,
+                    end: 1,
+                    ok: false,
+                    consumed: false,
+                },
+                Cached {
+                    rule: "A".into(),
+                    pos: 0,
+                    end: Some(1),
+                    ok: true,
+                },
+                Enter {
+                    rule: "B".into(),
+                    pos: 1,
+                },
+                Exit {
+                    rule: "B".into(),
+                    start: 1,
+                    end: 2,
+                    ok: true,
+                    consumed: true,
+                },
+                Exit {
+                    rule: "Root".into(),
+                    start: 0,
+                    end: 2,
+                    ok: true,
+                    consumed: true,
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn stepping() {
+        let mut s = session();
+        assert!(s.step_into());
+        assert_eq!(s.current(), 1);
+        assert!(s.step_over());
+        assert_eq!(s.current(), 2);
+        assert!(s.step_over());
+        assert_eq!(s.current(), 3);
+        assert_eq!(
+            s.stack().iter().map(|v| v.rule()).collect::<Vec<_>>(),
+            ["Root", "B"]
+        );
+        assert!(s.step_out());
+        assert_eq!(s.current(), 8);
+        assert!(!s.step_into());
+
+        assert!(s.back_over());
+        assert_eq!(s.current(), 0);
+        assert!(!s.back_into());
+    }
+
+    #[test]
+    fn empty_trace() {
+        let mut s = Session::new(String::new(), vec![]);
+        s.breakpoints.push("A".parse().unwrap());
+        assert!(!s.step_into());
+        assert!(!s.step_over());
+        assert!(!s.step_out());
+        assert!(!s.resume());
+        assert!(!s.back_into());
+        assert!(!s.back_over());
+        assert!(!s.back_out());
+        assert!(!s.back_resume());
+        assert_eq!(s.render(), "empty trace\n");
+    }
+
+    #[test]
+    fn breakpoints() {
+        let mut s = session();
+        s.breakpoints.push("B".parse().unwrap());
+        assert!(s.resume());
+        assert_eq!(s.current(), 3);
+        assert!(s.cached().is_empty());
+        assert!(s.resume());
+        assert_eq!(s.current(), 6);
+        assert_eq!(s.cached(), [&s.events[4]]);
+
+        s.breakpoints = vec!["@0".parse().unwrap()];
+        assert!(s.resume());
+        assert_eq!(s.current(), 8);
+        assert!(s.back_resume());
+        assert_eq!(s.current(), 5);
+        assert!("@x".parse::<Breakpoint>().is_err());
+    }
+
+    #[test]
+    fn record() {
+        use grammar_feature_parsing::default_feature_rule;
+        use parser::cached::CachedIter;
+        use parsers::chars::{CharParser, InputStreamIter};
+
+        let input = "Name = \"name\"\nIdent = \"[A-Za-z_0-9@~]+\"";
+        let (out, events) = parser::debug::record(|| {
+            CachedIter::new(InputStreamIter::new(input))
+                .full_parse(&default_feature_rule())
+                .is_ok()
+        });
+        assert!(out);
+        let count = |f: fn(&ParseEvent) -> bool| events.iter().filter(|v| f(v)).count();
+        assert_eq!(
+            count(|v| matches!(v, ParseEvent::Enter { .. })),
+            count(|v| matches!(v, ParseEvent::Exit { .. }))
+        );
+        assert!(count(|v| matches!(v, ParseEvent::Cached { .. })) > 0);
+        // пустой `Optional` удачен, но курсор не двигает
+        assert!(
+            count(|v| matches!(
+                v,
+                ParseEvent::Exit {
+                    ok: true,
+                    consumed: false,
+                    ..
+                }
+            )) > 0
+        );
+
+        let s = Session::new(input.into(), events);
+        assert_eq!(s.levels.last(), Some(&0));
+    }
+}
@@ -0,0 +1,187 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Отладчик воспроизводит трассу: вход разбирается один раз целиком с записью событий
+//! ([`record`]), а шаги вперёд и назад, точки останова и стек идут по записанным событиям.
+
+use grammar_feature_parsing::default_feature_rule;
+use grammar_runtime::RuntimeGrammar;
+use parser::{cached::CachedIter, debug::record};
+use parser_debugger::Session;
+use parsers::chars::{CharParser, InputStreamIter};
+use std::io::{self, BufRead, Write};
+
+const USAGE: &str = "\
+usage: parser-debugger [--grammar <file.abs> [--rule <Rule>]] <input>
+Без --grammar вход — .abs-файл, который разбирает встроенная грамматика .abs;
+без --rule берётся первое правило грамматики. Разбор выполняется один раз до первой команды,
+шаги воспроизводят его записанную трассу.";
+
+const HELP: &str = "\
+s  | step       шаг внутрь правила
+n  | next       шаг через правило
+o  | out        выход из правила
+c  | continue   до следующей точки останова
+rs, rn, ro, rc  то же самое в обратную сторону
+b <Rule|@offset>  поставить точку останова
+d <n>       удалить точку останова
+bl          список точек останова
+bt          стек правил
+q  | quit";
+
+/// Сообщение в stderr и выход с кодом `code`.
+fn exit(message: impl std::fmt::Display, code: i32) -> ! {
+    eprintln!("{message}");
+    std::process::exit(code)
+}
+
+fn read(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| exit(format!("{path}: {e}"), 1))
+}
+
+#[derive(Default)]
+struct Args {
+    grammar: Option<String>,
+    rule: Option<String>,
+    input: Option<String>,
+}
+
+fn args() -> Args {
+    let mut out = Args::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let slot = match arg.as_str() {
+            "--grammar" => &mut out.grammar,
+            "--rule" => &mut out.rule,
+            "-h" | "--help" => exit(USAGE, 0),
+            v if v.starts_with("--") => exit(format!("unknown option `{v}`\n{USAGE}"), 2),
+            _ if out.input.is_none() => {
+                out.input = Some(arg.clone());
+                continue;
+            }
+            v => exit(format!("unexpected argument `{v}`\n{USAGE}"), 2),
+        };
+        *slot = Some(
+            args.next()
+                .unwrap_or_else(|| exit(format!("`{arg}` expects a value"), 2)),
+        );
+    }
+    out
+}
+
+fn main() {
+    let args = args();
+    let Some(path) = args.input else {
+        exit(USAGE, 2)
+    };
+    if args.rule.is_some() && args.grammar.is_none() {
+        exit(format!("`--rule` needs `--grammar`\n{USAGE}"), 2)
+    }
+    let input = read(&path);
+
+    let (ok, events) = match &args.grammar {
+        None => record(|| {
+            CachedIter::new(InputStreamIter::new(&input))
+                .full_parse(&default_feature_rule())
+                .is_ok()
+        }),
+        Some(grammar_path) => {
+            let src = read(grammar_path);
+            let grammar = RuntimeGrammar::from_abs(&src)
+                .unwrap_or_else(|e| exit(format!("{grammar_path}: {}", e.message), 1));
+            let name = match args.rule {
+                Some(v) => v,
+                None => match grammar.grammar().rules().next() {
+                    Some(v) => v.name.clone(),
+                    None => exit(format!("{grammar_path}: grammar has no rules"), 1),
+                },
+            };
+            let rule = grammar
+                .rule(&name)
+                .unwrap_or_else(|e| exit(format!("{grammar_path}: {e}"), 1));
+            record(|| rule.parse(&input).is_ok())
+        }
+    };
+    println!(
+        "{path}: {} событий, разбор {}\n{HELP}\n",
+        events.len(),
+        if ok {
+            "успешен"
+        } else {
+            "неудачен"
+        }
+    );
+
+    let mut session = Session::new(input.clone(), events);
+    print!("{}", session.render());
+
+    let stdin = io::stdin();
+    loop {
+        print!("(dbg) ");
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let (cmd, arg) = line
+            .trim()
+            .split_once(' ')
+            .map_or((line.trim(), ""), |(a, b)| (a, b.trim()));
+
+        let moved = match cmd {
+            "s" | "step" => session.step_into(),
+            "n" | "next" => session.step_over(),
+            "o" | "out" => session.step_out(),
+            "c" | "continue" => session.resume(),
+            "rs" => session.back_into(),
+            "rn" => session.back_over(),
+            "ro" => session.back_out(),
+            "rc" => session.back_resume(),
+            "b" | "break" => {
+                match arg.parse() {
+                    Ok(v) => session.breakpoints.push(v),
+                    Err(e) => eprintln!("{e}"),
+                }
+                continue;
+            }
+            "d" | "delete" => {
+                match arg.parse::<usize>() {
+                    Ok(i) if i < session.breakpoints.len() => {
+                        session.breakpoints.remove(i);
+                    }
+                    _ => eprintln!("нет точки останова `{arg}`"),
+                }
+                continue;
+            }
+            "bl" => {
+                for (i, v) in session.breakpoints.iter().enumerate() {
+                    println!("{i}: {v}");
+                }
+                continue;
+            }
+            "bt" | "" => true,
+            "h" | "help" => {
+                println!("{HELP}");
+                continue;
+            }
+            "q" | "quit" => break,
+            _ => {
+                eprintln!("неизвестная команда `{cmd}`, h — справка");
+                continue;
+            }
+        };
+        if !moved {
+            println!("конец трассы");
+        }
+        print!("{}", session.render());
+    }
+}
@@ -141,6 +141,7 @@ token! {
         self pub Plus "+"
         self pub QuestionMark "?"
         self pub ExclamationPoint "!"
+        self pub Ampersand "&"
     }
 }
 
@@ -15,7 +15,9 @@ use grammar_extended_parser::{
     AnyOrParenOutput, IdentWithDefineGenericsOrIdentOutput, IdentWithGenericsOutput,
     quantificator_feature::*,
 };
-use grammar_shared_macros::{Ast, Ast_Generics, Generics, PATH, to_ident, to_src_ident};
+use grammar_shared_macros::{
+    Ast, Ast_Generics, Derives, Generics, PATH, named_rule, to_ident, to_src_ident,
+};
 use parser::{TransferRule, rules::SeqOutput};
 use parsers::chars::InputStreamTrait;
 use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
@@ -28,8 +30,12 @@ use syn::{Ident, LitStr};
 // TODO добавить джинерики
 pub fn grammar<'src, IS: InputStreamTrait<'src>>(
     output: <Grammar<'src> as TransferRule<IS>>::Output,
+    derives: Derives,
 ) -> (TokenStream2, Ast<'src>) {
-    let mut ast = Default::default();
+    let mut ast = Ast {
+        derives,
+        ..Default::default()
+    };
     let mut codegen = Codegen::<&mut Ast, _>::new(&mut ast);
 
     (
@@ -80,20 +86,21 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<&'_ mut Ast<'src>, IS> {
                     }
                     IdentWithDefineGenericsOrIdentOutput::Ident(v) => (to_src_ident(v), None),
                 };
+                let name = match head {
+                    IdentWithDefineGenericsOrIdentOutput::IdentWithDefineGenerics(
+                        IdentWithGenericsOutput { ident, .. },
+                    ) => ident,
+                    IdentWithDefineGenericsOrIdentOutput::Ident(v) => v,
+                };
                 Codegen::new(Ast_Generics {
                     ast: &mut *self.any,
                     generics,
                 })
-                .reg_expr_choice_expr(
-                    expr,
-                    match head {
-                        IdentWithDefineGenericsOrIdentOutput::IdentWithDefineGenerics(
-                            IdentWithGenericsOutput { ident, .. },
-                        ) => ident,
-                        IdentWithDefineGenericsOrIdentOutput::Ident(v) => v,
-                    },
-                )
-                .map(|expr| quote!(pub type #type_ = #expr;))
+                .reg_expr_choice_expr(expr, name)
+                .map(|expr| {
+                    let (label, expr) = named_rule(name, expr);
+                    quote!(#label pub type #type_ = #expr;)
+                })
                 .unwrap_or_default()
             }
         }
@@ -208,7 +215,9 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, &'_ mut Ast<'s
                 KleeneOutput::OneOrMore(v) | KleeneOutput::ZeroOrMore(v),
             )
             | QuantificatorOutput::Predicative(
-                PredicativeOutput::Optional(v) | PredicativeOutput::NegativeLookahead(v),
+                PredicativeOutput::Optional(v)
+                | PredicativeOutput::NegativeLookahead(v)
+                | PredicativeOutput::AndPredicate(v),
             )
             | QuantificatorOutput::RepeatQuantificator((v, ..))
             | QuantificatorOutput::Joinable(SeqOutput((v, ..))) => self.combinator_or_token(v),
@@ -230,6 +239,9 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, &'_ mut Ast<'s
                 PredicativeOutput::NegativeLookahead(..) => {
                     quote!(#path NegativeLookaheadRule<#expr>)
                 }
+                PredicativeOutput::AndPredicate(..) => {
+                    quote!(#path AndPredicateRule<#expr>)
+                }
             },
             QuantificatorOutput::RepeatQuantificator((_, b)) => match b {
                 RepeatQuantificatorOutput::Maximum(max) => {
@@ -15,11 +15,15 @@
 extern crate self as abstract_parser;
 
 use grammar_extended_parser::quantificator_feature::Grammar;
-use grammar_shared_macros::{raw_str_literal, syn_span};
+use grammar_shared_macros::{Derives, raw_str_literal, syn_span};
 use proc_macro::TokenStream;
 use quote::quote;
 use std::{env, fs::read_to_string, path::PathBuf};
-use syn::{LitStr, parse_macro_input};
+use syn::{
+    LitStr,
+    parse::{ParseStream, Parser},
+    parse_macro_input,
+};
 
 #[proc_macro]
 pub fn light_grammar(input: TokenStream) -> TokenStream {
@@ -31,14 +35,18 @@ pub fn light_grammar(input: TokenStream) -> TokenStream {
         Err(e) => return e.to_compile_error().into(),
     };
 
-    let (v, ast) = codegen::grammar(output);
+    let (v, ast) = codegen::grammar(output, Default::default());
     let ast = ast.light();
     quote!(#v #ast).into()
 }
 
 #[proc_macro]
 pub fn grammar(input: TokenStream) -> TokenStream {
-    let str_lit = parse_macro_input!(input as LitStr);
+    let parser = |input: ParseStream| Ok((input.parse::<Derives>()?, input.parse::<LitStr>()?));
+    let (derives, str_lit) = match parser.parse(input) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let src = str_lit.value();
 
     let output = match syn_span(str_lit, &src, &Grammar::default()) {
@@ -46,7 +54,7 @@ pub fn grammar(input: TokenStream) -> TokenStream {
         Err(e) => return e.to_compile_error().into(),
     };
 
-    let (v, ast) = codegen::grammar(output);
+    let (v, ast) = codegen::grammar(output, derives);
     quote!(#v #ast).into()
 }
 mod codegen;
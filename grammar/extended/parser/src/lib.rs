@@ -379,9 +379,11 @@ pub mod quantificator_feature {
             Predicative {
                 Optional(Optional)
                 NegativeLookahead(NegativeLookahead)
+                AndPredicate(AndPredicate)
             }
                 Optional ( CombinatorOrToken #[ignore] Space #[ignore] QuestionMark  )
                 NegativeLookahead ( #[ignore] ExclamationPoint #[ignore] Space CombinatorOrToken )
+                AndPredicate ( #[ignore] Ampersand #[ignore] Space CombinatorOrToken )
     "#}
 
     type JoinableExpr<'src> = SequenceRule<(
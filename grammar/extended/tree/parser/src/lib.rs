@@ -190,9 +190,11 @@ tree! {r#"
         Predicative {
             Optional(Optional)
             NegativeLookahead(NegativeLookahead)
+            AndPredicate(AndPredicate)
         }
             Optional ( CombinatorOrToken #[ignore] Space #[ignore] QuestionMark  )
             NegativeLookahead ( #[ignore] ExclamationPoint #[ignore] Space CombinatorOrToken )
+            AndPredicate ( #[ignore] Ampersand #[ignore] Space CombinatorOrToken )
 "#}
 
 type JoinableExpr<'src> = SequenceRule<(
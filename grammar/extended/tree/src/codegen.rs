@@ -10,7 +10,7 @@
 // THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
 // 
 
-use super::Ast;
+use super::{Ast, Names};
 use crate::SeqOutput;
 use grammar_extended_parser::{AnyOrParenOutput, IdentWithGenericsOutput};
 use grammar_extended_tree_parser::*;
@@ -50,17 +50,21 @@ impl<IS, Ast: ToTokens> ToTokens for Codegen<Ast, IS> {
 
 impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>, IS> {
     #[inline]
-    pub fn comment_or_item_output(&mut self, v: CommentOrItemOutput<'src, IS>) -> TokenStream2 {
+    pub fn comment_or_item_output(
+        &mut self,
+        v: CommentOrItemOutput<'src, IS>,
+        names: &mut Names<'src>,
+    ) -> TokenStream2 {
         match v {
             CommentOrItemOutput::Comment(v) => {
                 let v = LitStr::new(v, Span::call_site());
                 quote!(#[doc = #v])
             }
-            CommentOrItemOutput::Item(v) => self.item(v),
+            CommentOrItemOutput::Item(v) => self.item(v, names),
         }
     }
 
-    fn item(&mut self, v: ItemOutput<'src, IS>) -> TokenStream2 {
+    fn item(&mut self, v: ItemOutput<'src, IS>, names: &mut Names<'src>) -> TokenStream2 {
         let (head, mut ast_generics) = {
             let (ident, generics) = match v.clone() {
                 ItemOutput::Enum(EnumOutput { head, .. })
@@ -71,6 +75,7 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>,
                     IdentWithDefineGenericsOrIdentOutput::Ident(v) => (v, None),
                 },
             };
+            names.defined.push(ident);
 
             (
                 to_generic_ident(&to_ident(ident), &generics),
@@ -78,15 +83,24 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>,
                     ast: Ast {
                         sub_ast: &mut self.any,
                         ignored: Default::default(),
+                        names,
                     },
                     generics,
                 }),
             )
         };
 
+        // у негенерических деревьев обход для посетителей генерирует `grammar!`, через их
+        // метод у посетителя
+        let derives = if ast_generics.generics.is_some() {
+            ast_generics.ast.derives.clone()
+        } else {
+            ast_generics.ast.derives.without_visit()
+        };
+
         match v {
             ItemOutput::Enum(EnumOutput { variants, .. }) => {
-                let attrs = choice_attrs(&ast_generics.generics);
+                let attrs = choice_attrs(&ast_generics.generics, &derives);
 
                 let iter = variants.into_iter().map(|v| {
                     let ident_ = to_ident(v.item.ident);
@@ -113,7 +127,7 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>,
                             }
                             FieldOutput::Unnamed(v) => {
                                 ast_generics.ast.ignored.is_have_ignored_fields = false;
-                                let v = ast_generics.expr(v);
+                                let v = ast_generics.ignored_expr(v);
                                 quote!(#[abstract_parser(ignore)] _i: #v)
                             }
                         });
@@ -132,11 +146,16 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>,
                         vec![]
                     };
 
+                    let unparse = derives.unparse();
+                    let derives = &derives.0;
+
                     quote! {
                         #[abstract_parser::parsers::chars::macros::sequence_struct(
                             OutputGenerics: <'src, __IS: abstract_parser::parsers::chars::InputStreamTrait<'src> #(, #g: abstract_parser::TransferRule<__IS>)*>
+                            #unparse
                         )]
                         #[abstract_parser::macros::derive_bounds(
+                            #(#derives)*
                             Debug
                                 <'src, IS: abstract_parser::parsers::chars::InputStreamTrait<'src> #(, #g: abstract_parser::parsers::chars::TransferRule<'src, IS, Output: std::fmt::Debug> + Default)*>
                                 <'src, IS #(, #g)*>
@@ -156,7 +175,7 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>,
                             let v = match v {
                                 IgnoredExprVOutput::ParenedSeq(v) => ast_generics.seq(v),
                                 IgnoredExprVOutput::TupleStructExpr(v) => {
-                                    ast_generics.tuple_expr(v)
+                                    ast_generics.ignored_expr(tuple_struct_expr(v))
                                 }
                             };
                             quote!(#[abstract_parser(ignore)] #v)
@@ -164,8 +183,9 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<grammar_shared_macros::Ast<'src>,
                         TupleItemOutput::TupleStructExpr(v) => ast_generics.tuple_expr(v),
                     });
 
+                    let unparse = derives.unparse();
                     quote! {
-                        #[abstract_parser::parsers::chars::macros::sequence_struct]
+                        #[abstract_parser::parsers::chars::macros::sequence_struct(#unparse)]
                         pub struct #head(#(#iter),*);
                     }
                 }
@@ -192,11 +212,19 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, Ast<'_, 'src>>
 
     #[inline]
     pub fn tuple_expr(&mut self, v: TupleStructExprOutput<'src, IS>) -> TokenStream2 {
-        self.expr(match v {
-            TupleStructExprOutput::Choice(v) => ExprOutput::Combinator(CombinatorOutput::Choice(v)),
-            TupleStructExprOutput::Quantificator(v) => ExprOutput::Quantificator(v),
-            TupleStructExprOutput::Token(v) => ExprOutput::Token(v),
-        })
+        self.expr(tuple_struct_expr(v))
+    }
+
+    /// Выражение пропущенного поля: правило, записанное в нём одно, идёт в trivia `kind`.
+    fn ignored_expr(&mut self, v: ExprOutput<'src, IS>) -> TokenStream2 {
+        let is_ident = matches!(v, ExprOutput::Token(TokenOutput::Ident(..)));
+        let start = self.ast.names.used.len();
+        let v = self.expr(v);
+        if is_ident {
+            let idents = self.ast.names.used.split_off(start);
+            self.ast.names.ignored.extend(idents);
+        }
+        v
     }
 
     #[inline]
@@ -286,6 +314,10 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, Ast<'_, 'src>>
                     let v = self.combinator_or_token(v);
                     quote!(#path NegativeLookaheadRule<#v>)
                 }
+                PredicativeOutput::AndPredicate(v) => {
+                    let v = self.combinator_or_token(v);
+                    quote!(#path AndPredicateRule<#v>)
+                }
             },
             QuantificatorOutput::RepeatQuantificator((v, b)) => {
                 let v = self.combinator_or_token(v);
@@ -351,6 +383,7 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, Ast<'_, 'src>>
                     {
                         to_ident(name).to_token_stream()
                     } else {
+                        self.ast.names.used.push(name);
                         to_src_ident(name)
                     };
                     quote!(abstract_parser::rules::RecB<#ident>)
@@ -365,6 +398,7 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, Ast<'_, 'src>>
                     {
                         to_ident(name).to_token_stream()
                     } else {
+                        self.ast.names.used.push(name);
                         to_src_ident(name)
                     },
                     Some(to_ident(name)),
@@ -379,8 +413,20 @@ impl<'src, IS: InputStreamTrait<'src>> Codegen<Ast_Generics<'src, Ast<'_, 'src>>
         &mut self,
         IdentWithGenericsOutput { ident, generics }: <IdentWithExprGenerics<'src> as parser::TransferRule<IS>>::Output,
     ) -> Output {
+        self.ast.names.used.push(ident);
         let ident = to_ident(ident);
         let generics = generics.into_iter().map(|v| self.expr(v));
         (quote!(#ident<'src, #(#generics),*>), Some(ident))
     }
 }
+
+#[inline]
+fn tuple_struct_expr<'src, IS: InputStreamTrait<'src>>(
+    v: TupleStructExprOutput<'src, IS>,
+) -> ExprOutput<'src, IS> {
+    match v {
+        TupleStructExprOutput::Choice(v) => ExprOutput::Combinator(CombinatorOutput::Choice(v)),
+        TupleStructExprOutput::Quantificator(v) => ExprOutput::Quantificator(v),
+        TupleStructExprOutput::Token(v) => ExprOutput::Token(v),
+    }
+}
@@ -18,16 +18,41 @@ mod codegen;
 
 use crate::codegen::Codegen;
 use grammar_extended_tree_parser::Grammar;
-use grammar_shared_macros::syn_span;
+use grammar_shared_macros::{Derives, green_kind, syn_span};
 use parser::rules::SeqOutput;
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{ToTokens, quote};
-use syn::{LitStr, parse_macro_input};
+use syn::{
+    Ident, LitStr,
+    parse::{ParseStream, Parser},
+};
 
+/// `tree! { #[derive(..)] kind TreeKind r#"..."# }`: с `kind` рядом с деревьями генерируется
+/// перечисление видов узлов для `abstract_parser::green`, как `SyntaxKind` у `grammar!`. Имя
+/// задаётся явно: `tree!` часто вызывается несколько раз в одном модуле рядом с `grammar!`.
 #[proc_macro]
 pub fn tree(input: TokenStream) -> TokenStream {
-    let str_lit = parse_macro_input!(input as LitStr);
+    let parser = |input: ParseStream| {
+        let derives = input.parse::<Derives>()?;
+        let kind = if input.peek(Ident) {
+            let flag = input.parse::<Ident>()?;
+            if flag != "kind" {
+                return Err(syn::Error::new(
+                    flag.span(),
+                    "expected `kind` or a string literal",
+                ));
+            }
+            Some(input.parse::<Ident>()?)
+        } else {
+            None
+        };
+        Ok((derives, kind, input.parse::<LitStr>()?))
+    };
+    let (derives, kind, str_lit) = match parser.parse(input) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error().into(),
+    };
     let src = str_lit.value();
 
     let output = match syn_span(str_lit, &src, &Grammar::default()) {
@@ -35,18 +60,60 @@ pub fn tree(input: TokenStream) -> TokenStream {
         Err(e) => return e.to_compile_error().into(),
     };
 
-    let mut codegen = Codegen::new(grammar_shared_macros::Ast::default());
+    let mut codegen = Codegen::new(grammar_shared_macros::Ast {
+        derives,
+        ..Default::default()
+    });
 
+    let mut names = Names::default();
     let iter = output
         .into_iter()
-        .map(|v| codegen.comment_or_item_output(v));
+        .map(|v| codegen.comment_or_item_output(v, &mut names))
+        .collect::<Vec<_>>();
+    let kind = kind.map(|v| names.green_kind(&v));
+
+    quote!(#(#iter)* #codegen #kind).into()
+}
+
+/// Имена для `kind`: деревья блока, правила, на которые они ссылаются, и правила в
+/// пропущенных полях.
+#[derive(Default)]
+struct Names<'src> {
+    defined: Vec<&'src str>,
+    used: Vec<&'src str>,
+    ignored: Vec<&'src str>,
+}
 
-    quote!(#(#iter)* #codegen).into()
+impl Names<'_> {
+    /// Trivia, как у `grammar!`: `Space`, `Comment` и правила, которые встречаются только
+    /// пропущенными полями.
+    fn green_kind(self, kind: &Ident) -> TokenStream2 {
+        let trivia = self
+            .ignored
+            .iter()
+            .filter(|v| !self.used.contains(v))
+            .chain(&["Space", "Comment"])
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        let mut names = Vec::<String>::new();
+        for v in self
+            .defined
+            .into_iter()
+            .chain(self.used)
+            .chain(self.ignored)
+        {
+            if !names.iter().any(|name| name == v) {
+                names.push(v.to_owned());
+            }
+        }
+        green_kind(kind, &names, &trivia)
+    }
 }
 
 struct Ast<'sub_ast, 'src> {
     sub_ast: &'sub_ast mut grammar_shared_macros::Ast<'src>,
     ignored: IgnoredFields<'src>,
+    names: &'sub_ast mut Names<'src>,
 }
 
 const _: () = {
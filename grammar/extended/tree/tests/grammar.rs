@@ -12,6 +12,10 @@
 
 #![feature(phantom_variance_markers, macro_metavar_expr_concat)]
 
+use abstract_parser::{
+    parsers::chars::{CharParser, InputStreamIter},
+    rules::SeqOutput,
+};
 use grammar_core::parser::grammar::check;
 
 #[test]
@@ -25,6 +29,22 @@ fn grammar() {
     );
 }
 
+#[test]
+fn lookahead() {
+    check::<Keyword>("let", Ok(((), "let")));
+    check::<Kw>("fn", Ok(SeqOutput(((), "fn"))));
+    assert!(
+        InputStreamIter::new("lot")
+            .full_parse(&Keyword::default())
+            .is_err()
+    );
+    assert!(
+        InputStreamIter::new("fun")
+            .full_parse(&Kw::default())
+            .is_err()
+    );
+}
+
 use tmp::*;
 mod tmp {
     use abstract_parser::grammar::extended::{macros::grammar, tree::macros::tree};
@@ -46,5 +66,64 @@ mod tmp {
             A2("asd"{2,})
             B("asd" / <D>{2,3})
         }
+        Keyword (&"(let|fn)\b" Ident)
+    "#}
+}
+
+use kw::*;
+mod kw {
+    use super::Ident;
+    use abstract_parser::grammar::extended::macros::grammar;
+
+    grammar! {r#"
+        Kw = &"(let|fn)\b" Ident
+    "#}
+}
+
+#[test]
+fn green_kind() {
+    use abstract_parser::green::{GreenKind, green};
+
+    let src = "a, 1";
+    let (out, tree) =
+        green::<TreeKind, _>(|| InputStreamIter::new(src).full_parse(&Pair::default()));
+    assert!(out.is_ok());
+    assert_eq!(
+        tree[0].to_string(),
+        "\
+Pair 0..4
+  Word 0..1
+  Sep 1..3
+  Value 3..4
+    Num 3..4
+"
+    );
+    assert_eq!(TreeKind::from_rule("Value"), Some(TreeKind::Value));
+    assert_eq!(TreeKind::from_rule("Num"), Some(TreeKind::Num));
+    assert_eq!(TreeKind::Pair.name(), "Pair");
+    assert!(TreeKind::Sep.is_trivia());
+    assert!(!TreeKind::Word.is_trivia());
+}
+
+use kinds::*;
+mod kinds {
+    use abstract_parser::grammar::extended::{macros::grammar, tree::macros::tree};
+
+    grammar! {r#"
+        Word = "[a-z]+"
+        Num = "[0-9]+"
+        Sep = ",\s*"
+    "#}
+
+    tree! { kind TreeKind r#"
+        Pair {
+            key: Word,
+            Sep,
+            value: Value
+        }
+        Value {
+            Num(Num)
+            Word(Word)
+        }
     "#}
 }
@@ -23,7 +23,7 @@ const _: () = ();
 extern crate grammar_feature_parsing as parsing;
 
 use grammar_feature_parser::feature;
-use grammar_shared_macros::{raw_str_literal, syn_span, to_ident};
+use grammar_shared_macros::{raw_str_literal, syn_span, to_ident, Derives, SynSpan};
 use parser::{
     rules::{OptionalRule, SeqOutput, SequenceRule},
     Promotable,
@@ -35,10 +35,12 @@ use parsers::{
         rules::{IdentRule, SynToken},
     },
 };
-use parsing::{default_feature_rule, features_parse, parse_by_features};
+use parsing::{
+    default_feature_rule, features_parse, lint::lint, parse_by_features, syntax_kind, visitors,
+};
 use proc_macro::TokenStream;
 use proc_macro2::{Span, TokenStream as TokenStream2};
-use quote::{quote, ToTokens};
+use quote::{quote, quote_spanned, ToTokens};
 use std::{default, env, fs::read_to_string, path::PathBuf};
 use std_reset::prelude::{Default, Deref};
 use syn::{parse_macro_input, LitStr};
@@ -46,11 +48,25 @@ use syn::{parse_macro_input, LitStr};
 #[derive(Default, Deref)]
 struct Debug(#[default("debug")] &'static str);
 
+/// `warn`: предупреждения проверок (недостижимые правила) становятся предупреждениями
+/// компилятора на месте правила в литерале.
+#[derive(Default, Deref)]
+struct Warn(#[default("warn")] &'static str);
+
+#[derive(Default, Deref)]
+struct Railroad(#[default("railroad")] &'static str);
+
+/// `railroad "dir"`: каталог для диаграмм правил относительно вызывающего файла.
+type RailroadFlag = OptionalRule<SequenceRule<(IdentRule<Railroad>, SynToken<LitStr>)>>;
+
 #[proc_macro]
 pub fn grammar(input: TokenStream) -> TokenStream {
-    let SeqOutput((debug, str_lit)) = TokenStreamIter::new(input.clone())
+    let SeqOutput((derives, debug, warn, railroad, str_lit)) = TokenStreamIter::new(input.clone())
         .parse(&<SequenceRule<(
+            SynToken<Derives>,
             OptionalRule<IdentRule<Debug>>,
+            OptionalRule<IdentRule<Warn>>,
+            RailroadFlag,
             SynToken<LitStr>,
         )>>::default())
         .unwrap();
@@ -64,20 +80,60 @@ pub fn grammar(input: TokenStream) -> TokenStream {
     // .peekable();
     // let v = parse_by_features(&mut iter);
 
-    let v = features_parse(match syn_span(str_lit, &src, &default_feature_rule()) {
+    let span = SynSpan::from_str_lit(&src, str_lit.clone());
+    let features = match syn_span(str_lit, &src, &default_feature_rule()) {
         Ok(v) => v,
         Err(e) => return e.to_compile_error().into(),
-    });
+    };
+    // правила грамматики публичны, и любое может быть входом: недостижимые не ошибка,
+    // а предупреждение по флагу `warn`
+    let (warnings, errors) = lint(&features)
+        .into_iter()
+        .partition::<Vec<_>, _>(|v| v.warning);
+    if !errors.is_empty() {
+        return errors
+            .into_iter()
+            .map(|v| syn::Error::new(span.span(v.at), v.message).to_compile_error())
+            .collect::<TokenStream2>()
+            .into();
+    }
+    let warnings = warnings
+        .into_iter()
+        .filter(|_| warn.is_some())
+        .map(|v| warning(span.span(v.at), &v.message))
+        .collect::<TokenStream2>();
+    if let Some(SeqOutput((_, dir))) = &railroad {
+        if let Err(e) = write_railroad(&src, &dir.value()) {
+            return syn::Error::new(dir.span(), e).to_compile_error().into();
+        }
+    }
+    let kind = syntax_kind(&features);
+    let visit = visitors(&features, &derives);
+    let v = features_parse(features, &derives);
 
     if debug.is_some() {
         let v = raw_str_literal(&v.to_string());
         quote!(const GRAMMAR_DEBUG: &'static str = #v;)
     } else {
-        v
+        quote!(#v #kind #visit #warnings)
     }
     .into()
 }
 
+/// Стабильного `compile_warning!` нет: предупреждение — использование устаревшего элемента
+/// с `message` в `note`, на месте `span`.
+fn warning(span: Span, message: &str) -> TokenStream2 {
+    let use_ = quote_spanned!(span=> grammar_warning);
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_camel_case_types)]
+            struct grammar_warning;
+            let _ = #use_;
+        };
+    }
+}
+
 #[derive(Deref)]
 struct Ast<'a, 'src> {
     #[deref]
@@ -107,8 +163,9 @@ impl<'src> Ignored<'src> {
     }
 }
 
-#[proc_macro]
-pub fn grammar_from_file(input: TokenStream) -> TokenStream {
+/// Каталог файла, из которого вызван макрос: пути в `grammar_from_file!` и
+/// `snapshot_tests!` задаются относительно него.
+fn source_dir() -> PathBuf {
     let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
 
     let sub = {
@@ -135,19 +192,56 @@ pub fn grammar_from_file(input: TokenStream) -> TokenStream {
         }
     };
 
-    let SeqOutput((debug, lit_str)) = TokenStreamIter::new(input.clone())
+    root.join(sub).parent().unwrap().to_path_buf()
+}
+
+/// Пишет SVG-диаграммы правил и `index.html` в каталог `dir`.
+fn write_railroad(src: &str, dir: &str) -> Result<(), String> {
+    let grammar = grammar_runtime::ast::Grammar::parse(src).map_err(|e| e.to_string())?;
+    let dir = source_dir().join(dir);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{e} Path: {}.", dir.display()))?;
+    let title = dir
+        .file_name()
+        .map_or_else(String::new, |v| v.to_string_lossy().into_owned());
+    for (name, content) in grammar_runtime::railroad::railroad(&grammar, &title) {
+        let path = dir.join(name);
+        std::fs::write(&path, content).map_err(|e| format!("{e} Path: {}.", path.display()))?;
+    }
+    Ok(())
+}
+
+#[proc_macro]
+pub fn grammar_from_file(input: TokenStream) -> TokenStream {
+    let SeqOutput((derives, debug, warn, railroad, lit_str)) = TokenStreamIter::new(input.clone())
         .parse(&<SequenceRule<(
+            SynToken<Derives>,
             OptionalRule<IdentRule<Debug>>,
+            OptionalRule<IdentRule<Warn>>,
+            RailroadFlag,
             SynToken<LitStr>,
         )>>::default())
         .unwrap();
+    let railroad = railroad.map(|SeqOutput((flag, dir))| quote!(#flag #dir));
 
-    let full = root.join(sub).parent().unwrap().join(lit_str.value());
+    let full = source_dir().join(lit_str.value());
 
     proc_macro::tracked_path::path(full.to_string_lossy());
 
     let v = raw_str_literal(
         &read_to_string(&full).unwrap_or_else(|e| panic!("{e} Path: {}.", full.display())),
     );
-    quote!(abstract_parser::grammar::feature::grammar::grammar! {#debug #v}).into()
+    quote!(
+        abstract_parser::grammar::feature::grammar::grammar! {#derives #debug #warn #railroad #v}
+    )
+    .into()
+}
+
+/// ```rust,ignore
+/// snapshot_tests!("corpus/*.abs", Grammar);
+/// snapshot_tests!("corpus/*.doc", Doc, json);
+/// ```
+#[proc_macro]
+pub fn snapshot_tests(input: TokenStream) -> TokenStream {
+    snapshot::snapshot_tests(input)
 }
+mod snapshot;
@@ -47,7 +47,7 @@ fn cpcl_bench(c: &mut Criterion) {
             .unwrap();
         b.iter_batched(
             || output.clone(),
-            |output| grammar_feature_parsing::features_parse(output),
+            |output| grammar_feature_parsing::features_parse(output, &Default::default()),
             BatchSize::SmallInput,
         );
     });
@@ -31,7 +31,7 @@ use grammar_feature_parser::{
     AliasExprOutput, AliasRule, ChoiceRule, ParseTokenOutput, QuantificatorRule, SequenceRule,
     Token, TokenHeadOutput,
 };
-use grammar_shared_macros::{raw_str_literal, syn_span, to_ident};
+use grammar_shared_macros::{green_kind, named_rule, raw_str_literal, syn_span, to_ident, Derives};
 use parser::{
     cached::CachedIter,
     rules::{JoinableRule, Repeat, SeqOutput, VecChoiceRule, WrapRule},
@@ -41,6 +41,8 @@ use parsers::chars::{CharParser, InputStreamIter, InputStreamTrait};
 use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
 use quote::quote;
 
+pub mod lint;
+
 pub fn default_feature_rule<'src>() -> WrapRule<
     Space<'src>,
     JoinableRule<Repeat, VecChoiceRule<FeatureRule<'src>>, Space<'src>>,
@@ -119,6 +121,7 @@ pub fn parse_by_features<'src, IS: InputStreamTrait<'src>>(
                         Space::default(),
                     ))
                     .unwrap(),
+                    &Derives::default(),
                 ));
             }
         }
@@ -128,6 +131,7 @@ pub fn parse_by_features<'src, IS: InputStreamTrait<'src>>(
 
 pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
     features: Vec<<FeatureRule<'src> as TransferRule<IS>>::Output>,
+    derives: &Derives,
 ) -> TokenStream2 {
     features
         .into_iter()
@@ -182,9 +186,10 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
                     match v.head {
                         TokenHeadOutput::Unit(name) => {
                             let name = to_ident(name);
+                            let attrs = derives.attrs();
                             quote! {
                                 abstract_parser::parsers::chars::#macros! {
-                                    self pub #name #expr
+                                    #attrs self pub #name #expr
                                 }
                             }
                         }
@@ -192,11 +197,13 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
                             name,
                             type_,
                         }) => {
-                            let name = to_ident(name);
                             let name_parse = to_ident(&format!("{name}_parse"));
                             let type_ = to_ident(type_);
+                            let (label, rule) = named_rule(name, quote!(#name_parse<'src, #type_>));
+                            let name = to_ident(name);
                             quote! {
-                                pub type #name<'src> = #name_parse<'src, #type_>;
+                                #label
+                                pub type #name<'src> = #rule;
                                 abstract_parser::parsers::chars::#macros! {
                                     parse pub #name_parse #expr
                                 }
@@ -221,14 +228,14 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
                                 "{head} = {}",
                                 seq(&v.expr)
                             ));
-                            quote!(abstract_parser::grammar::extended::macros::grammar! {#v})
+                            quote!(abstract_parser::grammar::extended::macros::grammar! {#derives #v})
                         }
                         Feature::QuantificatorRule(v) => {
                             let v = raw_str_literal(&format!(
                                 "{head} = {}",
                                 quantificator(&v.expr)
                             ));
-                            quote!(abstract_parser::grammar::extended::macros::grammar! {#v})
+                            quote!(abstract_parser::grammar::extended::macros::grammar! {#derives #v})
                         }
                         Feature::AliasRule(v) => {
                             let is_module = matches!(&v.expr, AliasExprOutput::IdentWithExprGenerics(..));
@@ -243,7 +250,7 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
                                         }
                                     )
                                 ));
-                                quote!(abstract_parser::grammar::extended::macros::grammar! {#v})
+                                quote!(abstract_parser::grammar::extended::macros::grammar! {#derives #v})
                             };
 
                             if is_module {
@@ -259,7 +266,7 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
                                         "{head} = {}",
                                         choice(&v.expr)
                                     ));
-                                    quote!(abstract_parser::grammar::extended::macros::grammar! {#v})
+                                    quote!(abstract_parser::grammar::extended::macros::grammar! {#derives #v})
                                 }
                                 _ => {
                                     let body = match v {
@@ -316,7 +323,7 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
                                         _ => unreachable!()
                                     };
                                     let v = raw_str_literal(&format!("\n{head} {body}\n"));
-                                    quote!(abstract_parser::grammar::extended::tree::macros::tree! {#v})
+                                    quote!(abstract_parser::grammar::extended::tree::macros::tree! {#derives #v})
                                 }
                             };
 
@@ -349,6 +356,383 @@ pub fn features_parse<'src, IS: InputStreamTrait<'src>>(
         .collect()
 }
 
+/// `SyntaxKind` грамматики для [`abstract_parser::green`]: по варианту на каждое правило и
+/// токен грамматики, на внешние правила, на которые она ссылается (`Space`, `Ident`, ..), и
+/// `Anonymous` для литералов внутри правил. Trivia — `Space`, `Comment` и правила, которые
+/// встречаются только пропущенными полями.
+pub fn syntax_kind<'src, IS: InputStreamTrait<'src>>(
+    features: &[<FeatureRule<'src> as TransferRule<IS>>::Output],
+) -> TokenStream2 {
+    let mut defined = vec![];
+    let mut used = vec![];
+    let mut ignored = vec![];
+    for v in features {
+        let (head, exprs) = match v {
+            Feature::Choice(v) => (
+                &v.head,
+                v.variants
+                    .iter()
+                    .map(|v| (false, expr_(&v.item.value)))
+                    .collect(),
+            ),
+            Feature::Sequence(v) => (
+                &v.head,
+                match &v.fields {
+                    StructTypeOutput::Struct(v) => v
+                        .iter()
+                        .map(|v| match &v.item {
+                            FieldOutput::Named(v) => (false, expr_(&v.value)),
+                            FieldOutput::Unnamed(v) => (true, expr_(v)),
+                        })
+                        .collect(),
+                    StructTypeOutput::Tuple(v) => v
+                        .iter()
+                        .map(|v| {
+                            let tuple_struct_expr = |v: &_| match v {
+                                TupleStructExprOutput::Choice(v) => choice(v),
+                                TupleStructExprOutput::Quantificator(v) => quantificator(v),
+                                TupleStructExprOutput::Token(v) => token(v),
+                            };
+                            match v {
+                                TupleItemOutput::Ignored(IgnoredExprVOutput::ParenedSeq(v)) => {
+                                    (true, seq(v))
+                                }
+                                TupleItemOutput::Ignored(IgnoredExprVOutput::TupleStructExpr(
+                                    v,
+                                )) => (true, tuple_struct_expr(v)),
+                                TupleItemOutput::TupleStructExpr(v) => {
+                                    (false, tuple_struct_expr(v))
+                                }
+                            }
+                        })
+                        .collect(),
+                },
+            ),
+            Feature::ChoiceRule(v) => (&v.head, vec![(false, choice(&v.expr))]),
+            Feature::SequenceRule(v) => (&v.head, vec![(false, seq(&v.expr))]),
+            Feature::QuantificatorRule(v) => (&v.head, vec![(false, quantificator(&v.expr))]),
+            Feature::AliasRule(v) => (
+                &v.head,
+                vec![(
+                    false,
+                    token(&match v.expr.clone() {
+                        AliasExprOutput::IdentWithExprGenerics(v) => {
+                            TokenOutput::IdentWithExprGenerics(v)
+                        }
+                        AliasExprOutput::BoxedIdent(v) => TokenOutput::BoxedIdent(v),
+                        AliasExprOutput::Ident(v) => TokenOutput::Ident(v),
+                    }),
+                )],
+            ),
+            Feature::Token(v) => {
+                defined.push(match &v.head {
+                    TokenHeadOutput::Unit(v) | TokenHeadOutput::Base(v) => *v,
+                    TokenHeadOutput::Parse(v) => v.name,
+                });
+                continue;
+            }
+            Feature::Comment(..) => continue,
+        };
+        let (name, generics) = match head {
+            IdentWithDefineGenericsOrIdentOutput::Ident(v) => (*v, &[][..]),
+            IdentWithDefineGenericsOrIdentOutput::IdentWithDefineGenerics(v) => {
+                (v.ident, &v.generics[..])
+            }
+        };
+        defined.push(name);
+        for (is_ignored, expr) in exprs {
+            let expr_idents = idents(&expr)
+                .filter(|v| !generics.contains(v))
+                .map(String::from)
+                .collect::<Vec<_>>();
+            match &expr_idents[..] {
+                [v] if is_ignored && expr.trim() == v => ignored.push(v.clone()),
+                _ => used.extend(expr_idents),
+            }
+        }
+    }
+    let trivia = ignored
+        .iter()
+        .filter(|v| !used.contains(v))
+        .cloned()
+        .chain(["Space".to_owned(), "Comment".to_owned()])
+        .collect::<Vec<_>>();
+    used.extend(ignored);
+
+    let mut names = defined.into_iter().map(String::from).collect::<Vec<_>>();
+    for v in used {
+        if !names.contains(&v) {
+            names.push(v);
+        }
+    }
+    green_kind(&to_ident("SyntaxKind"), &names, &trivia)
+}
+
+/// Посетители из `#[derive(Visit, VisitMut, Fold)]` грамматики: трейт с методом на каждое
+/// негенерическое правило-дерево, модуль с обходом по умолчанию (`visit::visit_doc`, ..) и
+/// [`abstract_parser::visit`] для выходных типов и unit-токенов. Выход кортежной структуры —
+/// обычный кортеж, поэтому её метод вызывается из родителя, ссылающегося на неё напрямую
+/// (под повтором или `Option` она обходится структурно), а её обходу по умолчанию `IS`
+/// указывается явно: `visit::visit_pair::<IS, _>(self, node)`.
+pub fn visitors<'src, IS: InputStreamTrait<'src>>(
+    features: &[<FeatureRule<'src> as TransferRule<IS>>::Output],
+    derives: &Derives,
+) -> TokenStream2 {
+    let mut nodes = vec![];
+    let mut tuples = vec![];
+    let mut units = vec![];
+    for v in features {
+        match v {
+            Feature::Choice(EnumOutput {
+                head: IdentWithDefineGenericsOrIdentOutput::Ident(name),
+                variants,
+                ..
+            }) => nodes.push((
+                *name,
+                Err(variants
+                    .iter()
+                    .map(|v| (to_ident(v.item.ident), expr_(&v.item.value)))
+                    .collect::<Vec<_>>()),
+            )),
+            Feature::Sequence(StructOutput {
+                head: IdentWithDefineGenericsOrIdentOutput::Ident(name),
+                fields: StructTypeOutput::Struct(fields),
+                ..
+            }) => nodes.push((
+                *name,
+                Ok(fields
+                    .iter()
+                    .filter_map(|v| match &v.item {
+                        FieldOutput::Named(v) => {
+                            let name = to_ident(v.name);
+                            Some((quote!(.#name), expr_(&v.value)))
+                        }
+                        FieldOutput::Unnamed(..) => None,
+                    })
+                    .collect::<Vec<_>>()),
+            )),
+            Feature::Sequence(StructOutput {
+                head: IdentWithDefineGenericsOrIdentOutput::Ident(name),
+                fields: StructTypeOutput::Tuple(items),
+                ..
+            }) => {
+                let items = items
+                    .iter()
+                    .filter_map(|v| match v {
+                        TupleItemOutput::TupleStructExpr(v) => Some(match v {
+                            TupleStructExprOutput::Choice(v) => choice(v),
+                            TupleStructExprOutput::Quantificator(v) => quantificator(v),
+                            TupleStructExprOutput::Token(v) => token(v),
+                        }),
+                        TupleItemOutput::Ignored(..) => None,
+                    })
+                    .collect::<Vec<_>>();
+                // единственный элемент и есть выход, без кортежа
+                let single = items.len() == 1;
+                tuples.push(name.to_string());
+                nodes.push((
+                    *name,
+                    Ok(items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, v)| {
+                            let i = syn::Index::from(i);
+                            (if single { quote!() } else { quote!(.#i) }, v)
+                        })
+                        .collect()),
+                ))
+            }
+            Feature::Token(v) => {
+                if let TokenHeadOutput::Unit(name) = &v.head {
+                    units.push(to_ident(&format!("{name}Token")))
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let is = quote!(abstract_parser::parsers::chars::InputStreamTrait<'src>);
+    [
+        ("Visit", "visit_{}", "Walk"),
+        ("VisitMut", "visit_{}_mut", "WalkMut"),
+        ("Fold", "fold_{}", "FoldWith"),
+    ]
+    .iter()
+    .filter(|(derive, ..)| derives.contains(derive))
+    .map(|&(derive, method, walk)| {
+        let trait_ = to_ident(derive);
+        let module = to_ident(&snake_case(derive));
+        let walk = to_ident(walk);
+        let is_fold = derive == "Fold";
+        let (walk_method, ref_) = match derive {
+            "Visit" => (quote!(walk), quote!(&)),
+            "VisitMut" => (quote!(walk_mut), quote!(&mut)),
+            _ => (quote!(fold_with), quote!()),
+        };
+        let ret = is_fold.then(|| quote!(-> Self));
+
+        let method_of = |name: &str| to_ident(&method.replace("{}", &snake_case(name)));
+        // кортежная структура своего `Walk` не имеет: её метод вызывается напрямую
+        let call = |expr: &String, arg| {
+            if tuples.contains(expr) {
+                let method = method_of(expr);
+                quote!(v.#method(#arg))
+            } else {
+                quote!(abstract_parser::visit::#walk::#walk_method(#arg, v))
+            }
+        };
+
+        let nodes = nodes.iter().map(|(name, children)| {
+            let method = method_of(name);
+            let output = to_ident(&format!("{name}Output"));
+            let node = quote!(#output<'src, IS>);
+            let ret_node = is_fold.then(|| quote!(-> #node));
+            let body = match (children, is_fold) {
+                (Ok(fields), false) => {
+                    let calls = fields
+                        .iter()
+                        .map(|(path, expr)| call(expr, quote!(#ref_ (*node) #path)));
+                    quote!(#(#calls;)*)
+                }
+                (Ok(fields), true) => {
+                    let calls = fields.iter().map(|(path, expr)| {
+                        let call = call(expr, quote!(node #path));
+                        quote!(node #path = #call;)
+                    });
+                    quote! {
+                        let mut node = node;
+                        #(#calls)*
+                        node
+                    }
+                }
+                (Err(variants), false) => {
+                    let arms = variants.iter().map(|(variant, expr)| {
+                        let call = call(expr, quote!(node));
+                        quote!(#output::#variant(node) => #call)
+                    });
+                    quote! {
+                        match node {
+                            #(#arms),*
+                        }
+                    }
+                }
+                (Err(variants), true) => {
+                    let arms = variants.iter().map(|(variant, expr)| {
+                        let call = call(expr, quote!(node));
+                        quote!(#output::#variant(node) => #output::#variant(#call))
+                    });
+                    quote! {
+                        match node {
+                            #(#arms),*
+                        }
+                    }
+                }
+            };
+            let walk_impl = (!tuples.contains(&name.to_string())).then(|| {
+                quote! {
+                    impl<'src, IS: #is, V: #trait_<'src, IS> + ?Sized> abstract_parser::visit::#walk<V> for #node {
+                        #[inline]
+                        fn #walk_method(#ref_ self, v: &mut V) #ret {
+                            v.#method(self)
+                        }
+                    }
+                }
+            });
+            (
+                quote! {
+                    fn #method(&mut self, node: #ref_ #node) #ret_node {
+                        #module::#method(self, node)
+                    }
+                },
+                quote! {
+                    #[allow(unused_mut)]
+                    pub fn #method<'src, IS: #is, V: #trait_<'src, IS> + ?Sized>(
+                        v: &mut V,
+                        node: #ref_ #node,
+                    ) #ret_node {
+                        #body
+                    }
+                },
+                walk_impl,
+            )
+        });
+        let (methods, fns, impls) = nodes.fold((vec![], vec![], vec![]), |mut acc, v| {
+            acc.0.push(v.0);
+            acc.1.push(v.1);
+            acc.2.push(v.2);
+            acc
+        });
+        let ret = if is_fold { quote!(-> Self { self }) } else { quote!({}) };
+
+        quote! {
+            pub trait #trait_<'src, IS: #is> {
+                #(#methods)*
+            }
+
+            pub mod #module {
+                use super::*;
+
+                #(#fns)*
+            }
+
+            #(#impls)*
+
+            #(
+                impl<V: ?Sized> abstract_parser::visit::#walk<V> for #units {
+                    #[inline]
+                    fn #walk_method(#ref_ self, _: &mut V) #ret
+                }
+            )*
+        }
+    })
+    .collect()
+}
+
+/// `TypeAndCommand` -> `type_and_command`.
+fn snake_case(v: &str) -> String {
+    let mut out = String::new();
+    let chars = v.chars().collect::<Vec<_>>();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|v| v.is_lowercase());
+            if prev.is_lowercase() || prev.is_ascii_digit() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Идентификаторы выражения `.abs`, без содержимого литералов.
+fn idents(expr: &str) -> impl Iterator<Item = &str> {
+    let mut rest = expr;
+    std::iter::from_fn(move || loop {
+        let c = rest.chars().next()?;
+        if c == '"' {
+            let mut escaped = false;
+            let end = rest[1..]
+                .find(|c| {
+                    let end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                })
+                .map_or(rest.len(), |v| v + 2);
+            rest = rest[end..].strip_prefix('s').unwrap_or(&rest[end..]);
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            let (ident, tail) = rest.split_at(end);
+            rest = tail;
+            return Some(ident);
+        } else {
+            rest = &rest[c.len_utf8()..];
+        }
+    })
+}
+
 macro_rules! fast {
     ($($t:ident)+) => {
         pub type FeatureRule<'src> = Feature<$($t<'src>),+>;
@@ -439,7 +823,9 @@ fn quantificator<'src>(v: &QuantificatorOutput<'src, impl InputStreamTrait<'src>
     let expr = match v {
         QuantificatorOutput::Kleene(KleeneOutput::ZeroOrMore(v) | KleeneOutput::OneOrMore(v))
         | QuantificatorOutput::Predicative(
-            PredicativeOutput::Optional(v) | PredicativeOutput::NegativeLookahead(v),
+            PredicativeOutput::Optional(v)
+            | PredicativeOutput::NegativeLookahead(v)
+            | PredicativeOutput::AndPredicate(v),
         )
         | QuantificatorOutput::RepeatQuantificator((v, ..))
         | QuantificatorOutput::Joinable(SeqOutput((v, ..))) => match v {
@@ -461,6 +847,7 @@ fn quantificator<'src>(v: &QuantificatorOutput<'src, impl InputStreamTrait<'src>
         QuantificatorOutput::Predicative(v) => match v {
             PredicativeOutput::Optional(..) => format!("{expr}?"),
             PredicativeOutput::NegativeLookahead(..) => format!("!{expr}"),
+            PredicativeOutput::AndPredicate(..) => format!("&{expr}"),
         },
         QuantificatorOutput::RepeatQuantificator((_, q)) => format!("{expr}{}", q_(q)),
         QuantificatorOutput::Joinable(SeqOutput((_, j, join))) => format!(
@@ -0,0 +1,869 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Проверки грамматики до генерации кода: неопределённые и недостижимые правила, число
+//! дженерик-аргументов, левая рекурсия, повторы выражений, совпадающих с пустой строкой,
+//! и альтернативы выбора, которые перекрывает предыдущая. Место каждой находки —
+//! подстрока исходника, по ней [`SynSpan`](grammar_shared_macros::SynSpan) даёт точный спан.
+
+use super::*;
+use fancy_regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lint<'src> {
+    /// Подстрока исходника грамматики, к которой относится находка.
+    pub at: &'src str,
+    pub kind: LintKind,
+    pub message: String,
+    /// Предупреждение не останавливает генерацию.
+    pub warning: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+    Duplicate,
+    Undefined,
+    Arity,
+    EmptyLoop,
+    Shadowed,
+    LeftRecursion,
+    Unreachable,
+}
+
+impl LintKind {
+    /// Имя для кода диагностики: `undefined-rule`, `left-recursion`, ..
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate-rule",
+            Self::Undefined => "undefined-rule",
+            Self::Arity => "generic-arity",
+            Self::EmptyLoop => "empty-loop",
+            Self::Shadowed => "shadowed-alternative",
+            Self::LeftRecursion => "left-recursion",
+            Self::Unreachable => "unreachable-rule",
+        }
+    }
+}
+
+/// Правила из `grammar::core::parser`, доступные грамматике без определения: имя, число
+/// дженериков, может ли совпасть с пустой строкой (`Arg` — если может аргумент) и
+/// разбирается ли аргумент первым.
+const PRELUDE: &[(&str, usize, Nullable, bool)] = &[
+    ("Grammar", 1, Nullable::Yes, true),
+    ("Rule", 2, Nullable::No, true),
+    ("DefineHead", 1, Nullable::No, true),
+    ("Braced", 1, Nullable::No, false),
+    ("Chevroned", 1, Nullable::No, false),
+    ("Parened", 1, Nullable::No, false),
+    ("Spaced", 1, Nullable::Arg, true),
+    ("RegExpr", 0, Nullable::No, false),
+    ("StrLiteral", 0, Nullable::No, false),
+    ("Content", 0, Nullable::Yes, false),
+    ("Space", 0, Nullable::Yes, false),
+    ("StrictSpace", 0, Nullable::No, false),
+    ("Ident", 0, Nullable::No, false),
+    ("Number", 0, Nullable::No, false),
+    ("SPrefix", 0, Nullable::No, false),
+    ("DoubleQuote", 0, Nullable::No, false),
+    ("OpenBrace", 0, Nullable::No, false),
+    ("CloseBrace", 0, Nullable::No, false),
+    ("OpenChevron", 0, Nullable::No, false),
+    ("CloseChevron", 0, Nullable::No, false),
+    ("OpenParen", 0, Nullable::No, false),
+    ("CloseParen", 0, Nullable::No, false),
+    ("Eq", 0, Nullable::No, false),
+    ("Comma", 0, Nullable::No, false),
+    ("Dot", 0, Nullable::No, false),
+    ("Colon", 0, Nullable::No, false),
+    ("Semicolon", 0, Nullable::No, false),
+    ("Slash", 0, Nullable::No, false),
+    ("Asterisk", 0, Nullable::No, false),
+    ("Plus", 0, Nullable::No, false),
+    ("QuestionMark", 0, Nullable::No, false),
+    ("ExclamationPoint", 0, Nullable::No, false),
+    ("Ampersand", 0, Nullable::No, false),
+];
+
+/// Имена правил `grammar::core::parser` с числом дженерик-параметров.
+pub fn prelude() -> impl Iterator<Item = (&'static str, usize)> {
+    PRELUDE.iter().map(|v| (v.0, v.1))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Nullable {
+    Yes,
+    No,
+    Arg,
+}
+
+/// Выражение правила без различий между видами правил.
+#[derive(Debug, Clone, PartialEq)]
+enum Expr<'src> {
+    Ref(&'src str, Vec<Expr<'src>>),
+    /// Текст литерала и признак `"..."s` — подстрока, а не регулярка.
+    Lit(&'src str, bool),
+    Seq(Vec<Expr<'src>>),
+    Choice(Vec<Expr<'src>>),
+    /// `min` и есть ли верхняя граница; `join` — разделитель `**`.
+    Repeat {
+        expr: Box<Expr<'src>>,
+        min: usize,
+        bounded: bool,
+        join: Option<Box<Expr<'src>>>,
+    },
+    Optional(Box<Expr<'src>>),
+    Not(Box<Expr<'src>>),
+    And(Box<Expr<'src>>),
+}
+
+struct Def<'src> {
+    name: &'src str,
+    generics: Vec<&'src str>,
+    expr: Expr<'src>,
+}
+
+pub fn lint<'src, IS: InputStreamTrait<'src>>(
+    features: &[<FeatureRule<'src> as TransferRule<IS>>::Output],
+) -> Vec<Lint<'src>> {
+    let defs = features.iter().filter_map(def::<IS>).collect::<Vec<_>>();
+    Grammar::new(&defs).lints()
+}
+
+struct Grammar<'a, 'src> {
+    defs: &'a [Def<'src>],
+    by_name: HashMap<&'src str, &'a Def<'src>>,
+    nullable: HashMap<&'src str, bool>,
+    lints: Vec<Lint<'src>>,
+}
+
+impl<'a, 'src> Grammar<'a, 'src> {
+    fn new(defs: &'a [Def<'src>]) -> Self {
+        let mut v = Self {
+            defs,
+            by_name: defs.iter().map(|v| (v.name, v)).collect(),
+            nullable: HashMap::new(),
+            lints: vec![],
+        };
+        // наименьшая неподвижная точка: сначала ни одно правило не пустое
+        loop {
+            let nullable = defs
+                .iter()
+                .filter(|def| def.generics.is_empty())
+                .map(|def| (def.name, v.is_nullable(&def.expr, &HashMap::new())))
+                .collect::<HashMap<_, _>>();
+            if nullable == v.nullable {
+                break v;
+            }
+            v.nullable = nullable;
+        }
+    }
+
+    fn error(&mut self, at: &'src str, kind: LintKind, message: String) {
+        self.lints.push(Lint {
+            at,
+            kind,
+            message,
+            warning: false,
+        });
+    }
+
+    fn lints(mut self) -> Vec<Lint<'src>> {
+        for def in self.defs {
+            let mut duplicate = self.defs.iter().filter(|v| v.name == def.name);
+            if !std::ptr::eq(duplicate.next().unwrap(), def) {
+                self.error(
+                    def.name,
+                    LintKind::Duplicate,
+                    format!("rule `{}` is defined twice", def.name),
+                );
+            }
+            let env = def.generics.iter().map(|v| (*v, false)).collect();
+            self.check(&def.expr, &def.generics, &env);
+        }
+        self.left_recursion();
+        self.unreachable();
+        self.lints
+    }
+
+    fn check(&mut self, expr: &Expr<'src>, generics: &[&'src str], env: &HashMap<&'src str, bool>) {
+        match expr {
+            Expr::Ref(name, args) => {
+                args.iter().for_each(|v| self.check(v, generics, env));
+                let expected = if generics.contains(name) {
+                    return;
+                } else if let Some(def) = self.by_name.get(name) {
+                    def.generics.len()
+                } else if let Some((.., arity, _, _)) = PRELUDE.iter().find(|v| v.0 == *name) {
+                    *arity
+                } else {
+                    return self.error(
+                        name,
+                        LintKind::Undefined,
+                        format!("undefined rule `{name}`"),
+                    );
+                };
+                if args.len() != expected {
+                    self.error(
+                        name,
+                        LintKind::Arity,
+                        format!(
+                            "rule `{name}` expects {expected} generic argument(s), found {}",
+                            args.len()
+                        ),
+                    );
+                }
+            }
+            Expr::Lit(..) => (),
+            Expr::Seq(v) => v.iter().for_each(|v| self.check(v, generics, env)),
+            Expr::Choice(v) => {
+                v.iter().for_each(|v| self.check(v, generics, env));
+                self.shadowed(v, env);
+            }
+            Expr::Repeat {
+                expr: item,
+                bounded,
+                join,
+                ..
+            } => {
+                self.check(item, generics, env);
+                if let Some(join) = join {
+                    self.check(join, generics, env);
+                }
+                let empty = self.is_nullable(item, env)
+                    && join.as_ref().is_none_or(|v| self.is_nullable(v, env));
+                if !bounded && empty {
+                    self.error(
+                        first_str(expr),
+                        LintKind::EmptyLoop,
+                        format!(
+                            "`{}` can match empty input, repeating it loops forever",
+                            source(item)
+                        ),
+                    );
+                }
+            }
+            Expr::Optional(v) | Expr::Not(v) | Expr::And(v) => self.check(v, generics, env),
+        }
+    }
+
+    /// Альтернатива недостижима, если предыдущая всегда совпадает на её входе: пустая,
+    /// такая же или литерал-префикс её литерала (`"a" / "ab"`).
+    fn shadowed(&mut self, alternatives: &[Expr<'src>], env: &HashMap<&'src str, bool>) {
+        for (j, later) in alternatives.iter().enumerate() {
+            let shadow = alternatives[..j].iter().find(|earlier| {
+                self.is_nullable(earlier, env)
+                    || *earlier == later
+                    || match (self.literal(earlier), self.literal(later).and_then(plain)) {
+                        (Some((prefix, true)), Some(text)) => text.starts_with(prefix),
+                        (Some((reg_expr, false)), Some(text)) => {
+                            Regex::new(&format!("^(?:{reg_expr})"))
+                                .is_ok_and(|v| v.is_match(&text).unwrap_or(false))
+                        }
+                        _ => false,
+                    }
+            });
+            if let Some(shadow) = shadow {
+                let message = format!(
+                    "alternative `{}` is unreachable: `{}` before it always matches first",
+                    source(later),
+                    source(shadow)
+                );
+                self.error(first_str(later), LintKind::Shadowed, message);
+            }
+        }
+    }
+
+    /// Литерал выражения, в том числе через правило-токен.
+    fn literal(&self, expr: &Expr<'src>) -> Option<(&'src str, bool)> {
+        match expr {
+            Expr::Lit(v, is_sub_str) => Some((*v, *is_sub_str)),
+            Expr::Ref(name, args) if args.is_empty() => match &self.by_name.get(name)?.expr {
+                Expr::Lit(v, is_sub_str) => Some((*v, *is_sub_str)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `env` — пустые ли дженерик-параметры правила.
+    fn is_nullable(&self, expr: &Expr<'src>, env: &HashMap<&'src str, bool>) -> bool {
+        self.is_nullable_in(expr, env, &mut vec![])
+    }
+
+    /// `stack` — раскрываемые сейчас дженерик-правила: рекурсия через них не раскрывается.
+    fn is_nullable_in(
+        &self,
+        expr: &Expr<'src>,
+        env: &HashMap<&'src str, bool>,
+        stack: &mut Vec<&'src str>,
+    ) -> bool {
+        match expr {
+            Expr::Ref(name, args) => {
+                if let Some(v) = env.get(name) {
+                    return *v;
+                }
+                if let Some(v) = self.nullable.get(name) {
+                    return *v;
+                }
+                let args = args
+                    .iter()
+                    .map(|v| self.is_nullable_in(v, env, stack))
+                    .collect::<Vec<_>>();
+                match self.by_name.get(name) {
+                    Some(def) if def.generics.len() == args.len() && !stack.contains(name) => {
+                        let env = def.generics.iter().copied().zip(args).collect();
+                        stack.push(name);
+                        let v = self.is_nullable_in(&def.expr, &env, stack);
+                        stack.pop();
+                        v
+                    }
+                    Some(_) => false,
+                    None => PRELUDE
+                        .iter()
+                        .find(|v| v.0 == *name)
+                        .is_some_and(|v| match v.2 {
+                            Nullable::Yes => true,
+                            Nullable::No => false,
+                            Nullable::Arg => args.first().copied().unwrap_or(false),
+                        }),
+                }
+            }
+            Expr::Lit(v, true) => v.is_empty(),
+            Expr::Lit(v, false) => {
+                Regex::new(&format!("^(?:{v})")).is_ok_and(|v| v.is_match("").unwrap_or(false))
+            }
+            Expr::Seq(v) => v.iter().all(|v| self.is_nullable_in(v, env, stack)),
+            Expr::Choice(v) => v.iter().any(|v| self.is_nullable_in(v, env, stack)),
+            Expr::Repeat { expr, min, .. } => *min == 0 || self.is_nullable_in(expr, env, stack),
+            Expr::Optional(..) | Expr::Not(..) | Expr::And(..) => true,
+        }
+    }
+
+    /// Правила грамматики, которые выражение может вызвать, ещё ничего не разобрав.
+    /// Тело дженерик-правила раскрывается, только чтобы узнать, какие аргументы оно
+    /// вызывает первыми; `stack` не даёт раскрывать его рекурсивно.
+    fn left(&self, expr: &Expr<'src>, out: &mut Vec<&'src str>, stack: &mut Vec<&'src str>) {
+        let env = HashMap::new();
+        match expr {
+            Expr::Ref(name, args) => {
+                if let Some(def) = self.by_name.get(name) {
+                    out.push(def.name);
+                    if args.is_empty() || stack.contains(name) {
+                        return;
+                    }
+                    let mut leading = vec![];
+                    stack.push(name);
+                    self.left(&def.expr, &mut leading, stack);
+                    for (param, arg) in def.generics.iter().zip(args) {
+                        if leading.contains(param) {
+                            self.left(arg, out, stack);
+                        }
+                    }
+                    stack.pop();
+                } else if let Some(v) = PRELUDE.iter().find(|v| v.0 == *name) {
+                    if v.3 {
+                        args.iter().take(1).for_each(|v| self.left(v, out, stack));
+                    }
+                } else {
+                    // дженерик-параметр: аргумент раскрывается у вызывающего
+                    out.push(name);
+                }
+            }
+            Expr::Lit(..) => (),
+            Expr::Seq(v) => {
+                for v in v {
+                    self.left(v, out, stack);
+                    if !self.is_nullable(v, &env) {
+                        break;
+                    }
+                }
+            }
+            Expr::Choice(v) => v.iter().for_each(|v| self.left(v, out, stack)),
+            Expr::Repeat { expr, .. }
+            | Expr::Optional(expr)
+            | Expr::Not(expr)
+            | Expr::And(expr) => self.left(expr, out, stack),
+        }
+    }
+
+    fn left_recursion(&mut self) {
+        let edges = self
+            .defs
+            .iter()
+            .map(|def| {
+                let mut out = vec![];
+                self.left(&def.expr, &mut out, &mut vec![]);
+                (def.name, out)
+            })
+            .collect::<HashMap<_, _>>();
+        let mut reported = HashSet::new();
+        for def in self.defs {
+            if reported.contains(def.name) {
+                continue;
+            }
+            if let Some(cycle) = cycle(def.name, &edges) {
+                reported.extend(cycle.iter().copied());
+                self.error(
+                    def.name,
+                    LintKind::LeftRecursion,
+                    format!("left recursion: {}", cycle.join(" -> ")),
+                );
+            }
+        }
+    }
+
+    /// Правила, до которых не дойти от первого правила грамматики.
+    fn unreachable(&mut self) {
+        let Some(root) = self.defs.first() else {
+            return;
+        };
+        let mut reached = HashSet::from([root.name]);
+        let mut queue = vec![&root.expr];
+        while let Some(expr) = queue.pop() {
+            let mut refs = vec![];
+            refs_of(expr, &mut refs);
+            for name in refs {
+                if let Some(def) = self.by_name.get(name) {
+                    if reached.insert(def.name) {
+                        queue.push(&def.expr);
+                    }
+                }
+            }
+        }
+        for def in self.defs {
+            if !reached.contains(def.name) {
+                self.lints.push(Lint {
+                    at: def.name,
+                    kind: LintKind::Unreachable,
+                    message: format!("rule `{}` is never used from `{}`", def.name, root.name),
+                    warning: true,
+                });
+            }
+        }
+    }
+}
+
+fn def<'src, IS: InputStreamTrait<'src>>(
+    feature: &<FeatureRule<'src> as TransferRule<IS>>::Output,
+) -> Option<Def<'src>> {
+    let (head, expr) = match feature {
+        Feature::Choice(v) => (
+            &v.head,
+            Expr::Choice(v.variants.iter().map(|v| expr_(&v.item.value)).collect()),
+        ),
+        Feature::Sequence(v) => (
+            &v.head,
+            Expr::Seq(match &v.fields {
+                StructTypeOutput::Struct(v) => v
+                    .iter()
+                    .map(|v| match &v.item {
+                        FieldOutput::Named(v) => expr_(&v.value),
+                        FieldOutput::Unnamed(v) => expr_(v),
+                    })
+                    .collect(),
+                StructTypeOutput::Tuple(v) => v
+                    .iter()
+                    .map(|v| match v {
+                        TupleItemOutput::Ignored(IgnoredExprVOutput::ParenedSeq(v)) => seq(v),
+                        TupleItemOutput::Ignored(IgnoredExprVOutput::TupleStructExpr(v))
+                        | TupleItemOutput::TupleStructExpr(v) => match v {
+                            TupleStructExprOutput::Choice(v) => choice(v),
+                            TupleStructExprOutput::Quantificator(v) => quantificator(v),
+                            TupleStructExprOutput::Token(v) => token(v),
+                        },
+                    })
+                    .collect(),
+            }),
+        ),
+        Feature::ChoiceRule(v) => (&v.head, choice(&v.expr)),
+        Feature::SequenceRule(v) => (&v.head, seq(&v.expr)),
+        Feature::QuantificatorRule(v) => (&v.head, quantificator(&v.expr)),
+        Feature::AliasRule(v) => (
+            &v.head,
+            token(&match v.expr.clone() {
+                AliasExprOutput::IdentWithExprGenerics(v) => TokenOutput::IdentWithExprGenerics(v),
+                AliasExprOutput::BoxedIdent(v) => TokenOutput::BoxedIdent(v),
+                AliasExprOutput::Ident(v) => TokenOutput::Ident(v),
+            }),
+        ),
+        Feature::Token(v) => {
+            let SeqOutput((text, is_sub_str)) = &v.expr;
+            return Some(Def {
+                name: match &v.head {
+                    TokenHeadOutput::Unit(v) | TokenHeadOutput::Base(v) => v,
+                    TokenHeadOutput::Parse(v) => v.name,
+                },
+                generics: vec![],
+                expr: Expr::Lit(text, is_sub_str.is_some()),
+            });
+        }
+        Feature::Comment(..) => return None,
+    };
+    let (name, generics) = match head {
+        IdentWithDefineGenericsOrIdentOutput::Ident(v) => (*v, vec![]),
+        IdentWithDefineGenericsOrIdentOutput::IdentWithDefineGenerics(v) => {
+            (v.ident, v.generics.clone())
+        }
+    };
+    Some(Def {
+        name,
+        generics,
+        expr,
+    })
+}
+
+fn expr_<'src>(v: &ExprOutput<'src, impl InputStreamTrait<'src>>) -> Expr<'src> {
+    match v {
+        ExprOutput::Combinator(v) => combinator(v),
+        ExprOutput::Quantificator(v) => quantificator(v),
+        ExprOutput::Token(v) => token(v),
+    }
+}
+
+fn combinator<'src>(v: &CombinatorOutput<'src, impl InputStreamTrait<'src>>) -> Expr<'src> {
+    match v {
+        CombinatorOutput::Choice(v) => choice(v),
+        CombinatorOutput::Seq(v) => seq(v),
+    }
+}
+
+fn choice<'src>(
+    v: &[QuantificatorOrTokenOrSeqOutput<'src, impl InputStreamTrait<'src>>],
+) -> Expr<'src> {
+    Expr::Choice(
+        v.iter()
+            .map(|v| match v {
+                QuantificatorOrTokenOrSeqOutput::QuantificatorOrToken(v) => {
+                    quantificator_or_token(v)
+                }
+                QuantificatorOrTokenOrSeqOutput::ParenedSeq(v) => seq(v),
+            })
+            .collect(),
+    )
+}
+
+fn seq<'src, IS: InputStreamTrait<'src>>(
+    v: &[<ChoiceOrQuantificator<'src> as TransferRule<IS>>::Output],
+) -> Expr<'src> {
+    Expr::Seq(
+        v.iter()
+            .map(|v| match v {
+                AnyOrParenOutput::Any(v) => quantificator_or_token(v),
+                AnyOrParenOutput::Parensized(v) => choice(v),
+            })
+            .collect(),
+    )
+}
+
+fn quantificator_or_token<'src>(
+    v: &QuantificatorOrTokenOutput<'src, impl InputStreamTrait<'src>>,
+) -> Expr<'src> {
+    match v {
+        QuantificatorOrTokenOutput::Quantificator(v) => quantificator(v),
+        QuantificatorOrTokenOutput::Token(v) => token(v),
+    }
+}
+
+fn quantificator<'src>(v: &QuantificatorOutput<'src, impl InputStreamTrait<'src>>) -> Expr<'src> {
+    let combinator_or_token = |v: &AnyOrParenOutput<'src, _, _, _>| match v {
+        AnyOrParenOutput::Any(v) => token(v),
+        AnyOrParenOutput::Parensized(v) => combinator(v),
+    };
+    let repeat = |expr, min, bounded, join: Option<Expr<'src>>| Expr::Repeat {
+        expr: Box::new(expr),
+        min,
+        bounded,
+        join: join.map(Box::new),
+    };
+    let bounds = |v: &_| match v {
+        RepeatQuantificatorOutput::Maximum(..) => (0, true),
+        RepeatQuantificatorOutput::MinMax(v) => (v.min, true),
+        RepeatQuantificatorOutput::Minimum(v) => (*v, false),
+        RepeatQuantificatorOutput::Count(v) => (*v, true),
+    };
+    match v {
+        QuantificatorOutput::Kleene(KleeneOutput::ZeroOrMore(v)) => {
+            repeat(combinator_or_token(v), 0, false, None)
+        }
+        QuantificatorOutput::Kleene(KleeneOutput::OneOrMore(v)) => {
+            repeat(combinator_or_token(v), 1, false, None)
+        }
+        QuantificatorOutput::Predicative(PredicativeOutput::Optional(v)) => {
+            Expr::Optional(Box::new(combinator_or_token(v)))
+        }
+        QuantificatorOutput::Predicative(PredicativeOutput::NegativeLookahead(v)) => {
+            Expr::Not(Box::new(combinator_or_token(v)))
+        }
+        QuantificatorOutput::Predicative(PredicativeOutput::AndPredicate(v)) => {
+            Expr::And(Box::new(combinator_or_token(v)))
+        }
+        QuantificatorOutput::RepeatQuantificator((v, q)) => {
+            let (min, bounded) = bounds(q);
+            repeat(combinator_or_token(v), min, bounded, None)
+        }
+        QuantificatorOutput::Joinable(SeqOutput((v, j, join))) => {
+            let (min, bounded) = match j {
+                JoinableOutput::Repeat(..) => (0, false),
+                JoinableOutput::StrictRepeat(v) => bounds(v),
+            };
+            repeat(
+                combinator_or_token(v),
+                min,
+                bounded,
+                Some(combinator_or_token(join)),
+            )
+        }
+    }
+}
+
+fn token<'src>(v: &TokenOutput<'src, impl InputStreamTrait<'src>>) -> Expr<'src> {
+    match v {
+        TokenOutput::IdentWithExprGenerics(v) => {
+            Expr::Ref(v.ident, v.generics.iter().map(expr_).collect())
+        }
+        TokenOutput::BoxedIdent(v) | TokenOutput::Ident(v) => Expr::Ref(v, vec![]),
+        TokenOutput::StrLiteral(SeqOutput((v, is_sub_str))) => Expr::Lit(v, is_sub_str.is_some()),
+    }
+}
+
+/// Путь от `start` обратно к нему по рёбрам, если он есть.
+fn cycle<'src>(
+    start: &'src str,
+    edges: &HashMap<&'src str, Vec<&'src str>>,
+) -> Option<Vec<&'src str>> {
+    let mut path = vec![start];
+    let mut visited = HashSet::new();
+    fn go<'src>(
+        path: &mut Vec<&'src str>,
+        visited: &mut HashSet<&'src str>,
+        edges: &HashMap<&'src str, Vec<&'src str>>,
+    ) -> bool {
+        for next in edges.get(path.last().unwrap()).into_iter().flatten() {
+            if *next == path[0] {
+                path.push(next);
+                return true;
+            }
+            if visited.insert(next) {
+                path.push(next);
+                if go(path, visited, edges) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+    go(&mut path, &mut visited, edges).then_some(path)
+}
+
+fn refs_of<'src>(expr: &Expr<'src>, out: &mut Vec<&'src str>) {
+    match expr {
+        Expr::Ref(name, args) => {
+            out.push(name);
+            args.iter().for_each(|v| refs_of(v, out));
+        }
+        Expr::Lit(..) => (),
+        Expr::Seq(v) | Expr::Choice(v) => v.iter().for_each(|v| refs_of(v, out)),
+        Expr::Repeat { expr, join, .. } => {
+            refs_of(expr, out);
+            join.iter().for_each(|v| refs_of(v, out));
+        }
+        Expr::Optional(v) | Expr::Not(v) | Expr::And(v) => refs_of(v, out),
+    }
+}
+
+/// Текст регулярки без метасимволов, `\.` раскрывается.
+fn plain((v, is_sub_str): (&str, bool)) -> Option<String> {
+    if is_sub_str {
+        return Some(v.to_string());
+    }
+    let mut out = String::new();
+    let mut chars = v.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next()? {
+                c if c.is_ascii_punctuation() => out.push(c),
+                _ => return None,
+            },
+            '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$' => {
+                return None
+            }
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// Первая подстрока исходника в выражении — к ней привязывается находка.
+fn first_str<'src>(expr: &Expr<'src>) -> &'src str {
+    match expr {
+        Expr::Ref(v, _) | Expr::Lit(v, _) => v,
+        Expr::Seq(v) | Expr::Choice(v) => v.first().map_or("", first_str),
+        Expr::Repeat { expr, .. } | Expr::Optional(expr) | Expr::Not(expr) | Expr::And(expr) => {
+            first_str(expr)
+        }
+    }
+}
+
+/// Выражение в синтаксисе `.abs` для сообщений.
+fn source(expr: &Expr) -> String {
+    let list = |v: &[Expr], sep| v.iter().map(source).collect::<Vec<_>>().join(sep);
+    match expr {
+        Expr::Ref(name, args) if args.is_empty() => name.to_string(),
+        Expr::Ref(name, args) => format!("{name}<{}>", list(args, ", ")),
+        Expr::Lit(v, true) => format!(r#""{v}"s"#),
+        Expr::Lit(v, false) => format!(r#""{v}""#),
+        Expr::Seq(v) => format!("({})", list(v, " ")),
+        Expr::Choice(v) => format!("({})", list(v, " / ")),
+        Expr::Repeat {
+            expr,
+            min,
+            bounded,
+            join,
+        } => match (join, min, bounded) {
+            (Some(join), ..) => format!("{} ** {}", source(expr), source(join)),
+            (None, 0, false) => format!("{}*", source(expr)),
+            (None, 1, false) => format!("{}+", source(expr)),
+            (None, min, _) => format!("{}{{{min},..}}", source(expr)),
+        },
+        Expr::Optional(v) => format!("{}?", source(v)),
+        Expr::Not(v) => format!("!{}", source(v)),
+        Expr::And(v) => format!("&{}", source(v)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Правила грамматики по одному на строку; находки — (место, сообщение).
+    fn lints(rules: &[&str]) -> Vec<(String, String, bool)> {
+        let src = rules.join("\n");
+        let features = CachedIter::new(InputStreamIter::new(&src))
+            .full_parse(&default_feature_rule())
+            .unwrap();
+        lint(&features)
+            .into_iter()
+            .map(|v| (v.at.to_owned(), v.message, v.warning))
+            .collect()
+    }
+
+    fn errors(rules: &[&str]) -> Vec<(String, String)> {
+        lints(rules)
+            .into_iter()
+            .filter(|v| !v.2)
+            .map(|(at, message, _)| (at, message))
+            .collect()
+    }
+
+    fn error(at: &str, message: &str) -> (String, String) {
+        (at.to_owned(), message.to_owned())
+    }
+
+    #[test]
+    fn undefined() {
+        assert_eq!(
+            errors(&["Doc = Item ** Comma", "Item = Number / Missing"]),
+            [error("Missing", "undefined rule `Missing`")]
+        );
+        assert_eq!(
+            errors(&["Doc = Pair<Number, Ident> / Spaced", "Pair<A> = A A;"]),
+            [
+                error("Pair", "rule `Pair` expects 1 generic argument(s), found 2"),
+                error(
+                    "Spaced",
+                    "rule `Spaced` expects 1 generic argument(s), found 0"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unused() {
+        assert_eq!(
+            lints(&["Doc = Item+", r#"Item = "a""#, r#"Lost = "b""#]),
+            [(
+                "Lost".to_owned(),
+                "rule `Lost` is never used from `Doc`".to_owned(),
+                true
+            )]
+        );
+    }
+
+    #[test]
+    fn left_recursion() {
+        assert_eq!(
+            errors(&["Expr = Sum / Number", r#"Sum = Space Expr "+" Number;"#]),
+            [error("Expr", "left recursion: Expr -> Sum -> Expr")]
+        );
+        // через дженерик, который разбирает аргумент первым
+        assert_eq!(
+            errors(&[r#"List = (Spaced<List> ",") / Number"#]),
+            [error("List", "left recursion: List -> List")]
+        );
+        // `&` ничего не потребляет, вызов под ним тоже левый
+        assert_eq!(
+            errors(&["Expr = &Expr Number;"]),
+            [error("Expr", "left recursion: Expr -> Expr")]
+        );
+        assert_eq!(errors(&[r#"Expr = ("(" Expr ")") / Number"#]), []);
+    }
+
+    #[test]
+    fn empty_loop() {
+        assert_eq!(
+            errors(&["Doc = Item*", r#"Item = Space "a"?;"#]),
+            [error(
+                "Item",
+                "`Item` can match empty input, repeating it loops forever"
+            )]
+        );
+        assert_eq!(
+            errors(&[r#"Doc = "[a-z]*" ** """#]),
+            [error(
+                "[a-z]*",
+                r#"`"[a-z]*"` can match empty input, repeating it loops forever"#
+            )]
+        );
+        assert_eq!(errors(&[r#"Doc = "[a-z]*" ** ",""#, "Opt = Space{,3}"]), []);
+    }
+
+    #[test]
+    fn shadowed() {
+        assert_eq!(
+            errors(&[r#"Op = "a" / "ab"s / "[0-9]" / "7" / "b"?"#]),
+            [
+                error(
+                    "ab",
+                    r#"alternative `"ab"s` is unreachable: `"a"` before it always matches first"#
+                ),
+                error(
+                    "7",
+                    r#"alternative `"7"` is unreachable: `"[0-9]"` before it always matches first"#
+                ),
+            ]
+        );
+        assert_eq!(
+            errors(&[
+                r#"Op = Arrow / Minus / "-""#,
+                r#"Minus = "-""#,
+                r#"Arrow = "->""#
+            ]),
+            [error(
+                "-",
+                r#"alternative `"-"` is unreachable: `Minus` before it always matches first"#
+            )]
+        );
+        assert_eq!(errors(&[r#"Op = "ab" / "a" / "\\." / "a.""#]), []);
+    }
+}
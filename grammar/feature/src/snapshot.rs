@@ -0,0 +1,174 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! `snapshot_tests!`: по тесту на каждый файл корпуса, вывод разбора сравнивается со
+//! снимком `snapshots/<файл>.snap` рядом с корпусом.
+
+use crate::source_dir;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use std::fs::read_dir;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Ident, LitStr, Token, Type,
+};
+
+struct Args {
+    corpus: LitStr,
+    grammar: Type,
+    json: bool,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let corpus = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let grammar = input.parse()?;
+        let json = if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let format: Ident = input.parse()?;
+            if format != "json" {
+                return Err(syn::Error::new(format.span(), "expected `json`"));
+            }
+            true
+        } else {
+            false
+        };
+        Ok(Self {
+            corpus,
+            grammar,
+            json,
+        })
+    }
+}
+
+pub fn snapshot_tests(input: TokenStream) -> TokenStream {
+    let Args {
+        corpus,
+        grammar,
+        json,
+    } = parse_macro_input!(input);
+
+    let pattern = corpus.value();
+    let (dir, file) = pattern.rsplit_once('/').unwrap_or((".", &pattern));
+    let dir = source_dir().join(dir);
+    proc_macro::tracked_path::path(dir.to_string_lossy());
+
+    let mut files = match read_dir(&dir) {
+        Ok(v) => v
+            .filter_map(|v| Some(v.ok()?.path()))
+            .filter(|v| v.is_file())
+            .filter(|v| {
+                v.file_name()
+                    .and_then(|v| v.to_str())
+                    .is_some_and(|name| wildcard(file, name))
+            })
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            return syn::Error::new(corpus.span(), format!("{e} Path: {}.", dir.display()))
+                .to_compile_error()
+                .into()
+        }
+    };
+    if files.is_empty() {
+        return syn::Error::new(corpus.span(), format!("no files match {pattern:?}"))
+            .to_compile_error()
+            .into();
+    }
+    files.sort();
+
+    let render = if json {
+        quote! {
+            match &out {
+                Ok(v) => serde_json::to_string_pretty(v).unwrap() + "\n",
+                Err(e) => format!("{:#?}\n", e),
+            }
+        }
+    } else {
+        quote!(format!("{:#?}\n", out))
+    };
+
+    let tests = files.iter().map(|path| {
+        let name = path.file_name().unwrap().to_string_lossy();
+        let test = test_ident(path.file_stem().unwrap().to_string_lossy().as_ref());
+        let snap = dir.join("snapshots").join(format!("{name}.snap"));
+        let (path, snap) = (path.to_string_lossy(), snap.to_string_lossy());
+        quote! {
+            #[test]
+            fn #test() {
+                let out = abstract_parser::parsers::chars::CharParser::full_parse(
+                    &mut abstract_parser::cached::CachedIter::new(
+                        abstract_parser::parsers::chars::InputStreamIter::new(include_str!(#path)),
+                    ),
+                    &<#grammar>::default(),
+                );
+                abstract_parser::utils::snapshot::assert_snapshot(#snap, &#render);
+            }
+        }
+    });
+
+    let module = match &grammar {
+        Type::Path(v) => v.path.segments.last().map(|v| snake(&v.ident.to_string())),
+        _ => None,
+    }
+    .unwrap_or_else(|| "grammar".into());
+    let module = format_ident!("{module}_snapshots");
+
+    quote! {
+        mod #module {
+            use super::*;
+
+            #(#tests)*
+        }
+    }
+    .into()
+}
+
+/// `*` — любая подстрока, остальные символы сравниваются как есть.
+fn wildcard(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((head, tail)) => name.strip_prefix(head).is_some_and(|rest| {
+            (0..=rest.len())
+                .filter(|&i| rest.is_char_boundary(i))
+                .any(|i| wildcard(tail, &rest[i..]))
+        }),
+    }
+}
+
+fn snake(v: &str) -> String {
+    let mut out = String::new();
+    for (i, c) in v.chars().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            out.push('_');
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+fn test_ident(stem: &str) -> Ident {
+    let mut name = stem
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if !name.starts_with(|c: char| c.is_alphabetic() || c == '_') {
+        name.insert(0, '_');
+    }
+    Ident::new(&name, Span::call_site())
+}
@@ -0,0 +1,92 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{core::parser::*, feature::grammar::grammar},
+    parsers::chars::completion::{expected, Pattern},
+};
+
+grammar! {r#"
+Stmt {
+    Let(Let)
+    Print(Print)
+}
+Let = "let"s StrictSpace Ident Space "="s Space Expr;
+Print = "print"s StrictSpace Expr;
+Expr = Num / Ident
+Num = "[0-9]+"
+"#}
+
+/// Шаблоны и пути ожидаемых токенов.
+fn at(src: &str, offset: usize) -> Vec<(Pattern, Vec<String>)> {
+    expected(&Stmt::default(), src, offset)
+        .into_iter()
+        .map(|v| (v.pattern, v.path.iter().map(|v| v.to_string()).collect()))
+        .collect()
+}
+
+fn sub_str(v: &'static str) -> Pattern {
+    Pattern::SubStr(v.into())
+}
+
+fn reg_expr(v: &'static str) -> Pattern {
+    Pattern::RegExpr(v.into())
+}
+
+fn path(v: &[&str]) -> Vec<String> {
+    v.iter().map(|v| v.to_string()).collect()
+}
+
+#[test]
+fn alternatives() {
+    assert_eq!(
+        at("", 0),
+        [
+            (sub_str("let"), path(&["Stmt", "Let"])),
+            (sub_str("print"), path(&["Stmt", "Print"])),
+        ]
+    );
+    assert_eq!(
+        at("let x = ", 8),
+        [
+            (reg_expr("[0-9]+"), path(&["Stmt", "Let", "Expr"])),
+            (reg_expr("[A-Za-z_0-9]+"), path(&["Stmt", "Let", "Expr"])),
+        ]
+    );
+}
+
+/// Необязательный пробел не прячет следующий за ним литерал.
+#[test]
+fn optional() {
+    let expected = at("let x", 5);
+    assert_eq!(
+        expected.iter().map(|v| &v.0).collect::<Vec<_>>(),
+        [&reg_expr(r"\s+"), &sub_str("=")]
+    );
+    assert_eq!(expected[1].1, path(&["Stmt", "Let"]));
+}
+
+/// Вход после позиции не влияет, имя токена — имя правила.
+#[test]
+fn truncated() {
+    let expected = expected(&Stmt::default(), "print 1 + 2", 6);
+    assert_eq!(
+        expected
+            .iter()
+            .map(|v| v.name.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("Num"), Some("Ident")]
+    );
+    assert_eq!(expected[0].pattern.to_string(), r#"r"[0-9]+""#);
+}
@@ -0,0 +1,55 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{core::parser::*, feature::grammar::grammar},
+    parsers::chars::{CharParser, InputStreamIter},
+};
+use std::collections::HashSet;
+
+// `Hash` и `Eq` у `grammar!` своей генерации не требуют и передаются выходным типам
+grammar! {#[derive(Hash, Eq)] r#"
+Doc {
+    name: Ident,
+    Space,
+    kind: Kind,
+    Space,
+    values: Value ** Sep,
+    end: Bang / ".",
+}
+Kind {
+    Int(Num)
+    Pair(Pair)
+}
+Pair ("\(" Ident #[ignore] "," Num "\)")
+Value = Num / Ident
+Sep = ",\s*"
+Num: usize = "[0-9]+"
+unit Bang = "!"
+"#}
+
+#[test]
+fn hash() {
+    let parse = |src: &'static str| {
+        InputStreamIter::new(src)
+            .full_parse(&Doc::default())
+            .unwrap()
+    };
+    let set = ["doc (x,1) 1, a!", "doc 7 x.", "doc (x,1) 1, a!", "doc 7 x!"]
+        .iter()
+        .map(|v| parse(v))
+        .collect::<HashSet<_>>();
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&parse("doc 7 x.")));
+}
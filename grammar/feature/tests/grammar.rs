@@ -30,3 +30,22 @@ fn grammar() {
 }
 
 grammar_from_file!("grammar.abs");
+
+/// С `warn` недостижимое правило — предупреждение компилятора: без него `expect` не
+/// выполнится, и сборка теста упадёт.
+#[deny(unfulfilled_lint_expectations)]
+#[expect(deprecated)]
+mod warn {
+    use super::*;
+
+    grammar! { warn r#"
+Doc = Num
+Num = "[0-9]+"
+Unused = "x"
+"#}
+
+    #[test]
+    fn unreachable_is_a_warning() {
+        check::<Doc>("1", Ok("1"));
+    }
+}
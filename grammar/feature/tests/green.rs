@@ -0,0 +1,80 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{core::parser::*, feature::grammar::grammar},
+    green::{green, GreenKind},
+};
+use parsers::chars::{CharParser, InputStreamIter};
+
+grammar! {r#"
+List = Open Items "]";
+Items = Item ** Sep
+Item {
+    Pair(Pair)
+    Num(Num)
+    Ident(Ident)
+}
+Pair = Ident ":" Num;
+Open = "\["
+Sep = ",\s*"
+Num = "[0-9]+"
+"#}
+
+#[test]
+fn green_tree() {
+    let src = "[a, 1,b:2]";
+    let (out, tree) =
+        green::<SyntaxKind, _>(|| InputStreamIter::new(src).full_parse(&List::default()));
+    assert!(out.is_ok());
+    assert_eq!(tree.len(), 1);
+    assert_eq!(
+        tree[0].to_string(),
+        "\
+List 0..10
+  Open 0..1
+  Items 1..9
+    Item 1..2
+      Ident 1..2
+    Sep 2..4
+    Item 4..5
+      Num 4..5
+    Sep 5..6
+    Item 6..9
+      Pair 6..9
+        Ident 6..7
+        Anonymous 7..8
+        Num 8..9
+  Anonymous 9..10
+"
+    );
+    let pair = tree[0]
+        .preorder()
+        .find(|v| v.kind == SyntaxKind::Pair)
+        .unwrap();
+    assert_eq!(pair.text(src), "b:2");
+}
+
+#[test]
+fn syntax_kind() {
+    assert_eq!(SyntaxKind::from_rule("Pair"), Some(SyntaxKind::Pair));
+    // внешнее правило, на которое ссылается грамматика
+    assert_eq!(SyntaxKind::from_rule("Ident"), Some(SyntaxKind::Ident));
+    assert_eq!(
+        SyntaxKind::from_rule("Token12"),
+        Some(SyntaxKind::Anonymous)
+    );
+    assert_eq!(SyntaxKind::from_rule("SequenceRule"), None);
+    assert_eq!(SyntaxKind::Items.name(), "Items");
+}
@@ -0,0 +1,103 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{core::parser::*, feature::grammar::grammar},
+    parsers::chars::{
+        minimize,
+        minimize::{events, minimize, Failure},
+        CharParser, InputStreamIter,
+    },
+};
+use std::cell::Cell;
+
+grammar! {r#"
+Doc {
+    name: Ident,
+    Space,
+    items: Item ** Sep,
+}
+Item {
+    Num(Num)
+    One(Group<Num>)
+    Two(Group<Group<Num>>)
+    Three(Group<Group<Group<Num>>>)
+}
+Group<T> {
+    "\[",
+    inner: T ** Sep,
+    "\]",
+}
+Sep = ",\s*"
+Num: usize = "[0-9]+"
+"#}
+
+fn doc(item: &str) -> String {
+    let items = (0..300).map(|i| i.to_string()).collect::<Vec<_>>();
+    format!(
+        "doc {}, {item}, {}",
+        items[..150].join(", "),
+        items[150..].join(",\n")
+    )
+}
+
+fn max_depth(src: &str) -> usize {
+    let mut depth = 0;
+    while Failure::Depth(depth).check(&Doc::default(), src) {
+        depth += 1;
+    }
+    depth
+}
+
+#[test]
+fn depth() {
+    let limit = max_depth("doc [[1, 2], [3]]");
+    let minimal = minimize!(Doc, &doc("[[[4]], 5]"), Failure::Depth(limit)).unwrap();
+    assert!(max_depth(&minimal) > limit);
+    assert_eq!(minimal, "c 0,[[[");
+
+    assert_eq!(
+        minimize!(Doc, &doc("[[4], 5]"), Failure::Depth(limit)),
+        None
+    );
+}
+
+#[test]
+fn predicate() {
+    let input = doc("[2]");
+    let checks = Cell::new(0);
+    let has_13 = |src: &str| {
+        checks.set(checks.get() + 1);
+        InputStreamIter::new(src)
+            .full_parse(&Doc::default())
+            .is_ok_and(|v| v.items.contains(&ItemOutput::Num(13)))
+    };
+    let minimal = minimize(&input, has_13, |src| events(&Doc::default(), src));
+    assert_eq!(minimal.as_deref(), Some("c 13"));
+
+    // элементы `Num ** Sep` удаляются целиком, без перебора строк и символов
+    let by_structure = checks.replace(0);
+    assert_eq!(
+        minimize(&input, has_13, |_| vec![]).as_deref(),
+        Some("c 13")
+    );
+    assert!(
+        by_structure < checks.get(),
+        "{} {}",
+        by_structure,
+        checks.get()
+    );
+
+    assert_eq!(minimize("doc 1, 2", has_13, |_| vec![]), None);
+}
@@ -0,0 +1,74 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{
+        core::parser::*,
+        feature::grammar::{grammar, snapshot_tests},
+    },
+    parsers::chars::{CharParser, InputStreamIter},
+    serde,
+};
+
+grammar! {#[derive(Serialize, Deserialize)] r#"
+Doc {
+    name: Ident,
+    Space,
+    kind: Kind,
+    Space,
+    values: Value ** Sep,
+    end: Bang / ".",
+}
+Kind {
+    Int(Num)
+    Pair(Pair)
+}
+Pair ("\(" Ident #[ignore] "," Num "\)")
+Value = Num / Ident
+Sep = ",\s*"
+Num: usize = "[0-9]+"
+unit Bang = "!"
+"#}
+
+/// Тип выхода правила выводится из уже разобранного значения.
+fn from_json<'de, T: serde::Deserialize<'de>>(_: &T, json: &'de str) -> T {
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn json() {
+    let src = "doc (x,1) 1, a, 2!";
+    let out = InputStreamIter::new(src)
+        .full_parse(&Doc::default())
+        .unwrap();
+    let json = serde_json::to_string(&out).unwrap();
+    // имена полей и вариантов из грамматики, у анонимного выбора `Value` — `V0`, `V1`
+    assert_eq!(
+        json,
+        r#"{"name":"doc","kind":{"Pair":["(","x",1,")"]},"values":[{"V0":1},{"V1":"a"},{"V0":2}],"end":{"Bang":null}}"#
+    );
+    assert_eq!(from_json(&out, &json), out);
+
+    let out = InputStreamIter::new("doc 7 x.")
+        .full_parse(&Doc::default())
+        .unwrap();
+    let json = serde_json::to_string(&out).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"doc","kind":{"Int":7},"values":[{"V1":"x"}],"end":{"V1":"."}}"#
+    );
+    assert_eq!(from_json(&out, &json), out);
+}
+
+snapshot_tests!("corpus/*.doc", Doc, json);
@@ -0,0 +1,168 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{core::parser::*, feature::grammar::grammar},
+    parsers::chars::{differential::differential, CharParser, InputStreamIter},
+    property::{round_trip, Check, Rng},
+    unparse::{unparse, Unparse},
+};
+
+grammar! {#[derive(Unparse)] r#"
+Doc {
+    name: Ident,
+    Space,
+    items: Item ** Sep,
+    end: Bang / ".",
+}
+Item {
+    Int(Num)
+    Pair(Pair)
+    Group(Group<Num>)
+}
+Pair {
+    "\(",
+    key: Ident,
+    ",",
+    Space,
+    value: Num,
+    "\)",
+}
+Group<T> {
+    "\[",
+    inner: T ** Sep,
+    "\]",
+}
+Sep = ",\s*"
+Num: usize = "[0-9]+"
+unit Bang = "!"
+"#}
+
+fn parse(src: &str) -> DocOutput<'_, InputStreamIter<'_>> {
+    InputStreamIter::new(src)
+        .full_parse(&Doc::default())
+        .unwrap_or_else(|e| panic!("{:?}: {:?}", src, e))
+}
+
+#[test]
+fn canonical() {
+    let out = parse("doc 1,   (x,2), [3,\t4].");
+    // пропущенные литералы и пробелы печатаются канонично, разделители — без пробелов
+    assert_eq!(out.to_string(), "doc 1,(x, 2),[3,4].");
+    assert_eq!(parse("doc\n(y,\n7)!").to_string(), "doc (y, 7)!");
+}
+
+#[test]
+fn build() {
+    let out = DocOutput {
+        name: "label",
+        items: vec![
+            ItemOutput::Int(1),
+            ItemOutput::Pair(PairOutput { key: "x", value: 2 }),
+        ],
+        // безымянный выбор поля `end` генерируется рядом с правилом
+        end: ___Doc::endOutput::Bang(BangToken),
+    };
+    let src = unparse::<InputStreamIter, _>(&Doc::default(), &out);
+    assert_eq!(src, "label 1,(x, 2)!");
+    assert_eq!(parse(&src), out);
+}
+
+/// Детерминированный генератор исходников из кусочков грамматики.
+struct Docs(Rng);
+
+impl Docs {
+    fn next(&mut self, n: usize) -> usize {
+        self.0.below(n)
+    }
+
+    fn space(&mut self) -> &'static str {
+        ["", " ", "  ", "\n", "\t "][self.next(5)]
+    }
+
+    fn ident(&mut self) -> &'static str {
+        ["a", "key", "x_1", "doc"][self.next(4)]
+    }
+
+    fn item(&mut self) -> String {
+        match self.next(3) {
+            0 => self.next(1000).to_string(),
+            1 => format!("({},{}{})", self.ident(), self.space(), self.next(100)),
+            _ => {
+                let inner = (0..1 + self.next(3))
+                    .map(|_| self.next(10).to_string())
+                    .collect::<Vec<_>>();
+                format!("[{}]", inner.join(","))
+            }
+        }
+    }
+
+    fn doc(&mut self) -> String {
+        let items = (0..1 + self.next(5))
+            .map(|_| self.item())
+            .collect::<Vec<_>>()
+            .join(&format!(",{}", self.space()));
+        let end = if self.next(2) == 0 { "!" } else { "." };
+        format!("{} {}{}", self.ident(), items, end)
+    }
+}
+
+#[test]
+fn parse_unparse() {
+    let mut rng = Docs(Rng::new(42));
+    for _ in 0..500 {
+        let src = rng.doc();
+        let x = parse(&src);
+        let printed = x.to_string();
+        assert_eq!(parse(&printed), x, "{src:?} -> {printed:?}");
+        // печать канонична: повторная ничего не меняет
+        assert_eq!(parse(&printed).to_string(), printed);
+    }
+}
+
+#[test]
+fn round_trip_property() {
+    let mut docs = Docs(Rng::new(7));
+    Check::default().cases(300).run(
+        |rng| {
+            // каждый второй вход испорчен одним символом и чаще всего не разбирается
+            let mut src = docs.doc().chars().collect::<Vec<_>>();
+            if rng.below(2) == 1 {
+                let c = "(),[]!. 0a".chars().nth(rng.below(10)).unwrap();
+                src.insert(rng.below(src.len() + 1), c);
+            }
+            src
+        },
+        round_trip(
+            |src| InputStreamIter::new(src).full_parse(&Doc::default()).ok(),
+            |v| v.to_string(),
+        ),
+    );
+}
+
+#[test]
+fn canonical_rule() {
+    let mut out = String::new();
+    Unparse::<InputStreamIter>::canonical(&Pair::default(), &mut out);
+    assert_eq!(out, "(A, 0)");
+}
+
+#[test]
+fn cached_differential() {
+    for src in ["doc 1,   (x,2), [3,\t4].", "doc [1, 2]!", "doc (x, 1", "doc"] {
+        if let Err(e) = differential(&Doc::default(), src) {
+            panic!("{}", e);
+        }
+    }
+}
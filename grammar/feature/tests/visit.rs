@@ -0,0 +1,148 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+use abstract_parser::{
+    grammar::{core::parser::*, feature::grammar::grammar},
+    parsers::chars::{CharParser, InputStreamIter, InputStreamTrait},
+};
+
+grammar! {#[derive(Visit, VisitMut, Fold)] r#"
+Doc {
+    name: Ident,
+    Space,
+    items: Item ** Sep,
+    end: Bang / ".",
+}
+Item {
+    Interval(Interval)
+    Int(Num)
+    Pair(Pair)
+    Group(Group<Num>)
+}
+Group<T> {
+    "\[",
+    inner: T ** Sep,
+    "\]",
+}
+Pair {
+    "\(",
+    key: Ident,
+    ",",
+    value: Num,
+    "\)",
+}
+Interval ( Num #[ignore] "\.\." Num )
+Sep = ",\s*"
+Num: usize = "[0-9]+"
+unit Bang = "!"
+"#}
+
+#[derive(Default)]
+struct Stats {
+    items: usize,
+    keys: Vec<String>,
+    intervals: usize,
+}
+
+impl<'src, IS: InputStreamTrait<'src>> Visit<'src, IS> for Stats {
+    fn visit_item(&mut self, node: &ItemOutput<'src, IS>) {
+        self.items += 1;
+        visit::visit_item(self, node)
+    }
+
+    fn visit_pair(&mut self, node: &PairOutput<'src, IS>) {
+        self.keys.push(node.key.to_owned());
+        visit::visit_pair(self, node)
+    }
+
+    fn visit_interval(&mut self, node: &IntervalOutput<'src, IS>) {
+        self.intervals += 1;
+        // `IS` в кортеже не встречается, и его приходится указать
+        visit::visit_interval::<IS, _>(self, node)
+    }
+}
+
+/// Удваивает числа, и одиночные, и в парах.
+struct Double;
+
+impl<'src, IS: InputStreamTrait<'src>> VisitMut<'src, IS> for Double {
+    fn visit_item_mut(&mut self, node: &mut ItemOutput<'src, IS>) {
+        if let ItemOutput::Int(v) = node {
+            *v *= 2
+        }
+        visit_mut::visit_item_mut(self, node)
+    }
+
+    fn visit_pair_mut(&mut self, node: &mut PairOutput<'src, IS>) {
+        node.value *= 2
+    }
+
+    fn visit_interval_mut(&mut self, node: &mut IntervalOutput<'src, IS>) {
+        node.1 *= 2
+    }
+}
+
+/// Заменяет пары их значением, а интервалы — их длиной.
+struct Unpair;
+
+impl<'src, IS: InputStreamTrait<'src>> Fold<'src, IS> for Unpair {
+    fn fold_item(&mut self, node: ItemOutput<'src, IS>) -> ItemOutput<'src, IS> {
+        match fold::fold_item(self, node) {
+            ItemOutput::Pair(v) => ItemOutput::Int(v.value),
+            v => v,
+        }
+    }
+
+    fn fold_interval(&mut self, node: IntervalOutput<'src, IS>) -> IntervalOutput<'src, IS> {
+        (0, node.1 - node.0)
+    }
+}
+
+fn nums<'src, IS: InputStreamTrait<'src>>(out: &DocOutput<'src, IS>) -> Vec<usize> {
+    out.items
+        .iter()
+        .map(|v| match v {
+            ItemOutput::Int(v) => *v,
+            ItemOutput::Pair(v) => v.value,
+            ItemOutput::Interval((from, to)) => from + to,
+            ItemOutput::Group(v) => v.inner.iter().sum(),
+        })
+        .collect()
+}
+
+#[test]
+fn visit() {
+    let src = "doc 1, (x,2), 3, (y,4), [5, 6], 1..3!";
+    let mut out = InputStreamIter::new(src)
+        .full_parse(&Doc::default())
+        .unwrap();
+
+    let mut stats = Stats::default();
+    stats.visit_doc(&out);
+    assert_eq!(stats.items, 6);
+    assert_eq!(stats.keys, ["x", "y"]);
+    assert_eq!(stats.intervals, 1);
+
+    Double.visit_doc_mut(&mut out);
+    assert_eq!(nums(&out), [2, 4, 6, 8, 11, 7]);
+
+    let out = Unpair.fold_doc(out);
+    assert!(!out.items.iter().any(|v| matches!(v, ItemOutput::Pair(..))));
+    assert_eq!(nums(&out), [2, 4, 6, 8, 11, 5]);
+
+    // обход через `Walk` у поля вызывает тот же метод посетителя
+    let mut stats = Stats::default();
+    abstract_parser::visit::Walk::walk(&out.items, &mut stats);
+    assert_eq!((stats.items, stats.keys.len()), (6, 0));
+}
@@ -0,0 +1,456 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use std::{fmt, ops::Range};
+
+/// `.abs`-грамматика, прочитанная в рантайме: правила и комментарии в исходном порядке.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Grammar {
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Comment(Comment),
+    Rule(RuleDef),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comment {
+    /// Текст после `//`.
+    pub text: String,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleDef {
+    pub name: String,
+    pub generics: Vec<String>,
+    /// Позиции параметров дженерика в заголовке, по одной на `generics`.
+    pub generic_spans: Vec<Range<usize>>,
+    pub body: Body,
+    /// Всё определение, от имени (или `unit`) до конца тела.
+    pub span: Range<usize>,
+    pub name_span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Body {
+    /// `Name = expr`, `Name = a b;`, `Name = a / b`, `Name = a*`, `Name = Alias`
+    Expr(Expr),
+    /// `Name = "regex"`, `unit Name = ".."`, `Name: Type = ".."`
+    Token { kind: TokenKind, literal: Literal },
+    /// `Name { A(expr) B(expr) }`
+    Enum(Vec<Variant>),
+    /// `Name { a: expr, expr }`
+    Struct(Vec<Field>),
+    /// `Name ( expr #[ignore] expr )`
+    Tuple(Vec<TupleItem>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Base,
+    Unit,
+    Parse(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub comments: Vec<String>,
+    pub name: String,
+    pub expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub comments: Vec<String>,
+    pub name: Option<String>,
+    pub expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TupleItem {
+    pub ignored: bool,
+    pub expr: Expr,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Ref(Ref),
+    Literal(Literal),
+    Seq(Vec<Expr>),
+    Choice(Vec<Expr>),
+    Repeat(Box<Expr>, Repetition),
+    Optional(Box<Expr>),
+    NegativeLookahead(Box<Expr>),
+    /// `&expr`
+    AndPredicate(Box<Expr>),
+    /// `expr ** join`, `expr **{n,} join`
+    Joinable {
+        expr: Box<Expr>,
+        join: Box<Expr>,
+        repetition: Option<Repetition>,
+    },
+}
+
+/// `Name`, `<Name>`, `Name<A, B>`
+#[derive(Debug, Clone)]
+pub struct Ref {
+    pub name: String,
+    pub args: Vec<Expr>,
+    pub boxed: bool,
+    pub span: Range<usize>,
+}
+
+/// Позиция ссылки не участвует в сравнении.
+impl PartialEq for Ref {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.args == other.args && self.boxed == other.boxed
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Literal {
+    /// Содержимое кавычек как есть, без раскрытия экранирования.
+    pub value: String,
+    /// `"..."s` — подстрока, иначе регулярное выражение.
+    pub sub_str: bool,
+    /// От открывающей кавычки до `s` включительно.
+    pub span: Range<usize>,
+}
+
+/// Позиция литерала не участвует в сравнении.
+impl PartialEq for Literal {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.sub_str == other.sub_str
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Repetition {
+    /// `*`
+    ZeroOrMore,
+    /// `+`
+    OneOrMore,
+    /// `{n,}`
+    Min(usize),
+    /// `{,n}`
+    Max(usize),
+    /// `{n,m}`
+    MinMax(usize, usize),
+    /// `{n}`
+    Count(usize),
+}
+
+impl Grammar {
+    #[inline]
+    pub fn rules(&self) -> impl Iterator<Item = &RuleDef> {
+        self.items.iter().filter_map(|v| match v {
+            Item::Rule(v) => Some(v),
+            Item::Comment(..) => None,
+        })
+    }
+
+    #[inline]
+    pub fn rule(&self, name: &str) -> Option<&RuleDef> {
+        self.rules().find(|v| v.name == name)
+    }
+}
+
+impl Item {
+    #[inline]
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Comment(v) => v.span.clone(),
+            Self::Rule(v) => v.span.clone(),
+        }
+    }
+}
+
+impl RuleDef {
+    /// Имя с параметрами дженерика: `Name<A, B>`.
+    pub(crate) fn head(&self) -> String {
+        if self.generics.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}<{}>", self.name, self.generics.join(", "))
+        }
+    }
+}
+
+impl Body {
+    /// Выражения тела: варианты, поля и элементы кортежа по порядку; у токена — ни одного.
+    pub fn exprs(&self) -> Vec<&Expr> {
+        match self {
+            Self::Expr(v) => vec![v],
+            Self::Token { .. } => vec![],
+            Self::Enum(v) => v.iter().map(|v| &v.expr).collect(),
+            Self::Struct(v) => v.iter().map(|v| &v.expr).collect(),
+            Self::Tuple(v) => v.iter().map(|v| &v.expr).collect(),
+        }
+    }
+}
+
+impl Expr {
+    #[inline]
+    pub fn ident(name: impl Into<String>) -> Self {
+        Self::Ref(Ref {
+            name: name.into(),
+            args: vec![],
+            boxed: false,
+            span: 0..0,
+        })
+    }
+
+    /// Обходит все подвыражения, включая аргументы дженериков.
+    pub fn walk<'a>(&'a self, f: &mut impl FnMut(&'a Expr)) {
+        f(self);
+        match self {
+            Self::Ref(v) => v.args.iter().for_each(|v| v.walk(f)),
+            Self::Literal(..) => {}
+            Self::Seq(v) | Self::Choice(v) => v.iter().for_each(|v| v.walk(f)),
+            Self::Repeat(v, ..)
+            | Self::Optional(v)
+            | Self::NegativeLookahead(v)
+            | Self::AndPredicate(v) => v.walk(f),
+            Self::Joinable { expr, join, .. } => {
+                expr.walk(f);
+                join.walk(f);
+            }
+        }
+    }
+
+    /// Подставляет выражения вместо параметров дженерика.
+    pub fn substitute(&self, params: &[(&str, &Expr)]) -> Expr {
+        let sub = |v: &Expr| v.substitute(params);
+        match self {
+            Self::Ref(v) if v.args.is_empty() => params
+                .iter()
+                .find(|(name, _)| *name == v.name)
+                .map(|(_, expr)| (*expr).clone())
+                .unwrap_or_else(|| self.clone()),
+            Self::Ref(v) => Self::Ref(Ref {
+                args: v.args.iter().map(sub).collect(),
+                ..v.clone()
+            }),
+            Self::Literal(..) => self.clone(),
+            Self::Seq(v) => Self::Seq(v.iter().map(sub).collect()),
+            Self::Choice(v) => Self::Choice(v.iter().map(sub).collect()),
+            Self::Repeat(v, r) => Self::Repeat(Box::new(sub(v)), *r),
+            Self::Optional(v) => Self::Optional(Box::new(sub(v))),
+            Self::NegativeLookahead(v) => Self::NegativeLookahead(Box::new(sub(v))),
+            Self::AndPredicate(v) => Self::AndPredicate(Box::new(sub(v))),
+            Self::Joinable {
+                expr,
+                join,
+                repetition,
+            } => Self::Joinable {
+                expr: Box::new(sub(expr)),
+                join: Box::new(sub(join)),
+                repetition: *repetition,
+            },
+        }
+    }
+
+    /// Альтернатива выбора: последовательность, выбор и `**` — в скобках.
+    pub(crate) fn alternative(&self) -> String {
+        match self {
+            Self::Seq(..) | Self::Choice(..) | Self::Joinable { .. } => format!("({self})"),
+            v => v.to_string(),
+        }
+    }
+
+    /// Выражение без скобок можно подставить в последовательность/выбор.
+    #[inline]
+    fn is_atom(&self) -> bool {
+        matches!(self, Self::Ref(..) | Self::Literal(..))
+    }
+}
+
+impl fmt::Display for Repetition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroOrMore => write!(f, "*"),
+            Self::OneOrMore => write!(f, "+"),
+            Self::Min(v) => write!(f, "{{{v},}}"),
+            Self::Max(v) => write!(f, "{{,{v}}}"),
+            Self::MinMax(min, max) => write!(f, "{{{min},{max}}}"),
+            Self::Count(v) => write!(f, "{{{v}}}"),
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            r#""{}"{}"#,
+            self.value,
+            if self.sub_str { "s" } else { "" }
+        )
+    }
+}
+
+impl fmt::Display for Ref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.boxed {
+            write!(f, "<{}>", self.name)
+        } else if self.args.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}<{}>", self.name, join(&self.args, ", "))
+        }
+    }
+}
+
+/// Выражение в синтаксисе `.abs`; у последовательности верхнего уровня нет `;`.
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // операнд квантификатора: атом или выражение в скобках
+        let operand = |v: &Expr| {
+            if v.is_atom() {
+                v.to_string()
+            } else {
+                format!("({v})")
+            }
+        };
+        match self {
+            Self::Ref(v) => write!(f, "{v}"),
+            Self::Literal(v) => write!(f, "{v}"),
+            Self::Seq(v) => write!(
+                f,
+                "{}",
+                v.iter()
+                    .map(|v| match v {
+                        Self::Seq(..) | Self::Choice(..) => format!("({v})"),
+                        v => v.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ),
+            Self::Choice(v) => write!(
+                f,
+                "{}",
+                v.iter()
+                    .map(Self::alternative)
+                    .collect::<Vec<_>>()
+                    .join(" / ")
+            ),
+            Self::Repeat(v, r) => write!(f, "{}{r}", operand(v)),
+            Self::Optional(v) => write!(f, "{}?", operand(v)),
+            Self::NegativeLookahead(v) => write!(f, "!{}", operand(v)),
+            Self::AndPredicate(v) => write!(f, "&{}", operand(v)),
+            Self::Joinable {
+                expr,
+                join,
+                repetition,
+            } => write!(
+                f,
+                "{} **{} {}",
+                operand(expr),
+                repetition.map(|v| v.to_string()).unwrap_or_default(),
+                operand(join)
+            ),
+        }
+    }
+}
+
+/// Грамматика в каноническом виде: по элементу на строку, тела блоков с отступом в 4 пробела.
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.items {
+            match item {
+                Item::Comment(v) => writeln!(f, "//{}", v.text)?,
+                Item::Rule(v) => writeln!(f, "{v}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RuleDef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let head = self.head();
+        let comments = |f: &mut fmt::Formatter<'_>, v: &[String]| {
+            v.iter().try_for_each(|v| writeln!(f, "    //{v}"))
+        };
+        match &self.body {
+            Body::Expr(v @ Expr::Seq(..)) => write!(f, "{head} = {v};"),
+            Body::Expr(v) => write!(f, "{head} = {v}"),
+            Body::Token { kind, literal } => match kind {
+                TokenKind::Base => write!(f, "{head} = {literal}"),
+                TokenKind::Unit => write!(f, "unit {head} = {literal}"),
+                TokenKind::Parse(type_) => write!(f, "{head}: {type_} = {literal}"),
+            },
+            Body::Enum(v) => {
+                writeln!(f, "{head} {{")?;
+                for v in v {
+                    comments(f, &v.comments)?;
+                    writeln!(f, "    {v}")?;
+                }
+                write!(f, "}}")
+            }
+            Body::Struct(v) => {
+                writeln!(f, "{head} {{")?;
+                for v in v {
+                    comments(f, &v.comments)?;
+                    writeln!(f, "    {v},")?;
+                }
+                write!(f, "}}")
+            }
+            Body::Tuple(v) => {
+                writeln!(f, "{head} (")?;
+                for v in v {
+                    writeln!(f, "    {v}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Variant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", self.name, self.expr)
+    }
+}
+
+/// Поле без запятой.
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "{name}: {}", self.expr),
+            None => write!(f, "{}", self.expr),
+        }
+    }
+}
+
+impl fmt::Display for TupleItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.expr, self.ignored) {
+            (expr @ Expr::Seq(..), true) => write!(f, "#[ignore] ({expr})"),
+            (expr, true) => write!(f, "#[ignore] {expr}"),
+            (expr, false) => write!(f, "{expr}"),
+        }
+    }
+}
+
+#[inline]
+fn join(v: &[Expr], sep: &str) -> String {
+    v.iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(sep)
+}
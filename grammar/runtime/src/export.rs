@@ -0,0 +1,895 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Экспорт `.abs`-грамматики в ISO EBNF (ISO/IEC 14977), ABNF (RFC 5234) и pest.
+//!
+//! Регулярки токенов переводятся в выражения целевого синтаксиса: литералы, классы (`\d`,
+//! `\s`, `\w` — в ASCII-варианте), повторы, группы и альтернативы. Чего в цели нет,
+//! записывается её средствами с комментарием над правилом: дженерик раскрывается в правило на
+//! каждый экземпляр, `**` — в повтор разделителя с элементом, `!` в EBNF и ABNF — в
+//! специальную последовательность или прозу. Встроенные правила рантайма, на которые есть
+//! ссылки, дописываются в конец вместе с экземплярами.
+//!
+//! pest, как и `.abs`, — PEG, а регулярка внутри токена перебирает варианты с возвратом:
+//! после перевода в `"[0-9]+0"` жадный повтор съест последний ноль.
+
+use crate::{ast::*, CompileError, RuntimeGrammar, MAX_DEPTH};
+use regex_syntax::ast::{self as re, Ast};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Целевой синтаксис экспорта.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// ISO/IEC 14977.
+    Ebnf,
+    /// RFC 5234; строки с буквами — `%s"..."` из RFC 7405, чтобы регистр учитывался.
+    Abnf,
+    Pest,
+}
+
+/// Грамматика в синтаксисе `syntax`: правила без дженериков и комментарии в исходном порядке,
+/// затем экземпляры дженериков и встроенные правила, на которые есть ссылки.
+pub fn export(grammar: &RuntimeGrammar, syntax: Syntax) -> Result<String, CompileError> {
+    let mut exporter = Exporter {
+        grammar,
+        syntax,
+        names: HashMap::new(),
+        taken: HashSet::new(),
+        instances: HashMap::new(),
+        queue: VecDeque::new(),
+    };
+    // имена правил грамматики занимаются первыми, экземпляры получают суффикс при совпадении
+    for def in grammar.grammar().rules() {
+        if def.generics.is_empty() {
+            let name = exporter.unique(&def.name);
+            exporter.names.insert(def.name.clone(), name);
+        }
+    }
+
+    let mut out = String::new();
+    for item in &grammar.grammar().items {
+        match item {
+            Item::Comment(v) => out.push_str(&syntax.comment(v.text.trim())),
+            Item::Rule(def) if def.generics.is_empty() => {
+                let name = exporter.names[&def.name].clone();
+                exporter.rule(&name, def, &[], vec![], &mut out)?;
+            }
+            Item::Rule(def) => out.push_str(&syntax.comment(&format!(
+                "`{}` is generic: {} has no parameterized rules, its instances are expanded below",
+                def.head(),
+                syntax.name()
+            ))),
+        }
+    }
+    if !exporter.queue.is_empty() {
+        out.push('\n');
+    }
+    while let Some(v) = exporter.queue.pop_front() {
+        exporter.rule(&v.name, v.def, &v.args, vec![v.note], &mut out)?;
+    }
+    Ok(out)
+}
+
+impl Syntax {
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ebnf => "EBNF",
+            Self::Abnf => "ABNF",
+            Self::Pest => "pest",
+        }
+    }
+
+    fn comment(self, text: &str) -> String {
+        match self {
+            Self::Ebnf => format!("(* {} *)\n", text.replace("*)", "* )")),
+            Self::Abnf => format!("; {text}\n"),
+            Self::Pest => format!("// {text}\n"),
+        }
+    }
+
+    /// Имя правила в целевом синтаксисе: в ABNF нет `_`, встроенные правила pest заняты.
+    fn rule_name(self, name: &str) -> String {
+        match self {
+            Self::Ebnf => name.to_string(),
+            Self::Abnf => name.replace('_', "-"),
+            Self::Pest if PEST_BUILTINS.contains(&name) => format!("{name}_"),
+            Self::Pest => name.to_string(),
+        }
+    }
+
+    /// Ключ уникальности имени: имена ABNF не различают регистр.
+    #[inline]
+    fn key(self, name: &str) -> String {
+        match self {
+            Self::Abnf => name.to_lowercase(),
+            Self::Ebnf | Self::Pest => name.to_string(),
+        }
+    }
+}
+
+const PEST_BUILTINS: &[&str] = &[
+    "ANY",
+    "SOI",
+    "EOI",
+    "WHITESPACE",
+    "COMMENT",
+    "PUSH",
+    "POP",
+    "POP_ALL",
+    "PEEK",
+    "PEEK_ALL",
+    "DROP",
+    "NEWLINE",
+    "ASCII",
+    "ASCII_DIGIT",
+    "ASCII_NONZERO_DIGIT",
+    "ASCII_BIN_DIGIT",
+    "ASCII_OCT_DIGIT",
+    "ASCII_HEX_DIGIT",
+    "ASCII_ALPHA_LOWER",
+    "ASCII_ALPHA_UPPER",
+    "ASCII_ALPHA",
+    "ASCII_ALPHANUMERIC",
+];
+
+/// Правило, которое нужно дописать: экземпляр дженерика или встроенное правило.
+struct Instance<'g> {
+    name: String,
+    def: &'g RuleDef,
+    args: Vec<Expr>,
+    note: String,
+}
+
+struct Exporter<'g> {
+    grammar: &'g RuntimeGrammar,
+    syntax: Syntax,
+    /// Имя в выводе по экземпляру (`Name`, `Name<Arg>`).
+    names: HashMap<String, String>,
+    taken: HashSet<String>,
+    /// Число экземпляров каждого дженерика: раскрытие без конца обрывается на `MAX_DEPTH`.
+    instances: HashMap<&'g str, usize>,
+    queue: VecDeque<Instance<'g>>,
+}
+
+impl<'g> Exporter<'g> {
+    fn unique(&mut self, name: &str) -> String {
+        let base = self.syntax.rule_name(name);
+        let sep = if self.syntax == Syntax::Abnf {
+            "-"
+        } else {
+            "_"
+        };
+        let mut name = base.clone();
+        let mut n = 1;
+        while !self.taken.insert(self.syntax.key(&name)) {
+            n += 1;
+            name = format!("{base}{sep}{n}");
+        }
+        name
+    }
+
+    fn rule(
+        &mut self,
+        name: &str,
+        def: &RuleDef,
+        args: &[Expr],
+        mut notes: Vec<String>,
+        out: &mut String,
+    ) -> Result<(), CompileError> {
+        let params = def
+            .generics
+            .iter()
+            .map(String::as_str)
+            .zip(args)
+            .collect::<Vec<_>>();
+        let term = self.body(def, &params, &mut notes)?;
+        for v in &notes {
+            out.push_str(&self.syntax.comment(v));
+        }
+        let (text, _) = self.syntax.print(&term);
+        match self.syntax {
+            Syntax::Ebnf => out.push_str(&format!("{name} = {text} ;\n")),
+            Syntax::Abnf => out.push_str(&format!("{name} = {text}\n")),
+            Syntax::Pest => out.push_str(&format!("{name} = {{ {text} }}\n")),
+        }
+        Ok(())
+    }
+
+    fn body(
+        &mut self,
+        def: &RuleDef,
+        params: &[(&str, &Expr)],
+        notes: &mut Vec<String>,
+    ) -> Result<Term, CompileError> {
+        if let Body::Token { literal, .. } = &def.body {
+            return self.literal(literal, notes);
+        }
+        let mut terms = vec![];
+        for v in def.body.exprs() {
+            terms.push(self.expr(&v.substitute(params), notes)?);
+        }
+        Ok(match def.body {
+            Body::Enum(..) => Term::Choice(terms),
+            _ => Term::seq(terms),
+        })
+    }
+
+    fn expr(&mut self, expr: &Expr, notes: &mut Vec<String>) -> Result<Term, CompileError> {
+        let mut all = |v: &[Expr], notes: &mut Vec<String>| {
+            v.iter()
+                .map(|v| self.expr(v, notes))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match expr {
+            Expr::Ref(v) => Term::Rule(self.reference(v)?),
+            Expr::Literal(v) => self.literal(v, notes)?,
+            Expr::Seq(v) => Term::seq(all(v, notes)?),
+            Expr::Choice(v) => Term::Choice(all(v, notes)?),
+            Expr::Repeat(v, r) => {
+                let (min, max) = bounds(*r);
+                Term::repeat(self.expr(v, notes)?, min, max)
+            }
+            Expr::Optional(v) => Term::repeat(self.expr(v, notes)?, 0, Some(1)),
+            Expr::NegativeLookahead(v) | Expr::AndPredicate(v) => {
+                match self.syntax {
+                    Syntax::Ebnf => note(
+                        notes,
+                        format!("`{expr}`: EBNF has no lookahead, it is a special sequence"),
+                    ),
+                    Syntax::Abnf => note(
+                        notes,
+                        format!("`{expr}`: ABNF has no lookahead, it is a prose value"),
+                    ),
+                    Syntax::Pest => {}
+                }
+                let term = Box::new(self.expr(v, notes)?);
+                match expr {
+                    Expr::AndPredicate(..) => Term::And(term, v.to_string()),
+                    _ => Term::Not(term, v.to_string()),
+                }
+            }
+            Expr::Joinable {
+                expr: item,
+                join,
+                repetition,
+            } => {
+                note(
+                    notes,
+                    format!(
+                        "`{expr}`: {} has no separated repetition, it is expanded to \
+                         an item followed by repeated separator and item",
+                        self.syntax.name()
+                    ),
+                );
+                let (min, max) = bounds(repetition.unwrap_or(Repetition::ZeroOrMore));
+                let item = self.expr(item, notes)?;
+                let join = self.expr(join, notes)?;
+                joined(item, join, min, max)
+            }
+        })
+    }
+
+    fn literal(&self, v: &Literal, notes: &mut Vec<String>) -> Result<Term, CompileError> {
+        if v.sub_str {
+            return Ok(Term::Str(v.value.clone()));
+        }
+        if let Some(term) = regex(&v.value) {
+            return Ok(term);
+        }
+        note(
+            notes,
+            match self.syntax {
+                // прозы в pest нет: на месте регулярки заглушка, которую надо дописать
+                Syntax::Pest => format!(
+                    "regular expression {v} has no pest equivalent, \
+                     `!ANY ~ ANY` is a placeholder that never matches"
+                ),
+                _ => format!(
+                    "regular expression {v} has no {} equivalent",
+                    self.syntax.name()
+                ),
+            },
+        );
+        Ok(Term::Prose(format!("regex {}", v.value)))
+    }
+
+    /// Имя правила в выводе; экземпляры дженериков и встроенные правила ставятся в очередь.
+    fn reference(&mut self, r: &Ref) -> Result<String, CompileError> {
+        let key = Ref {
+            boxed: false,
+            ..r.clone()
+        }
+        .to_string();
+        if let Some(v) = self.names.get(&key) {
+            return Ok(v.clone());
+        }
+        let def = self
+            .grammar
+            .def(&r.name)
+            .ok_or_else(|| CompileError::Undefined {
+                name: r.name.clone(),
+                span: r.span.clone(),
+            })?;
+        if def.generics.len() != r.args.len() {
+            return Err(CompileError::Arity {
+                name: r.name.clone(),
+                expected: def.generics.len(),
+                found: r.args.len(),
+                span: r.span.clone(),
+            });
+        }
+
+        let (name, note) = if def.generics.is_empty() {
+            (
+                self.unique(&def.name),
+                format!("`{key}`: built-in rule of the grammar runtime"),
+            )
+        } else {
+            let count = self.instances.entry(def.name.as_str()).or_default();
+            *count += 1;
+            if *count > MAX_DEPTH {
+                return Err(CompileError::TooDeep {
+                    name: def.name.clone(),
+                });
+            }
+            let count = *count;
+            // `Braced<Item>` — `Braced_Item`, с выражениями в аргументах — номер экземпляра
+            let args = r
+                .args
+                .iter()
+                .map(|v| match v {
+                    Expr::Ref(v) if v.args.is_empty() => Some(v.name.as_str()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>();
+            let name = match args {
+                Some(args) => format!("{}_{}", def.name, args.join("_")),
+                None => format!("{}_{count}", def.name),
+            };
+            (
+                self.unique(&name),
+                format!("`{key}`: instance of generic `{}`", def.head()),
+            )
+        };
+        self.names.insert(key, name.clone());
+        self.queue.push_back(Instance {
+            name: name.clone(),
+            def,
+            args: r.args.clone(),
+            note,
+        });
+        Ok(name)
+    }
+}
+
+#[inline]
+fn note(notes: &mut Vec<String>, text: String) {
+    if !notes.contains(&text) {
+        notes.push(text);
+    }
+}
+
+fn bounds(v: Repetition) -> (usize, Option<usize>) {
+    match v {
+        Repetition::ZeroOrMore => (0, None),
+        Repetition::OneOrMore => (1, None),
+        Repetition::Min(min) => (min, None),
+        Repetition::Max(max) => (0, Some(max)),
+        Repetition::MinMax(min, max) => (min, Some(max)),
+        Repetition::Count(v) => (v, Some(v)),
+    }
+}
+
+/// `item **{min,max} join` как `item (join item){min-1,max-1}`, при `min = 0` — необязательно.
+fn joined(item: Term, join: Term, min: usize, max: Option<usize>) -> Term {
+    if max == Some(0) {
+        return Term::seq(vec![]);
+    }
+    let rest = Term::repeat(
+        Term::seq(vec![join, item.clone()]),
+        min.saturating_sub(1),
+        max.map(|v| v - 1),
+    );
+    let v = Term::seq(vec![item, rest]);
+    if min == 0 {
+        Term::repeat(v, 0, Some(1))
+    } else {
+        v
+    }
+}
+
+/// Выражение, общее для всех целевых синтаксисов.
+#[derive(Debug, Clone)]
+enum Term {
+    Rule(String),
+    Str(String),
+    /// Символ из диапазонов, при `negated` — любой другой.
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Seq(Vec<Term>),
+    Choice(Vec<Term>),
+    Repeat(Box<Term>, usize, Option<usize>),
+    /// Отрицательный просмотр вперёд и его операнд в записи `.abs`.
+    Not(Box<Term>, String),
+    /// Положительный просмотр вперёд и его операнд в записи `.abs`.
+    And(Box<Term>, String),
+    /// Описание того, что целевой синтаксис не выражает.
+    Prose(String),
+}
+
+impl Term {
+    /// Последовательность без вложенных последовательностей, соседние строки склеены.
+    fn seq(items: Vec<Term>) -> Term {
+        let mut out = vec![];
+        for v in items {
+            match (out.last_mut(), v) {
+                (_, Term::Seq(v)) => out.extend(v),
+                (Some(Term::Str(last)), Term::Str(v)) => last.push_str(&v),
+                (_, v) => out.push(v),
+            }
+        }
+        if out.len() == 1 {
+            out.remove(0)
+        } else {
+            Term::Seq(out)
+        }
+    }
+
+    fn repeat(term: Term, min: usize, max: Option<usize>) -> Term {
+        match (min, max) {
+            (1, Some(1)) => term,
+            (_, Some(0)) => Term::seq(vec![]),
+            _ => Term::Repeat(Box::new(term), min, max),
+        }
+    }
+
+    fn class(ranges: Vec<(char, char)>, negated: bool) -> Term {
+        match normalize(ranges) {
+            ranges if !negated && ranges.len() == 1 && ranges[0].0 == ranges[0].1 => {
+                Term::Str(ranges[0].0.to_string())
+            }
+            ranges => Term::Class { ranges, negated },
+        }
+    }
+}
+
+/// Уровни связывания при печати: выбор, последовательность, атом.
+const CHOICE: u8 = 0;
+const SEQ: u8 = 1;
+const ATOM: u8 = 2;
+
+impl Syntax {
+    /// Выражение в скобках, если оно связано слабее, чем требует место.
+    fn wrap(self, term: &Term, need: u8) -> String {
+        match self.print(term) {
+            (text, level) if level < need => format!("({text})"),
+            (text, _) => text,
+        }
+    }
+
+    fn print(self, term: &Term) -> (String, u8) {
+        match term {
+            Term::Rule(v) => (v.clone(), ATOM),
+            Term::Str(v) => self.string(v),
+            Term::Class {
+                ranges,
+                negated: false,
+            } => self.class(ranges),
+            Term::Class {
+                ranges,
+                negated: true,
+            } => {
+                let set = || {
+                    self.wrap(
+                        &Term::Class {
+                            ranges: ranges.clone(),
+                            negated: false,
+                        },
+                        ATOM,
+                    )
+                };
+                match self {
+                    Self::Abnf => self.class(&complement(ranges)),
+                    Self::Ebnf if ranges.is_empty() => ("? any character ?".into(), ATOM),
+                    Self::Ebnf => (format!("? any character ? - {}", set()), SEQ),
+                    Self::Pest if ranges.is_empty() => ("ANY".into(), ATOM),
+                    Self::Pest => (format!("!{} ~ ANY", set()), SEQ),
+                }
+            }
+            Term::Seq(v) if v.is_empty() => (r#""""#.into(), ATOM),
+            Term::Seq(v) => {
+                let sep = match self {
+                    Self::Ebnf => ", ",
+                    Self::Abnf => " ",
+                    Self::Pest => " ~ ",
+                };
+                (self.join(v, SEQ, sep), SEQ)
+            }
+            Term::Choice(v) => {
+                let sep = match self {
+                    Self::Abnf => " / ",
+                    Self::Ebnf | Self::Pest => " | ",
+                };
+                (self.join(v, SEQ, sep), CHOICE)
+            }
+            Term::Repeat(v, min, max) => self.repeat(v, *min, *max),
+            Term::Not(v, text) => match self {
+                Self::Pest => (format!("!{}", self.wrap(v, ATOM)), SEQ),
+                _ => self.prose(&format!("not followed by {text}")),
+            },
+            Term::And(v, text) => match self {
+                Self::Pest => (format!("&{}", self.wrap(v, ATOM)), SEQ),
+                _ => self.prose(&format!("followed by {text}")),
+            },
+            Term::Prose(v) => self.prose(v),
+        }
+    }
+
+    #[inline]
+    fn join(self, v: &[Term], need: u8, sep: &str) -> String {
+        v.iter()
+            .map(|v| self.wrap(v, need))
+            .collect::<Vec<_>>()
+            .join(sep)
+    }
+
+    fn repeat(self, term: &Term, min: usize, max: Option<usize>) -> (String, u8) {
+        match self {
+            Self::Ebnf => match (min, max) {
+                (0, Some(1)) => (format!("[ {} ]", self.wrap(term, CHOICE)), ATOM),
+                (0, None) => (format!("{{ {} }}", self.wrap(term, CHOICE)), ATOM),
+                (min, max) => {
+                    let mut parts = match min {
+                        0 => vec![],
+                        1 => vec![self.wrap(term, SEQ)],
+                        min => vec![format!("{min} * {}", self.wrap(term, ATOM))],
+                    };
+                    match max {
+                        None => parts.push(format!("{{ {} }}", self.wrap(term, CHOICE))),
+                        Some(max) if max == min + 1 => {
+                            parts.push(format!("[ {} ]", self.wrap(term, CHOICE)))
+                        }
+                        Some(max) if max > min => {
+                            parts.push(format!("{} * [ {} ]", max - min, self.wrap(term, CHOICE)))
+                        }
+                        Some(..) => {}
+                    }
+                    (parts.join(", "), SEQ)
+                }
+            },
+            Self::Abnf => {
+                if (min, max) == (0, Some(1)) {
+                    return (format!("[{}]", self.wrap(term, CHOICE)), ATOM);
+                }
+                let prefix = match (min, max) {
+                    (0, None) => "*".into(),
+                    (min, None) => format!("{min}*"),
+                    (min, Some(max)) if min == max => min.to_string(),
+                    (0, Some(max)) => format!("*{max}"),
+                    (min, Some(max)) => format!("{min}*{max}"),
+                };
+                (format!("{prefix}{}", self.wrap(term, ATOM)), SEQ)
+            }
+            Self::Pest => {
+                let suffix = match (min, max) {
+                    (0, Some(1)) => "?".into(),
+                    (0, None) => "*".into(),
+                    (1, None) => "+".into(),
+                    (min, None) => format!("{{{min},}}"),
+                    (0, Some(max)) => format!("{{,{max}}}"),
+                    (min, Some(max)) if min == max => format!("{{{min}}}"),
+                    (min, Some(max)) => format!("{{{min},{max}}}"),
+                };
+                (format!("{}{suffix}", self.wrap(term, ATOM)), SEQ)
+            }
+        }
+    }
+
+    fn string(self, v: &str) -> (String, u8) {
+        let parts = match self {
+            Self::Ebnf => ebnf_string(v),
+            Self::Abnf => abnf_string(v),
+            Self::Pest => vec![format!("\"{}\"", pest_escape(v, '"'))],
+        };
+        match parts.len() {
+            0 => (r#""""#.into(), ATOM),
+            1 => (parts.concat(), ATOM),
+            _ => (parts.join(if self == Self::Ebnf { ", " } else { " " }), SEQ),
+        }
+    }
+
+    fn class(self, ranges: &[(char, char)]) -> (String, u8) {
+        let mut items = vec![];
+        for &(from, to) in ranges {
+            match self {
+                _ if from == to => items.push(self.string(&from.to_string()).0),
+                // короткий диапазон в EBNF перечисляется, длинный — специальная последовательность
+                Self::Ebnf if to as u32 - from as u32 <= 9 => {
+                    items.extend((from..=to).map(|c| self.string(&c.to_string()).0))
+                }
+                Self::Ebnf => items.push(format!("? {} .. {} ?", ebnf_char(from), ebnf_char(to))),
+                Self::Abnf => items.push(format!("%x{:02X}-{:02X}", from as u32, to as u32)),
+                Self::Pest => items.push(format!(
+                    "'{}'..'{}'",
+                    pest_escape(&from.to_string(), '\''),
+                    pest_escape(&to.to_string(), '\'')
+                )),
+            }
+        }
+        match items.len() {
+            0 => match self {
+                Self::Pest => ("!ANY ~ ANY".into(), SEQ),
+                _ => self.prose("no character"),
+            },
+            1 => (items.remove(0), ATOM),
+            _ => (
+                items.join(if self == Self::Abnf { " / " } else { " | " }),
+                CHOICE,
+            ),
+        }
+    }
+
+    fn prose(self, text: &str) -> (String, u8) {
+        match self {
+            Self::Ebnf => (format!("? {} ?", escape_prose(text, &['?'])), ATOM),
+            Self::Abnf => (format!("<{}>", escape_prose(text, &['>'])), ATOM),
+            // pest выражает просмотр вперёд, проза в нём — только регулярка без перевода
+            Self::Pest => ("!ANY ~ ANY".into(), SEQ),
+        }
+    }
+}
+
+/// Строка терминалами EBNF: кавычки меняются, если внутри есть двойная, управляющие символы —
+/// специальные последовательности.
+fn ebnf_string(v: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut run = String::new();
+    let flush = |run: &mut String, parts: &mut Vec<String>| {
+        if !run.is_empty() {
+            let quote = if run.contains('"') { '\'' } else { '"' };
+            parts.push(format!("{quote}{run}{quote}"));
+            run.clear();
+        }
+    };
+    for c in v.chars() {
+        if c.is_control() {
+            flush(&mut run, &mut parts);
+            parts.push(format!("? {} ?", ebnf_char(c)));
+            continue;
+        }
+        if (c == '"' && run.contains('\'')) || (c == '\'' && run.contains('"')) {
+            flush(&mut run, &mut parts);
+        }
+        run.push(c);
+    }
+    flush(&mut run, &mut parts);
+    parts
+}
+
+/// Символ внутри специальной последовательности EBNF.
+fn ebnf_char(c: char) -> String {
+    match c {
+        c if c.is_control() || c == '?' => format!("U+{:04X}", c as u32),
+        '"' => "'\"'".into(),
+        c => format!("\"{c}\""),
+    }
+}
+
+/// Строка ABNF: печатные ASCII — в кавычках (`%s`, если есть буквы), остальное — `%x` через точку.
+fn abnf_string(v: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut run = String::new();
+    let mut codes: Vec<String> = vec![];
+    let flush_run = |run: &mut String, parts: &mut Vec<String>| {
+        if !run.is_empty() {
+            let prefix = if run.chars().any(|c| c.is_ascii_alphabetic()) {
+                "%s"
+            } else {
+                ""
+            };
+            parts.push(format!("{prefix}\"{run}\""));
+            run.clear();
+        }
+    };
+    let flush_codes = |codes: &mut Vec<String>, parts: &mut Vec<String>| {
+        if !codes.is_empty() {
+            parts.push(format!("%x{}", codes.join(".")));
+            codes.clear();
+        }
+    };
+    for c in v.chars() {
+        if matches!(c, ' '..='~') && c != '"' {
+            flush_codes(&mut codes, &mut parts);
+            run.push(c);
+        } else {
+            flush_run(&mut run, &mut parts);
+            codes.push(format!("{:02X}", c as u32));
+        }
+    }
+    flush_run(&mut run, &mut parts);
+    flush_codes(&mut codes, &mut parts);
+    parts
+}
+
+fn pest_escape(v: &str, quote: char) -> String {
+    let mut out = String::new();
+    for c in v.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{{{:X}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Текст прозы: запрещённые в ней символы и всё вне печатного ASCII — как `U+XXXX`.
+fn escape_prose(text: &str, forbidden: &[char]) -> String {
+    text.chars()
+        .map(|c| {
+            if matches!(c, ' '..='~') && !forbidden.contains(&c) {
+                c.to_string()
+            } else {
+                format!("U+{:04X}", c as u32)
+            }
+        })
+        .collect()
+}
+
+/// Регулярка токена как выражение; `None` — в ней якоря, флаги, Unicode-классы или
+/// конструкции fancy-regex.
+fn regex(src: &str) -> Option<Term> {
+    re::parse::Parser::new()
+        .parse(src)
+        .ok()
+        .and_then(|v| from_ast(&v))
+}
+
+fn from_ast(ast: &Ast) -> Option<Term> {
+    Some(match ast {
+        Ast::Empty(..) => Term::seq(vec![]),
+        Ast::Literal(v) => Term::Str(v.c.to_string()),
+        // `.` без флага `s` не совпадает с переводом строки
+        Ast::Dot(..) => Term::class(vec![('\n', '\n')], true),
+        Ast::ClassPerl(v) => Term::class(perl(&v.kind), v.negated),
+        Ast::ClassBracketed(v) => Term::class(class_set(&v.kind)?, v.negated),
+        Ast::Repetition(v) => {
+            let (min, max) = match &v.op.kind {
+                re::RepetitionKind::ZeroOrOne => (0, Some(1)),
+                re::RepetitionKind::ZeroOrMore => (0, None),
+                re::RepetitionKind::OneOrMore => (1, None),
+                re::RepetitionKind::Range(re::RepetitionRange::Exactly(n)) => {
+                    (*n as usize, Some(*n as usize))
+                }
+                re::RepetitionKind::Range(re::RepetitionRange::AtLeast(n)) => (*n as usize, None),
+                re::RepetitionKind::Range(re::RepetitionRange::Bounded(n, m)) => {
+                    (*n as usize, Some(*m as usize))
+                }
+            };
+            Term::repeat(from_ast(&v.ast)?, min, max)
+        }
+        Ast::Group(v) => match &v.kind {
+            re::GroupKind::NonCapturing(flags) if !flags.items.is_empty() => return None,
+            _ => from_ast(&v.ast)?,
+        },
+        Ast::Alternation(v) => Term::Choice(v.asts.iter().map(from_ast).collect::<Option<_>>()?),
+        Ast::Concat(v) => Term::seq(v.asts.iter().map(from_ast).collect::<Option<_>>()?),
+        Ast::Flags(..) | Ast::Assertion(..) | Ast::ClassUnicode(..) => return None,
+    })
+}
+
+fn class_set(set: &re::ClassSet) -> Option<Vec<(char, char)>> {
+    match set {
+        re::ClassSet::Item(v) => class_item(v),
+        re::ClassSet::BinaryOp(..) => None,
+    }
+}
+
+fn class_item(item: &re::ClassSetItem) -> Option<Vec<(char, char)>> {
+    let negate = |ranges: Vec<(char, char)>, negated: bool| {
+        if negated {
+            complement(&ranges)
+        } else {
+            ranges
+        }
+    };
+    Some(match item {
+        re::ClassSetItem::Empty(..) => vec![],
+        re::ClassSetItem::Literal(v) => vec![(v.c, v.c)],
+        re::ClassSetItem::Range(v) => vec![(v.start.c, v.end.c)],
+        re::ClassSetItem::Ascii(v) => negate(ascii(&v.kind), v.negated),
+        re::ClassSetItem::Perl(v) => negate(perl(&v.kind), v.negated),
+        re::ClassSetItem::Unicode(..) => return None,
+        re::ClassSetItem::Bracketed(v) => negate(class_set(&v.kind)?, v.negated),
+        re::ClassSetItem::Union(v) => v
+            .items
+            .iter()
+            .map(class_item)
+            .collect::<Option<Vec<_>>>()?
+            .concat(),
+    })
+}
+
+fn perl(kind: &re::ClassPerlKind) -> Vec<(char, char)> {
+    match kind {
+        re::ClassPerlKind::Digit => vec![('0', '9')],
+        re::ClassPerlKind::Space => vec![('\t', '\r'), (' ', ' ')],
+        re::ClassPerlKind::Word => vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')],
+    }
+}
+
+fn ascii(kind: &re::ClassAsciiKind) -> Vec<(char, char)> {
+    use re::ClassAsciiKind::*;
+    match kind {
+        Alnum => vec![('0', '9'), ('A', 'Z'), ('a', 'z')],
+        Alpha => vec![('A', 'Z'), ('a', 'z')],
+        Ascii => vec![('\0', '\x7F')],
+        Blank => vec![('\t', '\t'), (' ', ' ')],
+        Cntrl => vec![('\0', '\x1F'), ('\x7F', '\x7F')],
+        Digit => vec![('0', '9')],
+        Graph => vec![('!', '~')],
+        Lower => vec![('a', 'z')],
+        Print => vec![(' ', '~')],
+        Punct => vec![('!', '/'), (':', '@'), ('[', '`'), ('{', '~')],
+        Space => vec![('\t', '\r'), (' ', ' ')],
+        Upper => vec![('A', 'Z')],
+        Word => vec![('0', '9'), ('A', 'Z'), ('_', '_'), ('a', 'z')],
+        Xdigit => vec![('0', '9'), ('A', 'F'), ('a', 'f')],
+    }
+}
+
+/// Диапазоны по возрастанию, пересекающиеся и смежные слиты.
+fn normalize(mut ranges: Vec<(char, char)>) -> Vec<(char, char)> {
+    ranges.sort_unstable();
+    let mut out: Vec<(char, char)> = vec![];
+    for (from, to) in ranges {
+        match out.last_mut() {
+            Some(last) if from as u32 <= last.1 as u32 + 1 => last.1 = last.1.max(to),
+            _ => out.push((from, to)),
+        }
+    }
+    out
+}
+
+/// Все символы, не попавшие в `ranges`.
+fn complement(ranges: &[(char, char)]) -> Vec<(char, char)> {
+    let mut out = vec![];
+    let mut next = 0;
+    for (from, to) in normalize(ranges.to_vec()) {
+        gap(next, from as u32, &mut out);
+        next = to as u32 + 1;
+    }
+    gap(next, 0x11_0000, &mut out);
+    out
+}
+
+/// Символы `from..to` без суррогатов.
+fn gap(from: u32, to: u32, out: &mut Vec<(char, char)>) {
+    for (from, to) in [(from, to.min(0xD800)), (from.max(0xE000), to)] {
+        if from < to {
+            out.push((
+                char::from_u32(from).unwrap(),
+                char::from_u32(to - 1).unwrap(),
+            ));
+        }
+    }
+}
@@ -0,0 +1,298 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Форматирование `.abs`: отступы вложенных определений кратны 4 пробелам, пробелы
+//! вокруг `/`, `**`, `{n,m}` и дженериков единые, длинный выбор переносится по
+//! альтернативам, а имена полей структуры выравниваются. Комментарии и одиночные пустые
+//! строки между определениями сохраняются.
+
+use crate::{ast::*, LoadError};
+use std::{fmt::Write, ops::Range};
+
+/// Ширина строки, после которой выбор переносится, а однострочный блок раскрывается.
+pub const WIDTH: usize = 100;
+const INDENT: &str = "    ";
+/// Ширина табуляции при подсчёте отступа исходника.
+const TAB: usize = 4;
+
+/// Грамматика в каноническом виде; повторное форматирование ничего не меняет.
+pub fn format(src: &str) -> Result<String, LoadError> {
+    let grammar = Grammar::parse(src)?;
+    let mut out = String::new();
+    let mut prev = None;
+    for (item, depth) in grammar.items.iter().zip(nesting(src, &grammar)) {
+        let span = item.span();
+        let between = prev.map(|end| &src[end..span.start]);
+        prev = Some(span.end);
+        match item {
+            // комментарий в строке определения остаётся в ней
+            Item::Comment(v) if between.is_some_and(|v| !v.contains('\n')) => {
+                out.pop();
+                writeln!(out, " //{}", v.text.trim_end()).unwrap();
+                continue;
+            }
+            _ if between.is_some_and(blank_line) => out.push('\n'),
+            _ => {}
+        }
+        let pad = INDENT.repeat(depth);
+        match item {
+            Item::Comment(v) => writeln!(out, "{pad}//{}", v.text.trim_end()).unwrap(),
+            Item::Rule(v) => rule(&mut out, src, v, &pad),
+        }
+    }
+    Ok(out)
+}
+
+/// Уровень вложенности каждого элемента `grammar`, разобранной из `src`.
+///
+/// Уровень берётся из отступов: строка с большим отступом, чем у предыдущего определения,
+/// вложена в него, с меньшим — возвращается к уровню с таким же или меньшим отступом.
+/// Элемент в строке предыдущего остаётся на его уровне.
+pub fn nesting(src: &str, grammar: &Grammar) -> Vec<usize> {
+    let mut levels = vec![];
+    let mut prev: Option<(usize, usize)> = None;
+    grammar
+        .items
+        .iter()
+        .map(|item| {
+            let span = item.span();
+            let depth = match prev {
+                Some((end, depth)) if !src[end..span.start].contains('\n') => depth,
+                _ => level(&mut levels, column(src, span.start)),
+            };
+            prev = Some((span.end, depth));
+            depth
+        })
+        .collect()
+}
+
+/// Уровень вложенности строки с отступом `column`; `levels` — отступы открытых уровней.
+fn level(levels: &mut Vec<usize>, column: usize) -> usize {
+    while levels.last().is_some_and(|&v| column < v) {
+        levels.pop();
+    }
+    if levels.last().is_none_or(|&v| v < column) {
+        levels.push(column);
+    }
+    levels.len() - 1
+}
+
+/// Отступ строки до `pos`; табуляция дополняет до кратного [`TAB`].
+fn column(src: &str, pos: usize) -> usize {
+    let line = src[..pos].rfind('\n').map_or(0, |v| v + 1);
+    src[line..pos].chars().fold(0, |column, c| match c {
+        '\t' => (column / TAB + 1) * TAB,
+        _ => column + 1,
+    })
+}
+
+/// Между элементами есть строка из одних пробелов.
+fn blank_line(between: &str) -> bool {
+    let lines = between.split('\n').collect::<Vec<_>>();
+    lines.len() > 2
+        && lines[1..lines.len() - 1]
+            .iter()
+            .any(|v| v.trim().is_empty())
+}
+
+#[inline]
+fn width(v: &str) -> usize {
+    v.chars().count()
+}
+
+fn rule(out: &mut String, src: &str, def: &RuleDef, pad: &str) {
+    let head = def.head();
+    let members = match &def.body {
+        Body::Expr(Expr::Choice(v)) => {
+            let line = format!("{head} = {}", Expr::Choice(v.clone()));
+            if width(pad) + width(&line) <= WIDTH {
+                writeln!(out, "{pad}{line}").unwrap();
+                return;
+            }
+            // продолжения с отступом: `/` в начале строки сразу видно
+            write!(out, "{pad}{head} = {}", v[0].alternative()).unwrap();
+            for v in &v[1..] {
+                write!(out, "\n{pad}{INDENT}/ {}", v.alternative()).unwrap();
+            }
+            out.push('\n');
+            return;
+        }
+        Body::Expr(..) | Body::Token { .. } => {
+            writeln!(out, "{pad}{def}").unwrap();
+            return;
+        }
+        Body::Enum(v) => v
+            .iter()
+            .map(|v| Member {
+                comments: &v.comments,
+                trailing: None,
+                name: None,
+                text: v.to_string(),
+                span: extent(&v.expr),
+            })
+            .collect::<Vec<_>>(),
+        Body::Struct(v) => v
+            .iter()
+            .map(|v| Member {
+                comments: &v.comments,
+                trailing: None,
+                name: v.name.as_deref(),
+                text: v.expr.to_string(),
+                span: extent(&v.expr),
+            })
+            .collect(),
+        Body::Tuple(v) => v
+            .iter()
+            .map(|v| Member {
+                comments: &[],
+                trailing: None,
+                name: None,
+                text: v.to_string(),
+                span: extent(&v.expr),
+            })
+            .collect(),
+    };
+    let members = trailing(src, members);
+    let (open, close, sep) = match def.body {
+        Body::Struct(..) => ("{", "}", ","),
+        Body::Tuple(..) => ("(", ")", ""),
+        _ => ("{", "}", ""),
+    };
+
+    // блок, записанный в одну строку, в ней и остаётся, если помещается;
+    // кортеж в одну строку пишется без отступов, как тип в выражении
+    let items = members
+        .iter()
+        .map(Member::inline)
+        .collect::<Vec<_>>()
+        .join(if sep.is_empty() { " " } else { ", " });
+    let inline = match def.body {
+        Body::Tuple(..) => format!("{head}{open}{items}{close}"),
+        _ => format!("{head} {open} {items} {close}"),
+    };
+    if !src[def.span.clone()].contains('\n')
+        && members
+            .iter()
+            .all(|v| v.comments.is_empty() && v.trailing.is_none())
+        && width(pad) + width(&inline) <= WIDTH
+    {
+        writeln!(out, "{pad}{inline}").unwrap();
+        return;
+    }
+
+    writeln!(out, "{pad}{head} {open}").unwrap();
+    let groups = groups(src, &members);
+    for (i, group) in groups.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        // имена соседних полей выравниваются по самому длинному
+        let align = group
+            .iter()
+            .filter_map(|v| v.name)
+            .map(width)
+            .max()
+            .unwrap_or(0);
+        for v in *group {
+            for comment in v.comments {
+                writeln!(out, "{pad}{INDENT}//{}", comment.trim_end()).unwrap();
+            }
+            match v.name {
+                Some(name) => write!(
+                    out,
+                    "{pad}{INDENT}{name}: {:fill$}{}{sep}",
+                    "",
+                    v.text,
+                    fill = align - width(name)
+                ),
+                None => write!(out, "{pad}{INDENT}{}{sep}", v.text),
+            }
+            .unwrap();
+            match v.trailing {
+                Some(comment) => writeln!(out, " //{}", comment.trim_end()).unwrap(),
+                None => out.push('\n'),
+            }
+        }
+    }
+    writeln!(out, "{pad}{close}").unwrap();
+}
+
+/// Элемент блока: вариант, поле или элемент кортежа.
+struct Member<'a> {
+    comments: &'a [String],
+    /// Комментарий в строке элемента; разбор относит его к следующему элементу.
+    trailing: Option<&'a str>,
+    /// Имя поля структуры, выравнивается отдельно от выражения.
+    name: Option<&'a str>,
+    text: String,
+    span: Option<Range<usize>>,
+}
+
+impl Member<'_> {
+    fn inline(&self) -> String {
+        match self.name {
+            Some(name) => format!("{name}: {}", self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+/// Возвращает элементам комментарии из их строк: разбор отдаёт такой комментарий следующему
+/// элементу первым.
+fn trailing<'a>(src: &str, mut members: Vec<Member<'a>>) -> Vec<Member<'a>> {
+    for i in 1..members.len() {
+        let Some(end) = members[i - 1].span.as_ref().map(|v| v.end) else {
+            continue;
+        };
+        let line = src[end..].split('\n').next().unwrap_or_default();
+        if let [first, rest @ ..] = members[i].comments {
+            if line.contains("//") {
+                members[i - 1].trailing = Some(first);
+                members[i].comments = rest;
+            }
+        }
+    }
+    members
+}
+
+/// Элементы, разделённые в исходнике пустыми строками.
+fn groups<'m, 'a>(src: &str, members: &'m [Member<'a>]) -> Vec<&'m [Member<'a>]> {
+    let mut groups = vec![];
+    let mut start = 0;
+    for (i, pair) in members.windows(2).enumerate() {
+        if let (Some(a), Some(b)) = (&pair[0].span, &pair[1].span) {
+            if a.end <= b.start && blank_line(&src[a.end..b.start]) {
+                groups.push(&members[start..=i]);
+                start = i + 1;
+            }
+        }
+    }
+    groups.push(&members[start..]);
+    groups
+}
+
+/// Диапазон исходника от первого до последнего литерала или ссылки выражения.
+fn extent(expr: &Expr) -> Option<Range<usize>> {
+    let mut out: Option<Range<usize>> = None;
+    expr.walk(&mut |v| {
+        let span = match v {
+            Expr::Ref(v) => &v.span,
+            Expr::Literal(v) => &v.span,
+            _ => return,
+        };
+        out = Some(match out.take() {
+            Some(v) => v.start.min(span.start)..v.end.max(span.end),
+            None => span.clone(),
+        });
+    });
+    out
+}
@@ -0,0 +1,354 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Случайные предложения рантайм-грамматики: корпуса для бенчмарков и фаззинга.
+//! Обходит правило, выбирая варианты, число повторов и текст токенов по зерну;
+//! за пределом глубины идёт кратчайшими ветками.
+
+use crate::{
+    rules::{parses_as, DynRule, Marker, RuleSlot, Token},
+    CompileError, RuntimeGrammar,
+};
+use parser::property;
+use parsers::chars::rules::{reg_expr_pick, Pick};
+use std::{collections::HashMap, fmt, rc::Rc};
+
+/// Высота ветки, из которой нет конечного вывода.
+const INFINITE: usize = usize::MAX;
+
+/// Символы для порчи токенов.
+const NOISE: &[u8] = b"a0 _\n\t.,;:()[]{}<>\"'\\/|^~!?#$%&*+-=@";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerateError {
+    Compile(CompileError),
+    /// Правило не выводит ни одной конечной строки (`A = "x" A`).
+    Infinite(String),
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compile(v) => write!(f, "{v}"),
+            Self::Infinite(v) => write!(f, "rule `{v}` derives no finite sentence"),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+impl From<CompileError> for GenerateError {
+    #[inline]
+    fn from(v: CompileError) -> Self {
+        Self::Compile(v)
+    }
+}
+
+/// Генератор предложений; одно зерно и одни настройки дают одну и ту же последовательность.
+pub struct Generator<'g> {
+    grammar: &'g RuntimeGrammar,
+    rng: Rng,
+    max_depth: usize,
+    attempts: usize,
+    /// Наименьшая глубина вывода каждого слота; пересчитывается, когда слотов становится больше.
+    heights: HashMap<Rc<str>, usize>,
+}
+
+impl RuntimeGrammar {
+    /// Генератор предложений с зерном `seed`, см. [`Generator`].
+    #[inline]
+    pub fn generator(&self, seed: u64) -> Generator<'_> {
+        Generator {
+            grammar: self,
+            rng: Rng {
+                rng: property::Rng::new(seed),
+                max_repeat: 3,
+            },
+            max_depth: 16,
+            attempts: 100,
+            heights: HashMap::new(),
+        }
+    }
+}
+
+impl Generator<'_> {
+    /// Вложенность правил, после которой выбираются кратчайшие варианты и минимум повторов.
+    #[inline]
+    pub fn max_depth(mut self, v: usize) -> Self {
+        self.max_depth = v;
+        self
+    }
+
+    /// Сколько повторов сверх минимума допускают `*`, `+`, `**` и квантификаторы регулярок.
+    #[inline]
+    pub fn max_repeat(mut self, v: usize) -> Self {
+        self.rng.max_repeat = v;
+        self
+    }
+
+    /// Число попыток у [`Self::valid`] и [`Self::near_miss`].
+    #[inline]
+    pub fn attempts(mut self, v: usize) -> Self {
+        self.attempts = v;
+        self
+    }
+
+    /// Предложение без проверки: `!Expr`, предикаты регулярок и жадность токенов могут
+    /// сделать его неразбираемым.
+    pub fn sentence(&mut self, rule: &str) -> Result<String, GenerateError> {
+        Ok(self.pieces(rule)?.concat())
+    }
+
+    /// Предложение, которое правило разбирает целиком; `None`, если попытки кончились.
+    pub fn valid(&mut self, rule: &str) -> Result<Option<String>, GenerateError> {
+        let compiled = self.grammar.rule(rule)?;
+        for _ in 0..self.attempts {
+            let v = self.sentence(rule)?;
+            if compiled.parse(&v).is_ok() {
+                return Ok(Some(v));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Почти верное предложение: в сгенерированном удалён, повторён, переставлен или испорчен
+    /// один токен, и правило его уже не разбирает.
+    pub fn near_miss(&mut self, rule: &str) -> Result<Option<String>, GenerateError> {
+        let compiled = self.grammar.rule(rule)?;
+        for _ in 0..self.attempts {
+            let mut pieces = self.pieces(rule)?;
+            self.mutate(&mut pieces);
+            let v = pieces.concat();
+            if compiled.parse(&v).is_err() {
+                return Ok(Some(v));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Текст токенов предложения по порядку.
+    fn pieces(&mut self, rule: &str) -> Result<Vec<String>, GenerateError> {
+        let slot = self.grammar.rule(rule)?.slot;
+        self.update_heights();
+        if self.height(&slot) == INFINITE {
+            return Err(GenerateError::Infinite(rule.to_string()));
+        }
+        let mut out = vec![];
+        self.slot(&slot, 0, &mut out);
+        Ok(out)
+    }
+
+    fn mutate(&mut self, pieces: &mut Vec<String>) {
+        let filled = (0..pieces.len())
+            .filter(|&i| !pieces[i].is_empty())
+            .collect::<Vec<_>>();
+        let noise = char::from(NOISE[self.rng.below(NOISE.len())]).to_string();
+        if filled.is_empty() {
+            pieces.push(noise);
+            return;
+        }
+        let i = filled[self.rng.below(filled.len())];
+        match self.rng.below(5) {
+            0 => {
+                pieces.remove(i);
+            }
+            1 => pieces.insert(i, pieces[i].clone()),
+            2 if filled.len() > 1 => {
+                let j = filled[self.rng.below(filled.len())];
+                pieces.swap(i, j)
+            }
+            3 => pieces.insert(i + self.rng.below(2), noise),
+            _ => {
+                let piece = &mut pieces[i];
+                let (at, c) = piece
+                    .char_indices()
+                    .nth(self.rng.below(piece.chars().count()))
+                    .expect("кусок не пуст");
+                piece.replace_range(at..at + c.len_utf8(), &noise);
+            }
+        }
+    }
+
+    fn slot(&mut self, slot: &RuleSlot, depth: usize, out: &mut Vec<String>) {
+        let rule = slot.rule.get().expect("слот заполняется при компиляции");
+        self.walk(rule, depth + 1, out)
+    }
+
+    fn walk(&mut self, rule: &DynRule, depth: usize, out: &mut Vec<String>) {
+        let deep = depth >= self.max_depth;
+        match rule {
+            DynRule::Ref(slot) => self.slot(&upgrade(slot), depth, out),
+            DynRule::Seq(v) => v.0.iter().for_each(|v| self.walk(v, depth, out)),
+            DynRule::Choice(v) => {
+                let heights = v.0.iter().map(|v| self.rule_height(v)).collect::<Vec<_>>();
+                // глубже предела — только кратчайшие варианты, иначе любые конечные
+                let limit = if deep {
+                    heights.iter().copied().min().unwrap_or(INFINITE)
+                } else {
+                    INFINITE - 1
+                };
+                let variants = (0..v.0.len())
+                    .filter(|&i| heights[i] <= limit)
+                    .collect::<Vec<_>>();
+                if !variants.is_empty() {
+                    let i = variants[self.rng.below(variants.len())];
+                    self.walk(&v.0[i], depth, out)
+                }
+            }
+            DynRule::Repeat(rule, marker) => {
+                let (min, max) = bounds(marker);
+                let count = self.count(rule, min, max, deep);
+                (0..count).for_each(|_| self.walk(rule, depth, out))
+            }
+            DynRule::Optional(rule) => {
+                if self.count(rule, 0, Some(1), deep) == 1 {
+                    self.walk(rule, depth, out)
+                }
+            }
+            DynRule::Not(..) | DynRule::And(..) => {}
+            DynRule::Join { rule, join, min } => {
+                for i in 0..self.count(rule, *min, None, deep) {
+                    if i > 0 {
+                        self.walk(join, depth, out)
+                    }
+                    self.walk(rule, depth, out)
+                }
+            }
+            DynRule::Token { token, type_, .. } => out.push(match token {
+                Token::SubStr(v) => v.0.clone(),
+                Token::Regex(v) => {
+                    let reg_expr = v.reg_expr();
+                    let mut text = reg_expr_pick(reg_expr, &mut self.rng);
+                    // `Name: Type` — пробуем ещё, пока текст не разберётся в тип
+                    for _ in 0..self.attempts {
+                        if type_.as_ref().is_none_or(|v| parses_as(v, &text)) {
+                            break;
+                        }
+                        text = reg_expr_pick(reg_expr, &mut self.rng);
+                    }
+                    text
+                }
+            }),
+            DynRule::Named(_, rule) | DynRule::Ignored(rule) => self.walk(rule, depth, out),
+        }
+    }
+
+    /// Число повторов: за пределом глубины и у бесконечной ветки — минимум.
+    fn count(&mut self, rule: &DynRule, min: usize, max: Option<usize>, deep: bool) -> usize {
+        if deep || self.rule_height(rule) == INFINITE {
+            min
+        } else {
+            self.rng.repeat(min, max)
+        }
+    }
+
+    #[inline]
+    fn height(&self, slot: &RuleSlot) -> usize {
+        self.heights.get(&slot.name).copied().unwrap_or(INFINITE)
+    }
+
+    /// Наименьшая вложенность слотов, за которую ветка выводит конечную строку.
+    fn rule_height(&self, rule: &DynRule) -> usize {
+        match rule {
+            DynRule::Ref(slot) => self.height(&upgrade(slot)).saturating_add(1),
+            DynRule::Seq(v) => v.0.iter().map(|v| self.rule_height(v)).max().unwrap_or(0),
+            DynRule::Choice(v) => v
+                .0
+                .iter()
+                .map(|v| self.rule_height(v))
+                .min()
+                .unwrap_or(INFINITE),
+            DynRule::Repeat(rule, marker) => match bounds(marker).0 {
+                0 => 0,
+                _ => self.rule_height(rule),
+            },
+            DynRule::Optional(..) | DynRule::Not(..) | DynRule::And(..) | DynRule::Token { .. } => {
+                0
+            }
+            DynRule::Join { rule, join, min } => match min {
+                0 => 0,
+                1 => self.rule_height(rule),
+                _ => self.rule_height(rule).max(self.rule_height(join)),
+            },
+            DynRule::Named(_, rule) | DynRule::Ignored(rule) => self.rule_height(rule),
+        }
+    }
+
+    /// Высоты слотов неподвижной точкой: рекурсивные правила сходятся снизу.
+    fn update_heights(&mut self) {
+        let grammar = self.grammar;
+        let slots = grammar.slots.borrow();
+        if self.heights.len() == slots.len() {
+            return;
+        }
+        self.heights = slots
+            .values()
+            .map(|v| (v.name.clone(), INFINITE))
+            .collect();
+        loop {
+            let mut changed = false;
+            for slot in slots.values() {
+                let height = self.rule_height(slot.rule.get().expect("слот заполнен"));
+                if height < self.height(slot) {
+                    self.heights.insert(slot.name.clone(), height);
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
+/// Границы повторов маркера, `None` — без верхней.
+fn bounds(marker: &Marker) -> (usize, Option<usize>) {
+    match marker {
+        Marker::Repeat(..) => (0, None),
+        Marker::Min(v) => (v.min, None),
+        Marker::Max(v) => (0, Some(v.max)),
+        Marker::MinMax(v) => (*v.range.start(), Some(*v.range.end())),
+        Marker::Count(v) => (v.count, Some(v.count)),
+        Marker::Empty => (0, Some(0)),
+    }
+}
+
+#[inline]
+fn upgrade(slot: &std::rc::Weak<RuleSlot>) -> Rc<RuleSlot> {
+    slot.upgrade().expect("генератор не переживает грамматику")
+}
+
+/// [`property::Rng`] с настраиваемым числом повторов.
+#[derive(Debug, Clone)]
+struct Rng {
+    rng: property::Rng,
+    max_repeat: usize,
+}
+
+impl Rng {
+    #[inline]
+    fn below(&mut self, n: usize) -> usize {
+        self.rng.below(n)
+    }
+}
+
+impl Pick for Rng {
+    #[inline]
+    fn pick(&mut self, n: usize) -> usize {
+        self.below(n)
+    }
+
+    #[inline]
+    fn repeat(&mut self, min: usize, max: Option<usize>) -> usize {
+        self.rng.repeat_up_to(min, max, self.max_repeat)
+    }
+}
@@ -0,0 +1,598 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Импорт грамматик ABNF (RFC 5234, строки `%s`/`%i` из RFC 7405) в `.abs`.
+//!
+//! Конкатенация становится последовательностью, `/` — выбором, `n*m` — повтором, `[..]` —
+//! `?`. Строка `"..."` в ABNF не различает регистр и переводится в регулярку с `(?i)`, `%s"..."`
+//! — в подстроку; числа `%x41`, `%x0D.0A` — в строки, диапазоны `%x41-5A` — в классы. `=/`
+//! дописывает альтернативы к правилу, определённому раньше. Имена не различают регистр и
+//! переводятся в `UpperCamelCase`; базовые правила приложения B.1 (`ALPHA`, `DIGIT`, `CRLF`,
+//! ...), на которые есть ссылки и которые грамматика не определяет сама, дописываются в конец.
+//!
+//! ABNF описывает контекстно-свободный язык, а `.abs` — PEG: выбор берёт первую подошедшую
+//! альтернативу, повтор жаден и не отдаёт совпавшее. Альтернативы из одних строк, где одна —
+//! начало следующей (`"a" / "ab"`), переставляются от длинной к короткой; в остальном
+//! грамматику, рассчитанную на перебор (`*DIGIT DIGIT`), придётся переписать.
+//!
+//! Значения `%x` — коды символов: рантайм разбирает `&str`, и для байтовых протоколов вход
+//! переводится в символы U+0000–U+00FF функцией [`latin1`]. Прозы `<...>` в `.abs` нет, на неё
+//! импорт возвращает ошибку.
+
+use super::{body, camel_case, comment, def, regex, repeat, string, Builder, ImportError, Place};
+use crate::ast::*;
+use std::collections::HashMap;
+
+/// Разбирает ABNF и переводит её в `.abs`: правила и комментарии в исходном порядке, затем
+/// базовые правила RFC 5234, на которые есть ссылки.
+pub fn import(src: &str) -> Result<Grammar, ImportError> {
+    let (entries, rules) = Parser::new(src).rulelist()?;
+    let mut builder = Builder::default();
+    let names = rules
+        .iter()
+        .map(|v| (v.key.clone(), builder.unique(&camel_case(&v.name))))
+        .collect::<HashMap<_, _>>();
+    let mut importer = Importer {
+        names,
+        core: vec![],
+        builder,
+    };
+
+    let mut items = vec![];
+    for v in &entries {
+        match v {
+            Entry::Comment(v) => items.push(comment(v.as_str())),
+            Entry::Rule(i) => {
+                let rule = &rules[*i];
+                items.extend(rule.comments.iter().map(|v| comment(v.as_str())));
+                let name = importer.names[&rule.key].clone();
+                importer.builder.start(&name);
+                let term = match rule.alternatives.as_slice() {
+                    [v] => v.clone(),
+                    v => Term::Choice(v.to_vec()),
+                };
+                let expr = importer.term(&term)?;
+                let expr = importer.builder.fit(expr, Place::Body);
+                importer.builder.push(def(name, body(expr)), &mut items);
+            }
+        }
+    }
+    if !importer.core.is_empty() {
+        items.push(comment(" core rules of RFC 5234, appendix B.1"));
+        importer
+            .core
+            .sort_by_key(|v| CORE.iter().position(|(name, _)| name == v));
+        for v in importer.core {
+            let (_, literal) = CORE.iter().find(|(name, _)| *name == v).unwrap();
+            let name = importer.names[v].clone();
+            items.push(Item::Rule(def(name, body(regex(literal.to_string())))));
+        }
+    }
+    Ok(Grammar { items })
+}
+
+/// Байты как символы U+0000–U+00FF, чтобы `%x80-FF` из грамматики совпадали с байтами входа.
+/// Позиции узлов дерева — байтовые смещения в полученной строке, а не во входе.
+pub fn latin1(input: &[u8]) -> String {
+    input.iter().map(|&v| char::from(v)).collect()
+}
+
+/// Базовые правила RFC 5234 (приложение B.1) и их регулярки.
+const CORE: &[(&str, &str)] = &[
+    ("alpha", "[A-Za-z]"),
+    ("bit", "[01]"),
+    ("char", r"[\x01-\x7F]"),
+    ("cr", r"\r"),
+    ("crlf", r"\r\n"),
+    ("ctl", r"[\x00-\x1F\x7F]"),
+    ("digit", "[0-9]"),
+    ("dquote", r"\x22"),
+    ("hexdig", "[0-9A-Fa-f]"),
+    ("htab", r"\t"),
+    ("lf", r"\n"),
+    ("lwsp", r"(?:[ \t]|\r\n[ \t])*"),
+    ("octet", r"[\x00-\xFF]"),
+    ("sp", " "),
+    ("vchar", r"[\x21-\x7E]"),
+    ("wsp", r"[ \t]"),
+];
+
+#[derive(Debug)]
+struct AbnfRule {
+    /// Имя из первого определения.
+    name: String,
+    /// Имя в нижнем регистре: имена ABNF не различают регистр.
+    key: String,
+    /// Альтернативы `=` и всех `=/`.
+    alternatives: Vec<Term>,
+    /// Комментарии внутри определений, переносятся над правилом.
+    comments: Vec<String>,
+}
+
+#[derive(Debug)]
+enum Entry {
+    /// Текст после `;`.
+    Comment(String),
+    /// Индекс правила; `=/` новых элементов не добавляет.
+    Rule(usize),
+}
+
+/// Выражение ABNF.
+#[derive(Debug, Clone)]
+enum Term {
+    /// `"..."`, `%s"..."`, `%i"..."`, `%x0D.0A`.
+    Str {
+        value: String,
+        sensitive: bool,
+    },
+    /// `%x41-5A`
+    Range(char, char),
+    Ref {
+        key: String,
+        pos: usize,
+    },
+    Seq(Vec<Term>),
+    Choice(Vec<Term>),
+    Optional(Box<Term>),
+    Repeat(Box<Term>, usize, Option<usize>),
+}
+
+struct Parser<'src> {
+    src: &'src str,
+    pos: usize,
+    /// Отступ строк, с которых начинаются правила; строка с большим отступом продолжает
+    /// предыдущее. Грамматику, скопированную из RFC с отступом, можно не выравнивать.
+    indent: usize,
+    /// Комментарии текущего правила.
+    comments: Vec<String>,
+    /// Конец последнего записанного комментария: после отката `gap` проходит его снова.
+    noted: usize,
+}
+
+impl<'src> Parser<'src> {
+    fn new(src: &'src str) -> Self {
+        let indent = src
+            .lines()
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| v.len() - v.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        Self {
+            src,
+            pos: 0,
+            indent,
+            comments: vec![],
+            noted: 0,
+        }
+    }
+
+    #[inline]
+    fn error(&self, message: impl Into<String>) -> ImportError {
+        ImportError {
+            pos: self.pos,
+            message: message.into(),
+        }
+    }
+
+    #[inline]
+    fn rest(&self) -> &'src str {
+        &self.src[self.pos..]
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Комментарий от `;` до конца строки, без перевода строки.
+    fn comment(&mut self) -> String {
+        let line = self.rest()[1..].split('\n').next().unwrap_or_default();
+        self.pos += 1 + line.len();
+        line.trim_end_matches('\r').trim_end().to_string()
+    }
+
+    /// Позиция следующей непустой строки, если она продолжает правило.
+    fn continuation(&self) -> Option<usize> {
+        let mut pos = self.pos;
+        loop {
+            let line = self.src[pos..].split('\n').next()?;
+            let text = line.trim_start();
+            if !text.is_empty() {
+                return (line.len() - text.len() > self.indent).then_some(pos);
+            }
+            pos += line.len() + 1;
+            if pos > self.src.len() {
+                return None;
+            }
+        }
+    }
+
+    /// Пробелы, комментарии и переносы внутри правила; `true`, если что-то пропущено. На конце
+    /// правила останавливается перед переводом строки.
+    fn gap(&mut self) -> bool {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\r') => {
+                    self.next();
+                }
+                Some(';') => {
+                    let start = self.pos;
+                    let v = self.comment();
+                    if start >= self.noted {
+                        self.comments.push(v);
+                        self.noted = self.pos;
+                    }
+                }
+                Some('\n') => {
+                    let pos = self.pos;
+                    self.pos += 1;
+                    match self.continuation() {
+                        Some(v) => self.pos = v,
+                        None => {
+                            self.pos = pos;
+                            break;
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+        self.pos != start
+    }
+
+    fn rulelist(mut self) -> Result<(Vec<Entry>, Vec<AbnfRule>), ImportError> {
+        let (mut entries, mut rules) = (vec![], Vec::<AbnfRule>::new());
+        loop {
+            let rest = self.rest();
+            self.pos += rest.len() - rest.trim_start().len();
+            match self.peek() {
+                None => return Ok((entries, rules)),
+                Some(';') => {
+                    let v = self.comment();
+                    entries.push(Entry::Comment(v));
+                }
+                Some(..) => {
+                    let pos = self.pos;
+                    let (name, incremental, alternative) = self.rule()?;
+                    let key = name.to_lowercase();
+                    let comments = std::mem::take(&mut self.comments);
+                    match rules.iter().position(|v| v.key == key) {
+                        Some(i) if incremental => {
+                            // `=/` к выбору добавляет альтернативы, а не вложенный выбор
+                            match alternative {
+                                Term::Choice(v) => rules[i].alternatives.extend(v),
+                                v => rules[i].alternatives.push(v),
+                            }
+                            rules[i].comments.extend(comments);
+                        }
+                        Some(..) => {
+                            return Err(ImportError {
+                                pos,
+                                message: format!(
+                                    "rule `{name}` is defined twice, use `=/` to add alternatives"
+                                ),
+                            })
+                        }
+                        None if incremental => {
+                            return Err(ImportError {
+                                pos,
+                                message: format!("`=/` adds to undefined rule `{name}`"),
+                            })
+                        }
+                        None => {
+                            entries.push(Entry::Rule(rules.len()));
+                            rules.push(AbnfRule {
+                                name,
+                                key,
+                                alternatives: match alternative {
+                                    Term::Choice(v) => v,
+                                    v => vec![v],
+                                },
+                                comments,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn rulename(&mut self) -> Option<String> {
+        let rest = self.rest();
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(rest[..len].to_string())
+    }
+
+    /// `name = elements` или `name =/ elements`.
+    fn rule(&mut self) -> Result<(String, bool, Term), ImportError> {
+        let name = self
+            .rulename()
+            .ok_or_else(|| self.error("expected rule name"))?;
+        self.gap();
+        if self.next() != Some('=') {
+            self.pos -= 1;
+            return Err(self.error("expected `=` or `=/`"));
+        }
+        let incremental = self.rest().starts_with('/');
+        if incremental {
+            self.next();
+        }
+        self.gap();
+        let term = self.alternation()?;
+        self.gap();
+        match self.peek() {
+            None | Some('\n') => Ok((name, incremental, term)),
+            Some(c) => Err(self.error(format!("unexpected `{c}`"))),
+        }
+    }
+
+    fn alternation(&mut self) -> Result<Term, ImportError> {
+        let mut v = vec![self.concatenation()?];
+        loop {
+            let pos = self.pos;
+            self.gap();
+            if self.peek() != Some('/') {
+                self.pos = pos;
+                break;
+            }
+            self.next();
+            self.gap();
+            v.push(self.concatenation()?);
+        }
+        Ok(match v.len() {
+            1 => v.pop().unwrap(),
+            _ => Term::Choice(v),
+        })
+    }
+
+    fn concatenation(&mut self) -> Result<Term, ImportError> {
+        let mut v = vec![self.repetition()?];
+        loop {
+            let pos = self.pos;
+            let separated = self.gap();
+            let element = self
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphanumeric() || "*([\"%<".contains(c));
+            if !separated || !element {
+                self.pos = pos;
+                break;
+            }
+            v.push(self.repetition()?);
+        }
+        Ok(match v.len() {
+            1 => v.pop().unwrap(),
+            _ => Term::Seq(v),
+        })
+    }
+
+    fn digits(&mut self) -> Result<Option<usize>, ImportError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Ok(None);
+        }
+        let value = rest[..len]
+            .parse()
+            .map_err(|_| self.error("repetition bound is too large"))?;
+        self.pos += len;
+        Ok(Some(value))
+    }
+
+    /// `element`, `n element`, `n*m element`.
+    fn repetition(&mut self) -> Result<Term, ImportError> {
+        let min = self.digits()?;
+        let (min, max) = if self.rest().starts_with('*') {
+            self.next();
+            (min.unwrap_or(0), self.digits()?)
+        } else if let Some(v) = min {
+            (v, Some(v))
+        } else {
+            return self.element();
+        };
+        Ok(Term::Repeat(Box::new(self.element()?), min, max))
+    }
+
+    fn element(&mut self) -> Result<Term, ImportError> {
+        let pos = self.pos;
+        match self.peek() {
+            Some(c @ ('(' | '[')) => {
+                self.next();
+                self.gap();
+                let v = self.alternation()?;
+                self.gap();
+                let close = if c == '(' { ')' } else { ']' };
+                if self.next() != Some(close) {
+                    self.pos -= usize::from(self.pos > pos + 1);
+                    return Err(self.error(format!("expected `{close}`")));
+                }
+                Ok(if c == '(' {
+                    v
+                } else {
+                    Term::Optional(Box::new(v))
+                })
+            }
+            Some('"') => self.char_val(false),
+            Some('%') => {
+                self.next();
+                match self.next().map(|v| v.to_ascii_lowercase()) {
+                    Some('s') if self.peek() == Some('"') => self.char_val(true),
+                    Some('i') if self.peek() == Some('"') => self.char_val(false),
+                    Some('x') => self.num_val(16),
+                    Some('d') => self.num_val(10),
+                    Some('b') => self.num_val(2),
+                    _ => {
+                        self.pos = pos;
+                        Err(self.error("expected `%x`, `%d`, `%b`, `%s\"` or `%i\"`"))
+                    }
+                }
+            }
+            Some('<') => {
+                let len = self.rest().find('>').map_or(self.rest().len(), |v| v + 1);
+                Err(self.error(format!(
+                    "prose value `{}` has no equivalent in .abs",
+                    &self.rest()[..len]
+                )))
+            }
+            _ => match self.rulename() {
+                Some(name) => Ok(Term::Ref {
+                    key: name.to_lowercase(),
+                    pos,
+                }),
+                None => Err(self.error("expected element")),
+            },
+        }
+    }
+
+    /// `"..."`: печатные ASCII без `"`, переносов и экранирования.
+    fn char_val(&mut self, sensitive: bool) -> Result<Term, ImportError> {
+        let start = self.pos;
+        self.next();
+        let len = self
+            .rest()
+            .find(['"', '\n'])
+            .filter(|v| self.rest()[*v..].starts_with('"'))
+            .ok_or_else(|| ImportError {
+                pos: start,
+                message: "unterminated string".into(),
+            })?;
+        let value = self.rest()[..len].to_string();
+        self.pos += len + 1;
+        Ok(Term::Str { value, sensitive })
+    }
+
+    /// Число после `%x`: `41`, `41-5A` или `0D.0A`.
+    fn num_val(&mut self, radix: u32) -> Result<Term, ImportError> {
+        let mut value = String::new();
+        value.push(self.number(radix)?);
+        if self.rest().starts_with('-') {
+            self.next();
+            let end = self.number(radix)?;
+            return Ok(Term::Range(value.pop().unwrap(), end));
+        }
+        while self.rest().starts_with('.') {
+            self.next();
+            value.push(self.number(radix)?);
+        }
+        Ok(Term::Str {
+            value,
+            sensitive: true,
+        })
+    }
+
+    fn number(&mut self, radix: u32) -> Result<char, ImportError> {
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("expected number"));
+        }
+        let c = u32::from_str_radix(&rest[..len], radix)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid character value"))?;
+        self.pos += len;
+        Ok(c)
+    }
+}
+
+struct Importer {
+    /// Имя правила в нижнем регистре → имя в `.abs`.
+    names: HashMap<String, String>,
+    /// Базовые правила, на которые есть ссылки.
+    core: Vec<&'static str>,
+    builder: Builder,
+}
+
+impl Importer {
+    fn term(&mut self, term: &Term) -> Result<Expr, ImportError> {
+        Ok(match term {
+            Term::Str { value, sensitive } => string(value, !sensitive),
+            Term::Range(start, end) if start == end => string(&start.to_string(), false),
+            Term::Range(start, end) => regex(format!("[{}-{}]", code(*start), code(*end))),
+            Term::Ref { key, pos } => {
+                if !self.names.contains_key(key) {
+                    let (name, _) =
+                        CORE.iter()
+                            .find(|(name, _)| name == key)
+                            .ok_or_else(|| ImportError {
+                                pos: *pos,
+                                message: format!("undefined rule `{key}`"),
+                            })?;
+                    let v = self.builder.unique(&camel_case(name));
+                    self.names.insert(key.clone(), v);
+                    self.core.push(name);
+                }
+                Expr::ident(self.names[key].as_str())
+            }
+            Term::Seq(v) => Expr::Seq(v.iter().map(|v| self.term(v)).collect::<Result<_, _>>()?),
+            Term::Choice(v) => Expr::Choice(
+                ordered(v)
+                    .iter()
+                    .map(|v| self.term(v))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Term::Optional(v) => Expr::Optional(Box::new(self.term(v)?)),
+            Term::Repeat(v, min, max) => repeat(self.term(v)?, *min, *max),
+        })
+    }
+}
+
+/// Граница диапазона `%x`: буквы и цифры как есть, остальное — кодом.
+fn code(c: char) -> String {
+    match c {
+        c if c.is_ascii_alphanumeric() => c.to_string(),
+        c if c.is_ascii() => format!(r"\x{:02X}", c as u32),
+        c => format!(r"\x{{{:X}}}", c as u32),
+    }
+}
+
+/// Альтернативы-строки, где одна — начало следующей, ставятся от длинной к короткой: выбор
+/// PEG иначе всегда брал бы короткую.
+fn ordered(v: &[Term]) -> Vec<Term> {
+    let strings = v
+        .iter()
+        .map(|v| match v {
+            Term::Str { value, .. } => Some(value.to_lowercase()),
+            Term::Range(start, end) if start == end => Some(start.to_lowercase().collect()),
+            _ => None,
+        })
+        .collect::<Option<Vec<String>>>();
+    let mut v = v.to_vec();
+    if let Some(strings) = strings {
+        let shadowed = strings
+            .iter()
+            .enumerate()
+            .any(|(i, a)| strings[i + 1..].iter().any(|b| b.starts_with(a.as_str())));
+        if shadowed {
+            let mut pairs = strings.into_iter().zip(v).collect::<Vec<_>>();
+            pairs.sort_by_key(|(v, _)| std::cmp::Reverse(v.chars().count()));
+            v = pairs.into_iter().map(|(_, v)| v).collect();
+        }
+    }
+    v
+}
@@ -0,0 +1,312 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Импорт грамматик других генераторов парсеров в `.abs`.
+//!
+//! Результат — [`Grammar`]: его `Display` даёт текст `.abs`, а [`grammar_macro`] оборачивает
+//! текст в вызов `grammar!`.
+
+pub mod abnf;
+pub mod pest;
+
+use crate::ast::*;
+use std::{collections::HashSet, fmt};
+
+/// Исходный синтаксис импорта.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Pest,
+    /// RFC 5234.
+    Abnf,
+}
+
+impl Source {
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Pest => "pest",
+            Self::Abnf => "ABNF",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportError {
+    /// Байтовая позиция в исходной грамматике.
+    pub pos: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.pos)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[inline]
+pub fn import(src: &str, source: Source) -> Result<Grammar, ImportError> {
+    match source {
+        Source::Pest => pest::import(src),
+        Source::Abnf => abnf::import(src),
+    }
+}
+
+/// Вызов `grammar!` с текстом `.abs` в сырой строке, у которой хватает `#`.
+pub fn grammar_macro(abs: &str) -> String {
+    let hashes = (0..)
+        .find(|n| !abs.contains(&format!("\"{}", "#".repeat(*n))))
+        .map_or(0, |n: usize| n.max(1));
+    let hashes = "#".repeat(hashes);
+    format!("grammar! {{r{hashes}\"\n{}\n\"{hashes}}}\n", abs.trim_end())
+}
+
+/// Место выражения в родителе: от него зависит, что `.abs` может записать без правила.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Place {
+    Body,
+    Item,
+    Alternative,
+    Operand,
+}
+
+/// Имена и вспомогательные правила, общие для импортёров.
+#[derive(Default)]
+struct Builder {
+    taken: HashSet<String>,
+    /// Правило, для которого сейчас заводятся вспомогательные.
+    current: String,
+    parts: usize,
+    helpers: Vec<RuleDef>,
+}
+
+impl Builder {
+    /// Занимает `name`, при совпадении — с числовым суффиксом.
+    fn unique(&mut self, name: &str) -> String {
+        let name = (1..)
+            .map(|n| match n {
+                1 => name.to_string(),
+                n => format!("{name}{n}"),
+            })
+            .find(|v| !self.taken.contains(v))
+            .unwrap();
+        self.taken.insert(name.clone());
+        name
+    }
+
+    /// Начинает правило `name`: вспомогательные нумеруются заново.
+    #[inline]
+    fn start(&mut self, name: &str) {
+        self.current = name.to_string();
+        self.parts = 0;
+    }
+
+    /// Дописывает правило и накопленные для него вспомогательные.
+    #[inline]
+    fn push(&mut self, def: RuleDef, items: &mut Vec<Item>) {
+        items.push(Item::Rule(def));
+        items.extend(self.helpers.drain(..).map(Item::Rule));
+    }
+
+    /// Выносит выражение во вспомогательное правило `RulePartN` и возвращает ссылку на него.
+    fn helper(&mut self, expr: Expr) -> Expr {
+        let expr = self.fit(expr, Place::Body);
+        self.parts += 1;
+        let name = self.unique(&format!("{}Part{}", self.current, self.parts));
+        self.helpers.push(def(name.clone(), body(expr)));
+        Expr::ident(name)
+    }
+
+    /// Приводит выражение к тому, что записывается в `.abs`: вложенные последовательности и
+    /// выборы сливаются, а квантификатор под квантификатором и `**` в альтернативе выносятся в
+    /// правила.
+    fn fit(&mut self, expr: Expr, place: Place) -> Expr {
+        let expr = match expr {
+            Expr::Seq(v) => {
+                let mut items = vec![];
+                for v in v {
+                    match self.fit(v, Place::Item) {
+                        Expr::Seq(v) => items.extend(v),
+                        v => items.push(v),
+                    }
+                }
+                match items.len() {
+                    0 if place == Place::Item => Expr::Seq(items),
+                    0 => Expr::Literal(Literal {
+                        value: String::new(),
+                        sub_str: true,
+                        span: 0..0,
+                    }),
+                    1 => items.pop().unwrap(),
+                    _ => Expr::Seq(items),
+                }
+            }
+            Expr::Choice(v) => {
+                let mut items = vec![];
+                for v in v {
+                    match self.fit(v, Place::Alternative) {
+                        Expr::Choice(v) => items.extend(v),
+                        v => items.push(v),
+                    }
+                }
+                match items.len() {
+                    1 => items.pop().unwrap(),
+                    _ => Expr::Choice(items),
+                }
+            }
+            Expr::Repeat(v, r) => Expr::Repeat(Box::new(self.fit(*v, Place::Operand)), r),
+            Expr::Optional(v) => Expr::Optional(Box::new(self.fit(*v, Place::Operand))),
+            Expr::NegativeLookahead(v) => {
+                Expr::NegativeLookahead(Box::new(self.fit(*v, Place::Operand)))
+            }
+            Expr::AndPredicate(v) => Expr::AndPredicate(Box::new(self.fit(*v, Place::Operand))),
+            Expr::Joinable {
+                expr,
+                join,
+                repetition,
+            } => Expr::Joinable {
+                expr: Box::new(self.fit(*expr, Place::Operand)),
+                join: Box::new(self.fit(*join, Place::Operand)),
+                repetition,
+            },
+            v => v,
+        };
+        let fits = match (place, &expr) {
+            (Place::Operand, Expr::Seq(v)) => !v.is_empty(),
+            (Place::Operand, v) => {
+                matches!(v, Expr::Ref(..) | Expr::Literal(..) | Expr::Choice(..))
+            }
+            (Place::Alternative, Expr::Joinable { .. }) => false,
+            _ => true,
+        };
+        if fits {
+            expr
+        } else {
+            self.helper(expr)
+        }
+    }
+}
+
+/// `snake_case`, `kebab-case` и `SCREAMING_CASE` → `UpperCamelCase`.
+fn camel_case(name: &str) -> String {
+    let name = name
+        .split(['_', '-'])
+        .filter(|v| !v.is_empty())
+        .map(|part| {
+            let part = if part.chars().all(|c| !c.is_ascii_lowercase()) {
+                part.to_ascii_lowercase()
+            } else {
+                part.to_string()
+            };
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect::<String>();
+    if name.is_empty() {
+        "Rule".into()
+    } else {
+        name
+    }
+}
+
+#[inline]
+fn comment(text: impl Into<String>) -> Item {
+    Item::Comment(Comment {
+        text: text.into(),
+        span: 0..0,
+    })
+}
+
+#[inline]
+fn def(name: String, body: Body) -> RuleDef {
+    RuleDef {
+        name,
+        generics: vec![],
+        generic_spans: vec![],
+        body,
+        span: 0..0,
+        name_span: 0..0,
+    }
+}
+
+/// Одиночный литерал записывается токеном, остальное — выражением.
+#[inline]
+fn body(expr: Expr) -> Body {
+    match expr {
+        Expr::Literal(literal) => Body::Token {
+            kind: TokenKind::Base,
+            literal,
+        },
+        expr => Body::Expr(expr),
+    }
+}
+
+#[inline]
+fn regex(value: String) -> Expr {
+    Expr::Literal(Literal {
+        value,
+        sub_str: false,
+        span: 0..0,
+    })
+}
+
+/// Строка без кавычек, `\` и управляющих символов — подстрока, иначе регулярка.
+fn string(value: &str, insensitive: bool) -> Expr {
+    let insensitive = insensitive && value.chars().any(|c| c.is_lowercase() || c.is_uppercase());
+    if !insensitive && !value.contains(|c: char| c == '"' || c == '\\' || c.is_control()) {
+        return Expr::Literal(Literal {
+            value: value.to_string(),
+            sub_str: true,
+            span: 0..0,
+        });
+    }
+    regex(format!(
+        "{}{}",
+        if insensitive { "(?i)" } else { "" },
+        value.chars().map(escape).collect::<String>()
+    ))
+}
+
+/// Символ внутри регулярки `.abs`: `"` закрыл бы литерал, поэтому пишется как `\x22`.
+fn escape(c: char) -> String {
+    match c {
+        '"' => r"\x22".into(),
+        '\n' => r"\n".into(),
+        '\r' => r"\r".into(),
+        '\t' => r"\t".into(),
+        c if c.is_ascii_control() => format!(r"\x{:02X}", c as u32),
+        c if c.is_control() => format!(r"\x{{{:X}}}", c as u32),
+        '\\' | '.' | '+' | '*' | '?' | '(' | ')' | '|' | '[' | ']' | '{' | '}' | '^' | '$'
+        | '#' | '&' | '-' | '~' => format!("\\{c}"),
+        c => c.to_string(),
+    }
+}
+
+/// Повтор без пропуска; `{1}` — сам элемент, `{0,1}` — `?`.
+fn repeat(expr: Expr, min: usize, max: Option<usize>) -> Expr {
+    let repetition = match (min, max) {
+        (_, Some(0)) => return Expr::Seq(vec![]),
+        (1, Some(1)) => return expr,
+        (0, Some(1)) => return Expr::Optional(Box::new(expr)),
+        (0, None) => Repetition::ZeroOrMore,
+        (1, None) => Repetition::OneOrMore,
+        (min, None) => Repetition::Min(min),
+        (0, Some(max)) => Repetition::Max(max),
+        (min, Some(max)) if min == max => Repetition::Count(min),
+        (min, Some(max)) => Repetition::MinMax(min, max),
+    };
+    Expr::Repeat(Box::new(expr), repetition)
+}
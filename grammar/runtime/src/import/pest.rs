@@ -0,0 +1,748 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Импорт грамматик [pest](https://pest.rs) в `.abs`.
+//!
+//! `~` становится последовательностью, `|` — выбором, `?`, `*`, `+`, `{n,m}`, `!` и `&` —
+//! одноимёнными квантификаторами. Строки без кавычек и `\` переносятся подстроками `"..."s`,
+//! остальное — регулярками: `^"..."` получает `(?i)`, `'a'..'z'` — класс `[a-z]`, встроенные
+//! `ANY`, `NEWLINE`, `ASCII_*` и Unicode-свойства — свои регулярки. Имена правил переводятся в
+//! `UpperCamelCase`.
+//!
+//! Неявный пропуск `WHITESPACE`/`COMMENT` становится явным правилом `Space`: в неатомарных
+//! правилах оно вставляется между элементами последовательности и между повторами (`a*` —
+//! `a ** Space`). Правило, вызванное из атомарного (`@`, `$`), в pest тоже атомарно, поэтому
+//! для таких вызовов заводится копия `NameAtomic` без `Space`. Все элементы `@`-правил на
+//! верхнем уровне последовательности становятся полями `#[ignore]`, так что в дереве, как и в
+//! pest, их нет. Тихое правило (`_`) в pest прячет только свою пару, а не детей, поэтому
+//! ссылки на него остаются обычными: в дереве у него, в отличие от pest, есть свой узел.
+//! Вложенный внутрь выражения `Space` в дереве тоже остаётся.
+//!
+//! Операций со стеком (`PUSH`, `POP`, `PEEK`, `DROP`) в `.abs` нет, на них импорт
+//! возвращает ошибку.
+
+use super::{
+    body, camel_case, comment, def, escape, regex, repeat, string, Builder, ImportError, Place,
+};
+use crate::ast::*;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Разбирает pest-грамматику и переводит её в `.abs`: правила и комментарии верхнего уровня в
+/// исходном порядке, затем `Space` и атомарные копии правил.
+pub fn import(src: &str) -> Result<Grammar, ImportError> {
+    let entries = Parser {
+        src,
+        pos: 0,
+        comments: vec![],
+    }
+    .grammar()?;
+    let rules = entries
+        .iter()
+        .filter_map(|v| match v {
+            Entry::Rule(v) => Some((v.name.as_str(), v)),
+            Entry::Comment(..) => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut importer = Importer {
+        rules,
+        names: HashMap::new(),
+        skip: None,
+        sensitive: HashSet::new(),
+        atomic: HashMap::new(),
+        queue: VecDeque::new(),
+        builder: Builder::default(),
+    };
+    for v in &entries {
+        if let Entry::Rule(v) = v {
+            if importer.names.contains_key(&v.name) {
+                return Err(ImportError {
+                    pos: v.pos,
+                    message: format!("rule `{}` is defined twice", v.name),
+                });
+            }
+            let name = importer.builder.unique(&camel_case(&v.name));
+            importer.names.insert(v.name.clone(), name);
+        }
+    }
+    let skip = ["WHITESPACE", "COMMENT"]
+        .iter()
+        .filter_map(|v| importer.names.get(*v))
+        .map(|v| Expr::ident(v.as_str()))
+        .collect::<Vec<_>>();
+    if !skip.is_empty() {
+        importer.skip = Some(importer.builder.unique("Space"));
+        importer.sensitive = importer.sensitive();
+    }
+
+    let mut items = vec![];
+    for v in &entries {
+        match v {
+            Entry::Comment(v) => items.push(comment(v)),
+            Entry::Rule(v) => {
+                let name = importer.names[&v.name].clone();
+                importer.rule(name, v, atomicity(v).unwrap_or(false), &mut items)?;
+            }
+        }
+    }
+    if let Some(name) = importer.skip.clone() {
+        items.push(comment(
+            " implicit WHITESPACE and COMMENT of pest between the elements of non-atomic rules",
+        ));
+        let skip = match skip.len() {
+            1 => skip.into_iter().next().unwrap(),
+            _ => Expr::Choice(skip),
+        };
+        items.push(Item::Rule(def(
+            name,
+            Body::Expr(Expr::Repeat(Box::new(skip), Repetition::ZeroOrMore)),
+        )));
+    }
+    if !importer.queue.is_empty() {
+        items.push(comment(
+            " rules called from atomic rules: pest skips no whitespace inside them",
+        ));
+    }
+    while let Some(v) = importer.queue.pop_front() {
+        let rule = importer.rules[v.as_str()];
+        let name = importer.atomic[&v].clone();
+        importer.rule(name, rule, true, &mut items)?;
+    }
+    Ok(Grammar { items })
+}
+
+/// Правило pest до перевода.
+#[derive(Debug)]
+struct PestRule {
+    name: String,
+    pos: usize,
+    /// `_`, `@`, `$` или `!` перед `{`.
+    modifier: Option<char>,
+    body: Term,
+}
+
+#[derive(Debug)]
+enum Entry {
+    /// Текст после `//`; строки блочного комментария идут по одной.
+    Comment(String),
+    Rule(PestRule),
+}
+
+/// Выражение pest.
+#[derive(Debug, Clone)]
+enum Term {
+    Str(String),
+    /// `^"..."`
+    Insensitive(String),
+    /// `'a'..'z'`
+    Range(char, char),
+    Ident {
+        name: String,
+        pos: usize,
+    },
+    Seq(Vec<Term>),
+    Choice(Vec<Term>),
+    Optional(Box<Term>),
+    /// `*`, `+`, `{n}`, `{n,}`, `{,m}`, `{n,m}`: нижняя и необязательная верхняя граница.
+    Repeat(Box<Term>, usize, Option<usize>),
+    Not(Box<Term>),
+    And(Box<Term>),
+}
+
+const STACK: &[&str] = &[
+    "PUSH",
+    "PUSH_LITERAL",
+    "POP",
+    "POP_ALL",
+    "PEEK",
+    "PEEK_ALL",
+    "DROP",
+];
+
+struct Parser<'src> {
+    src: &'src str,
+    pos: usize,
+    /// Комментарии верхнего уровня, ещё не перенесённые в грамматику.
+    comments: Vec<String>,
+}
+
+impl<'src> Parser<'src> {
+    #[inline]
+    fn error(&self, message: impl Into<String>) -> ImportError {
+        ImportError {
+            pos: self.pos,
+            message: message.into(),
+        }
+    }
+
+    #[inline]
+    fn rest(&self) -> &'src str {
+        &self.src[self.pos..]
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Пропускает пробелы и комментарии; с `keep` комментарии запоминаются для грамматики.
+    fn skip(&mut self, keep: bool) -> Result<(), ImportError> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+            if let Some(line) = trimmed.strip_prefix("//") {
+                let line = line.split('\n').next().unwrap_or_default();
+                if keep {
+                    self.comments.push(line.trim_end().to_string());
+                }
+                self.pos += "//".len() + line.len();
+            } else if trimmed.starts_with("/*") {
+                let start = self.pos;
+                let mut depth = 0;
+                loop {
+                    if self.rest().starts_with("/*") {
+                        depth += 1;
+                        self.pos += 2;
+                    } else if self.rest().starts_with("*/") {
+                        depth -= 1;
+                        self.pos += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else if self.next().is_none() {
+                        self.pos = start;
+                        return Err(self.error("unterminated block comment"));
+                    }
+                }
+                if keep {
+                    let text = &self.src[start + 2..self.pos - 2];
+                    self.comments.extend(
+                        text.lines()
+                            .map(|v| v.trim())
+                            .filter(|v| !v.is_empty())
+                            .map(|v| format!(" {v}")),
+                    );
+                }
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Съедает `token` после пробелов и комментариев.
+    fn eat(&mut self, token: &str) -> Result<bool, ImportError> {
+        self.skip(false)?;
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        Ok(found)
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ImportError> {
+        if self.eat(token)? {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{token}`")))
+        }
+    }
+
+    fn ident(&mut self) -> Option<String> {
+        let rest = self.rest();
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            return None;
+        }
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Some(rest[..len].to_string())
+    }
+
+    fn number(&mut self) -> Result<Option<usize>, ImportError> {
+        self.skip(false)?;
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Ok(None);
+        }
+        let value = rest[..len]
+            .parse()
+            .map_err(|_| self.error("repetition bound is too large"))?;
+        self.pos += len;
+        Ok(Some(value))
+    }
+
+    fn grammar(mut self) -> Result<Vec<Entry>, ImportError> {
+        let mut entries = vec![];
+        loop {
+            self.skip(true)?;
+            entries.extend(self.comments.drain(..).map(Entry::Comment));
+            if self.rest().is_empty() {
+                return Ok(entries);
+            }
+            entries.push(Entry::Rule(self.rule()?));
+        }
+    }
+
+    /// `name = modifier? { expression }`
+    fn rule(&mut self) -> Result<PestRule, ImportError> {
+        let pos = self.pos;
+        let name = self
+            .ident()
+            .ok_or_else(|| self.error("expected rule name"))?;
+        self.expect("=")?;
+        self.skip(false)?;
+        let modifier = self.peek().filter(|v| "_@$!".contains(*v));
+        if modifier.is_some() {
+            self.next();
+        }
+        self.expect("{")?;
+        let body = self.choice()?;
+        self.expect("}")?;
+        Ok(PestRule {
+            name,
+            pos,
+            modifier,
+            body,
+        })
+    }
+
+    /// `a | b`, допускается `|` перед первой альтернативой.
+    fn choice(&mut self) -> Result<Term, ImportError> {
+        self.eat("|")?;
+        let mut v = vec![self.seq()?];
+        while self.eat("|")? {
+            v.push(self.seq()?);
+        }
+        Ok(if v.len() == 1 {
+            v.pop().unwrap()
+        } else {
+            Term::Choice(v)
+        })
+    }
+
+    fn seq(&mut self) -> Result<Term, ImportError> {
+        let mut v = vec![self.term()?];
+        while self.eat("~")? {
+            v.push(self.term()?);
+        }
+        Ok(if v.len() == 1 {
+            v.pop().unwrap()
+        } else {
+            Term::Seq(v)
+        })
+    }
+
+    /// `#tag = &!node?*+{n,m}`: постфиксы связываются раньше префиксов, метка отбрасывается.
+    fn term(&mut self) -> Result<Term, ImportError> {
+        if self.eat("#")? {
+            self.ident()
+                .ok_or_else(|| self.error("expected tag name"))?;
+            self.expect("=")?;
+        }
+        let mut prefixes = vec![];
+        loop {
+            if self.eat("&")? {
+                prefixes.push('&');
+            } else if self.eat("!")? {
+                prefixes.push('!');
+            } else {
+                break;
+            }
+        }
+        let mut term = self.node()?;
+        loop {
+            term = if self.eat("?")? {
+                Term::Optional(Box::new(term))
+            } else if self.eat("*")? {
+                Term::Repeat(Box::new(term), 0, None)
+            } else if self.eat("+")? {
+                Term::Repeat(Box::new(term), 1, None)
+            } else if self.eat("{")? {
+                let min = self.number()?;
+                let (min, max) = if self.eat(",")? {
+                    (min.unwrap_or(0), self.number()?)
+                } else {
+                    let v = min.ok_or_else(|| self.error("expected repetition bound"))?;
+                    (v, Some(v))
+                };
+                self.expect("}")?;
+                Term::Repeat(Box::new(term), min, max)
+            } else {
+                break;
+            };
+        }
+        for v in prefixes.into_iter().rev() {
+            term = match v {
+                '&' => Term::And(Box::new(term)),
+                _ => Term::Not(Box::new(term)),
+            };
+        }
+        Ok(term)
+    }
+
+    fn node(&mut self) -> Result<Term, ImportError> {
+        self.skip(false)?;
+        let pos = self.pos;
+        match self.peek() {
+            Some('(') => {
+                self.next();
+                let v = self.choice()?;
+                self.expect(")")?;
+                Ok(v)
+            }
+            Some('"') => Ok(Term::Str(self.string()?)),
+            Some('^') => {
+                self.next();
+                if self.peek() != Some('"') {
+                    return Err(self.error("expected string after `^`"));
+                }
+                Ok(Term::Insensitive(self.string()?))
+            }
+            Some('\'') => {
+                let start = self.character()?;
+                self.expect("..")?;
+                self.skip(false)?;
+                if self.peek() != Some('\'') {
+                    return Err(self.error("expected character"));
+                }
+                Ok(Term::Range(start, self.character()?))
+            }
+            _ => match self.ident() {
+                Some(name) if STACK.contains(&name.as_str()) => {
+                    self.pos = pos;
+                    Err(self.error(format!(
+                        "stack operation `{name}` has no equivalent in .abs"
+                    )))
+                }
+                Some(name) => Ok(Term::Ident { name, pos }),
+                None => Err(self.error("expected expression")),
+            },
+        }
+    }
+
+    /// `"..."` с экранированием pest.
+    fn string(&mut self) -> Result<String, ImportError> {
+        let start = self.pos;
+        self.next();
+        let mut v = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(v),
+                Some('\\') => v.push(self.escape()?),
+                Some(c) => v.push(c),
+                None => {
+                    self.pos = start;
+                    return Err(self.error("unterminated string"));
+                }
+            }
+        }
+    }
+
+    /// `'c'`
+    fn character(&mut self) -> Result<char, ImportError> {
+        self.next();
+        let c = match self.next() {
+            Some('\\') => self.escape()?,
+            Some(c) => c,
+            None => return Err(self.error("unterminated character")),
+        };
+        if self.next() != Some('\'') {
+            return Err(self.error("expected `'`"));
+        }
+        Ok(c)
+    }
+
+    /// Символ после `\`: `\n`, `\x41`, `\u{0041}`, ...
+    fn escape(&mut self) -> Result<char, ImportError> {
+        let pos = self.pos - 1;
+        let invalid = move || ImportError {
+            pos,
+            message: "invalid escape sequence".into(),
+        };
+        let code = match self.next() {
+            Some('n') => return Ok('\n'),
+            Some('r') => return Ok('\r'),
+            Some('t') => return Ok('\t'),
+            Some('0') => return Ok('\0'),
+            Some(c @ ('\\' | '"' | '\'')) => return Ok(c),
+            Some('x') => {
+                let hex = self.rest().get(..2).ok_or_else(invalid)?;
+                let code = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+                self.pos += 2;
+                code
+            }
+            Some('u') => {
+                let rest = self.rest();
+                let hex = rest
+                    .strip_prefix('{')
+                    .and_then(|v| v.split_once('}'))
+                    .map(|v| v.0)
+                    .ok_or_else(invalid)?;
+                let code = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+                self.pos += hex.len() + 2;
+                code
+            }
+            _ => return Err(invalid()),
+        };
+        char::from_u32(code).ok_or_else(invalid)
+    }
+}
+
+/// `Some(true)` — атомарное правило, `Some(false)` — явно неатомарное (`!`), `None` —
+/// атомарность вызывающего. `WHITESPACE` и `COMMENT` pest вызывает атомарно.
+fn atomicity(rule: &PestRule) -> Option<bool> {
+    match rule.modifier {
+        Some('@' | '$') => Some(true),
+        Some('!') => Some(false),
+        _ if matches!(rule.name.as_str(), "WHITESPACE" | "COMMENT") => Some(true),
+        _ => None,
+    }
+}
+
+struct Importer<'a> {
+    rules: HashMap<&'a str, &'a PestRule>,
+    /// Имя правила pest → имя в `.abs`.
+    names: HashMap<String, String>,
+    /// Имя правила пропуска, если в грамматике есть `WHITESPACE` или `COMMENT`.
+    skip: Option<String>,
+    /// Правила без модификатора атомарности, которые в атомарном контексте разбирают иначе.
+    sensitive: HashSet<&'a str>,
+    /// Правило pest → имя его атомарной копии.
+    atomic: HashMap<String, String>,
+    queue: VecDeque<String>,
+    builder: Builder,
+}
+
+impl<'a> Importer<'a> {
+    /// Неподвижная точка: правило чувствительно, если в нём есть последовательность или повтор
+    /// либо ссылка на чувствительное правило без своей атомарности.
+    fn sensitive(&self) -> HashSet<&'a str> {
+        fn sensitive(term: &Term, rules: &HashMap<&str, &PestRule>, set: &HashSet<&str>) -> bool {
+            match term {
+                Term::Str(..) | Term::Insensitive(..) | Term::Range(..) => false,
+                Term::Ident { name, .. } => rules
+                    .get(name.as_str())
+                    .is_some_and(|v| atomicity(v).is_none() && set.contains(name.as_str())),
+                Term::Seq(..) | Term::Repeat(..) => true,
+                Term::Choice(v) => v.iter().any(|v| sensitive(v, rules, set)),
+                Term::Optional(v) | Term::Not(v) | Term::And(v) => sensitive(v, rules, set),
+            }
+        }
+        let mut set = HashSet::new();
+        loop {
+            let found = self
+                .rules
+                .iter()
+                .filter(|(name, rule)| {
+                    atomicity(rule).is_none()
+                        && !set.contains(*name)
+                        && sensitive(&rule.body, &self.rules, &set)
+                })
+                .map(|(name, _)| *name)
+                .collect::<Vec<_>>();
+            if found.is_empty() {
+                return set;
+            }
+            set.extend(found);
+        }
+    }
+
+    /// Переводит правило в контексте `atomic` и дописывает его со вспомогательными правилами.
+    fn rule(
+        &mut self,
+        name: String,
+        rule: &PestRule,
+        atomic: bool,
+        items: &mut Vec<Item>,
+    ) -> Result<(), ImportError> {
+        self.builder.start(&name);
+        let spaced = !atomic && self.skip.is_some();
+        let body = match &rule.body {
+            Term::Seq(terms) => {
+                let mut fields = vec![];
+                for (i, term) in terms.iter().enumerate() {
+                    if i > 0 && spaced {
+                        fields.push(TupleItem {
+                            ignored: true,
+                            expr: self.space(),
+                        });
+                    }
+                    // в pest у всего внутри `@` нет своих пар
+                    let ignored = rule.modifier == Some('@');
+                    let expr = self.term(term, atomic)?;
+                    match self.builder.fit(expr, Place::Item) {
+                        Expr::Seq(v) if !ignored => {
+                            fields.extend(v.into_iter().map(|expr| TupleItem { ignored, expr }))
+                        }
+                        expr => fields.push(TupleItem { ignored, expr }),
+                    }
+                }
+                fields.retain(|v| !matches!(&v.expr, Expr::Seq(v) if v.is_empty()));
+                if fields.len() > 1 && fields.iter().any(|v| v.ignored) {
+                    Body::Tuple(fields)
+                } else {
+                    body(self.builder.fit(
+                        Expr::Seq(fields.into_iter().map(|v| v.expr).collect()),
+                        Place::Body,
+                    ))
+                }
+            }
+            term => {
+                let expr = self.term(term, atomic)?;
+                body(self.builder.fit(expr, Place::Body))
+            }
+        };
+        self.builder.push(def(name, body), items);
+        Ok(())
+    }
+
+    #[inline]
+    fn space(&self) -> Expr {
+        Expr::ident(self.skip.as_deref().unwrap_or_default())
+    }
+
+    fn term(&mut self, term: &Term, atomic: bool) -> Result<Expr, ImportError> {
+        let spaced = !atomic && self.skip.is_some();
+        Ok(match term {
+            Term::Str(v) => string(v, false),
+            Term::Insensitive(v) => string(v, true),
+            Term::Range(start, end) if start == end => string(&start.to_string(), false),
+            Term::Range(start, end) => regex(format!("[{}-{}]", escape(*start), escape(*end))),
+            Term::Ident { name, pos } => self.reference(name, *pos, atomic)?,
+            Term::Seq(v) => {
+                let mut items = vec![];
+                for (i, v) in v.iter().enumerate() {
+                    if i > 0 && spaced {
+                        items.push(self.space());
+                    }
+                    items.push(self.term(v, atomic)?);
+                }
+                Expr::Seq(items)
+            }
+            Term::Choice(v) => Expr::Choice(
+                v.iter()
+                    .map(|v| self.term(v, atomic))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Term::Optional(v) => Expr::Optional(Box::new(self.term(v, atomic)?)),
+            Term::Not(v) => Expr::NegativeLookahead(Box::new(self.term(v, atomic)?)),
+            Term::And(v) => Expr::AndPredicate(Box::new(self.term(v, atomic)?)),
+            Term::Repeat(v, min, max) => {
+                let expr = self.term(v, atomic)?;
+                if spaced {
+                    self.spaced_repeat(expr, *min, *max)
+                } else {
+                    repeat(expr, *min, *max)
+                }
+            }
+        })
+    }
+
+    /// Повтор с пропуском между элементами, как его разворачивает pest:
+    /// `a*` — `(a (skip a)*)?`, `a{n,m}` — `a (skip a){n-1,m-1}`.
+    fn spaced_repeat(&mut self, expr: Expr, min: usize, max: Option<usize>) -> Expr {
+        if max == Some(0) {
+            return Expr::Seq(vec![]);
+        }
+        if (min, max) == (0, None) {
+            return Expr::Joinable {
+                expr: Box::new(expr),
+                join: Box::new(self.space()),
+                repetition: None,
+            };
+        }
+        // элемент повторяется дважды, поэтому сложный выносится в правило
+        let expr = match expr {
+            expr @ (Expr::Ref(..) | Expr::Literal(..)) => expr,
+            expr if max == Some(1) => expr,
+            expr => {
+                let expr = self.builder.fit(expr, Place::Body);
+                self.builder.helper(expr)
+            }
+        };
+        let rest = repeat(
+            Expr::Seq(vec![self.space(), expr.clone()]),
+            min.saturating_sub(1),
+            max.map(|v| v - 1),
+        );
+        match min {
+            0 => Expr::Optional(Box::new(Expr::Seq(vec![expr, rest]))),
+            _ => Expr::Seq(vec![expr, rest]),
+        }
+    }
+
+    fn reference(&mut self, name: &str, pos: usize, atomic: bool) -> Result<Expr, ImportError> {
+        let Some(rule) = self.rules.get(name).copied() else {
+            return builtin(name).ok_or_else(|| ImportError {
+                pos,
+                message: format!("undefined rule `{name}`"),
+            });
+        };
+        if !atomic || atomicity(rule).is_some() || !self.sensitive.contains(name) {
+            return Ok(Expr::ident(self.names[name].as_str()));
+        }
+        if !self.atomic.contains_key(name) {
+            let v = self.builder.unique(&format!("{}Atomic", self.names[name]));
+            self.atomic.insert(name.to_string(), v);
+            self.queue.push_back(name.to_string());
+        }
+        Ok(Expr::ident(self.atomic[name].as_str()))
+    }
+}
+
+/// Встроенное правило pest; `SOI` — пустая последовательность, разбор и так начинается с начала.
+fn builtin(name: &str) -> Option<Expr> {
+    let class = match name {
+        "SOI" => return Some(Expr::Seq(vec![])),
+        "EOI" => return Some(Expr::NegativeLookahead(Box::new(regex("(?s).".into())))),
+        "ANY" => "(?s).",
+        "NEWLINE" => r"\n|\r\n|\r",
+        "ASCII" => r"[\x00-\x7F]",
+        "ASCII_DIGIT" => "[0-9]",
+        "ASCII_NONZERO_DIGIT" => "[1-9]",
+        "ASCII_BIN_DIGIT" => "[01]",
+        "ASCII_OCT_DIGIT" => "[0-7]",
+        "ASCII_HEX_DIGIT" => "[0-9a-fA-F]",
+        "ASCII_ALPHA_LOWER" => "[a-z]",
+        "ASCII_ALPHA_UPPER" => "[A-Z]",
+        "ASCII_ALPHA" => "[a-zA-Z]",
+        "ASCII_ALPHANUMERIC" => "[a-zA-Z0-9]",
+        "ALPHABETIC" => r"\p{Alphabetic}",
+        "WHITE_SPACE" => r"\p{White_Space}",
+        "UPPERCASE" => r"\p{Uppercase}",
+        "LOWERCASE" => r"\p{Lowercase}",
+        "XID_START" => r"\p{XID_Start}",
+        "XID_CONTINUE" => r"\p{XID_Continue}",
+        "LETTER" => r"\p{L}",
+        "UPPERCASE_LETTER" => r"\p{Lu}",
+        "LOWERCASE_LETTER" => r"\p{Ll}",
+        "MARK" => r"\p{M}",
+        "NUMBER" => r"\p{N}",
+        "DECIMAL_NUMBER" => r"\p{Nd}",
+        "PUNCTUATION" => r"\p{P}",
+        "SYMBOL" => r"\p{S}",
+        "SEPARATOR" => r"\p{Z}",
+        _ => return None,
+    };
+    Some(regex(class.into()))
+}
@@ -0,0 +1,502 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(specialization)]
+#![allow(incomplete_features)]
+
+pub mod ast;
+pub mod export;
+mod format;
+mod generate;
+pub mod import;
+mod load;
+pub mod railroad;
+pub mod rules;
+
+pub use ast::Grammar;
+pub use format::{format, nesting, WIDTH};
+pub use generate::{GenerateError, Generator};
+use ast::{Body, Expr, Literal, Ref, Repetition, RuleDef, TokenKind};
+pub use load::LoadError;
+use parser::{
+    rules::{Repeat, SCountRepeatRule, SMax, SMin, SMinMax, VecChoiceRule, VecSequenceRule},
+    Cursorable,
+};
+use parsers::chars::{
+    rules::{SRegExprToken, SSubStrToken},
+    CharParser, InputStreamIter,
+};
+pub use rules::{DynRule, Expected, Node, RuleError};
+use rules::{Marker, RuleSlot, Token};
+use std::{
+    cell::{Cell, OnceCell, RefCell},
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    ops::Range,
+    rc::Rc,
+};
+
+/// Ошибка разбора входа: результат `full_parse` и место, дальше которого разбор не продвинулся.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<'src> {
+    pub inner: Box<parsers::chars::ParseError<'src, Vec<Node>, RuleError<'src>>>,
+    pub pos: usize,
+    /// Токены, ожидавшиеся на `pos`; пусто — там ожидался конец ввода.
+    pub expected: Vec<Rc<str>>,
+}
+
+impl<'src> ParseError<'src> {
+    fn new(
+        input: &'src str,
+        inner: parsers::chars::ParseError<'src, Vec<Node>, RuleError<'src>>,
+        expected: Expected,
+    ) -> Self {
+        // при ошибке курсор откатывается в начало, и остаток ничего не говорит о месте
+        let stopped = match inner.parse_result {
+            Ok(..) => input.len() - inner.residue.len(),
+            Err(..) => 0,
+        };
+        let pos = stopped.max(expected.pos);
+        Self {
+            inner: Box::new(inner),
+            pos,
+            expected: if expected.pos == pos {
+                expected.tokens
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.expected.as_slice() {
+            [] => write!(f, "expected end of input")?,
+            [v] => write!(f, "expected {v}")?,
+            v => write!(f, "expected one of {}", v.join(", "))?,
+        }
+        write!(f, " at byte {}", self.pos)
+    }
+}
+
+impl std::error::Error for ParseError<'_> {}
+
+/// Глубина вложенных экземпляров дженериков, после которой раскрытие считается бесконечным.
+const MAX_DEPTH: usize = 256;
+
+/// `.abs`-грамматика, интерпретируемая поверх `CharsIter` без кодогенерации.
+pub struct RuntimeGrammar {
+    ast: Grammar,
+    prelude: Grammar,
+    /// Скомпилированные правила по имени экземпляра (`Name`, `Name<Arg>`).
+    slots: RefCell<HashMap<String, Rc<RuleSlot>>>,
+    depth: Cell<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompileError {
+    Undefined {
+        name: String,
+        span: Range<usize>,
+    },
+    Arity {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Range<usize>,
+    },
+    InvalidRegex {
+        literal: String,
+        message: String,
+    },
+    /// Конструкция разбирается парсером `.abs`, но не поддержана правилами ядра.
+    Unsupported(String),
+    TooDeep {
+        name: String,
+    },
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Undefined { name, .. } => write!(f, "undefined rule `{name}`"),
+            Self::Arity {
+                name,
+                expected,
+                found,
+                ..
+            } => write!(
+                f,
+                "rule `{name}` expects {expected} generic argument(s), found {found}"
+            ),
+            Self::InvalidRegex { literal, message } => {
+                write!(f, "invalid regular expression {literal}: {message}")
+            }
+            Self::Unsupported(v) => write!(f, "unsupported construct `{v}`"),
+            Self::TooDeep { name } => write!(f, "generic expansion of `{name}` does not terminate"),
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+impl CompileError {
+    /// Место в исходнике грамматики, если оно известно.
+    #[inline]
+    pub fn span(&self) -> Option<Range<usize>> {
+        match self {
+            Self::Undefined { span, .. } | Self::Arity { span, .. } => Some(span.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl RuntimeGrammar {
+    pub fn new(ast: Grammar) -> Self {
+        Self {
+            ast,
+            prelude: Grammar::parse(include_str!("prelude.abs")).expect("prelude.abs"),
+            slots: Default::default(),
+            depth: Cell::new(0),
+        }
+    }
+
+    #[inline]
+    pub fn from_abs(src: &str) -> Result<Self, LoadError> {
+        Grammar::parse(src).map(Self::new)
+    }
+
+    #[inline]
+    pub fn grammar(&self) -> &Grammar {
+        &self.ast
+    }
+
+    /// Определение из грамматики или из встроенных правил `grammar_core`.
+    #[inline]
+    pub fn def(&self, name: &str) -> Option<&RuleDef> {
+        self.ast.rule(name).or_else(|| self.prelude.rule(name))
+    }
+
+    pub fn rule(&self, name: &str) -> Result<GrammarRule<'_>, CompileError> {
+        self.instance(&Ref {
+            name: name.to_string(),
+            args: vec![],
+            boxed: false,
+            span: 0..0,
+        })
+        .map(|slot| GrammarRule {
+            slot,
+            _grammar: PhantomData,
+        })
+    }
+
+    #[inline]
+    pub fn parse<'src>(&self, rule: &str, input: &'src str) -> Result<Node, Error<'src>> {
+        Ok(self.rule(rule)?.parse(input)?)
+    }
+
+    /// Компилирует все правила без дженериков; в дженериках проверяет ссылки.
+    pub fn check(&self) -> Result<(), Vec<CompileError>> {
+        let mut errors = vec![];
+        for def in self.ast.rules() {
+            if def.generics.is_empty() {
+                // ошибка во вложенном правиле всплывает из каждого, кто на него ссылается
+                match self.rule(&def.name) {
+                    Err(e) if !errors.contains(&e) => errors.push(e),
+                    _ => {}
+                }
+                continue;
+            }
+            for expr in def.body.exprs() {
+                expr.walk(&mut |v| match v {
+                    Expr::Ref(v) if v.args.is_empty() && def.generics.contains(&v.name) => {}
+                    Expr::Ref(v) => match self.def(&v.name) {
+                        None => errors.push(CompileError::Undefined {
+                            name: v.name.clone(),
+                            span: v.span.clone(),
+                        }),
+                        Some(d) if d.generics.len() != v.args.len() => {
+                            errors.push(CompileError::Arity {
+                                name: v.name.clone(),
+                                expected: d.generics.len(),
+                                found: v.args.len(),
+                                span: v.span.clone(),
+                            })
+                        }
+                        Some(..) => {}
+                    },
+                    _ => {}
+                });
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn instance(&self, r: &Ref) -> Result<Rc<RuleSlot>, CompileError> {
+        // `<Name>` в рантайме ничем не отличается от `Name`
+        let key = Ref {
+            boxed: false,
+            ..r.clone()
+        }
+        .to_string();
+        if let Some(v) = self.slots.borrow().get(&key) {
+            return Ok(v.clone());
+        }
+        let def = self.def(&r.name).ok_or_else(|| CompileError::Undefined {
+            name: r.name.clone(),
+            span: r.span.clone(),
+        })?;
+        if def.generics.len() != r.args.len() {
+            return Err(CompileError::Arity {
+                name: r.name.clone(),
+                expected: def.generics.len(),
+                found: r.args.len(),
+                span: r.span.clone(),
+            });
+        }
+        if self.depth.get() >= MAX_DEPTH {
+            return Err(CompileError::TooDeep {
+                name: r.name.clone(),
+            });
+        }
+
+        // слот регистрируется до компиляции тела, чтобы рекурсивные ссылки на него замкнулись
+        let slot = Rc::new(RuleSlot {
+            name: key.as_str().into(),
+            rule: OnceCell::new(),
+            unit: matches!(
+                def.body,
+                Body::Token {
+                    kind: TokenKind::Unit,
+                    ..
+                }
+            ),
+        });
+        self.slots.borrow_mut().insert(key.clone(), slot.clone());
+        let params = def
+            .generics
+            .iter()
+            .map(String::as_str)
+            .zip(&r.args)
+            .collect::<Vec<_>>();
+
+        self.depth.set(self.depth.get() + 1);
+        let rule = self.body(def, &params);
+        self.depth.set(self.depth.get() - 1);
+
+        match rule {
+            Ok(rule) => {
+                let _ = slot.rule.set(rule);
+                Ok(slot)
+            }
+            Err(e) => {
+                self.slots.borrow_mut().remove(&key);
+                Err(e)
+            }
+        }
+    }
+
+    fn body(&self, def: &RuleDef, params: &[(&str, &Expr)]) -> Result<DynRule, CompileError> {
+        let expr = |v: &Expr| self.expr(&v.substitute(params));
+        Ok(match &def.body {
+            Body::Expr(v) => expr(v)?,
+            Body::Token { kind, literal } => token(
+                literal,
+                def.name.as_str().into(),
+                false,
+                match kind {
+                    TokenKind::Parse(v) => Some(v.as_str().into()),
+                    TokenKind::Base | TokenKind::Unit => None,
+                },
+            )?,
+            Body::Enum(v) => DynRule::Choice(VecChoiceRule(
+                v.iter()
+                    .map(|v| {
+                        Ok(DynRule::Named(
+                            v.name.as_str().into(),
+                            Box::new(expr(&v.expr)?),
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            Body::Struct(v) => DynRule::Seq(VecSequenceRule(
+                v.iter()
+                    .map(|v| {
+                        let rule = Box::new(expr(&v.expr)?);
+                        Ok(match &v.name {
+                            Some(name) => DynRule::Named(name.as_str().into(), rule),
+                            None => DynRule::Ignored(rule),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+            Body::Tuple(v) => DynRule::Seq(VecSequenceRule(
+                v.iter()
+                    .map(|v| {
+                        let rule = expr(&v.expr)?;
+                        Ok(if v.ignored {
+                            DynRule::Ignored(Box::new(rule))
+                        } else {
+                            rule
+                        })
+                    })
+                    .collect::<Result<_, _>>()?,
+            )),
+        })
+    }
+
+    fn expr(&self, expr: &Expr) -> Result<DynRule, CompileError> {
+        let boxed = |v: &Expr| self.expr(v).map(Box::new);
+        let all = |v: &[Expr]| v.iter().map(|v| self.expr(v)).collect::<Result<_, _>>();
+        Ok(match expr {
+            Expr::Ref(v) => DynRule::Ref(Rc::downgrade(&self.instance(v)?)),
+            Expr::Literal(v) => token(v, v.to_string().into(), true, None)?,
+            Expr::Seq(v) => DynRule::Seq(VecSequenceRule(all(v)?)),
+            Expr::Choice(v) => DynRule::Choice(VecChoiceRule(all(v)?)),
+            Expr::Repeat(v, r) => DynRule::Repeat(boxed(v)?, marker(*r)?),
+            Expr::Optional(v) => DynRule::Optional(boxed(v)?),
+            Expr::NegativeLookahead(v) => DynRule::Not(boxed(v)?),
+            Expr::AndPredicate(v) => DynRule::And(boxed(v)?),
+            Expr::Joinable {
+                expr: rule,
+                join,
+                repetition,
+            } => DynRule::Join {
+                rule: boxed(rule)?,
+                join: boxed(join)?,
+                min: match repetition {
+                    None => 0,
+                    Some(Repetition::Min(v)) => *v,
+                    Some(..) => return Err(CompileError::Unsupported(expr.to_string())),
+                },
+            },
+        })
+    }
+}
+
+/// Скомпилированное правило; ссылки внутри него живут, пока жива грамматика.
+pub struct GrammarRule<'g> {
+    slot: Rc<RuleSlot>,
+    _grammar: PhantomData<&'g RuntimeGrammar>,
+}
+
+impl GrammarRule<'_> {
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.slot.name()
+    }
+
+    pub fn parse<'src>(&self, input: &'src str) -> Result<Node, ParseError<'src>> {
+        rules::take_expected();
+        let out = InputStreamIter::new(input).full_parse(&*self.slot);
+        let expected = rules::take_expected();
+        out.map(|v| self.root(v, 0..input.len()))
+            .map_err(|e| ParseError::new(input, e, expected))
+    }
+
+    /// Узел правила; `unit`-правило своего узла не даёт, и корнем становится пустой узел.
+    #[inline]
+    fn root(&self, mut nodes: Vec<Node>, span: Range<usize>) -> Node {
+        nodes.pop().unwrap_or_else(|| Node {
+            rule: self.slot.name.clone(),
+            span,
+            children: vec![],
+        })
+    }
+}
+
+impl<'src> parser::TransferRule<InputStreamIter<'src>> for GrammarRule<'_> {
+    type Output = Node;
+    type Error = RuleError<'src>;
+
+    #[inline]
+    fn transfer(
+        &self,
+        input_stream: parser::InputStream<InputStreamIter<'src>>,
+    ) -> Result<Self::Output, parser::ProductionError<Self::Error>> {
+        let start = *input_stream.cursor();
+        let out = self.slot.transfer(input_stream)?;
+        Ok(self.root(out, start..*input_stream.cursor()))
+    }
+}
+
+#[derive(Debug)]
+pub enum Error<'src> {
+    Compile(CompileError),
+    Parse(ParseError<'src>),
+}
+
+impl From<CompileError> for Error<'_> {
+    #[inline]
+    fn from(v: CompileError) -> Self {
+        Self::Compile(v)
+    }
+}
+
+impl<'src> From<ParseError<'src>> for Error<'src> {
+    #[inline]
+    fn from(v: ParseError<'src>) -> Self {
+        Self::Parse(v)
+    }
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compile(v) => write!(f, "{v}"),
+            Self::Parse(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+fn token(
+    literal: &Literal,
+    name: Rc<str>,
+    leaf: bool,
+    type_: Option<Rc<str>>,
+) -> Result<DynRule, CompileError> {
+    Ok(DynRule::Token {
+        token: if literal.sub_str {
+            Token::SubStr(SSubStrToken(literal.value.clone()))
+        } else {
+            Token::Regex(SRegExprToken::try_new(&literal.value).map_err(|e| {
+                CompileError::InvalidRegex {
+                    literal: literal.to_string(),
+                    message: e.to_string(),
+                }
+            })?)
+        },
+        name,
+        leaf,
+        type_,
+    })
+}
+
+/// `SMin`/`SMax`/`SMinMax` не принимают нулевых границ, поэтому они сводятся к другим маркерам.
+fn marker(v: Repetition) -> Result<Marker, CompileError> {
+    Ok(match v {
+        Repetition::ZeroOrMore | Repetition::Min(0) => Marker::Repeat(Repeat),
+        Repetition::OneOrMore => Marker::Min(SMin { min: 1 }),
+        Repetition::Min(min) => Marker::Min(SMin { min }),
+        Repetition::Max(0) | Repetition::MinMax(0, 0) | Repetition::Count(0) => Marker::Empty,
+        Repetition::Max(max) | Repetition::MinMax(0, max) => Marker::Max(SMax { max }),
+        Repetition::MinMax(min, max) if min <= max => Marker::MinMax(SMinMax { range: min..=max }),
+        Repetition::MinMax(..) => return Err(CompileError::Unsupported(v.to_string())),
+        Repetition::Count(count) => Marker::Count(SCountRepeatRule { count }),
+    })
+}
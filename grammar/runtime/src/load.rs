@@ -0,0 +1,379 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use crate::ast::*;
+use grammar_extended::{
+    parser::AnyOrParenOutput,
+    tree::parser::{
+        ChoiceOrQuantificator, CombinatorOrToken, CombinatorOutput, ExprOutput, FieldOutput,
+        IdentWithDefineGenericsOrIdentOutput, IgnoredExprVOutput, JoinableOutput, KleeneOutput,
+        PredicativeOutput, QuantificatorOrTokenOrSeqOutput, QuantificatorOrTokenOutput,
+        QuantificatorOutput, RepeatQuantificatorOutput, StructTypeOutput, TokenOutput,
+        TupleItemOutput, TupleStructExprOutput,
+    },
+};
+use grammar_feature_parser::{AliasExprOutput, ParseTokenOutput, TokenHeadOutput};
+use grammar_feature_parsing::{default_feature_rule, Feature, FeatureRule};
+use parser::{cached::CachedIter, rules::SeqOutput, TransferRule};
+use parsers::chars::{CharParser, InputStreamIter, InputStreamTrait};
+use std::{fmt, ops::Range};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadError {
+    /// Байтовая позиция, на которой остановился разбор грамматики.
+    pub pos: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at byte {}", self.message, self.pos)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl Grammar {
+    /// Разбирает `.abs` тем же `default_feature_rule`, что и макрос `grammar_feature!`.
+    pub fn parse(src: &str) -> Result<Self, LoadError> {
+        let features = CachedIter::new(InputStreamIter::new(src))
+            .full_parse(&default_feature_rule())
+            .map_err(|e| LoadError {
+                pos: src.len() - e.residue.len(),
+                message: match e.parse_result {
+                    Ok(..) => "unexpected input",
+                    Err(..) => "invalid grammar",
+                }
+                .into(),
+            })?;
+        Ok(Loader { src }.grammar(features))
+    }
+}
+
+struct Loader<'src> {
+    src: &'src str,
+}
+
+impl<'src> Loader<'src> {
+    /// Выводы парсера — подстроки `src`, поэтому позицию берём из указателя.
+    #[inline]
+    fn span(&self, v: &str) -> Range<usize> {
+        let start = v.as_ptr() as usize - self.src.as_ptr() as usize;
+        debug_assert!(start + v.len() <= self.src.len());
+        start..start + v.len()
+    }
+
+    /// `value` — содержимое кавычек, позиция литерала захватывает кавычки и `s`.
+    #[inline]
+    fn literal(&self, value: &str, sub_str: bool) -> Literal {
+        let span = self.span(value);
+        Literal {
+            value: value.to_string(),
+            sub_str,
+            span: span.start - 1..span.end + 1 + usize::from(sub_str),
+        }
+    }
+
+    fn grammar<IS: InputStreamTrait<'src>>(
+        &self,
+        features: Vec<<FeatureRule<'src> as TransferRule<IS>>::Output>,
+    ) -> Grammar {
+        let mut items = features
+            .into_iter()
+            .map(|v| self.item(v))
+            .collect::<Vec<_>>();
+
+        // определение тянется до следующего элемента, без хвостовых пробелов
+        let starts = items
+            .iter()
+            .skip(1)
+            .map(|v| match v {
+                Item::Comment(v) => v.span.start,
+                Item::Rule(v) => v.span.start,
+            })
+            .chain([self.src.len()])
+            .collect::<Vec<_>>();
+        for (item, end) in items.iter_mut().zip(starts) {
+            if let Item::Rule(v) = item {
+                v.span.end = v.span.start + self.src[v.span.start..end].trim_end().len();
+            }
+        }
+        Grammar { items }
+    }
+
+    fn item<IS: InputStreamTrait<'src>>(
+        &self,
+        v: <FeatureRule<'src> as TransferRule<IS>>::Output,
+    ) -> Item {
+        let (head, body) = match v {
+            Feature::Comment(v) => {
+                let span = self.span(v);
+                return Item::Comment(Comment {
+                    text: v.to_string(),
+                    span: span.start - "//".len()..span.end,
+                });
+            }
+            Feature::Token(v) => {
+                let (name, kind) = match v.head {
+                    TokenHeadOutput::Unit(name) => (name, TokenKind::Unit),
+                    TokenHeadOutput::Parse(ParseTokenOutput { name, type_ }) => {
+                        (name, TokenKind::Parse(type_.to_string()))
+                    }
+                    TokenHeadOutput::Base(name) => (name, TokenKind::Base),
+                };
+                let SeqOutput((value, sub_str)) = v.expr;
+                let name_span = self.span(name);
+                let start = match kind {
+                    TokenKind::Unit => self.src[..name_span.start]
+                        .trim_end()
+                        .strip_suffix("unit")
+                        .map_or(name_span.start, str::len),
+                    _ => name_span.start,
+                };
+                return Item::Rule(RuleDef {
+                    name: name.to_string(),
+                    generics: vec![],
+                    generic_spans: vec![],
+                    body: Body::Token {
+                        kind,
+                        literal: self.literal(value, sub_str.is_some()),
+                    },
+                    span: start..name_span.end,
+                    name_span,
+                });
+            }
+            Feature::Choice(v) => (
+                v.head,
+                Body::Enum(
+                    v.variants
+                        .iter()
+                        .map(|v| Variant {
+                            comments: v.comments.iter().map(|v| v.to_string()).collect(),
+                            name: v.item.ident.to_string(),
+                            expr: self.expr(&v.item.value),
+                        })
+                        .collect(),
+                ),
+            ),
+            Feature::Sequence(v) => (
+                v.head,
+                match &v.fields {
+                    StructTypeOutput::Struct(v) => Body::Struct(
+                        v.iter()
+                            .map(|v| {
+                                let (name, expr) = match &v.item {
+                                    FieldOutput::Named(v) => {
+                                        (Some(v.name.to_string()), self.expr(&v.value))
+                                    }
+                                    FieldOutput::Unnamed(v) => (None, self.expr(v)),
+                                };
+                                Field {
+                                    comments: v.comments.iter().map(|v| v.to_string()).collect(),
+                                    name,
+                                    expr,
+                                }
+                            })
+                            .collect(),
+                    ),
+                    StructTypeOutput::Tuple(v) => Body::Tuple(
+                        v.iter()
+                            .map(|v| match v {
+                                TupleItemOutput::Ignored(v) => TupleItem {
+                                    ignored: true,
+                                    expr: match v {
+                                        IgnoredExprVOutput::TupleStructExpr(v) => {
+                                            self.tuple_struct_expr(v)
+                                        }
+                                        IgnoredExprVOutput::ParenedSeq(v) => Expr::Seq(self.seq(v)),
+                                    },
+                                },
+                                TupleItemOutput::TupleStructExpr(v) => TupleItem {
+                                    ignored: false,
+                                    expr: self.tuple_struct_expr(v),
+                                },
+                            })
+                            .collect(),
+                    ),
+                },
+            ),
+            Feature::ChoiceRule(v) => (v.head, Body::Expr(Expr::Choice(self.choice(&v.expr)))),
+            Feature::SequenceRule(v) => (v.head, Body::Expr(Expr::Seq(self.seq(&v.expr)))),
+            Feature::QuantificatorRule(v) => (v.head, Body::Expr(self.quantificator(&v.expr))),
+            Feature::AliasRule(v) => (
+                v.head,
+                Body::Expr(self.token(&match v.expr {
+                    AliasExprOutput::IdentWithExprGenerics(v) => {
+                        TokenOutput::IdentWithExprGenerics(v)
+                    }
+                    AliasExprOutput::BoxedIdent(v) => TokenOutput::BoxedIdent(v),
+                    AliasExprOutput::Ident(v) => TokenOutput::Ident(v),
+                })),
+            ),
+        };
+        let (name, generics) = match head {
+            IdentWithDefineGenericsOrIdentOutput::Ident(v) => (v, vec![]),
+            IdentWithDefineGenericsOrIdentOutput::IdentWithDefineGenerics(v) => {
+                (v.ident, v.generics.to_vec())
+            }
+        };
+        let name_span = self.span(name);
+        Item::Rule(RuleDef {
+            name: name.to_string(),
+            generic_spans: generics.iter().map(|v| self.span(v)).collect(),
+            generics: generics.iter().map(|v| v.to_string()).collect(),
+            body,
+            span: name_span.clone(),
+            name_span,
+        })
+    }
+
+    fn expr<IS: InputStreamTrait<'src>>(&self, v: &ExprOutput<'src, IS>) -> Expr {
+        match v {
+            ExprOutput::Combinator(v) => self.combinator(v),
+            ExprOutput::Quantificator(v) => self.quantificator(v),
+            ExprOutput::Token(v) => self.token(v),
+        }
+    }
+
+    #[inline]
+    fn combinator<IS: InputStreamTrait<'src>>(&self, v: &CombinatorOutput<'src, IS>) -> Expr {
+        match v {
+            CombinatorOutput::Choice(v) => Expr::Choice(self.choice(v)),
+            CombinatorOutput::Seq(v) => Expr::Seq(self.seq(v)),
+        }
+    }
+
+    fn choice<IS: InputStreamTrait<'src>>(
+        &self,
+        v: &[QuantificatorOrTokenOrSeqOutput<'src, IS>],
+    ) -> Vec<Expr> {
+        v.iter()
+            .map(|v| match v {
+                QuantificatorOrTokenOrSeqOutput::QuantificatorOrToken(v) => {
+                    self.quantificator_or_token(v)
+                }
+                QuantificatorOrTokenOrSeqOutput::ParenedSeq(v) => Expr::Seq(self.seq(v)),
+            })
+            .collect()
+    }
+
+    fn seq<IS: InputStreamTrait<'src>>(
+        &self,
+        v: &[<ChoiceOrQuantificator<'src> as TransferRule<IS>>::Output],
+    ) -> Vec<Expr> {
+        v.iter()
+            .map(|v| match v {
+                AnyOrParenOutput::Any(v) => self.quantificator_or_token(v),
+                AnyOrParenOutput::Parensized(v) => Expr::Choice(self.choice(v)),
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn tuple_struct_expr<IS: InputStreamTrait<'src>>(
+        &self,
+        v: &TupleStructExprOutput<'src, IS>,
+    ) -> Expr {
+        match v {
+            TupleStructExprOutput::Choice(v) => Expr::Choice(self.choice(v)),
+            TupleStructExprOutput::Quantificator(v) => self.quantificator(v),
+            TupleStructExprOutput::Token(v) => self.token(v),
+        }
+    }
+
+    #[inline]
+    fn quantificator_or_token<IS: InputStreamTrait<'src>>(
+        &self,
+        v: &QuantificatorOrTokenOutput<'src, IS>,
+    ) -> Expr {
+        match v {
+            QuantificatorOrTokenOutput::Quantificator(v) => self.quantificator(v),
+            QuantificatorOrTokenOutput::Token(v) => self.token(v),
+        }
+    }
+
+    #[inline]
+    fn combinator_or_token<IS: InputStreamTrait<'src>>(
+        &self,
+        v: &<CombinatorOrToken<'src> as TransferRule<IS>>::Output,
+    ) -> Expr {
+        match v {
+            AnyOrParenOutput::Any(v) => self.token(v),
+            AnyOrParenOutput::Parensized(v) => self.combinator(v),
+        }
+    }
+
+    fn quantificator<IS: InputStreamTrait<'src>>(&self, v: &QuantificatorOutput<'src, IS>) -> Expr {
+        let boxed = |v| Box::new(self.combinator_or_token(v));
+        match v {
+            QuantificatorOutput::Kleene(KleeneOutput::ZeroOrMore(v)) => {
+                Expr::Repeat(boxed(v), Repetition::ZeroOrMore)
+            }
+            QuantificatorOutput::Kleene(KleeneOutput::OneOrMore(v)) => {
+                Expr::Repeat(boxed(v), Repetition::OneOrMore)
+            }
+            QuantificatorOutput::Predicative(PredicativeOutput::Optional(v)) => {
+                Expr::Optional(boxed(v))
+            }
+            QuantificatorOutput::Predicative(PredicativeOutput::NegativeLookahead(v)) => {
+                Expr::NegativeLookahead(boxed(v))
+            }
+            QuantificatorOutput::Predicative(PredicativeOutput::AndPredicate(v)) => {
+                Expr::AndPredicate(boxed(v))
+            }
+            QuantificatorOutput::RepeatQuantificator((v, q)) => {
+                Expr::Repeat(boxed(v), repetition(q))
+            }
+            QuantificatorOutput::Joinable(SeqOutput((v, j, join))) => Expr::Joinable {
+                expr: boxed(v),
+                join: boxed(join),
+                repetition: match j {
+                    JoinableOutput::Repeat(..) => None,
+                    JoinableOutput::StrictRepeat(q) => Some(repetition(q)),
+                },
+            },
+        }
+    }
+
+    fn token<IS: InputStreamTrait<'src>>(&self, v: &TokenOutput<'src, IS>) -> Expr {
+        let (name, args, boxed) = match v {
+            TokenOutput::IdentWithExprGenerics(v) => (
+                v.ident,
+                v.generics.iter().map(|v| self.expr(v)).collect(),
+                false,
+            ),
+            TokenOutput::BoxedIdent(v) => (*v, vec![], true),
+            TokenOutput::Ident(v) => (*v, vec![], false),
+            TokenOutput::StrLiteral(SeqOutput((value, sub_str))) => {
+                return Expr::Literal(self.literal(value, sub_str.is_some()))
+            }
+        };
+        Expr::Ref(Ref {
+            name: name.to_string(),
+            args,
+            boxed,
+            span: self.span(name),
+        })
+    }
+}
+
+#[inline]
+fn repetition<'src, IS: InputStreamTrait<'src>>(
+    v: &RepeatQuantificatorOutput<'src, IS>,
+) -> Repetition {
+    match v {
+        RepeatQuantificatorOutput::Maximum(v) => Repetition::Max(*v),
+        RepeatQuantificatorOutput::MinMax(v) => Repetition::MinMax(v.min, v.max),
+        RepeatQuantificatorOutput::Minimum(v) => Repetition::Min(*v),
+        RepeatQuantificatorOutput::Count(v) => Repetition::Count(*v),
+    }
+}
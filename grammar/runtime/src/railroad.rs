@@ -0,0 +1,591 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Синтаксические (railroad) диаграммы `.abs`-грамматики: по SVG на правило и страница
+//! `index.html` со всеми диаграммами, где ссылки на правила ведут к их определениям.
+//!
+//! Элемент диаграммы входит слева и выходит справа на базовой линии. Первая ветвь выбора
+//! идёт по ней, остальные — ниже; повтор возвращается по нижней петле, на которой стоит
+//! разделитель `**`, а границы повтора подписаны под петлёй.
+
+use crate::ast::*;
+use std::fmt::Write;
+
+/// Радиус скруглений.
+const ARC: f64 = 10.;
+/// Зазор между ветвями по вертикали.
+const GAP: f64 = 8.;
+/// Высота рамки токена и ссылки.
+const BOX: f64 = 22.;
+/// Ширина символа шрифта рамок и подписей.
+const CHAR: f64 = 8.;
+const SMALL_CHAR: f64 = 6.5;
+/// Высота подписи.
+const LABEL: f64 = 14.;
+/// Отступ рамки предпросмотра от содержимого.
+const INSET: f64 = 8.;
+/// Поля диаграммы.
+const PAD: f64 = 20.;
+
+const STYLE: &str = "\
+path { stroke: #333; stroke-width: 2; fill: none; }
+rect { stroke: #333; stroke-width: 2; fill: #fff; }
+rect.terminal { fill: #fec; }
+rect.regex { fill: #ecf8ec; }
+rect.nonterminal { fill: #e8f0fe; }
+rect.group { fill: none; stroke-width: 1; stroke-dasharray: 4 3; }
+text { font: 13px monospace; text-anchor: middle; fill: #000; }
+text.label, text.title { text-anchor: start; }
+text.label { font-size: 11px; fill: #555; }
+text.title { font-weight: bold; }
+a text { fill: #14c; text-decoration: underline; }";
+
+/// Элемент диаграммы: `up` и `down` — высота над базовой линией и под ней.
+#[derive(Debug, Clone)]
+struct Node {
+    kind: Kind,
+    width: f64,
+    up: f64,
+    down: f64,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    /// Прямой путь.
+    Skip,
+    Terminal {
+        text: String,
+        regex: bool,
+    },
+    NonTerminal {
+        text: String,
+        href: Option<String>,
+    },
+    Seq(Vec<Node>),
+    Choice(Vec<Node>),
+    Loop {
+        item: Box<Node>,
+        join: Option<Box<Node>>,
+        label: Option<String>,
+    },
+    /// Подпись над элементом: имя варианта или поля.
+    Label(String, Box<Node>),
+    /// Пунктирная рамка с подписью: предпросмотр.
+    Group(String, Box<Node>),
+}
+
+impl Node {
+    #[inline]
+    fn skip() -> Self {
+        Self {
+            kind: Kind::Skip,
+            width: 0.,
+            up: 0.,
+            down: 0.,
+        }
+    }
+
+    fn framed(text: String, kind: impl FnOnce(String) -> Kind) -> Self {
+        Self {
+            width: text_width(&text, CHAR) + 2. * ARC,
+            up: BOX / 2.,
+            down: BOX / 2.,
+            kind: kind(text),
+        }
+    }
+
+    #[inline]
+    fn terminal(text: String, regex: bool) -> Self {
+        Self::framed(text, |text| Kind::Terminal { text, regex })
+    }
+
+    #[inline]
+    fn non_terminal(text: String, href: Option<String>) -> Self {
+        Self::framed(text, |text| Kind::NonTerminal { text, href })
+    }
+
+    fn seq(mut items: Vec<Node>) -> Self {
+        match items.len() {
+            0 => return Self::skip(),
+            1 => return items.pop().unwrap(),
+            _ => {}
+        }
+        Self {
+            width: items.iter().map(|v| v.width).sum::<f64>() + ARC * (items.len() - 1) as f64,
+            up: items.iter().map(|v| v.up).fold(0., f64::max),
+            down: items.iter().map(|v| v.down).fold(0., f64::max),
+            kind: Kind::Seq(items),
+        }
+    }
+
+    fn choice(mut items: Vec<Node>) -> Self {
+        if items.len() == 1 {
+            return items.pop().unwrap();
+        }
+        let offsets = offsets(&items);
+        Self {
+            width: items.iter().map(|v| v.width).fold(0., f64::max) + 4. * ARC,
+            up: items[0].up,
+            down: offsets.last().unwrap() + items.last().unwrap().down,
+            kind: Kind::Choice(items),
+        }
+    }
+
+    #[inline]
+    fn optional(item: Node) -> Self {
+        Self::choice(vec![Self::skip(), item])
+    }
+
+    fn repeat(item: Node, join: Option<Node>, label: Option<String>) -> Self {
+        let inner = inner_width(&item, join.as_ref(), label.as_deref());
+        let dy = loop_offset(&item, join.as_ref());
+        Self {
+            width: inner + 2. * ARC,
+            up: item.up,
+            down: dy + join.as_ref().map_or(0., |v| v.down) + label.as_ref().map_or(0., |_| LABEL),
+            kind: Kind::Loop {
+                item: Box::new(item),
+                join: join.map(Box::new),
+                label,
+            },
+        }
+    }
+
+    fn label(text: String, item: Node) -> Self {
+        Self {
+            width: item.width.max(text_width(&text, SMALL_CHAR)),
+            up: item.up + LABEL,
+            down: item.down,
+            kind: Kind::Label(text, Box::new(item)),
+        }
+    }
+
+    fn group(text: String, item: Node) -> Self {
+        Self {
+            width: item.width.max(text_width(&text, SMALL_CHAR)) + 2. * INSET,
+            up: item.up + INSET + LABEL,
+            down: item.down + INSET,
+            kind: Kind::Group(text, Box::new(item)),
+        }
+    }
+
+    /// Рисует элемент с входом в (`x`, `y`).
+    fn draw(&self, x: f64, y: f64, out: &mut String) {
+        match &self.kind {
+            Kind::Skip => line(out, x, y, self.width),
+            Kind::Terminal { text, regex } => {
+                let class = if *regex { "regex" } else { "terminal" };
+                frame(out, x, y, self.width, class, ARC, text);
+            }
+            Kind::NonTerminal { text, href } => {
+                if let Some(href) = href {
+                    write!(out, r#"<a href="{}">"#, escape(href)).unwrap();
+                }
+                frame(out, x, y, self.width, "nonterminal", 0., text);
+                if href.is_some() {
+                    out.push_str("</a>");
+                }
+            }
+            Kind::Seq(items) => {
+                let mut x = x;
+                for (i, v) in items.iter().enumerate() {
+                    if i > 0 {
+                        line(out, x, y, ARC);
+                        x += ARC;
+                    }
+                    v.draw(x, y, out);
+                    x += v.width;
+                }
+            }
+            Kind::Choice(items) => {
+                let inner = self.width - 4. * ARC;
+                for (v, dy) in items.iter().zip(offsets(items)) {
+                    let (left, right) = (x + 2. * ARC, x + 2. * ARC + inner);
+                    if dy == 0. {
+                        line(out, x, y, 2. * ARC);
+                        line(out, right, y, 2. * ARC);
+                    } else {
+                        let v_len = dy - 2. * ARC;
+                        writeln!(
+                            out,
+                            r#"<path d="M{x} {y}a{ARC} {ARC} 0 0 1 {ARC} {ARC}v{v_len}a{ARC} {ARC} 0 0 0 {ARC} {ARC}"/>"#
+                        )
+                        .unwrap();
+                        writeln!(
+                            out,
+                            r#"<path d="M{right} {}a{ARC} {ARC} 0 0 0 {ARC} -{ARC}v-{v_len}a{ARC} {ARC} 0 0 1 {ARC} -{ARC}"/>"#,
+                            y + dy
+                        )
+                        .unwrap();
+                    }
+                    v.draw(left, y + dy, out);
+                    line(out, left + v.width, y + dy, inner - v.width);
+                }
+            }
+            Kind::Loop { item, join, label } => {
+                let inner = self.width - 2. * ARC;
+                let (left, right) = (x + ARC, x + ARC + inner);
+                line(out, x, y, ARC);
+                centered(out, item, left, y, inner);
+                line(out, right, y, ARC);
+
+                let dy = loop_offset(item, join.as_deref());
+                let v_len = dy - 2. * ARC;
+                writeln!(
+                    out,
+                    r#"<path d="M{right} {y}a{ARC} {ARC} 0 0 1 {ARC} {ARC}v{v_len}a{ARC} {ARC} 0 0 1 -{ARC} {ARC}"/>"#
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    r#"<path d="M{left} {}a{ARC} {ARC} 0 0 1 -{ARC} -{ARC}v-{v_len}a{ARC} {ARC} 0 0 1 {ARC} -{ARC}"/>"#,
+                    y + dy
+                )
+                .unwrap();
+                match join {
+                    Some(join) => centered(out, join, left, y + dy, inner),
+                    None => line(out, left, y + dy, inner),
+                }
+                if let Some(label) = label {
+                    let bottom = y + dy + join.as_ref().map_or(0., |v| v.down);
+                    text(out, "label", x + ARC, bottom + LABEL - 3., label);
+                }
+            }
+            Kind::Label(label, item) => {
+                text(out, "label", x, y - item.up - 4., label);
+                item.draw(x, y, out);
+                line(out, x + item.width, y, self.width - item.width);
+            }
+            Kind::Group(label, item) => {
+                let top = y - self.up;
+                writeln!(
+                    out,
+                    r#"<rect class="group" x="{x}" y="{top}" width="{}" height="{}" rx="{ARC}"/>"#,
+                    self.width,
+                    self.up + self.down
+                )
+                .unwrap();
+                text(out, "label", x + INSET, top + LABEL - 2., label);
+                line(out, x, y, INSET);
+                centered(out, item, x + INSET, y, self.width - 2. * INSET);
+                line(out, x + self.width - INSET, y, INSET);
+            }
+        }
+    }
+}
+
+/// Смещения ветвей выбора от базовой линии: первая на ней, остальные ниже с зазором.
+fn offsets(items: &[Node]) -> Vec<f64> {
+    let mut out = Vec::<f64>::with_capacity(items.len());
+    for (i, v) in items.iter().enumerate() {
+        out.push(match i {
+            0 => 0.,
+            _ => {
+                let prev = &items[i - 1];
+                let dy = out[i - 1] + prev.down + GAP + v.up;
+                if i == 1 {
+                    dy.max(2. * ARC)
+                } else {
+                    dy
+                }
+            }
+        });
+    }
+    out
+}
+
+/// Смещение нижней петли повтора.
+fn loop_offset(item: &Node, join: Option<&Node>) -> f64 {
+    (item.down + GAP + join.map_or(0., |v| v.up)).max(2. * ARC)
+}
+
+fn inner_width(item: &Node, join: Option<&Node>, label: Option<&str>) -> f64 {
+    item.width
+        .max(join.map_or(0., |v| v.width))
+        .max(label.map_or(0., |v| text_width(v, SMALL_CHAR)))
+}
+
+#[inline]
+fn text_width(text: &str, char: f64) -> f64 {
+    text.chars().count() as f64 * char
+}
+
+/// Рисует `item` посередине участка ширины `width`, продлевая путь до краёв.
+fn centered(out: &mut String, item: &Node, x: f64, y: f64, width: f64) {
+    let pad = (width - item.width) / 2.;
+    line(out, x, y, pad);
+    item.draw(x + pad, y, out);
+    line(out, x + pad + item.width, y, pad);
+}
+
+fn line(out: &mut String, x: f64, y: f64, width: f64) {
+    if width > 0. {
+        writeln!(out, r#"<path d="M{x} {y}h{width}"/>"#).unwrap();
+    }
+}
+
+fn frame(out: &mut String, x: f64, y: f64, width: f64, class: &str, rx: f64, label: &str) {
+    writeln!(
+        out,
+        r#"<rect class="{class}" x="{x}" y="{}" width="{width}" height="{BOX}" rx="{rx}"/>"#,
+        y - BOX / 2.
+    )
+    .unwrap();
+    text(out, "", x + width / 2., y + 4., label);
+}
+
+fn text(out: &mut String, class: &str, x: f64, y: f64, text: &str) {
+    let class = match class {
+        "" => String::new(),
+        v => format!(r#" class="{v}""#),
+    };
+    writeln!(
+        out,
+        r#"<text{class} x="{x}" y="{y}">{}</text>"#,
+        escape(text)
+    )
+    .unwrap();
+}
+
+fn escape(v: &str) -> String {
+    let mut out = String::with_capacity(v.len());
+    for c in v.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Строит элементы диаграмм правил грамматики.
+struct Builder<'g, F> {
+    grammar: &'g Grammar,
+    /// Адрес определения правила по имени.
+    link: F,
+}
+
+impl<F: Fn(&str) -> String> Builder<'_, F> {
+    fn body(&self, rule: &RuleDef) -> Node {
+        let expr = |v: &Expr| self.expr(v, &rule.generics);
+        match &rule.body {
+            Body::Expr(v) => expr(v),
+            Body::Token { literal, .. } => literal_node(literal),
+            Body::Enum(v) => Node::choice(
+                v.iter()
+                    .map(|v| Node::label(v.name.clone(), expr(&v.expr)))
+                    .collect(),
+            ),
+            Body::Struct(v) => Node::seq(
+                v.iter()
+                    .map(|v| match &v.name {
+                        Some(name) => Node::label(name.clone(), expr(&v.expr)),
+                        None => expr(&v.expr),
+                    })
+                    .collect(),
+            ),
+            Body::Tuple(v) => Node::seq(v.iter().map(|v| expr(&v.expr)).collect()),
+        }
+    }
+
+    fn expr(&self, expr: &Expr, generics: &[String]) -> Node {
+        let sub = |v: &Expr| self.expr(v, generics);
+        match expr {
+            Expr::Ref(v) => {
+                let defined = !generics.contains(&v.name) && self.grammar.rule(&v.name).is_some();
+                Node::non_terminal(v.to_string(), defined.then(|| (self.link)(&v.name)))
+            }
+            Expr::Literal(v) => literal_node(v),
+            Expr::Seq(v) => Node::seq(v.iter().map(sub).collect()),
+            Expr::Choice(v) => Node::choice(v.iter().map(sub).collect()),
+            Expr::Repeat(v, repetition) => repeat(sub(v), None, Some(*repetition)),
+            Expr::Optional(v) => Node::optional(sub(v)),
+            Expr::NegativeLookahead(v) => Node::group("not".into(), sub(v)),
+            Expr::AndPredicate(v) => Node::group("and".into(), sub(v)),
+            Expr::Joinable {
+                expr,
+                join,
+                repetition,
+            } => repeat(
+                sub(expr),
+                Some(sub(join)),
+                Some(repetition.unwrap_or(Repetition::ZeroOrMore)),
+            ),
+        }
+    }
+}
+
+fn literal_node(v: &Literal) -> Node {
+    if v.sub_str {
+        Node::terminal(v.value.clone(), false)
+    } else if let Some(text) = unescape(&v.value) {
+        Node::terminal(text, false)
+    } else {
+        Node::terminal(format!("/{}/", v.value), true)
+    }
+}
+
+/// Регулярка, совпадающая ровно с одной строкой (вроде `\[`), показывается как
+/// обычный терминал.
+fn unescape(regex: &str) -> Option<String> {
+    let mut out = String::new();
+    let mut chars = regex.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(c) if !c.is_alphanumeric() => out.push(c),
+                _ => return None,
+            },
+            '.' | '^' | '$' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' => {
+                return None
+            }
+            c => out.push(c),
+        }
+    }
+    (!out.is_empty()).then_some(out)
+}
+
+/// Повтор с подписью границ; если повтор допускает ноль, он ещё и необязателен.
+fn repeat(item: Node, join: Option<Node>, repetition: Option<Repetition>) -> Node {
+    let (min, label) = match repetition.unwrap_or(Repetition::OneOrMore) {
+        Repetition::ZeroOrMore => (0, None),
+        Repetition::OneOrMore => (1, None),
+        Repetition::Min(v) => (v, (v > 1).then(|| format!("at least {v}"))),
+        Repetition::Max(v) => (0, Some(format!("at most {v}"))),
+        Repetition::MinMax(min, max) => (min, Some(format!("{min} to {max} times"))),
+        Repetition::Count(v) => (v, Some(format!("{v} times"))),
+    };
+    let node = Node::repeat(item, join, label);
+    if min == 0 {
+        Node::optional(node)
+    } else {
+        node
+    }
+}
+
+/// Диаграмма правила `rule` грамматики `grammar` — документ SVG с заголовком `Name<T>`.
+/// `link` даёт адрес определения правила по имени; ссылки на правила вне грамматики и
+/// параметры дженерика не подсвечиваются.
+pub fn diagram(grammar: &Grammar, rule: &RuleDef, link: impl Fn(&str) -> String) -> String {
+    let node = Builder { grammar, link }.body(rule);
+    let width = node.width + 2. * (PAD + ARC);
+    let top = PAD + LABEL + GAP;
+    let height = top + node.up + node.down + PAD;
+    let y = top + node.up;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" class="railroad" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+    writeln!(out, "<style>\n{STYLE}\n</style>").unwrap();
+    text(&mut out, "title", PAD, PAD + LABEL - 4., &rule.head());
+    // вход и выход: засечки на концах базовой линии
+    writeln!(out, r#"<path d="M{PAD} {}v{BOX}"/>"#, y - BOX / 2.).unwrap();
+    line(&mut out, PAD, y, ARC);
+    node.draw(PAD + ARC, y, &mut out);
+    let end = PAD + ARC + node.width;
+    line(&mut out, end, y, ARC);
+    writeln!(out, r#"<path d="M{} {}v{BOX}"/>"#, end + ARC, y - BOX / 2.).unwrap();
+    out.push_str("</svg>\n");
+    out
+}
+
+/// Файлы документации: `<Правило>.svg` на каждое правило со ссылками между файлами и
+/// `index.html` со всеми диаграммами, комментариями перед правилами, текстом определения
+/// и списком правил, которые ссылаются на данное.
+pub fn railroad(grammar: &Grammar, title: &str) -> Vec<(String, String)> {
+    let mut files = grammar
+        .rules()
+        .map(|v| {
+            let svg = diagram(grammar, v, |name| format!("{name}.svg"));
+            (format!("{}.svg", v.name), svg)
+        })
+        .collect::<Vec<_>>();
+    files.push(("index.html".into(), index(grammar, title)));
+    files
+}
+
+fn index(grammar: &Grammar, title: &str) -> String {
+    let title = escape(title);
+    let mut out = String::new();
+    writeln!(
+        out,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>\nbody {{ font-family: sans-serif; margin: 2em; }}\n\
+         pre {{ background: #f6f6f6; padding: 0.5em; }}\n\
+         section {{ margin-bottom: 2em; }}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n<ul>"
+    )
+    .unwrap();
+    for v in grammar.rules() {
+        writeln!(out, "<li><a href=\"#{0}\">{0}</a></li>", v.name).unwrap();
+    }
+    out.push_str("</ul>\n");
+
+    let mut comments = vec![];
+    for item in &grammar.items {
+        let rule = match item {
+            Item::Comment(v) => {
+                comments.push(v.text.trim());
+                continue;
+            }
+            Item::Rule(v) => v,
+        };
+        writeln!(
+            out,
+            "<section id=\"{0}\">\n<h2><a href=\"{0}.svg\">{1}</a></h2>",
+            rule.name,
+            escape(&rule.head())
+        )
+        .unwrap();
+        if !comments.is_empty() {
+            writeln!(out, "<p>{}</p>", escape(&comments.join(" "))).unwrap();
+            comments.clear();
+        }
+        out.push_str(&diagram(grammar, rule, |name| format!("#{name}")));
+        writeln!(out, "<pre>{}</pre>", escape(&rule.to_string())).unwrap();
+        let users = used_by(grammar, &rule.name);
+        if !users.is_empty() {
+            let links = users
+                .iter()
+                .map(|v| format!("<a href=\"#{v}\">{v}</a>"))
+                .collect::<Vec<_>>();
+            writeln!(out, "<p>Used by: {}</p>", links.join(", ")).unwrap();
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Правила, в телах которых есть ссылка на `name`.
+fn used_by<'g>(grammar: &'g Grammar, name: &str) -> Vec<&'g str> {
+    grammar
+        .rules()
+        .filter(|rule| {
+            let mut found = false;
+            for expr in rule.body.exprs() {
+                expr.walk(&mut |v| {
+                    if let Expr::Ref(v) = v {
+                        found |= v.name == name && !rule.generics.contains(&v.name);
+                    }
+                });
+            }
+            found
+        })
+        .map(|v| v.name.as_str())
+        .collect()
+}
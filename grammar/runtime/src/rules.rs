@@ -0,0 +1,473 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use parser::{
+    logs::RuleName,
+    rules::{
+        AndPredicateRule, JoinableRule, LessThanMin, LookaheadMatched, MinMaxRepeatError,
+        MoreThanMax, NegativeLookaheadRule, OptionalRule, Repeat, RepeatRule, SCountRepeatRule,
+        SMax, SMin, SMinJoinableRule, SMinMax, VecChoiceRule, VecSequenceRule,
+    },
+    Cursorable, InputStream, ProductionError, Promotable, TransferRule,
+};
+use parsers::chars::{
+    rules::{RegExprError, SRegExprToken, SSubStrToken, TokenRuleTrait},
+    InputStreamIter,
+};
+use std::{
+    borrow::Cow,
+    cell::{Cell, OnceCell, RefCell},
+    fmt,
+    ops::Range,
+    rc::{Rc, Weak},
+};
+
+/// Узел дерева разбора рантайм-грамматики.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    /// Имя правила, варианта или поля; для анонимного литерала — сам литерал в кавычках.
+    pub rule: Rc<str>,
+    /// Байтовый диапазон во входной строке.
+    pub span: Range<usize>,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    #[inline]
+    pub fn text<'a>(&self, src: &'a str) -> &'a str {
+        &src[self.span.clone()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleError<'src> {
+    Token(Box<RegExprError<'src>>),
+    /// Подстрока токена `Name: Type` не разбирается в `Type`.
+    Parse {
+        type_: Rc<str>,
+        text: &'src str,
+    },
+    Choice(Vec<ProductionError<RuleError<'src>>>),
+    LessThanMin(usize),
+    MoreThanMax(usize),
+    CountMismatch(usize),
+    LookaheadMatched,
+}
+
+impl From<LessThanMin> for RuleError<'_> {
+    #[inline]
+    fn from(LessThanMin(v): LessThanMin) -> Self {
+        Self::LessThanMin(v)
+    }
+}
+
+impl From<MoreThanMax> for RuleError<'_> {
+    #[inline]
+    fn from(MoreThanMax(v): MoreThanMax) -> Self {
+        Self::MoreThanMax(v)
+    }
+}
+
+impl From<MinMaxRepeatError> for RuleError<'_> {
+    #[inline]
+    fn from(v: MinMaxRepeatError) -> Self {
+        match v {
+            MinMaxRepeatError::LessThanMin(v) => Self::LessThanMin(v),
+            MinMaxRepeatError::MoreThanMax(v) => Self::MoreThanMax(v),
+        }
+    }
+}
+
+impl From<LookaheadMatched> for RuleError<'_> {
+    #[inline]
+    fn from(_: LookaheadMatched) -> Self {
+        Self::LookaheadMatched
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Token {
+    Regex(SRegExprToken),
+    SubStr(SSubStrToken),
+}
+
+/// Маркер повторения, приведённый к правилам ядра; нулевые границы разрешены заранее.
+#[derive(Debug, Clone)]
+pub enum Marker {
+    Repeat(Repeat),
+    Min(SMin),
+    Max(SMax),
+    MinMax(SMinMax),
+    Count(SCountRepeatRule),
+    /// `{0}`, `{,0}`: ничего не разбирает.
+    Empty,
+}
+
+/// Правило, собранное из `.abs` в рантайме поверх правил ядра.
+#[derive(Debug, Clone)]
+pub enum DynRule {
+    /// Ссылка на определение; слоты живут в `RuntimeGrammar`.
+    Ref(Weak<RuleSlot>),
+    Seq(VecSequenceRule<DynRule>),
+    Choice(VecChoiceRule<DynRule>),
+    Repeat(Box<DynRule>, Marker),
+    Optional(Box<DynRule>),
+    Not(Box<DynRule>),
+    And(Box<DynRule>),
+    /// `rule ** join`, при `min > 0` — `rule **{min,} join`
+    Join {
+        rule: Box<DynRule>,
+        join: Box<DynRule>,
+        min: usize,
+    },
+    /// `name` — имя определения или сам литерал в кавычках; лист в дереве даёт только
+    /// анонимный литерал (`leaf`), `type_` — тип токена `Name: Type`.
+    Token {
+        token: Token,
+        name: Rc<str>,
+        leaf: bool,
+        type_: Option<Rc<str>>,
+    },
+    /// Вариант перечисления или именованное поле.
+    Named(Rc<str>, Box<DynRule>),
+    /// `#[ignore]` и безымянные поля: разбираются, но в дерево не попадают.
+    Ignored(Box<DynRule>),
+}
+
+/// Определение правила (или экземпляр дженерика), на которое ссылаются `DynRule::Ref`.
+#[derive(Debug)]
+pub struct RuleSlot {
+    pub(crate) name: Rc<str>,
+    pub(crate) rule: OnceCell<DynRule>,
+    /// `unit`-токен разбирается, но узла не даёт.
+    pub(crate) unit: bool,
+}
+
+impl RuleSlot {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+type Out<'src> = Result<Vec<Node>, ProductionError<RuleError<'src>>>;
+
+impl<'src> TransferRule<InputStreamIter<'src>> for RuleSlot {
+    type Output = Vec<Node>;
+    type Error = RuleError<'src>;
+
+    fn transfer(&self, input_stream: InputStream<InputStreamIter<'src>>) -> Out<'src> {
+        let start = *input_stream.cursor();
+        let children = self
+            .rule
+            .get()
+            .expect("слот заполняется при компиляции")
+            .transfer(input_stream)?;
+        if self.unit {
+            return Ok(vec![]);
+        }
+        Ok(vec![Node {
+            rule: self.name.clone(),
+            span: start..*input_stream.cursor(),
+            children,
+        }])
+    }
+}
+
+impl RuleName for RuleSlot {
+    #[inline]
+    fn rule_name(&self) -> Cow<'static, str> {
+        Cow::Owned(self.name.to_string())
+    }
+}
+
+impl fmt::Display for RuleSlot {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// `*`, `?` и `**` без минимума не ошибаются: неудача — пустой вывод. Наружу проходит
+/// только зацикливание повтора, иначе выбор перешёл бы к следующей альтернативе.
+fn or_empty<'src>(out: Result<Vec<Node>, ProductionError<()>>) -> Out<'src> {
+    match out {
+        Err(ProductionError::ZeroProgressLoop(e)) => Err(ProductionError::ZeroProgressLoop(e)),
+        out => Ok(out.unwrap_or_default()),
+    }
+}
+
+impl<'src> TransferRule<InputStreamIter<'src>> for DynRule {
+    type Output = Vec<Node>;
+    type Error = RuleError<'src>;
+
+    /// `VecSequenceRule`/`VecChoiceRule` вызывают `transfer` напрямую, поэтому курсор при
+    /// неудаче откатываем сами.
+    #[inline]
+    fn transfer(&self, input_stream: InputStream<InputStreamIter<'src>>) -> Out<'src> {
+        let start = *input_stream.cursor();
+        let out = self.transfer_(input_stream);
+        if out.is_err() {
+            *input_stream.cursor() = start;
+        }
+        out
+    }
+}
+
+impl DynRule {
+    fn transfer_<'src>(&self, input_stream: InputStream<InputStreamIter<'src>>) -> Out<'src> {
+        match self {
+            Self::Ref(slot) => input_stream.parse(&*upgrade(slot)),
+            Self::Seq(v) => v.transfer(input_stream).map(flatten),
+            Self::Choice(v) => v
+                .transfer(input_stream)
+                .map_err(|e| e.to(RuleError::Choice)),
+            Self::Repeat(rule, marker) => {
+                let rule = Operand::new(rule);
+                match marker {
+                    Marker::Repeat(marker) => or_empty(
+                        RepeatRule {
+                            rule,
+                            marker: marker.clone(),
+                        }
+                        .transfer(input_stream)
+                        .map(flatten),
+                    ),
+                    Marker::Min(marker) => RepeatRule {
+                        rule,
+                        marker: marker.clone(),
+                    }
+                    .transfer(input_stream)
+                    .map(flatten)
+                    .map_err(|e| e.to(Into::into)),
+                    Marker::Max(marker) => RepeatRule {
+                        rule,
+                        marker: marker.clone(),
+                    }
+                    .transfer(input_stream)
+                    .map(flatten)
+                    .map_err(|e| e.to(Into::into)),
+                    Marker::MinMax(marker) => RepeatRule {
+                        rule,
+                        marker: marker.clone(),
+                    }
+                    .transfer(input_stream)
+                    .map(flatten)
+                    .map_err(|e| e.to(Into::into)),
+                    Marker::Count(marker) => RepeatRule {
+                        rule,
+                        marker: marker.clone(),
+                    }
+                    .transfer(input_stream)
+                    .map(flatten)
+                    .map_err(|e| e.to(|_| RuleError::CountMismatch(marker.count))),
+                    Marker::Empty => Ok(vec![]),
+                }
+            }
+            Self::Optional(rule) => or_empty(
+                OptionalRule(Operand::new(rule))
+                    .transfer(input_stream)
+                    .map(Option::unwrap_or_default),
+            ),
+            Self::Not(rule) => {
+                NEGATED.with(|v| v.set(v.get() + 1));
+                let out = NegativeLookaheadRule(Operand::new(rule)).transfer(input_stream);
+                NEGATED.with(|v| v.set(v.get() - 1));
+                out.map(|_| vec![]).map_err(|e| e.to(Into::into))
+            }
+            Self::And(rule) => {
+                // `transfer` откатывает курсор только при неудаче
+                let start = *input_stream.cursor();
+                let out = AndPredicateRule(Operand::new(rule)).transfer(input_stream);
+                *input_stream.cursor() = start;
+                out.map(|_| vec![])
+            }
+            Self::Join { rule, join, min } => {
+                let join_rule = JoinableRule {
+                    rule: Operand::new(rule),
+                    join: Operand::new(join),
+                    repeat_rule: Repeat,
+                };
+                if *min == 0 {
+                    or_empty(join_rule.transfer(input_stream).map(flatten))
+                } else {
+                    SMinJoinableRule {
+                        min: *min,
+                        join_rule,
+                    }
+                    .transfer(input_stream)
+                    .map(flatten)
+                    .map_err(|e| e.to(Into::into))
+                }
+            }
+            Self::Token {
+                token,
+                name,
+                leaf,
+                type_,
+            } => {
+                let start = *input_stream.cursor();
+                let text = match token {
+                    Token::Regex(v) => TokenRuleTrait::transfer(v, input_stream),
+                    Token::SubStr(v) => TokenRuleTrait::transfer(v, input_stream),
+                }
+                .map_err(|e| e.to(|e| RuleError::Token(Box::new(e))))
+                .inspect_err(|_| expect(start, name))?;
+                if let Some(type_) = type_.as_ref().filter(|v| !parses_as(v, text)) {
+                    expect(start, name);
+                    return Err(ProductionError::Token(RuleError::Parse {
+                        type_: type_.clone(),
+                        text,
+                    }));
+                }
+                Ok(if *leaf {
+                    vec![Node {
+                        rule: name.clone(),
+                        span: start..*input_stream.cursor(),
+                        children: vec![],
+                    }]
+                } else {
+                    vec![]
+                })
+            }
+            Self::Named(name, rule) => {
+                let start = *input_stream.cursor();
+                let children = rule.transfer(input_stream)?;
+                Ok(vec![Node {
+                    rule: name.clone(),
+                    span: start..*input_stream.cursor(),
+                    children,
+                }])
+            }
+            Self::Ignored(rule) => rule.transfer(input_stream).map(|_| vec![]),
+        }
+    }
+}
+
+impl RuleName for DynRule {
+    fn rule_name(&self) -> Cow<'static, str> {
+        match self {
+            Self::Ref(slot) => upgrade(slot).rule_name(),
+            Self::Seq(..) => "Seq".into(),
+            Self::Choice(..) => "Choice".into(),
+            Self::Repeat(..) => "Repeat".into(),
+            Self::Optional(..) => "Optional".into(),
+            Self::Not(..) => "NegativeLookahead".into(),
+            Self::And(..) => "AndPredicate".into(),
+            Self::Join { .. } => "Joinable".into(),
+            Self::Token { name, .. } => name.to_string().into(),
+            Self::Named(name, ..) => name.to_string().into(),
+            Self::Ignored(..) => "Ignored".into(),
+        }
+    }
+}
+
+/// Операнд правила ядра. Ссылку разворачиваем до слота, чтобы `parse` ядра дал одно
+/// событие трассировки с именем правила, а не два.
+enum Operand<'r> {
+    Rule(&'r DynRule),
+    Slot(Rc<RuleSlot>),
+}
+
+impl<'r> Operand<'r> {
+    #[inline]
+    fn new(rule: &'r DynRule) -> Self {
+        match rule {
+            DynRule::Ref(slot) => Self::Slot(upgrade(slot)),
+            rule => Self::Rule(rule),
+        }
+    }
+}
+
+impl<'src> TransferRule<InputStreamIter<'src>> for Operand<'_> {
+    type Output = Vec<Node>;
+    type Error = RuleError<'src>;
+
+    #[inline]
+    fn transfer(&self, input_stream: InputStream<InputStreamIter<'src>>) -> Out<'src> {
+        match self {
+            Self::Rule(v) => v.transfer(input_stream),
+            Self::Slot(v) => v.transfer(input_stream),
+        }
+    }
+}
+
+impl RuleName for Operand<'_> {
+    #[inline]
+    fn rule_name(&self) -> Cow<'static, str> {
+        match self {
+            Self::Rule(v) => v.rule_name(),
+            Self::Slot(v) => v.rule_name(),
+        }
+    }
+}
+
+/// Самая дальняя позиция, на которой не сошёлся токен, и что на ней ожидалось.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub pos: usize,
+    /// Имена токенов без повторов, в порядке попыток.
+    pub tokens: Vec<Rc<str>>,
+}
+
+thread_local! {
+    static EXPECTED: RefCell<Expected> = RefCell::default();
+    /// Глубина вложенных `!Expr`: несошедшийся под отрицанием токен ничего не ожидает.
+    static NEGATED: Cell<usize> = const { Cell::new(0) };
+}
+
+fn expect(pos: usize, name: &Rc<str>) {
+    if NEGATED.with(Cell::get) > 0 {
+        return;
+    }
+    EXPECTED.with_borrow_mut(|v| {
+        if pos > v.pos {
+            *v = Expected {
+                pos,
+                tokens: vec![],
+            };
+        }
+        if pos == v.pos && !v.tokens.contains(name) {
+            v.tokens.push(name.clone());
+        }
+    })
+}
+
+/// Забирает накопленное с прошлого вызова.
+#[inline]
+pub(crate) fn take_expected() -> Expected {
+    EXPECTED.take()
+}
+
+#[inline]
+fn upgrade(slot: &Weak<RuleSlot>) -> Rc<RuleSlot> {
+    slot.upgrade()
+        .expect("правила нельзя использовать после удаления RuntimeGrammar")
+}
+
+#[inline]
+fn flatten(v: Vec<Vec<Node>>) -> Vec<Node> {
+    v.into_iter().flatten().collect()
+}
+
+/// Проверка `Name: Type` для типов из std; остальные типы в рантайме не проверить.
+pub(crate) fn parses_as(type_: &str, text: &str) -> bool {
+    macro_rules! check {
+        ($($t:ty)+) => {
+            match type_ {
+                $(stringify!($t) => text.parse::<$t>().is_ok(),)+
+                _ => true,
+            }
+        };
+    }
+    check!(u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize f32 f64 bool char)
+}
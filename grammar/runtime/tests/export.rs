@@ -0,0 +1,170 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use grammar_runtime::{
+    export::{export, Syntax},
+    CompileError, RuntimeGrammar,
+};
+
+const LIST: &str = include_str!("../../../cli/tests/data/list.abs");
+
+const PAIRS: &str = r#"
+// пары
+Doc = Pair<Key, Num> **{2,} Sep !Semi Tail<"[^;]">;
+Pair<K, V> = K "="s V;
+Tail<R> = R{,3} Semi;
+Sep = ", "s
+unit Semi = ";"s
+Key = "[a-z_]\w*"
+Num = "-?\d+(\.\d{1,2})?"
+ANY = "\t|\r\n"
+"#;
+
+fn run(src: &str, syntax: Syntax) -> Result<String, CompileError> {
+    export(&RuntimeGrammar::from_abs(src).unwrap(), syntax)
+}
+
+#[test]
+fn ebnf() {
+    let digit = r#""0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9""#;
+    assert_eq!(
+        run(LIST, Syntax::Ebnf).unwrap(),
+        format!(
+            r#"(* [a, 1, b] *)
+(* `Item ** ",\s*"`: EBNF has no separated repetition, it is expanded to an item followed by repeated separator and item *)
+List = "[", [ Item, {{ ",", {{ ? U+0009 ? | ? U+000A ? | ? U+000B ? | ? U+000C ? | ? U+000D ? | " " }}, Item }} ], "]" ;
+Item = Number | Ident ;
+
+(* `Number`: built-in rule of the grammar runtime *)
+Number = ({digit}), {{ {digit} }} ;
+(* `Ident`: built-in rule of the grammar runtime *)
+Ident = ({digit} | ? "A" .. "Z" ? | "_" | ? "a" .. "z" ?), {{ {digit} | ? "A" .. "Z" ? | "_" | ? "a" .. "z" ? }} ;
+"#
+        )
+    );
+
+    let pairs = run(PAIRS, Syntax::Ebnf).unwrap();
+    assert!(pairs.contains(
+        "(* `!Semi`: EBNF has no lookahead, it is a special sequence *)\n\
+         Doc = Pair_Key_Num, Sep, Pair_Key_Num, { Sep, Pair_Key_Num }, ? not followed by Semi ?, Tail_1 ;\n"
+    ));
+    assert!(pairs.contains("Tail_1 = 3 * [ ? any character ? - \";\" ], Semi ;\n"));
+    assert!(pairs.contains("ANY = ? U+0009 ? | ? U+000D ?, ? U+000A ? ;\n"));
+}
+
+#[test]
+fn abnf() {
+    assert_eq!(
+        run(LIST, Syntax::Abnf).unwrap(),
+        r#"; [a, 1, b]
+; `Item ** ",\s*"`: ABNF has no separated repetition, it is expanded to an item followed by repeated separator and item
+List = "[" [Item *("," *(%x09-0D / " ") Item)] "]"
+Item = Number / Ident
+
+; `Number`: built-in rule of the grammar runtime
+Number = 1*%x30-39
+; `Ident`: built-in rule of the grammar runtime
+Ident = 1*(%x30-39 / %x41-5A / "_" / %x61-7A)
+"#
+    );
+
+    assert_eq!(
+        run(PAIRS, Syntax::Abnf).unwrap(),
+        r#"; пары
+; `Pair<Key, Num> **{2,} Sep`: ABNF has no separated repetition, it is expanded to an item followed by repeated separator and item
+; `!Semi`: ABNF has no lookahead, it is a prose value
+Doc = Pair-Key-Num 1*(Sep Pair-Key-Num) <not followed by Semi> Tail-1
+; `Pair<K, V>` is generic: ABNF has no parameterized rules, its instances are expanded below
+; `Tail<R>` is generic: ABNF has no parameterized rules, its instances are expanded below
+Sep = ", "
+Semi = ";"
+Key = ("_" / %x61-7A) *(%x30-39 / %x41-5A / "_" / %x61-7A)
+Num = ["-"] 1*%x30-39 ["." 1*2%x30-39]
+ANY = %x09 / %x0D.0A
+
+; `Pair<Key, Num>`: instance of generic `Pair<K, V>`
+Pair-Key-Num = Key "=" Num
+; `Tail<"[^;]">`: instance of generic `Tail<R>`
+Tail-1 = *3(%x00-3A / %x3C-D7FF / %xE000-10FFFF) Semi
+"#
+    );
+}
+
+#[test]
+fn pest() {
+    assert_eq!(
+        run(PAIRS, Syntax::Pest).unwrap(),
+        r#"// пары
+// `Pair<Key, Num> **{2,} Sep`: pest has no separated repetition, it is expanded to an item followed by repeated separator and item
+Doc = { Pair_Key_Num ~ (Sep ~ Pair_Key_Num)+ ~ !Semi ~ Tail_1 }
+// `Pair<K, V>` is generic: pest has no parameterized rules, its instances are expanded below
+// `Tail<R>` is generic: pest has no parameterized rules, its instances are expanded below
+Sep = { ", " }
+Semi = { ";" }
+Key = { ("_" | 'a'..'z') ~ ('0'..'9' | 'A'..'Z' | "_" | 'a'..'z')* }
+Num = { "-"? ~ '0'..'9'+ ~ ("." ~ '0'..'9'{1,2})? }
+ANY_ = { "\t" | "\r\n" }
+
+// `Pair<Key, Num>`: instance of generic `Pair<K, V>`
+Pair_Key_Num = { Key ~ "=" ~ Num }
+// `Tail<"[^;]">`: instance of generic `Tail<R>`
+Tail_1 = { (!";" ~ ANY){,3} ~ Semi }
+"#
+    );
+}
+
+#[test]
+fn lookahead() {
+    let src = "Word = &Start !\"[0-9]\" Start;\nStart = \"[a-z]\"\n";
+    assert!(run(src, Syntax::Pest)
+        .unwrap()
+        .starts_with("Word = { &Start ~ !'0'..'9' ~ Start }\n"));
+    assert!(run(src, Syntax::Ebnf)
+        .unwrap()
+        .contains("Word = ? followed by Start ?, ? not followed by \"[0-9]\" ?, Start ;\n"));
+    assert!(run(src, Syntax::Abnf)
+        .unwrap()
+        .contains("Word = <followed by Start> <not followed by \"[0-9]\"> Start\n"));
+}
+
+#[test]
+fn untranslatable() {
+    let src = "Word = \"\\bx\"\n";
+    assert_eq!(
+        run(src, Syntax::Abnf).unwrap(),
+        "; regular expression \"\\bx\" has no ABNF equivalent\nWord = <regex \\bx>\n"
+    );
+    assert_eq!(
+        run(src, Syntax::Pest).unwrap(),
+        "// regular expression \"\\bx\" has no pest equivalent, `!ANY ~ ANY` is a placeholder \
+         that never matches\nWord = { !ANY ~ ANY }\n"
+    );
+    assert!(matches!(
+        run("A = B C;\n", Syntax::Ebnf),
+        Err(CompileError::Undefined { name, .. }) if name == "B"
+    ));
+    assert!(matches!(
+        run("A = \"a\" B<C>;\nB<X, Y> = X Y;\nC = \"c\"\n", Syntax::Ebnf),
+        Err(CompileError::Arity {
+            expected: 2,
+            found: 1,
+            ..
+        })
+    ));
+    assert!(matches!(
+        run(
+            "A = \"a\" B<C>;\nB<X> = X B<Twice<X>>;\nTwice<X> = X X;\nC = \"c\"\n",
+            Syntax::Pest
+        ),
+        Err(CompileError::TooDeep { .. })
+    ));
+}
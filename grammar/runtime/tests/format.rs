@@ -0,0 +1,105 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use grammar_runtime::{format, Grammar};
+
+/// Все `.abs` репозитория, которые разбирает `default_feature_rule`.
+const ABS: [&str; 5] = [
+    include_str!("../../feature/src/parsing/benches/cpcl/grammar.abs"),
+    include_str!("../../feature/tests/grammar.abs"),
+    include_str!("../../core/tree/tests/grammar.abs"),
+    include_str!("../src/prelude.abs"),
+    include_str!("../../../cli/tests/data/list.abs"),
+];
+
+#[test]
+fn canonical() {
+    let src = "
+// комментарий   
+Doc=Item**\",\"   & \";\"s Tail ;
+
+
+Item {
+\t// число
+  Num(Num)   Name(Name<\"[a-z]+\",Up>)
+}
+\tNum: usize=\"[0-9]+\" // только десятичные
+\t\tName<R,U> = R/U
+  \t  Tail ( #[ignore]  \";\"s Space? )
+Pair { key: Name<\"[a-z]\", Up>, v: Num }
+Long { first: Name<\"[a-z]+\", Up>, second_field: Name<\"[A-Z]+\", Up>, third_field: Name<\"[0-9]+\", Up> }
+Record {
+    key: Num,   // ключ   
+    longer_key: Num{ 1 , 3 },
+
+    Num,
+    other: Num ** Space,
+}
+Up = \"[A-Z]+\" / \"[A-Z]+[0-9]+\" / \"[A-Z]+_[A-Z]+\" / \"[A-Z]+-[A-Z]+\" / \"[A-Z]+:[A-Z]+\" / (\"[A-Z]+\" \"\\.[A-Z]+\")
+";
+    let formatted = format(src).unwrap();
+    assert_eq!(
+        formatted,
+        r#"// комментарий
+Doc = Item ** "," &";"s Tail;
+
+Item {
+    // число
+    Num(Num)
+    Name(Name<"[a-z]+", Up>)
+}
+    Num: usize = "[0-9]+" // только десятичные
+        Name<R, U> = R / U
+        Tail(#[ignore] ";"s Space?)
+Pair { key: Name<"[a-z]", Up>, v: Num }
+Long {
+    first:        Name<"[a-z]+", Up>,
+    second_field: Name<"[A-Z]+", Up>,
+    third_field:  Name<"[0-9]+", Up>,
+}
+Record {
+    key:        Num, // ключ
+    longer_key: Num{1,3},
+
+    Num,
+    other: Num ** Space,
+}
+Up = "[A-Z]+"
+    / "[A-Z]+[0-9]+"
+    / "[A-Z]+_[A-Z]+"
+    / "[A-Z]+-[A-Z]+"
+    / "[A-Z]+:[A-Z]+"
+    / ("[A-Z]+" "\.[A-Z]+")
+"#
+    );
+    assert_eq!(format(&formatted).unwrap(), formatted);
+}
+
+#[test]
+fn idempotent() {
+    for src in ABS {
+        let formatted = format(src).unwrap();
+        assert_eq!(format(&formatted).unwrap(), formatted);
+
+        let (before, after) = (
+            Grammar::parse(src).unwrap(),
+            Grammar::parse(&formatted).unwrap(),
+        );
+        assert_eq!(after.items.len(), before.items.len(), "{formatted}");
+        for (a, b) in after.rules().zip(before.rules()) {
+            assert_eq!(
+                (&a.name, &a.generics, &a.body),
+                (&b.name, &b.generics, &b.body)
+            );
+        }
+    }
+}
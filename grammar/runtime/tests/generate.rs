@@ -0,0 +1,111 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use grammar_runtime::{GenerateError, RuntimeGrammar};
+
+const CPCL: &str = include_str!("../../feature/src/parsing/benches/cpcl/grammar.abs");
+
+const EXPR: &str = r#"
+Expr {
+    Num(Num)
+    Paren(Paren)
+    Sum(Sum)
+}
+Paren ("("s Expr ")"s)
+Sum {
+    "\[",
+    items: Expr ** ","s,
+    "\]",
+}
+Num: u8 = "[0-9]{1,3}"
+"#;
+
+#[test]
+fn reproducible() {
+    let grammar = RuntimeGrammar::from_abs(EXPR).unwrap();
+    let run = |seed| {
+        let mut generator = grammar.generator(seed);
+        (0..20)
+            .map(|_| generator.sentence("Expr").unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(run(7), run(7));
+    assert_ne!(run(7), run(8));
+}
+
+#[test]
+fn valid() {
+    // в `LabelSession` соседние числа сливаются через необязательный `I`, поэтому команды
+    for (src, rule) in [(EXPR, "Expr"), (CPCL, "Command"), (CPCL, "CommandStart")] {
+        let grammar = RuntimeGrammar::from_abs(src).unwrap();
+        let mut generator = grammar.generator(1).max_depth(8);
+        for _ in 0..50 {
+            let v = generator.valid(rule).unwrap().expect(rule);
+            grammar.parse(rule, &v).unwrap();
+        }
+    }
+}
+
+#[test]
+fn depth() {
+    let grammar = RuntimeGrammar::from_abs(EXPR).unwrap();
+    let mut generator = grammar.generator(3).max_depth(4).max_repeat(2);
+    for _ in 0..200 {
+        let v = generator.sentence("Expr").unwrap();
+        // `Num` — u8, поэтому повтор токена проверяется типом
+        grammar.parse("Expr", &v).unwrap();
+        let mut depth = 0usize;
+        let mut max = 0;
+        for c in v.chars() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                _ => {}
+            }
+            max = max.max(depth);
+        }
+        // за пределом вложенность растёт, только пока не встретится кратчайший вариант
+        assert!(max <= 4, "{}", v);
+    }
+}
+
+#[test]
+fn near_miss() {
+    for (src, rule) in [(EXPR, "Expr"), (CPCL, "LabelSession")] {
+        let grammar = RuntimeGrammar::from_abs(src).unwrap();
+        let mut generator = grammar.generator(5);
+        for _ in 0..50 {
+            let v = generator.near_miss(rule).unwrap().expect(rule);
+            assert!(grammar.parse(rule, &v).is_err(), "{:?}", v);
+        }
+    }
+}
+
+#[test]
+fn errors() {
+    let grammar = RuntimeGrammar::from_abs(
+        r#"
+        Loop ("x"s Loop)
+        Fine = Loop?
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        grammar.generator(0).sentence("Loop"),
+        Err(GenerateError::Infinite("Loop".into()))
+    );
+    assert_eq!(grammar.generator(0).sentence("Fine").unwrap(), "");
+    assert!(matches!(
+        grammar.generator(0).sentence("Missing"),
+        Err(GenerateError::Compile(..))
+    ));
+}
@@ -0,0 +1,352 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use grammar_runtime::{
+    import::{abnf, grammar_macro, import, pest, ImportError, Source},
+    Grammar, Node, RuntimeGrammar,
+};
+
+const JSON: &str = include_str!("../../../cli/tests/data/json.pest");
+const HTTP: &str = include_str!("../../../cli/tests/data/http.abnf");
+
+/// `Node` в виде `Rule(child child)` с текстом листьев.
+fn sexpr(node: &Node, src: &str) -> String {
+    if node.children.is_empty() {
+        format!("{}={:?}", node.rule, node.text(src))
+    } else {
+        format!(
+            "{}({})",
+            node.rule,
+            node.children
+                .iter()
+                .map(|v| sexpr(v, src))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+/// Импортирует pest и проверяет, что текст `.abs` читается обратно в ту же грамматику.
+fn abs(src: &str) -> String {
+    checked(pest::import(src).unwrap())
+}
+
+fn checked(grammar: Grammar) -> String {
+    let text = grammar.to_string();
+    assert_eq!(Grammar::parse(&text).unwrap().to_string(), text);
+    text
+}
+
+#[test]
+fn json() {
+    let text = abs(JSON);
+    assert!(text.starts_with(
+        r#"//! A JSON grammar
+Whitespace = " "s / "\t" / "\r" / "\n"
+/// The whole document
+Json (
+    #[ignore] Space
+    Object / Array
+    #[ignore] Space
+    !"(?s)."
+)
+"#
+    ));
+    assert!(text.contains(
+        "\nPair (\n    String\n    #[ignore] Space\n    \":\"s\n    #[ignore] Space\n    Value\n)\n"
+    ));
+    assert!(text.contains("\nString = \"\\x22\" Inner \"\\x22\";\nInner = CharAtomic*\n"));
+    assert!(text.ends_with(
+        r#"// implicit WHITESPACE and COMMENT of pest between the elements of non-atomic rules
+Space = Whitespace*
+// rules called from atomic rules: pest skips no whitespace inside them
+CharAtomic = (!("\x22" / "\\") "(?s).") / ("\\" ("\x22" / "\\" / "/"s / "b"s / "f"s / "n"s / "r"s / "t"s)) / ("\\" "u"s "[0-9a-fA-F]"{4})
+"#
+    ));
+
+    let grammar = RuntimeGrammar::from_abs(&text).unwrap();
+    let input = " {\"a\" : [1, -2.5e3], \"b\\n\": null} ";
+    assert_eq!(
+        sexpr(&grammar.parse("Pair", "\"k\": 10").unwrap(), "\"k\": 10"),
+        r#"Pair(String("\x22"="\"" Inner(CharAtomic("(?s)."="k")) "\x22"="\"") ":"s=":" Value(Number="10"))"#
+    );
+    assert!(grammar.parse("Json", input).is_ok());
+    let input = "[1 , -2.5e3]";
+    assert_eq!(
+        sexpr(&grammar.parse("Array", input).unwrap(), input),
+        r#"Array("["s="[" Space="" Value(Number="1") Space(Whitespace(" "s=" ")) ","s="," Space(Whitespace(" "s=" ")) Value(Number="-2.5e3") Space="" "]"s="]")"#
+    );
+    // тихое `value` прячет в pest только свою пару, значения остаются в дереве
+    let input = r#"{"a": [1, true]}"#;
+    let tree = sexpr(&grammar.parse("Json", input).unwrap(), input);
+    assert!(tree.contains(r#"Value(Number="1")"#), "{}", tree);
+    assert!(tree.contains(r#"Value(Boolean("true"s="true"))"#), "{}", tree);
+    assert!(grammar.parse("Json", "[1, 2 3]").is_err());
+    assert!(grammar.parse("Json", "[1, 2,]").is_err());
+}
+
+#[test]
+fn repetitions() {
+    let text = abs(r#"
+WHITESPACE = _{ " " }
+list = { item* ~ item{2,3} ~ item{,2} ~ ("x"?)* ~ ("a" ~ "b"){2} ~ 'a'..'z'+ }
+item = { "i" }
+"#);
+    assert_eq!(
+        text,
+        r#"Whitespace = " "s
+List (
+    Item ** Space
+    #[ignore] Space
+    Item
+    (Space Item){1,2}
+    #[ignore] Space
+    (Item (Space Item)?)?
+    #[ignore] Space
+    ListPart1 ** Space
+    #[ignore] Space
+    ListPart2
+    Space
+    ListPart2
+    #[ignore] Space
+    "[a-z]"
+    (Space "[a-z]")*
+)
+ListPart1 = "x"s?
+ListPart2 = "a"s Space "b"s;
+Item = "i"s
+// implicit WHITESPACE and COMMENT of pest between the elements of non-atomic rules
+Space = Whitespace*
+"#
+    );
+
+    // без WHITESPACE и COMMENT пропуска нет, повторы переносятся как есть
+    assert_eq!(
+        abs("list = { \"a\"* ~ \"b\"{2,3} ~ \"c\"{,2} ~ \"d\"{1} ~ \"e\"{0,1} ~ (\"f\"+)? }\n"),
+        "List = \"a\"s* \"b\"s{2,3} \"c\"s{,2} \"d\"s \"e\"s? ListPart1?;\nListPart1 = \"f\"s+\n"
+    );
+}
+
+#[test]
+fn lookahead() {
+    let text = abs(r#"
+word = { &("a" | "b") ~ !"c" ~ ASCII_ALPHA+ ~ &EOI }
+"#);
+    assert_eq!(
+        text,
+        r#"Word = &("a"s / "b"s) !"c"s "[a-zA-Z]"+ &WordPart1;
+WordPart1 = !"(?s)."
+"#
+    );
+
+    let grammar = RuntimeGrammar::from_abs(&text).unwrap();
+    let input = "bad";
+    assert_eq!(
+        sexpr(&grammar.parse("Word", input).unwrap(), input),
+        r#"Word("[a-zA-Z]"="b" "[a-zA-Z]"="a" "[a-zA-Z]"="d")"#
+    );
+    assert!(grammar.parse("Word", "dab").is_err());
+}
+
+#[test]
+fn atomic() {
+    let text = abs(r##"
+WHITESPACE = _{ " " | "\n" }
+COMMENT = _{ "#" ~ (!"\n" ~ ANY)* }
+ident = @{ alpha ~ (alpha | digit)* }
+alpha = { 'a'..'z' | "_" }
+digit = { '0'..'9' }
+pair = ${ key ~ "=" ~ value }
+key = { ident ~ ("." ~ ident)* }
+value = !{ alpha ~ alpha }
+sum = { key ~ ("+" ~ key)* }
+"##);
+    assert!(text.contains(
+        "\nIdent (\n    #[ignore] Alpha\n    #[ignore] (Alpha / Digit)*\n)\nAlpha = \"[a-z]\" / \"_\"s\n"
+    ));
+    assert!(text.contains("\nPair = KeyAtomic \"=\"s Value;\n"));
+    assert!(text.contains("\nValue (\n    Alpha\n    #[ignore] Space\n    Alpha\n)\n"));
+    assert!(text.contains("\nSpace = (Whitespace / Comment)*\n"));
+    assert!(text.ends_with("KeyAtomic = Ident (\".\"s Ident)*;\n"));
+
+    let grammar = RuntimeGrammar::from_abs(&text).unwrap();
+    let input = "a.b # c\n + c";
+    assert_eq!(
+        sexpr(&grammar.parse("Sum", input).unwrap(), input),
+        r#"Sum(Key(Ident="a" "."s="." Space="" Ident="b") "+"s="+" Space(Whitespace(" "s=" ")) Key(Ident="c"))"#
+    );
+    assert!(grammar.parse("Pair", "a.b=x y").is_ok());
+    assert!(grammar.parse("Pair", "a. b=xy").is_err());
+}
+
+#[test]
+fn strings() {
+    assert_eq!(
+        abs(r#"
+s = { "plain" ~ "q\"\\" ~ "\t\x41\u{416}" ~ ^"kw" ~ ^"1+" ~ 'a'..'a' ~ '-'..'.' ~ "a.b" }
+"#),
+        r#"S = "plain"s "q\x22\\" "\tAЖ" "(?i)kw" "1+"s "a"s "[\--\.]" "a.b"s;
+"#
+    );
+    let grammar =
+        RuntimeGrammar::from_abs(&abs("s = { \"[\\\"\" ~ ^\"Ab\" ~ \"*\\n\" }\n")).unwrap();
+    assert!(grammar.parse("S", "[\"aB*\n").is_ok());
+    assert!(grammar.parse("S", "[\"aC*\n").is_err());
+}
+
+#[test]
+fn names() {
+    assert_eq!(
+        abs("my_rule = { \"a\" }\nmyRule = { my_rule }\nSPACE = { \"s\" }\n_x = { space }\nspace = { SPACE }\n"),
+        "MyRule = \"a\"s\nMyRule2 = MyRule\nSpace = \"s\"s\nX = Space2\nSpace2 = Space\n"
+    );
+}
+
+#[test]
+fn errors() {
+    let error = |src: &str| pest::import(src).unwrap_err();
+    assert_eq!(
+        error("a = { PUSH(\"x\") ~ POP }"),
+        ImportError {
+            pos: 6,
+            message: "stack operation `PUSH` has no equivalent in .abs".into()
+        }
+    );
+    assert_eq!(error("a = { b }").message, "undefined rule `b`");
+    assert_eq!(error("a = { b }").pos, 6);
+    assert_eq!(error("a = { \"x }").message, "unterminated string");
+    assert_eq!(error("a = { \"x\" ").message, "expected `}`");
+    assert_eq!(error("a = { \"\\q\" }").message, "invalid escape sequence");
+    assert_eq!(
+        error("a = { \"x\" }\na = { \"y\" }").to_string(),
+        "rule `a` is defined twice at byte 12"
+    );
+}
+
+#[test]
+fn macro_invocation() {
+    let grammar = import("a = { \"#\" ~ b }\nb = { \"b\" }\n", Source::Pest).unwrap();
+    assert_eq!(
+        grammar_macro(&grammar.to_string()),
+        "grammar! {r##\"\nA = \"#\"s B;\nB = \"b\"s\n\"##}\n"
+    );
+    assert_eq!(grammar_macro("A = B\n"), "grammar! {r#\"\nA = B\n\"#}\n");
+}
+
+#[test]
+fn abnf_http() {
+    let grammar = abnf::import(HTTP).unwrap();
+    let text = checked(grammar.clone());
+    assert!(text.starts_with(
+        r#"// HTTP request line, RFC 9112
+RequestLine = Method Sp RequestTarget Sp HttpVersion Crlf;
+Method = Token
+RequestTarget = OriginForm
+OriginForm = AbsolutePath ("?"s Query)?;
+AbsolutePath = ("/"s Segment)+
+"#
+    ));
+    assert!(text.contains("\n// case-sensitive\nHttpName = \"HTTP\"s\n"));
+    assert!(text.contains(" / Digit / Alpha / \"[\\x{80}-\\x{FF}]\"\n"));
+    assert!(text.ends_with(
+        r#"// core rules of RFC 5234, appendix B.1
+Alpha = "[A-Za-z]"
+Crlf = "\r\n"
+Digit = "[0-9]"
+Hexdig = "[0-9A-Fa-f]"
+Sp = " "
+"#
+    ));
+
+    let grammar = RuntimeGrammar::new(grammar);
+    let input = "GET /a/b%20c?x=1 HTTP/1.1\r\n";
+    let tree = sexpr(&grammar.parse("RequestLine", input).unwrap(), input);
+    assert!(
+        tree.contains(r#"HttpVersion(HttpName="HTTP" "/"s="/" Digit="1" "."s="." Digit="1")"#),
+        "{}",
+        tree
+    );
+    assert!(grammar.parse("RequestLine", "GET /a http/1.1\r\n").is_err());
+    assert!(grammar
+        .parse("RequestLine", "GET /a%2 HTTP/1.1\r\n")
+        .is_err());
+}
+
+#[test]
+fn abnf_values() {
+    let text = checked(
+        abnf::import(
+            "upper = %x41-5A / %x20-21\n\
+             newline = %d13.10 / %b1010\n\
+             word = 3*5\"ab\" *3%i\"c\" 2\"1\" 1*2(\"x\" \"y\")\n\
+             Opt = [*upper] 0*1word\n\
+             op = \"<\" / \"<=\" / \">\"\n\
+             op =/ \">=\"\n\
+             OPT =/ \"!\"  ; the same rule\n",
+        )
+        .unwrap(),
+    );
+    assert_eq!(
+        text,
+        r#"Upper = "[A-Z]" / "[\x20-\x21]"
+Newline = "\r\n" / "\n"
+Word = "(?i)ab"{3,5} "(?i)c"{,3} "1"s{2} ("(?i)x" "(?i)y"){1,2};
+// the same rule
+Opt = (OptPart1? Word?) / "!"s
+OptPart1 = Upper*
+Op = "<="s / ">="s / "<"s / ">"s
+"#
+    );
+
+    let grammar =
+        RuntimeGrammar::from_abs(&checked(abnf::import("octets = 1*%x80-FF %x00\n").unwrap()))
+            .unwrap();
+    let input = abnf::latin1(&[0x80, 0xFF, 0x00]);
+    assert_eq!(grammar.parse("Octets", &input).unwrap().span, 0..5);
+    assert!(grammar
+        .parse("Octets", &abnf::latin1(&[0x7F, 0x00]))
+        .is_err());
+}
+
+#[test]
+fn abnf_indented() {
+    // правила с общим отступом, продолжение — с большим; пустая строка внутри правила
+    let text = checked(abnf::import("  a = b\n\n      / \"c\"\n  b = \"b\"\n").unwrap());
+    assert_eq!(text, "A = B / \"(?i)c\"\nB = \"(?i)b\"\n");
+}
+
+#[test]
+fn abnf_errors() {
+    let error = |src: &str| abnf::import(src).unwrap_err();
+    assert_eq!(
+        error("a = <some prose>\n"),
+        ImportError {
+            pos: 4,
+            message: "prose value `<some prose>` has no equivalent in .abs".into()
+        }
+    );
+    assert_eq!(error("a = b\n").message, "undefined rule `b`");
+    assert_eq!(error("a = b\n").pos, 4);
+    assert_eq!(
+        error("a = \"x\"\nA = \"y\"\n").to_string(),
+        "rule `A` is defined twice, use `=/` to add alternatives at byte 8"
+    );
+    assert_eq!(
+        error("a =/ \"x\"\n").message,
+        "`=/` adds to undefined rule `a`"
+    );
+    assert_eq!(error("a = \"x\n").message, "unterminated string");
+    assert_eq!(error("a = (\"x\"\n").message, "expected `)`");
+    assert_eq!(error("a : \"x\"\n").message, "expected `=` or `=/`");
+    assert_eq!(error("a = \"x\" )\n").message, "unexpected `)`");
+    assert_eq!(error("a = %q1\n").pos, 4);
+}
@@ -0,0 +1,102 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use grammar_runtime::{railroad::railroad, Grammar};
+
+/// Надписи элементов диаграммы в порядке вывода.
+fn texts(svg: &str) -> Vec<&str> {
+    svg.split("<text")
+        .skip(1)
+        .map(|v| &v[v.find('>').unwrap() + 1..v.find("</text>").unwrap()])
+        .collect()
+}
+
+#[test]
+fn files() {
+    let grammar = Grammar::parse(include_str!("../../../cli/tests/data/list.abs")).unwrap();
+    let files = railroad(&grammar, "list");
+    let names = files.iter().map(|(v, _)| v.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["List.svg", "Item.svg", "index.html"]);
+
+    let list = &files[0].1;
+    assert_eq!(texts(list), ["List", "[", "Item", r"/,\s*/", "]"]);
+    assert!(list.contains(r#"<a href="Item.svg">"#));
+    // `Number` и `Ident` определены вне грамматики
+    assert_eq!(
+        texts(&files[1].1),
+        ["Item", "Number", "Number", "Name", "Ident"]
+    );
+    assert!(!files[1].1.contains("<a "));
+
+    let index = &files[2].1;
+    assert!(index.contains("<title>list</title>"));
+    assert!(index.contains("<p>[a, 1, b]</p>"));
+    assert!(index.contains(r##"<a href="#Item"><rect"##));
+    assert!(index.contains(r##"<p>Used by: <a href="#List">List</a></p>"##));
+}
+
+#[test]
+fn repetitions() {
+    let grammar = Grammar::parse(
+        r#"
+Doc = X{2,4} X{3,} X{,5} X{2} X **{2,} "," X? !X &X;
+X = "x"
+"#,
+    )
+    .unwrap();
+    // подпись границ выводится после элемента, под петлёй
+    let doc = &railroad(&grammar, "")[0].1;
+    assert_eq!(
+        texts(doc),
+        [
+            "Doc",
+            "X",
+            "2 to 4 times",
+            "X",
+            "at least 3",
+            "X",
+            "at most 5",
+            "X",
+            "2 times",
+            "X",
+            ",",
+            "at least 2",
+            "X",
+            "not",
+            "X",
+            "and",
+            "X",
+        ]
+    );
+}
+
+#[test]
+fn repository_grammars() {
+    for src in [
+        include_str!("../../feature/src/parsing/benches/cpcl/grammar.abs"),
+        include_str!("../../feature/tests/grammar.abs"),
+        include_str!("../src/prelude.abs"),
+    ] {
+        let grammar = Grammar::parse(src).unwrap();
+        for (name, file) in railroad(&grammar, "grammar") {
+            assert!(!file.contains("NaN") && !file.contains("\"inf"), "{}", name);
+            assert_eq!(file.matches("<a ").count(), file.matches("</a>").count());
+            if name.ends_with(".svg") {
+                assert!(
+                    file.starts_with("<svg ") && file.ends_with("</svg>\n"),
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use grammar_runtime::{
+    ast::{Body, Expr, Item, Repetition, TokenKind},
+    CompileError, Grammar, Node, RuntimeGrammar,
+};
+
+const CPCL: &str = include_str!("../../feature/src/parsing/benches/cpcl/grammar.abs");
+
+/// `Node` в виде `Rule(child child)` с текстом листьев, чтобы сравнивать деревья строкой.
+fn sexpr(node: &Node, src: &str) -> String {
+    if node.children.is_empty() {
+        format!("{}={:?}", node.rule, node.text(src))
+    } else {
+        format!(
+            "{}({})",
+            node.rule,
+            node.children
+                .iter()
+                .map(|v| sexpr(v, src))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+fn parse(grammar: &str, rule: &str, input: &str) -> String {
+    parse_with(&RuntimeGrammar::from_abs(grammar).unwrap(), rule, input)
+}
+
+fn parse_with(grammar: &RuntimeGrammar, rule: &str, input: &str) -> String {
+    sexpr(&grammar.parse(rule, input).unwrap(), input)
+}
+
+#[test]
+fn load_feature_sample() {
+    let src = include_str!("../../feature/tests/grammar.abs");
+    let grammar = Grammar::parse(src).unwrap();
+
+    assert!(matches!(&grammar.items[0], Item::Comment(v) if v.text == " dsfsdf sdfs fsdf "));
+    let names = grammar.rules().map(|v| v.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            "Name",
+            "Name2",
+            "Name3",
+            "Str1",
+            "WithGeneric",
+            "Alias",
+            "Str2",
+            "Str3",
+            "Str4",
+            "Str5",
+            "RegExpr",
+            "Any",
+            "DoubleSlash",
+            "Tab",
+            "Ident",
+            "Space",
+            "NextLine",
+            "ParamPath",
+            "SignedInt20",
+            "Joinables"
+        ]
+    );
+}
+
+#[test]
+fn cpcl_checks() {
+    let grammar = RuntimeGrammar::from_abs(CPCL).unwrap();
+    assert_eq!(grammar.check(), Ok(()));
+}
+
+#[test]
+fn cpcl_commands() {
+    let grammar = RuntimeGrammar::from_abs(CPCL).unwrap();
+
+    assert_eq!(
+        parse_with(&grammar, "CommandStart", "! 0 200 200 210 1\r\n"),
+        r#"CommandStart(x_offset(IntLit="0") x_dpi(IntLit="200") y_dpi(IntLit="200") max_height(IntLit="210") quantity(IntLit="1"))"#
+    );
+    assert_eq!(
+        parse_with(&grammar, "Command", r#"TEXT 4 0 30 40 "Hello""#),
+        r#"Command(TextVariant(TextVariantCmd(variant(TextVariant(TEXT("TEXT"="TEXT"))) font(IntLit="4") size(IntLit="0") x(IntLit="30") y(IntLit="40") text(StrLit="\"Hello\""))))"#
+    );
+    // `IntLit: u32` проверяется разбором подстроки
+    assert!(grammar.parse("IntLit", "99999999999").is_err());
+}
+
+#[test]
+fn quantificators() {
+    let grammar = r#"
+        List = Spaced<Item> ** ","
+        Item = Ident ":"s Number;
+        Digits = "[0-9]"{2,3}
+        NotKeyword = !"let"s Ident;
+        Keyword = &"let|fn" Ident;
+        Signed = "-"? Number;
+        Args = Number **{2,} ","
+    "#;
+    assert_eq!(
+        parse(grammar, "List", "a:1, b:2"),
+        r#"List(Spaced<Item>(Space="" Item(Ident="a" ":"s=":" Number="1") Space="") Spaced<Item>(Space(StrictSpace=" ") Item(Ident="b" ":"s=":" Number="2") Space=""))"#
+    );
+    assert_eq!(
+        parse(grammar, "Digits", "123"),
+        r#"Digits("[0-9]"="1" "[0-9]"="2" "[0-9]"="3")"#
+    );
+    assert_eq!(
+        parse(grammar, "Signed", "-5"),
+        r#"Signed("-"="-" Number="5")"#
+    );
+    assert_eq!(parse(grammar, "Signed", "5"), r#"Signed(Number="5")"#);
+    // `&` ничего не потребляет и не даёт узла
+    assert_eq!(parse(grammar, "Keyword", "let"), r#"Keyword(Ident="let")"#);
+
+    let grammar = RuntimeGrammar::from_abs(grammar).unwrap();
+    assert!(grammar.parse("Digits", "1").is_err());
+    assert!(grammar.parse("Digits", "1234").is_err());
+    assert!(grammar.parse("NotKeyword", "let").is_err());
+    assert!(grammar.parse("NotKeyword", "lot").is_ok());
+    assert!(grammar.parse("Keyword", "lot").is_err());
+    assert!(grammar.parse("Args", "1").is_err());
+    assert!(grammar.parse("Args", "1,2").is_ok());
+}
+
+#[test]
+fn trees() {
+    let grammar = r#"
+        Expr {
+            Call(Call)
+            Var(Ident)
+        }
+        Call {
+            name: Ident,
+            args: Parened<Args>,
+        }
+        Args = <Expr> ** Spaced<Comma>
+        Pair (Ident #[ignore] "=" Number)
+    "#;
+    assert_eq!(
+        parse(grammar, "Expr", "f(x, g(y))"),
+        r#"Expr(Call(Call(name(Ident="f") args(Parened<Args>(Args(Expr(Var(Ident="x")) Expr(Call(Call(name(Ident="g") args(Parened<Args>(Args(Expr(Var(Ident="y"))))))))))))))"#
+    );
+    assert_eq!(
+        parse(grammar, "Pair", "a=1"),
+        r#"Pair(Ident="a" Number="1")"#
+    );
+}
+
+#[test]
+fn generics() {
+    let grammar = RuntimeGrammar::from_abs(
+        r#"
+        Tagged<T> = "<"s T ">"s;
+        Both = Tagged<Ident> Tagged<Number>;
+    "#,
+    )
+    .unwrap();
+    let input = "<a><1>";
+    assert_eq!(
+        parse_with(&grammar, "Both", input),
+        r#"Both(Tagged<Ident>("<"s="<" Ident="a" ">"s=">") Tagged<Number>("<"s="<" Number="1" ">"s=">"))"#
+    );
+    assert!(matches!(
+        grammar.rule("Tagged"),
+        Err(CompileError::Arity {
+            expected: 1,
+            found: 0,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn compile_errors() {
+    let src = "A = B C;\nB = \"b\"\nD = \"(\"";
+    let grammar = RuntimeGrammar::from_abs(src).unwrap();
+    let errors = grammar.check().unwrap_err();
+    assert_eq!(
+        errors[0],
+        CompileError::Undefined {
+            name: "C".into(),
+            span: 6..7
+        }
+    );
+    assert!(matches!(&errors[1..], [CompileError::InvalidRegex { .. }]));
+    assert_eq!(
+        RuntimeGrammar::from_abs("A = B\n%%").err().map(|v| v.pos),
+        Some(6)
+    );
+}
+
+#[test]
+fn display_round_trip() {
+    let src = r#"A<T> = (T / "x"s)+ !B ((C D) / E)? F **{2,} ",";
+B = C{2,3} D{,4} E{1,} G{5};"#;
+    let grammar = Grammar::parse(src).unwrap();
+    let exprs = grammar
+        .rules()
+        .map(|v| match &v.body {
+            Body::Expr(v) => v.to_string(),
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exprs,
+        [
+            r#"(T / "x"s)+ !B ((C D) / E)? F **{2,} ",""#,
+            "C{2,3} D{,4} E{1,} G{5}"
+        ]
+    );
+    assert_eq!(Grammar::parse(&exprs[0]).ok(), None);
+
+    let token = Grammar::parse("unit Kw = \"kw\"s").unwrap();
+    let def = token.rules().next().unwrap();
+    assert_eq!(def.span, 0..15);
+    assert_eq!(def.name_span, 5..7);
+    assert!(matches!(
+        &def.body,
+        Body::Token { kind: TokenKind::Unit, literal } if literal.sub_str
+    ));
+    assert!(matches!(
+        &grammar.rule("B").unwrap().body,
+        Body::Expr(Expr::Seq(v)) if matches!(v[0], Expr::Repeat(_, Repetition::MinMax(2, 3)))
+    ));
+}
+
+#[test]
+fn grammar_display() {
+    for src in [include_str!("../../feature/tests/grammar.abs"), CPCL] {
+        let grammar = Grammar::parse(src).unwrap();
+        let printed = grammar.to_string();
+        let reparsed = Grammar::parse(&printed).unwrap();
+        assert_eq!(reparsed.items.len(), grammar.items.len(), "{printed}");
+        for (a, b) in reparsed.rules().zip(grammar.rules()) {
+            assert_eq!(
+                (&a.name, &a.generics, &a.body),
+                (&b.name, &b.generics, &b.body)
+            );
+        }
+        assert_eq!(reparsed.to_string(), printed);
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let grammar = RuntimeGrammar::from_abs(
+        r#"List = "\[" Item ** "," "\]";
+Item = Number / Ident
+Number: usize = "[0-9]+"
+Ident = "[a-z]+""#,
+    )
+    .unwrap();
+    let error = grammar.rule("List").unwrap().parse("[a,1,]").unwrap_err();
+    assert_eq!(error.pos, 5);
+    assert_eq!(error.to_string(), "expected one of Number, Ident at byte 5");
+
+    let error = grammar.rule("Item").unwrap().parse("a1").unwrap_err();
+    assert_eq!((error.pos, error.expected.len()), (1, 0));
+    assert_eq!(error.to_string(), "expected end of input at byte 1");
+
+    let error = grammar.rule("List").unwrap().parse("[").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        r#"expected one of Number, Ident, "\]" at byte 1"#
+    );
+}
+
+#[test]
+fn unit_rule() {
+    // `unit`-правило не даёт узла, корнем разбора становится пустой узел
+    let grammar = RuntimeGrammar::from_abs("unit Kw = \"kw\"s\nStmt = Kw \"[a-z]+\";").unwrap();
+    let node = grammar.parse("Kw", "kw").unwrap();
+    assert_eq!(
+        (&*node.rule, node.span, node.children),
+        ("Kw", 0..2, vec![])
+    );
+    assert_eq!(parse_with(&grammar, "Stmt", "kwx"), r#"Stmt("[a-z]+"="x")"#);
+    let v = grammar.generator(1).valid("Kw").unwrap();
+    assert_eq!(v.as_deref(), Some("kw"));
+}
+
+#[test]
+fn zero_progress_loop() {
+    use parser::{
+        progress::{with_zero_progress, ZeroProgress, ZeroProgressLoop},
+        ProductionError,
+    };
+
+    let grammar = RuntimeGrammar::from_abs(
+        r#"Doc = Item* "b";
+Item = A?
+A = "a""#,
+    )
+    .unwrap();
+    assert_eq!(
+        parse_with(&grammar, "Doc", "aab"),
+        r#"Doc(Item(A="a") Item(A="a") Item="" "b"="b")"#
+    );
+
+    let error = with_zero_progress(ZeroProgress::Error, || {
+        grammar.rule("Doc").unwrap().parse("aab").unwrap_err()
+    });
+    assert_eq!(
+        error.inner.parse_result,
+        Err(ProductionError::ZeroProgressLoop(ZeroProgressLoop {
+            rule: "Item".into(),
+            pos: 2,
+        }))
+    );
+}
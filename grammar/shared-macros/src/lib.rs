@@ -97,6 +97,102 @@ fn raw_str_literal_test() {
 pub struct Ast<'src> {
     pub tokens: Vec<GenToken>,
     pub choices: Vec<(Ident, Vec<Output>, MaybeGenerics<'src>)>,
+    pub derives: Derives,
+}
+
+/// `#[derive(..)]` перед грамматикой: к всегда выводимым `Debug`, `Clone`, `PartialEq`
+/// выходных типов добавляет `Serialize`, `Deserialize` (фича `serde` у `abstract-parser`),
+/// посетителей `Visit`, `VisitMut`, `Fold` и печать обратно в текст `Unparse`. Остальные
+/// derive (`Hash`, `Eq`, свои) передаются выходным типам как есть.
+#[derive(Default, Clone)]
+pub struct Derives(pub Vec<Ident>);
+
+impl Derives {
+    const SERDE: [&str; 2] = ["Serialize", "Deserialize"];
+    const VISIT: [&str; 3] = ["Visit", "VisitMut", "Fold"];
+    const UNPARSE: &'static str = "Unparse";
+    const ALWAYS: [&str; 3] = ["Debug", "Clone", "PartialEq"];
+
+    /// Атрибуты для негенерических типов, например unit-токенов.
+    pub fn attrs(&self) -> TokenStream2 {
+        let v = self
+            .0
+            .iter()
+            .filter(|v| Self::SERDE.iter().any(|s| v == s))
+            .collect::<Vec<_>>();
+        let forwarded = self.forwarded().collect::<Vec<_>>();
+        let forwarded = (!forwarded.is_empty()).then(|| quote!(#[derive(#(#forwarded),*)]));
+        if v.is_empty() {
+            return quote!(#forwarded);
+        }
+        quote! {
+            #forwarded
+            #[derive(#(abstract_parser::serde::#v),*)]
+            #[serde(crate = "abstract_parser::serde")]
+        }
+    }
+
+    /// Derive без своей генерации кода, передаются типам как есть.
+    fn forwarded(&self) -> impl Iterator<Item = &Ident> {
+        self.0.iter().filter(|v| {
+            !(Self::SERDE.iter().chain(&Self::VISIT).any(|s| v == s) || *v == Self::UNPARSE)
+        })
+    }
+
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|v| v == name)
+    }
+
+    /// Поле `Unparse` для `sequence_struct` и `choice_rule`.
+    pub fn unparse(&self) -> TokenStream2 {
+        if self.contains(Self::UNPARSE) {
+            quote!(Unparse: true)
+        } else {
+            quote!()
+        }
+    }
+
+    /// Без посетителей: их обход для правил-деревьев генерирует сам `grammar!`.
+    pub fn without_visit(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|v| !Self::VISIT.iter().any(|s| v == s))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl syn::parse::Parse for Derives {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut derives = vec![];
+        for attr in syn::Attribute::parse_outer(input)? {
+            if !attr.path().is_ident("derive") {
+                return Err(syn::Error::new_spanned(attr, "expected #[derive(..)]"));
+            }
+            attr.parse_nested_meta(|meta| {
+                let ident = meta.path.require_ident()?;
+                if !Self::ALWAYS.iter().any(|v| ident == v) && !derives.contains(ident) {
+                    derives.push(ident.clone());
+                }
+                Ok(())
+            })?;
+        }
+        Ok(Self(derives))
+    }
+}
+
+/// Для вложенных макросов грамматики.
+impl ToTokens for Derives {
+    #[inline]
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        if !self.0.is_empty() {
+            let v = &self.0;
+            tokens.extend(quote!(#[derive(#(#v),*)]));
+        }
+    }
 }
 
 pub struct GenToken {
@@ -216,7 +312,7 @@ impl ToTokens for Ast<'_> {
                         .unwrap_or(Ident::new(&format!("V{i}"), Span::call_site()));
                     quote!(#ident(#item))
                 });
-                let attrs = choice_attrs(generics);
+                let attrs = choice_attrs(generics, &self.derives);
                 quote! {
                     #[abstract_parser::parsers::chars::macros::choice_rule(#attrs)]
                     pub enum #head {
@@ -252,17 +348,22 @@ pub fn ident_generics<'src>(
 }
 
 #[inline]
-pub fn choice_attrs<'src>(generics: &MaybeGenerics<'src>) -> TokenStream2 {
+pub fn choice_attrs<'src>(generics: &MaybeGenerics<'src>, derives: &Derives) -> TokenStream2 {
     if let Some(generics) = generics {
-        generics.choice_attrs()
+        generics.choice_attrs(derives)
     } else {
-        CHOICE_ATTR_FIELDS()
+        choice_attr_fields(derives)
     }
 }
 
-pub const CHOICE_ATTR_FIELDS: fn() -> TokenStream2 = || {
+pub const CHOICE_ATTR_FIELDS: fn() -> TokenStream2 = || choice_attr_fields(&Derives::default());
+
+fn choice_attr_fields(derives: &Derives) -> TokenStream2 {
+    let unparse = derives.unparse();
+    let derives = &derives.0;
     quote! {
         OutputAttrs: #[abstract_parser::macros::derive_bounds(
+            #(#derives)*
             Debug
                 <'src, IS: abstract_parser::parsers::chars::InputStreamTrait<'src>>
                 <'src, IS>
@@ -285,8 +386,9 @@ pub const CHOICE_ATTR_FIELDS: fn() -> TokenStream2 = || {
                 <'src, IS>
         )]
         OutputGenerics: <'src, __IS: abstract_parser::parsers::chars::InputStreamTrait<'src>>
+        #unparse
     }
-};
+}
 
 #[derive(Deref, Clone)]
 pub struct Generics<'src>(pub Vec<&'src str>);
@@ -297,11 +399,14 @@ impl<'src> Generics<'src> {
         self.0.into_iter().map(to_ident)
     }
 
-    pub fn choice_attrs(&self) -> TokenStream2 {
+    pub fn choice_attrs(&self, derives: &Derives) -> TokenStream2 {
         let generics = self;
         let g = generics.iter().map(|v| to_ident(v)).collect::<Vec<_>>();
+        let unparse = derives.unparse();
+        let derives = &derives.0;
         quote! {
             OutputAttrs: #[abstract_parser::macros::derive_bounds(
+                #(#derives)*
                 Debug
                     <'src, IS: abstract_parser::parsers::chars::InputStreamTrait<'src>, #(#g: abstract_parser::parsers::chars::TransferRule<'src, IS, Output: std::fmt::Debug>),*>
                     <'src, IS, #(#g),*>
@@ -324,6 +429,7 @@ impl<'src> Generics<'src> {
                     <'src, IS, #(#g),*>
             )]
             OutputGenerics: <'src, __IS: abstract_parser::parsers::chars::InputStreamTrait<'src>, #(#g: abstract_parser::parsers::chars::TransferRule<'src, __IS>),*>
+            #unparse
         }
     }
 }
@@ -338,6 +444,72 @@ impl ToTokens for Generics<'_> {
 
 pub const PATH: fn() -> TokenStream2 = || quote!(abstract_parser::rules::);
 
+/// Синоним `type Name = expr` не отдельный тип, своё имя в трассировке и зелёном дереве ему
+/// даёт метка `__Name` в `NamedRule`. Возвращает определение метки и обёрнутое выражение.
+pub fn named_rule(name: &str, expr: TokenStream2) -> (TokenStream2, TokenStream2) {
+    let path = PATH();
+    let label = to_ident(&format!("__{name}"));
+    let name = LitStr::new(name, Span::call_site());
+    (
+        quote! {
+            #[doc(hidden)]
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct #label;
+            impl abstract_parser::logs::RuleLabel for #label {
+                const NAME: &'static str = #name;
+            }
+        },
+        quote!(#path NamedRule<#label, #expr>),
+    )
+}
+
+/// Перечисление `kind` видов узлов зелёного дерева (`abstract_parser::green::GreenKind`) для
+/// `grammar!` и `tree!`: по варианту на имя из `names`, `Anonymous` для литералов внутри правил
+/// (`Token0`, ..), trivia — имена из `trivia`.
+pub fn green_kind(kind: &Ident, names: &[String], trivia: &[String]) -> TokenStream2 {
+    let variants = names.iter().map(|v| to_ident(v)).collect::<Vec<_>>();
+    let trivia = variants
+        .iter()
+        .zip(names)
+        .filter(|(_, v)| trivia.contains(v))
+        .map(|(v, _)| quote!(Self::#v))
+        .reduce(|a, b| quote!(#a | #b))
+        .map_or(quote!(false), |v| quote!(matches!(self, #v)));
+    quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum #kind {
+            #(#variants,)*
+            Anonymous,
+        }
+        impl abstract_parser::green::GreenKind for #kind {
+            fn from_rule(name: &str) -> Option<Self> {
+                match name {
+                    #(#names => Some(Self::#variants),)*
+                    // литералы внутри правил — токены `Token0`, `Token1`, ..
+                    v if v
+                        .strip_prefix("Token")
+                        .is_some_and(|v| !v.is_empty() && v.bytes().all(|v| v.is_ascii_digit())) =>
+                    {
+                        Some(Self::Anonymous)
+                    }
+                    _ => None,
+                }
+            }
+
+            fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variants => #names,)*
+                    Self::Anonymous => "Anonymous",
+                }
+            }
+
+            fn is_trivia(self) -> bool {
+                #trivia
+            }
+        }
+    }
+}
+
 // pub struct StrLiteralInputStream<'src> {
 //     iter: InputStreamIter<'src>,
 //     literal: Literal,
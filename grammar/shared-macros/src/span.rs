@@ -90,7 +90,7 @@ impl<'src> SynSpan<'src> {
     pub fn from_str_lit(src: &'src str, str_lit: LitStr) -> Self {
         Self {
             src,
-            span: SynSpanV::Span(str_lit.to_token_stream().to_string().parse().unwrap()),
+            span: SynSpanV::Span(str_lit.token()),
         }
     }
 
@@ -110,10 +110,10 @@ impl<'src> SynSpan<'src> {
                 let start = sub_str.as_ptr() as usize - self.src.as_ptr() as usize
                     + literal.to_string().find('"').unwrap()
                     + 1;
+                // у литерала из `grammar_from_file!` нет исходника, к которому привязать подспан
                 literal
                     .subspan(start..start + sub_str.len())
-                    .unwrap()
-                    .into()
+                    .unwrap_or_else(|| literal.span())
             }
             SynSpanV::CallSite => Span::call_site(),
         }
@@ -0,0 +1,697 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Язык `.abs` для [`serve`](crate::serve): файлы `.abs` и грамматики в сырых строках
+//! `grammar!`/`tree!` файлов `.rs`. Диагностики — ошибки разбора и находки
+//! [`lint`], остальное строится по [`Grammar`] из `grammar-runtime`.
+
+use crate::{Diagnostic, Document, Language, Severity, SymbolKind};
+use grammar_feature_parsing::{
+    default_feature_rule,
+    lint::{lint, prelude, LintKind},
+};
+use grammar_runtime::{ast::*, nesting};
+use parser::cached::CachedIter;
+use parsers::chars::{CharParser, InputStreamIter};
+use serde_json::{json, Map, Value};
+use std::{collections::HashSet, ops::Range};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Abs;
+
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    /// При ошибке разбора — грамматика без строк, которые не разобрались.
+    grammar: Grammar,
+    /// Уровни вложенности `grammar.items`.
+    nesting: Vec<usize>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Language for Abs {
+    type Analysis = Analysis;
+
+    fn name(&self) -> &str {
+        "abstract-parser"
+    }
+
+    fn capabilities(&self) -> Value {
+        json!({
+            "definitionProvider": true,
+            "referencesProvider": true,
+            "hoverProvider": true,
+            "renameProvider": {"prepareProvider": true},
+            "documentSymbolProvider": true,
+            "completionProvider": {"triggerCharacters": ["<"]},
+        })
+    }
+
+    fn analyze(&self, document: &Document) -> Analysis {
+        if document.is_rust() {
+            // блоки разных модулей — разные области видимости: находки по каждому отдельно
+            let blocks = embedded(&document.text);
+            let src = mask(&document.text, &blocks);
+            let grammar = Grammar::parse(&src).unwrap_or_default();
+            return Analysis {
+                nesting: nesting(&src, &grammar),
+                grammar,
+                diagnostics: blocks
+                    .iter()
+                    .flat_map(|v| lints(&mask(&document.text, std::slice::from_ref(v)), true))
+                    .collect(),
+            };
+        }
+        let src = document.text.clone();
+        let mut diagnostics = vec![];
+        let (src, grammar) = match Grammar::parse(&src) {
+            Ok(grammar) => {
+                diagnostics.extend(lints(&src, false));
+                (src, grammar)
+            }
+            Err(e) => {
+                let line = failed_line(&src, e.pos);
+                diagnostics.push(Diagnostic {
+                    range: if line.is_empty() { e.pos..e.pos } else { line },
+                    severity: Severity::Error,
+                    code: None,
+                    message: e.message,
+                });
+                recover(src, e.pos)
+            }
+        };
+        Analysis {
+            nesting: nesting(&src, &grammar),
+            grammar,
+            diagnostics,
+        }
+    }
+
+    fn diagnostics(&self, _: &Document, analysis: &Analysis) -> Vec<Diagnostic> {
+        analysis.diagnostics.clone()
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        params: &Value,
+        document: &Document,
+        analysis: &Analysis,
+    ) -> Option<Result<Value, String>> {
+        let at = document
+            .offset(&params["position"])
+            .and_then(|v| analysis.at(v));
+        let location = |span| json!({"uri": document.uri, "range": document.range(span)});
+        Some(match method {
+            "textDocument/definition" => Ok(at
+                .and_then(|v| analysis.declaration(v.symbol))
+                .map_or(Value::Null, location)),
+            "textDocument/references" => Ok(match at {
+                Some(at) => {
+                    let declaration = params["context"]["includeDeclaration"]
+                        .as_bool()
+                        .unwrap_or(true);
+                    analysis
+                        .occurrences()
+                        .into_iter()
+                        .filter(|v| v.symbol == at.symbol && (declaration || !v.declaration))
+                        .map(|v| location(v.span))
+                        .collect()
+                }
+                None => Value::Null,
+            }),
+            "textDocument/hover" => Ok(match at.and_then(|v| Some((analysis.hover(v.symbol)?, v))) {
+                Some((text, at)) => json!({
+                    "contents": {"kind": "markdown", "value": text},
+                    "range": document.range(at.span),
+                }),
+                None => Value::Null,
+            }),
+            "textDocument/prepareRename" => match at {
+                Some(at) => analysis.renamable(at.symbol).map(|name| {
+                    json!({"range": document.range(at.span), "placeholder": name})
+                }),
+                None => Ok(Value::Null),
+            },
+            "textDocument/rename" => match at {
+                Some(at) => analysis
+                    .rename(at.symbol, params["newName"].as_str().unwrap_or_default())
+                    .map(|spans| {
+                        let edits = spans
+                            .into_iter()
+                            .map(|(span, text)| json!({"range": document.range(span), "newText": text}))
+                            .collect();
+                        let mut changes = Map::new();
+                        changes.insert(document.uri.clone(), Value::Array(edits));
+                        json!({ "changes": changes })
+                    }),
+                None => Ok(Value::Null),
+            },
+            "textDocument/documentSymbol" => Ok(analysis
+                .symbols()
+                .iter()
+                .map(|v| v.to_json(document))
+                .collect()),
+            "textDocument/completion" => Ok(analysis
+                .completion(document.offset(&params["position"]))
+                .into()),
+            _ => return None,
+        })
+    }
+}
+
+/// Находки [`lint`] с позициями в `src`. В `.rs` правила могут ссылаться на Rust-типы
+/// модуля и все публичны, поэтому неопределённые и недостижимые правила там не ошибка,
+/// а число аргументов проверяется только у правил самой грамматики.
+fn lints(src: &str, is_rust: bool) -> Vec<Diagnostic> {
+    let Ok(features) =
+        CachedIter::new(InputStreamIter::new(src)).full_parse(&default_feature_rule())
+    else {
+        return vec![];
+    };
+    let grammar = if is_rust {
+        Grammar::parse(src).unwrap_or_default()
+    } else {
+        Grammar::default()
+    };
+    lint(&features)
+        .into_iter()
+        .filter(|v| match v.kind {
+            LintKind::Undefined | LintKind::Unreachable => !is_rust,
+            LintKind::Arity => !is_rust || grammar.rule(v.at).is_some(),
+            _ => true,
+        })
+        .map(|v| {
+            let start = v.at.as_ptr() as usize - src.as_ptr() as usize;
+            Diagnostic {
+                range: start..start + v.at.len(),
+                severity: if v.warning {
+                    Severity::Warning
+                } else {
+                    Severity::Error
+                },
+                code: Some(v.kind.name()),
+                message: v.message,
+            }
+        })
+        .collect()
+}
+
+/// Строка, на которой остановился разбор: от первого непробельного символа после `pos`
+/// до конца строки. Пустая, если дальше только пробелы.
+fn failed_line(src: &str, pos: usize) -> Range<usize> {
+    let start = src.len() - src[pos..].trim_start().len();
+    let end = src[start..].find('\n').map_or(src.len(), |v| start + v);
+    start..src[..end].trim_end().len().max(start)
+}
+
+/// Пока правило набирается, грамматика не разбирается целиком. Строки, на которых
+/// разбор останавливается, заменяются пробелами, пока остальное не разберётся: переходы,
+/// подсказки и дополнение работают по прочим правилам.
+fn recover(mut src: String, mut pos: usize) -> (String, Grammar) {
+    loop {
+        let line = failed_line(&src, pos);
+        if line.is_empty() {
+            return (src, Grammar::default());
+        }
+        let blank = " ".repeat(line.len());
+        src.replace_range(line, &blank);
+        match Grammar::parse(&src) {
+            Ok(grammar) => return (src, grammar),
+            Err(e) => pos = e.pos,
+        }
+    }
+}
+
+/// Сырые строки `grammar!` и `tree!` в `.rs`, которые разбирает `grammar-feature`.
+/// Строки диалектов `grammar::extended` и `grammar::core::tree` пропускаются: их
+/// проверяет свой макрос.
+fn embedded(text: &str) -> Vec<Range<usize>> {
+    let mut out = vec![];
+    let mut pos = 0;
+    while let Some(v) = next_grammar(text, pos) {
+        pos = v.end;
+        if Grammar::parse(&text[v.clone()]).is_ok() {
+            out.push(v);
+        }
+    }
+    out
+}
+
+/// `text`, в котором всё вне `blocks` заменено пробелами. Переводы строк и байтовые
+/// смещения сохраняются, поэтому позиции в грамматике — позиции в файле.
+fn mask(text: &str, blocks: &[Range<usize>]) -> String {
+    let mut out = text
+        .bytes()
+        .map(|v| if v == b'\n' { v } else { b' ' })
+        .collect::<Vec<_>>();
+    for v in blocks {
+        out[v.clone()].copy_from_slice(&text.as_bytes()[v.clone()]);
+    }
+    String::from_utf8(out).expect("grammar strings are cut at quotes")
+}
+
+#[inline]
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Содержимое сырой строки в первом после `from` вызове `grammar!` или `tree!`: перед
+/// строкой могут стоять только атрибуты (`#[derive(..)]`).
+fn next_grammar(text: &str, from: usize) -> Option<Range<usize>> {
+    let mut pos = from;
+    loop {
+        let (at, len) = ["grammar!", "tree!"]
+            .iter()
+            .filter_map(|v| Some((pos + text[pos..].find(v)?, v.len())))
+            .min()?;
+        pos = at + len;
+        if text[..at].chars().next_back().is_some_and(is_ident_char) {
+            continue;
+        }
+        let Some(mut rest) = text[pos..]
+            .trim_start()
+            .strip_prefix(['{', '(', '['])
+            .map(str::trim_start)
+        else {
+            continue;
+        };
+        while rest.starts_with("#[") {
+            let Some(end) = rest.find(']') else { break };
+            rest = rest[end + 1..].trim_start();
+        }
+        let Some(raw) = rest.strip_prefix('r') else {
+            continue;
+        };
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        if !raw[hashes..].starts_with('"') {
+            continue;
+        }
+        let start = text.len() - raw.len() + hashes + 1;
+        let end = start + text[start..].find(&format!("\"{}", "#".repeat(hashes)))?;
+        return Some(start..end);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol<'g> {
+    Rule(&'g str),
+    /// Номер правила в [`Grammar::rules`] и номер параметра.
+    Generic(usize, usize),
+}
+
+#[derive(Debug, Clone)]
+struct Occurrence<'g> {
+    span: Range<usize>,
+    symbol: Symbol<'g>,
+    /// Имя в заголовке определения, а не ссылка.
+    declaration: bool,
+}
+
+/// Элемент `textDocument/documentSymbol`; `range` захватывает вложенные определения.
+struct DocumentSymbol {
+    name: String,
+    detail: String,
+    kind: SymbolKind,
+    range: Range<usize>,
+    selection: Range<usize>,
+    children: Vec<DocumentSymbol>,
+}
+
+impl DocumentSymbol {
+    fn to_json(&self, document: &Document) -> Value {
+        json!({
+            "name": self.name,
+            "detail": self.detail,
+            "kind": self.kind as u8,
+            "range": document.range(self.range.clone()),
+            "selectionRange": document.range(self.selection.clone()),
+            "children": self.children.iter().map(|v| v.to_json(document)).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Вид символа и `CompletionItemKind` LSP для определения.
+fn kinds(def: &RuleDef) -> (SymbolKind, u8) {
+    match def.body {
+        Body::Enum(..) => (SymbolKind::Enum, 13),
+        Body::Struct(..) | Body::Tuple(..) => (SymbolKind::Struct, 22),
+        Body::Token { .. } => (SymbolKind::Constant, 21),
+        Body::Expr(..) => (SymbolKind::Function, 3),
+    }
+}
+
+/// Имя с параметрами дженерика: `Name<A, B>`.
+fn head(def: &RuleDef) -> String {
+    if def.generics.is_empty() {
+        def.name.clone()
+    } else {
+        format!("{}<{}>", def.name, def.generics.join(", "))
+    }
+}
+
+fn is_ident(v: &str) -> bool {
+    v.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && v.chars().all(is_ident_char)
+}
+
+impl Analysis {
+    fn occurrences(&self) -> Vec<Occurrence<'_>> {
+        let mut out = vec![];
+        for (i, def) in self.grammar.rules().enumerate() {
+            out.push(Occurrence {
+                span: def.name_span.clone(),
+                symbol: Symbol::Rule(&def.name),
+                declaration: true,
+            });
+            out.extend(
+                def.generic_spans
+                    .iter()
+                    .enumerate()
+                    .map(|(j, span)| Occurrence {
+                        span: span.clone(),
+                        symbol: Symbol::Generic(i, j),
+                        declaration: true,
+                    }),
+            );
+            for expr in def.body.exprs() {
+                expr.walk(&mut |v| {
+                    if let Expr::Ref(v) = v {
+                        out.push(Occurrence {
+                            span: v.span.clone(),
+                            symbol: match def.generics.iter().position(|g| *g == v.name) {
+                                Some(j) => Symbol::Generic(i, j),
+                                None => Symbol::Rule(&v.name),
+                            },
+                            declaration: false,
+                        })
+                    }
+                });
+            }
+        }
+        out
+    }
+
+    /// Имя под курсором; курсор сразу за именем тоже на нём.
+    fn at(&self, offset: usize) -> Option<Occurrence<'_>> {
+        self.occurrences()
+            .into_iter()
+            .find(|v| v.span.start <= offset && offset <= v.span.end)
+    }
+
+    fn declaration(&self, symbol: Symbol<'_>) -> Option<Range<usize>> {
+        self.occurrences()
+            .into_iter()
+            .find(|v| v.declaration && v.symbol == symbol)
+            .map(|v| v.span)
+    }
+
+    #[inline]
+    fn rule(&self, i: usize) -> &RuleDef {
+        self.grammar.rules().nth(i).expect("symbol of this grammar")
+    }
+
+    fn hover(&self, symbol: Symbol<'_>) -> Option<String> {
+        match symbol {
+            Symbol::Rule(name) => match self.grammar.rule(name) {
+                Some(def) => Some(format!(
+                    "```rust\n{}\n```",
+                    Types::new(&self.grammar).definition(def)
+                )),
+                None => prelude()
+                    .any(|v| v.0 == name)
+                    .then(|| format!("rule `{name}` from `grammar::core::parser`")),
+            },
+            Symbol::Generic(i, j) => {
+                let def = self.rule(i);
+                Some(format!(
+                    "```rust\n{}::Output\n```\ngeneric parameter of `{}`",
+                    def.generics[j],
+                    head(def)
+                ))
+            }
+        }
+    }
+
+    /// Текущее имя символа, если его можно переименовать.
+    fn renamable(&self, symbol: Symbol<'_>) -> Result<Value, String> {
+        match symbol {
+            Symbol::Rule(name) if self.grammar.rule(name).is_some() => Ok(json!(name)),
+            Symbol::Rule(name) => Err(format!("rule `{name}` is not defined in this document")),
+            Symbol::Generic(i, j) => Ok(json!(self.rule(i).generics[j])),
+        }
+    }
+
+    /// Правки переименования: позиции всех вхождений и новое имя.
+    fn rename(
+        &self,
+        symbol: Symbol<'_>,
+        name: &str,
+    ) -> Result<Vec<(Range<usize>, String)>, String> {
+        self.renamable(symbol)?;
+        if !is_ident(name) {
+            return Err(format!("`{name}` is not a valid identifier"));
+        }
+        let taken = match symbol {
+            Symbol::Rule(old) => {
+                old != name && (self.grammar.rule(name).is_some() || prelude().any(|v| v.0 == name))
+            }
+            Symbol::Generic(i, j) => self
+                .rule(i)
+                .generics
+                .iter()
+                .enumerate()
+                .any(|(k, v)| k != j && v == name),
+        };
+        if taken {
+            return Err(format!("`{name}` is already defined"));
+        }
+        Ok(self
+            .occurrences()
+            .into_iter()
+            .filter(|v| v.symbol == symbol)
+            .map(|v| (v.span, name.to_owned()))
+            .collect())
+    }
+
+    /// Определения с вложенными по отступам определениями.
+    fn symbols(&self) -> Vec<DocumentSymbol> {
+        fn close(stack: &mut Vec<(usize, DocumentSymbol)>, roots: &mut Vec<DocumentSymbol>) {
+            let (_, v) = stack.pop().unwrap();
+            match stack.last_mut() {
+                Some((_, parent)) => {
+                    parent.range.end = parent.range.end.max(v.range.end);
+                    parent.children.push(v);
+                }
+                None => roots.push(v),
+            }
+        }
+        let mut roots = vec![];
+        let mut stack = vec![];
+        for (item, &depth) in self.grammar.items.iter().zip(&self.nesting) {
+            let Item::Rule(def) = item else { continue };
+            while stack.last().is_some_and(|(v, _)| *v >= depth) {
+                close(&mut stack, &mut roots);
+            }
+            stack.push((
+                depth,
+                DocumentSymbol {
+                    name: def.name.clone(),
+                    detail: head(def),
+                    kind: kinds(def).0,
+                    range: def.span.clone(),
+                    selection: def.name_span.clone(),
+                    children: vec![],
+                },
+            ));
+        }
+        while !stack.is_empty() {
+            close(&mut stack, &mut roots);
+        }
+        roots
+    }
+
+    /// Правила грамматики, правила `grammar::core::parser` и параметры дженерика
+    /// определения, в котором стоит курсор.
+    fn completion(&self, offset: Option<usize>) -> Vec<Value> {
+        let mut seen = HashSet::new();
+        let mut out = vec![];
+        for def in self.grammar.rules() {
+            if seen.insert(def.name.as_str()) {
+                out.push(json!({"label": def.name, "kind": kinds(def).1, "detail": head(def)}));
+            }
+        }
+        for (name, _) in prelude() {
+            if seen.insert(name) {
+                out.push(json!({"label": name, "kind": 3, "detail": "grammar::core::parser"}));
+            }
+        }
+        let def = offset.and_then(|offset| {
+            self.grammar
+                .rules()
+                .find(|v| v.span.start <= offset && offset <= v.span.end)
+        });
+        for name in def.iter().flat_map(|v| &v.generics) {
+            out.push(json!({"label": name, "kind": 25, "detail": "generic parameter"}));
+        }
+        out
+    }
+}
+
+/// Rust-типы вывода правил, которые сгенерирует `grammar!`. Ссылки на правила-выражения
+/// раскрываются; рекурсивные, внешние и с неверным числом аргументов остаются проекцией
+/// `<Name<'src> as TransferRule<IS>>::Output`.
+struct Types<'g> {
+    grammar: &'g Grammar,
+    /// Раскрываемые сейчас правила.
+    stack: Vec<&'g str>,
+}
+
+impl<'g> Types<'g> {
+    fn new(grammar: &'g Grammar) -> Self {
+        Self {
+            grammar,
+            stack: vec![],
+        }
+    }
+
+    /// Объявление вывода: тип дерева целиком или тип вывода остальных правил.
+    fn definition(&mut self, def: &'g RuleDef) -> String {
+        let params = def
+            .generics
+            .iter()
+            .map(|v| format!(", {v}"))
+            .collect::<String>();
+        let output = format!("{}Output<'src, IS{params}>", def.name);
+        let generics = &def.generics;
+        self.stack.push(&def.name);
+        let mut out = String::new();
+        match &def.body {
+            Body::Enum(v) => {
+                out += &format!("pub enum {output} {{\n");
+                for v in v {
+                    out += &format!("    {}({}),\n", v.name, self.expr(&v.expr, generics));
+                }
+                out += "}";
+            }
+            Body::Struct(v) => {
+                out += &format!("pub struct {output} {{\n");
+                for v in v {
+                    if let Some(name) = &v.name {
+                        out += &format!("    pub {name}: {},\n", self.expr(&v.expr, generics));
+                    }
+                }
+                out += "}";
+            }
+            Body::Tuple(v) => {
+                let mut fields = vec![];
+                for v in v.iter().filter(|v| !v.ignored) {
+                    fields.push(format!("pub {}", self.expr(&v.expr, generics)));
+                }
+                out = format!("pub struct {output}({});", fields.join(", "));
+            }
+            Body::Token { .. } | Body::Expr(..) => {
+                out = format!(
+                    "<{}<'src{params}> as TransferRule<IS>>::Output = {}",
+                    def.name,
+                    self.body(def, generics)
+                );
+            }
+        }
+        self.stack.pop();
+        out
+    }
+
+    /// Тип вывода правила, которое не дерево.
+    fn body(&mut self, def: &RuleDef, generics: &[String]) -> String {
+        match &def.body {
+            Body::Token {
+                kind: TokenKind::Base,
+                ..
+            } => "&'src str".into(),
+            Body::Token {
+                kind: TokenKind::Unit,
+                ..
+            } => format!("{}Token", def.name),
+            Body::Token {
+                kind: TokenKind::Parse(v),
+                ..
+            } => v.clone(),
+            Body::Expr(v) => self.expr(v, generics),
+            Body::Enum(..) | Body::Struct(..) | Body::Tuple(..) => {
+                format!("{}Output<'src, IS>", def.name)
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr, generics: &[String]) -> String {
+        match expr {
+            Expr::Ref(v) => {
+                let out = self.reference(v, generics);
+                if v.boxed {
+                    format!("Box<{out}>")
+                } else {
+                    out
+                }
+            }
+            Expr::Literal(..) => "&'src str".into(),
+            Expr::Seq(v) => format!("SeqOutput<({})>", self.list(v, generics)),
+            Expr::Choice(v) => format!("ChoiceOutput{}<{}>", v.len(), self.list(v, generics)),
+            Expr::Repeat(v, Repetition::Count(n)) => format!("[{}; {n}]", self.expr(v, generics)),
+            Expr::Repeat(v, _) | Expr::Joinable { expr: v, .. } => {
+                format!("Vec<{}>", self.expr(v, generics))
+            }
+            Expr::Optional(v) => format!("Option<{}>", self.expr(v, generics)),
+            Expr::NegativeLookahead(..) | Expr::AndPredicate(..) => "()".into(),
+        }
+    }
+
+    fn list(&mut self, v: &[Expr], generics: &[String]) -> String {
+        let mut out = vec![];
+        for v in v {
+            out.push(self.expr(v, generics));
+        }
+        out.join(", ")
+    }
+
+    fn reference(&mut self, v: &Ref, generics: &[String]) -> String {
+        if generics.contains(&v.name) {
+            return format!("{}::Output", v.name);
+        }
+        let args = v.args.iter().map(|v| format!(", {v}")).collect::<String>();
+        let def = match self.grammar.rule(&v.name) {
+            Some(def) if def.generics.len() == v.args.len() => def,
+            _ => return format!("<{}<'src{args}> as TransferRule<IS>>::Output", v.name),
+        };
+        match &def.body {
+            Body::Enum(..) | Body::Struct(..) | Body::Tuple(..) => {
+                format!("{}Output<'src, IS{args}>", def.name)
+            }
+            Body::Expr(expr) if !self.stack.contains(&def.name.as_str()) => {
+                let params = def
+                    .generics
+                    .iter()
+                    .map(String::as_str)
+                    .zip(&v.args)
+                    .collect::<Vec<_>>();
+                let expr = expr.substitute(&params);
+                self.stack.push(&def.name);
+                let out = self.expr(&expr, generics);
+                self.stack.pop();
+                out
+            }
+            Body::Expr(..) => format!("<{}<'src{args}> as TransferRule<IS>>::Output", v.name),
+            Body::Token { .. } => self.body(def, generics),
+        }
+    }
+}
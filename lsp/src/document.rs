@@ -0,0 +1,82 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use serde_json::{json, Value};
+use std::ops::Range;
+
+/// Открытый в редакторе документ. Позиции LSP — строка и столбец в UTF-16, внутри
+/// сервера — байтовые смещения в `text`.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub uri: String,
+    pub version: i64,
+    pub text: String,
+    /// Смещения начал строк.
+    lines: Vec<usize>,
+}
+
+impl Document {
+    pub fn new(uri: impl Into<String>, version: i64, text: impl Into<String>) -> Self {
+        let text = text.into();
+        let lines = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            uri: uri.into(),
+            version,
+            text,
+            lines,
+        }
+    }
+
+    /// Документ с расширением `.rs`: грамматики в нём — строки макросов.
+    #[inline]
+    pub fn is_rust(&self) -> bool {
+        self.uri.ends_with(".rs")
+    }
+
+    /// Строка и столбец в UTF-16.
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let line = self.lines.partition_point(|&v| v <= offset) - 1;
+        let start = self.lines[line];
+        (line, utf16_len(&self.text[start..offset]))
+    }
+
+    pub fn position(&self, offset: usize) -> Value {
+        let (line, character) = self.line_column(offset);
+        json!({"line": line, "character": character})
+    }
+
+    pub fn range(&self, range: Range<usize>) -> Value {
+        json!({"start": self.position(range.start), "end": self.position(range.end)})
+    }
+
+    /// Смещение позиции LSP; столбец за концом строки прижимается к её концу.
+    pub fn offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let mut character = position["character"].as_u64()? as usize;
+        let start = *self.lines.get(line)?;
+        let end = self.lines.get(line + 1).map_or(self.text.len(), |v| v - 1);
+        for (i, c) in self.text[start..end].char_indices() {
+            if character < c.len_utf16() {
+                return Some(start + i);
+            }
+            character -= c.len_utf16();
+        }
+        Some(end)
+    }
+}
+
+#[inline]
+pub(crate) fn utf16_len(v: &str) -> usize {
+    v.chars().map(char::len_utf16).sum()
+}
@@ -0,0 +1,506 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Языковой сервер для языка, заданного `grammar!`: по правилу верхнего уровня и
+//! небольшому [`Config`] — диагностики с восстановлением после ошибки, символы документа,
+//! сворачивание скобок, семантические токены и дополнение. Всё строится по
+//! событиям разбора ([`record`]) и зелёному дереву ([`build`]), поэтому типизированный
+//! вывод грамматики не нужен.
+//!
+//! ```ignore
+//! const ZPL: Config<zpl::SyntaxKind> = Config {
+//!     name: "zpl",
+//!     parse: |src| parse(&zpl::Grammar::default(), src),
+//!     symbols: &[(zpl::SyntaxKind::Command, SymbolKind::Function)],
+//!     tokens: &[(zpl::SyntaxKind::Comment, TokenType::Comment)],
+//!     folding: FOLDING,
+//! };
+//! serve(&ZPL, io::stdin().lock(), io::stdout().lock())
+//! ```
+
+use crate::{document::utf16_len, Diagnostic, Document, Language, Severity, SymbolKind};
+use parser::{
+    debug::{record, ParseEvent},
+    green::{build, GreenKind, GreenNode},
+    Cursorable, Promotable,
+};
+use parsers::chars::{iter::CharsIter, TransferRule};
+use serde_json::{json, Value};
+use std::ops::Range;
+
+/// Разбирает начало `src` правилом верхнего уровня: сколько байт разобрано, `None` —
+/// правило не совпало. Из неё собирается [`Config::parse`].
+pub fn parse<'src, Rule: TransferRule<'src, CharsIter<'src>>>(
+    rule: &Rule,
+    src: &'src str,
+) -> Option<usize> {
+    let mut input_stream = CharsIter::new(src);
+    input_stream.parse(rule).ok()?;
+    Some(*input_stream.cursor())
+}
+
+/// Тип семантического токена; номер варианта — индекс в [`TokenType::LEGEND`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenType {
+    Keyword,
+    Type,
+    Function,
+    Variable,
+    Parameter,
+    Property,
+    EnumMember,
+    String,
+    Number,
+    Comment,
+    Operator,
+}
+
+impl TokenType {
+    pub const LEGEND: [&'static str; 11] = [
+        "keyword",
+        "type",
+        "function",
+        "variable",
+        "parameter",
+        "property",
+        "enumMember",
+        "string",
+        "number",
+        "comment",
+        "operator",
+    ];
+}
+
+/// Открывающие скобки `Braced` и `Parened` из `grammar::core::parser`.
+pub const FOLDING: &[&str] = &["OpenBrace", "OpenParen"];
+
+/// Язык для [`serve`](crate::serve): `K` — `SyntaxKind` грамматики.
+#[derive(Debug, Clone, Copy)]
+pub struct Config<K: 'static> {
+    /// Имя в `serverInfo` и поле `source` диагностик.
+    pub name: &'static str,
+    /// Разбор правилом верхнего уровня, обычно `|src| parse(&Grammar::default(), src)`.
+    pub parse: fn(&str) -> Option<usize>,
+    /// Виды узлов, которые становятся символами документа. Имя символа — текст первого
+    /// листа узла, вложенные символы — дети.
+    pub symbols: &'static [(K, SymbolKind)],
+    /// Типы семантических токенов по видам листьев. Литералы правил (`Anonymous`) без
+    /// своего типа подсвечиваются как `keyword`, остальные листья — только из списка.
+    pub tokens: &'static [(K, TokenType)],
+    /// Открывающие токены: сворачивается правило, которое с них начинается, обычно
+    /// [`FOLDING`].
+    pub folding: &'static [&'static str],
+}
+
+/// Лист, который пробовал разбор: правило с видом, у которого нет детей с видом.
+#[derive(Debug, Clone)]
+struct Attempt<K> {
+    pos: usize,
+    /// Докуда дошло правило, в том числе неудачное.
+    end: usize,
+    ok: bool,
+    /// Ближайший предок с видом: родитель листа в зелёном дереве.
+    parent: Option<K>,
+    kind: K,
+}
+
+#[derive(Debug, Clone)]
+pub struct Analysis<K> {
+    /// Деревья всех разобранных участков документа.
+    roots: Vec<GreenNode<K>>,
+    attempts: Vec<Attempt<K>>,
+    folds: Vec<Range<usize>>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<K: GreenKind + PartialEq> Language for Config<K> {
+    type Analysis = Analysis<K>;
+
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn capabilities(&self) -> Value {
+        json!({
+            "documentSymbolProvider": true,
+            "foldingRangeProvider": true,
+            "semanticTokensProvider": {
+                "legend": {"tokenTypes": TokenType::LEGEND, "tokenModifiers": []},
+                "full": true,
+            },
+            "completionProvider": {},
+        })
+    }
+
+    /// После ошибки разбор продолжается правилом верхнего уровня со следующей строки.
+    /// Участок, который сразу же не разбирается, ошибкой не считается: это продолжение
+    /// предыдущей.
+    fn analyze(&self, document: &Document) -> Analysis<K> {
+        let src = document.text.as_str();
+        let mut analysis = Analysis {
+            roots: vec![],
+            attempts: vec![],
+            folds: vec![],
+            diagnostics: vec![],
+        };
+        let mut pos = 0;
+        loop {
+            let (parsed, events) = record(|| (self.parse)(&src[pos..]));
+            let events = events
+                .into_iter()
+                .map(|v| shift(v, pos))
+                .collect::<Vec<_>>();
+            analysis.roots.extend(build::<K>(&events));
+            let attempts = attempts::<K>(&events);
+            analysis.folds.extend(folds(&events, self.folding));
+            let end = pos + parsed.unwrap_or(0);
+            if parsed.is_some() && end == src.len() {
+                analysis.attempts.extend(attempts);
+                break;
+            }
+            // ошибка там, докуда дошло самое дальнее правило
+            let at = events
+                .iter()
+                .filter_map(|v| match v {
+                    ParseEvent::Exit { end, .. } => Some(*end),
+                    _ => None,
+                })
+                .fold(end, usize::max);
+            if pos == 0 || pos < at {
+                analysis.diagnostics.push(error(src, at, &attempts));
+            }
+            analysis.attempts.extend(attempts);
+            match src[at..].find('\n') {
+                Some(v) => pos = at + v + 1,
+                None => break,
+            }
+        }
+        analysis
+    }
+
+    fn diagnostics(&self, _: &Document, analysis: &Analysis<K>) -> Vec<Diagnostic> {
+        analysis.diagnostics.clone()
+    }
+
+    fn request(
+        &self,
+        method: &str,
+        params: &Value,
+        document: &Document,
+        analysis: &Analysis<K>,
+    ) -> Option<Result<Value, String>> {
+        Some(Ok(match method {
+            "textDocument/documentSymbol" => self.symbols(document, &analysis.roots).into(),
+            "textDocument/foldingRange" => analysis
+                .folds
+                .iter()
+                .filter_map(|v| {
+                    let start = document.line_column(v.start).0;
+                    let end = document.line_column(v.end).0;
+                    (start < end).then(|| json!({"startLine": start, "endLine": end}))
+                })
+                .collect(),
+            "textDocument/semanticTokens/full" => {
+                json!({ "data": self.semantic_tokens(document, &analysis.roots) })
+            }
+            "textDocument/completion" => match document.offset(&params["position"]) {
+                Some(offset) => completion(document, analysis, offset).into(),
+                None => Value::Null,
+            },
+            _ => return None,
+        }))
+    }
+}
+
+impl<K: GreenKind + PartialEq> Config<K> {
+    fn symbols(&self, document: &Document, nodes: &[GreenNode<K>]) -> Vec<Value> {
+        let mut out = vec![];
+        for node in nodes {
+            let children = self.symbols(document, &node.children);
+            let Some(&(_, kind)) = self.symbols.iter().find(|v| v.0 == node.kind) else {
+                out.extend(children);
+                continue;
+            };
+            let leaf = node
+                .preorder()
+                .find(|v| v.children.is_empty() && !v.kind.is_trivia() && !v.span.is_empty())
+                .unwrap_or(node);
+            let name = leaf.text(&document.text).lines().next().unwrap_or_default();
+            out.push(json!({
+                "name": if name.trim().is_empty() { node.kind.name() } else { name.trim() },
+                "detail": node.kind.name(),
+                "kind": kind as u8,
+                "range": document.range(node.span.clone()),
+                "selectionRange": document.range(leaf.span.clone()),
+                "children": children,
+            }));
+        }
+        out
+    }
+
+    fn token_type(&self, kind: K) -> Option<TokenType> {
+        match self.tokens.iter().find(|v| v.0 == kind) {
+            Some(v) => Some(v.1),
+            None => (kind.name() == "Anonymous").then_some(TokenType::Keyword),
+        }
+    }
+
+    /// Листья с типом токена в относительной кодировке LSP: по пять чисел на токен,
+    /// многострочный лист режется по строкам.
+    fn semantic_tokens(&self, document: &Document, roots: &[GreenNode<K>]) -> Vec<usize> {
+        let mut leaves = roots
+            .iter()
+            .flat_map(GreenNode::preorder)
+            .filter(|v| v.children.is_empty() && !v.span.is_empty())
+            .filter_map(|v| Some((v.span.clone(), self.token_type(v.kind)?)))
+            .collect::<Vec<_>>();
+        leaves.sort_by_key(|v| v.0.start);
+        let mut out = vec![];
+        let (mut last, mut prev) = (0, (0, 0));
+        for (span, type_) in leaves {
+            // повторы из кэша могут перекрываться с уже выданным
+            if span.start < last {
+                continue;
+            }
+            last = span.end;
+            let mut start = span.start;
+            for line in document.text[span].split_inclusive('\n') {
+                let (row, column) = document.line_column(start);
+                let len = utf16_len(line.trim_end_matches(['\r', '\n']));
+                start += line.len();
+                if len == 0 {
+                    continue;
+                }
+                let delta = match row - prev.0 {
+                    0 => column - prev.1,
+                    _ => column,
+                };
+                out.extend([row - prev.0, delta, len, type_ as usize, 0]);
+                prev = (row, column);
+            }
+        }
+        out
+    }
+}
+
+fn shift(event: ParseEvent, by: usize) -> ParseEvent {
+    match event {
+        ParseEvent::Enter { rule, pos } => ParseEvent::Enter {
+            rule,
+            pos: pos + by,
+        },
+        ParseEvent::Exit {
+            rule,
+            start,
+            end,
+            ok,
+            consumed,
+        } => ParseEvent::Exit {
+            rule,
+            start: start + by,
+            end: end + by,
+            ok,
+            consumed,
+        },
+        ParseEvent::Cached { rule, pos, end, ok } => ParseEvent::Cached {
+            rule,
+            pos: pos + by,
+            end: end.map(|v| v + by),
+            ok,
+        },
+    }
+}
+
+fn attempts<K: GreenKind>(events: &[ParseEvent]) -> Vec<Attempt<K>> {
+    // вид правила и были ли у него дети с видом
+    let mut stack = Vec::<(Option<K>, bool)>::new();
+    let mut out = vec![];
+    for event in events {
+        match event {
+            ParseEvent::Enter { rule, .. } => stack.push((K::from_rule(rule), false)),
+            ParseEvent::Exit { start, end, ok, .. } => {
+                let Some((kind, inner)) = stack.pop() else {
+                    continue;
+                };
+                match kind {
+                    Some(kind) if !inner => out.push(Attempt {
+                        pos: *start,
+                        end: *end,
+                        ok: *ok,
+                        parent: stack.iter().rev().find_map(|v| v.0),
+                        kind,
+                    }),
+                    // дети безымянного правила — дети ближайшего предка с видом
+                    None if !inner => continue,
+                    _ => {}
+                }
+                if let Some(parent) = stack.last_mut() {
+                    parent.1 = true;
+                }
+            }
+            ParseEvent::Cached { .. } => {}
+        }
+    }
+    out
+}
+
+/// Участки удачных правил, которые начинаются удачным токеном из `open`. Обёртки вроде
+/// `Braced` — синонимы `WrapRule`, поэтому узнаются по первому ребёнку, а не по имени.
+fn folds(events: &[ParseEvent], open: &[&str]) -> Vec<Range<usize>> {
+    // первый ребёнок каждого открытого правила: имя и удачен ли
+    let mut stack = Vec::<Option<(&str, bool)>>::new();
+    let mut out = Vec::<Range<usize>>::new();
+    for event in events {
+        let (rule, ok) = match event {
+            ParseEvent::Enter { .. } => {
+                stack.push(None);
+                continue;
+            }
+            ParseEvent::Exit {
+                rule,
+                start,
+                end,
+                consumed: ok,
+                ..
+            } => {
+                let first = stack.pop().flatten();
+                if *ok && first.is_some_and(|(v, ok)| ok && open.contains(&v)) {
+                    // повтор из кэша или вложенная обёртка того же участка
+                    if !out.contains(&(*start..*end)) {
+                        out.push(*start..*end);
+                    }
+                }
+                (rule, *ok)
+            }
+            ParseEvent::Cached { rule, ok, .. } => (rule, *ok),
+        };
+        if let Some(first @ None) = stack.last_mut() {
+            *first = Some((rule, ok));
+        }
+    }
+    out
+}
+
+/// Имя ожидаемого листа: у литерала — имя родителя.
+fn expected_name<K: GreenKind>(v: &Attempt<K>) -> Option<&'static str> {
+    match v.kind.name() {
+        "Anonymous" => v.parent.map(K::name),
+        name => Some(name),
+    }
+}
+
+/// Ошибка на `at` со списком листьев, которые там не разобрались.
+fn error<K: GreenKind>(src: &str, at: usize, attempts: &[Attempt<K>]) -> Diagnostic {
+    let mut expected = vec![];
+    for v in attempts.iter().filter(|v| v.pos == at && !v.ok) {
+        match expected_name(v) {
+            Some(name) if !v.kind.is_trivia() && !expected.contains(&name) => expected.push(name),
+            _ => {}
+        }
+    }
+    let end = src[at..].chars().next().map_or(at, |c| at + c.len_utf8());
+    Diagnostic {
+        range: at..end,
+        severity: Severity::Error,
+        code: None,
+        message: match &expected[..] {
+            [] => "unexpected input".into(),
+            v => format!(
+                "expected {}",
+                v.iter()
+                    .map(|v| format!("`{v}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        },
+    }
+}
+
+/// Слово перед курсором — лист, который разобрался до курсора, а если такого нет —
+/// от последней позиции, где разбор пробовал листья, не доходящие до курсора.
+/// Варианты — тексты листьев документа с теми же видом и родителем: литералы правила,
+/// уже встречавшиеся ключи, имена и т. п.
+fn completion<K: GreenKind + PartialEq>(
+    document: &Document,
+    analysis: &Analysis<K>,
+    offset: usize,
+) -> Vec<Value> {
+    let text = document.text.as_str();
+    let word = text[..offset]
+        .trim_end_matches(|c: char| !c.is_whitespace())
+        .len();
+    let pending = |v: &&Attempt<K>| !v.ok || offset <= v.end;
+    // лист, который кончается на курсоре, — недописанное слово
+    let typed = analysis
+        .attempts
+        .iter()
+        .filter(|v| v.ok && v.end == offset && word <= v.pos && v.pos < offset)
+        .map(|v| v.pos)
+        .max();
+    let Some(at) = typed.or_else(|| {
+        (word..=offset).rev().find(|&pos| {
+            text.is_char_boundary(pos)
+                && analysis
+                    .attempts
+                    .iter()
+                    .filter(pending)
+                    .any(|v| v.pos == pos)
+        })
+    }) else {
+        return vec![];
+    };
+    let expected = analysis
+        .attempts
+        .iter()
+        .filter(pending)
+        .filter(|v| v.pos == at && !v.kind.is_trivia())
+        .map(|v| (v.parent, v.kind))
+        .collect::<Vec<_>>();
+
+    fn leaves<'a, K: GreenKind>(
+        nodes: &'a [GreenNode<K>],
+        parent: Option<K>,
+        out: &mut Vec<(Option<K>, &'a GreenNode<K>)>,
+    ) {
+        for v in nodes {
+            match &v.children[..] {
+                [] => out.push((parent, v)),
+                children => leaves(children, Some(v.kind), out),
+            }
+        }
+    }
+    let mut nodes = vec![];
+    leaves(&analysis.roots, None, &mut nodes);
+
+    let mut out = vec![];
+    let mut seen = vec![];
+    for (parent, node) in nodes {
+        let label = node.text(text);
+        let is_expected = expected.contains(&(parent, node.kind));
+        if !is_expected || node.span.start == at || label.is_empty() || label.contains('\n') {
+            continue;
+        }
+        if seen.contains(&label) {
+            continue;
+        }
+        seen.push(label);
+        let anonymous = node.kind.name() == "Anonymous";
+        out.push(json!({
+            "label": label,
+            // Keyword и Variable
+            "kind": if anonymous { 14 } else { 6 },
+            "detail": if anonymous { parent.map_or("", K::name) } else { node.kind.name() },
+            "textEdit": {"range": document.range(at..offset), "newText": label},
+        }));
+    }
+    out
+}
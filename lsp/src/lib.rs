@@ -0,0 +1,234 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Языковой сервер через stdio: [`serve`] ведёт сессию LSP, открытые документы и
+//! публикацию диагностик, а всё, что зависит от языка, отдаёт [`Language`].
+
+pub mod abs;
+mod document;
+pub mod grammar;
+pub mod rpc;
+
+pub use document::Document;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    ops::Range,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error = 1,
+    Warning = 2,
+    Information = 3,
+    Hint = 4,
+}
+
+/// `SymbolKind` LSP, которые встречаются в грамматиках.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Module = 2,
+    Namespace = 3,
+    Class = 5,
+    Method = 6,
+    Property = 7,
+    Field = 8,
+    Enum = 10,
+    Function = 12,
+    Variable = 13,
+    Constant = 14,
+    String = 15,
+    Number = 16,
+    Key = 20,
+    EnumMember = 22,
+    Struct = 23,
+    Event = 24,
+    Operator = 25,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub range: Range<usize>,
+    pub severity: Severity,
+    /// Код находки, например `left-recursion`.
+    pub code: Option<&'static str>,
+    pub message: String,
+}
+
+/// Язык, который обслуживает [`serve`].
+pub trait Language {
+    /// Разбор документа, общий для диагностик и запросов; пересчитывается при каждом
+    /// изменении текста.
+    type Analysis;
+
+    /// Имя в `serverInfo` и поле `source` диагностик.
+    fn name(&self) -> &str;
+
+    /// `capabilities` ответа на `initialize`, кроме `textDocumentSync`.
+    fn capabilities(&self) -> Value;
+
+    fn analyze(&self, document: &Document) -> Self::Analysis;
+
+    fn diagnostics(&self, document: &Document, analysis: &Self::Analysis) -> Vec<Diagnostic>;
+
+    /// Запрос `textDocument/*` к открытому документу. `None` — метод не поддержан,
+    /// `Err` — запрос не выполнить (например, переименование в занятое имя).
+    fn request(
+        &self,
+        method: &str,
+        params: &Value,
+        document: &Document,
+        analysis: &Self::Analysis,
+    ) -> Option<Result<Value, String>>;
+}
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const REQUEST_FAILED: i64 = -32803;
+
+/// Обслуживает клиента до `exit` или конца входа. `true` — перед `exit` пришёл
+/// `shutdown`, то есть сервер завершается штатно.
+pub fn serve<L: Language>(
+    language: &L,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> io::Result<bool> {
+    let mut documents = HashMap::<String, (Document, L::Analysis)>::new();
+    let mut shutdown = false;
+    while let Some(body) = rpc::read_body(&mut input)? {
+        let message = match serde_json::from_slice::<Value>(&body) {
+            Ok(v) => v,
+            // id запроса не прочитать: по JSON-RPC ответ с `"id": null`
+            Err(e) => {
+                rpc::write(
+                    &mut output,
+                    &json!({
+                        "jsonrpc": "2.0",
+                        "id": null,
+                        "error": {"code": PARSE_ERROR, "message": e.to_string()},
+                    }),
+                )?;
+                continue;
+            }
+        };
+        let Some(method) = message["method"].as_str() else {
+            // ответ клиента на запрос сервера: сервер запросов не шлёт
+            continue;
+        };
+        let params = &message["params"];
+        let id = message.get("id");
+        let document = &params["textDocument"];
+        let result = match method {
+            "initialize" => {
+                let mut capabilities = language.capabilities();
+                capabilities["textDocumentSync"] = json!(1);
+                Ok(json!({
+                    "capabilities": capabilities,
+                    "serverInfo": {"name": language.name()},
+                }))
+            }
+            "shutdown" => {
+                shutdown = true;
+                Ok(Value::Null)
+            }
+            "exit" => return Ok(shutdown),
+            "textDocument/didOpen" | "textDocument/didChange" => {
+                let uri = document["uri"].as_str().unwrap_or_default();
+                let text = match method {
+                    "textDocument/didOpen" => document["text"].as_str(),
+                    // синхронизация полная: последнее изменение — весь текст
+                    _ => params["contentChanges"]
+                        .as_array()
+                        .and_then(|v| v.last())
+                        .and_then(|v| v["text"].as_str()),
+                };
+                if let Some(text) = text {
+                    let document =
+                        Document::new(uri, document["version"].as_i64().unwrap_or(0), text);
+                    let analysis = language.analyze(&document);
+                    let diagnostics = language.diagnostics(&document, &analysis);
+                    publish(language, &mut output, &document, &diagnostics)?;
+                    documents.insert(uri.to_owned(), (document, analysis));
+                }
+                continue;
+            }
+            "textDocument/didClose" => {
+                if let Some((document, _)) =
+                    documents.remove(document["uri"].as_str().unwrap_or_default())
+                {
+                    publish(language, &mut output, &document, &[])?;
+                }
+                continue;
+            }
+            _ if id.is_none() => continue,
+            _ => match document["uri"].as_str().map(|v| documents.get(v)) {
+                Some(Some((document, analysis))) => {
+                    match language.request(method, params, document, analysis) {
+                        Some(Ok(v)) => Ok(v),
+                        Some(Err(e)) => Err((REQUEST_FAILED, e)),
+                        None => Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+                    }
+                }
+                Some(None) => Err((INVALID_PARAMS, "document is not open".into())),
+                None => Err((METHOD_NOT_FOUND, format!("unknown method `{method}`"))),
+            },
+        };
+        let Some(id) = id else { continue };
+        let response = match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": code, "message": message},
+            }),
+        };
+        rpc::write(&mut output, &response)?;
+    }
+    Ok(false)
+}
+
+fn publish<L: Language>(
+    language: &L,
+    output: &mut impl Write,
+    document: &Document,
+    diagnostics: &[Diagnostic],
+) -> io::Result<()> {
+    let diagnostics = diagnostics
+        .iter()
+        .map(|v| {
+            let mut diagnostic = json!({
+                "range": document.range(v.range.clone()),
+                "severity": v.severity as i64,
+                "source": language.name(),
+                "message": v.message,
+            });
+            if let Some(code) = v.code {
+                diagnostic["code"] = json!(code);
+            }
+            diagnostic
+        })
+        .collect::<Vec<_>>();
+    rpc::write(
+        output,
+        &json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": {
+                "uri": document.uri,
+                "version": document.version,
+                "diagnostics": diagnostics,
+            },
+        }),
+    )
+}
@@ -0,0 +1,224 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(phantom_variance_markers, macro_metavar_expr_concat)]
+
+#[path = "../../benches/zpl/grammar.rs"]
+mod zpl;
+
+use abstract_parser::grammar::{core::parser::*, feature::grammar::grammar};
+use abstract_parser_lsp::{
+    grammar::{parse, Config, TokenType, FOLDING},
+    rpc, serve, SymbolKind,
+};
+use serde_json::{json, Value};
+use std::io::Cursor;
+
+grammar! {r#"
+Doc = Spaced<Entry ** Space>
+Entry {
+    Section(Section)
+    Pair(Pair)
+}
+Section {
+    name: Key,
+    Space,
+    body: Braced<Spaced<Pair ** Space>>
+}
+Pair = Key Space "="s Space Scalar;
+Scalar = Num / Str
+Num = "[0-9]+"
+Key = "[a-z_]+"
+Str = "\"[^\"]*\""
+"#}
+
+const CONF: Config<SyntaxKind> = Config {
+    name: "conf",
+    parse: |src| parse(&Doc::default(), src),
+    symbols: &[
+        (SyntaxKind::Section, SymbolKind::Namespace),
+        (SyntaxKind::Pair, SymbolKind::Property),
+    ],
+    tokens: &[
+        (SyntaxKind::Key, TokenType::Property),
+        (SyntaxKind::Str, TokenType::String),
+        (SyntaxKind::Num, TokenType::Number),
+    ],
+    folding: FOLDING,
+};
+
+const URI: &str = "file:///test.conf";
+
+/// Открывает `text` и выполняет запросы к нему: опубликованные диагностики и результаты.
+fn session<K: abstract_parser::green::GreenKind + PartialEq>(
+    config: &Config<K>,
+    text: &str,
+    requests: &[(&str, Value)],
+) -> (Value, Vec<Value>) {
+    let mut input = vec![];
+    let document = json!({"uri": URI, "languageId": "conf", "version": 1, "text": text});
+    let mut messages = vec![
+        json!({"jsonrpc": "2.0", "id": 0, "method": "initialize", "params": {}}),
+        json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {"textDocument": document}}),
+    ];
+    for (id, (method, params)) in requests.iter().enumerate() {
+        let mut params = params.clone();
+        params["textDocument"] = json!({ "uri": URI });
+        messages.push(json!({"jsonrpc": "2.0", "id": id + 1, "method": method, "params": params}));
+    }
+    messages.push(json!({"jsonrpc": "2.0", "id": requests.len() + 1, "method": "shutdown"}));
+    messages.push(json!({"jsonrpc": "2.0", "method": "exit"}));
+    for v in &messages {
+        rpc::write(&mut input, v).unwrap();
+    }
+    let mut output = vec![];
+    assert!(serve(config, Cursor::new(input), &mut output).unwrap());
+
+    let mut output = Cursor::new(output);
+    let mut diagnostics = Value::Null;
+    let mut results = vec![];
+    while let Some(v) = rpc::read(&mut output).unwrap() {
+        match v["method"].as_str() {
+            Some("textDocument/publishDiagnostics") => {
+                diagnostics = v["params"]["diagnostics"].clone()
+            }
+            _ if v["id"] != 0 => results.push(v["result"].clone()),
+            _ => {}
+        }
+    }
+    results.pop(); // shutdown
+    (diagnostics, results)
+}
+
+fn range(start: (usize, usize), end: (usize, usize)) -> Value {
+    json!({
+        "start": {"line": start.0, "character": start.1},
+        "end": {"line": end.0, "character": end.1},
+    })
+}
+
+const SRC: &str = "name = \"demo\"\nserver {\n    port = 80\n    host = \"x\"\n}\n";
+
+#[test]
+fn symbols_and_folding() {
+    let (diagnostics, results) = session(
+        &CONF,
+        SRC,
+        &[
+            ("textDocument/documentSymbol", json!({})),
+            ("textDocument/foldingRange", json!({})),
+        ],
+    );
+    assert_eq!(diagnostics, json!([]));
+
+    let symbols = &results[0];
+    let names = |v: &Value| {
+        v.as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["name"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(symbols), ["name", "server"]);
+    assert_eq!(names(&symbols[1]["children"]), ["port", "host"]);
+    assert_eq!(symbols[1]["kind"], SymbolKind::Namespace as u8);
+    assert_eq!(symbols[1]["range"], range((1, 0), (4, 1)));
+    assert_eq!(symbols[1]["selectionRange"], range((1, 0), (1, 6)));
+
+    assert_eq!(results[1], json!([{"startLine": 1, "endLine": 4}]));
+}
+
+#[test]
+fn semantic_tokens() {
+    let (_, results) = session(
+        &CONF,
+        "a = 1\nb = \"x\"",
+        &[("textDocument/semanticTokens/full", json!({}))],
+    );
+    let (key, string, number, keyword) = (
+        TokenType::Property as usize,
+        TokenType::String as usize,
+        TokenType::Number as usize,
+        TokenType::Keyword as usize,
+    );
+    assert_eq!(
+        results[0]["data"],
+        json!([
+            0, 0, 1, key, 0, 0, 2, 1, keyword, 0, 0, 2, 1, number, 0, 1, 0, 1, key, 0, 0, 2, 1,
+            keyword, 0, 0, 2, 3, string, 0,
+        ])
+    );
+}
+
+/// После ошибки разбор продолжается со следующей строки.
+#[test]
+fn recovery() {
+    let (diagnostics, results) = session(
+        &CONF,
+        "a = 1\nb = ?\nc = 2\n",
+        &[("textDocument/documentSymbol", json!({}))],
+    );
+    assert_eq!(
+        diagnostics,
+        json!([{
+            "range": range((1, 4), (1, 5)),
+            "severity": 1,
+            "source": "conf",
+            "message": "expected `Num`, `Str`",
+        }])
+    );
+    let names = results[0]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["name"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "c"]);
+}
+
+#[test]
+fn completion() {
+    let labels = |text: &str, line, character| {
+        let position = json!({"position": {"line": line, "character": character}});
+        let (_, results) = session(&CONF, text, &[("textDocument/completion", position)]);
+        results[0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["label"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>()
+    };
+    // ключи пар, уже встречавшиеся в документе
+    assert_eq!(
+        labels("mode = \"fast\"\nlevel = 1\nm", 2, 1),
+        ["mode", "level"]
+    );
+    // литерал правила
+    assert_eq!(labels("a = 1\nb ", 1, 2), ["="]);
+}
+
+/// Грамматика описаний команд ZPL: символы — команды.
+#[test]
+fn zpl() {
+    const ZPL: Config<zpl::SyntaxKind> = Config {
+        name: "zpl",
+        parse: |src| parse(&zpl::Grammar::default(), src),
+        symbols: &[(zpl::SyntaxKind::Command, SymbolKind::Function)],
+        tokens: &[],
+        folding: FOLDING,
+    };
+    let src = include_str!("../../benches/zpl/grammar.abs");
+    let (diagnostics, results) = session(&ZPL, src, &[("textDocument/documentSymbol", json!({}))]);
+    assert_eq!(diagnostics, json!([]));
+    let commands = src.lines().filter(|v| v.starts_with(['^', '~'])).count();
+    assert_eq!(results[0].as_array().unwrap().len(), commands);
+}
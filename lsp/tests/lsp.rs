@@ -0,0 +1,368 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use abstract_parser_lsp::rpc;
+use serde_json::{json, Value};
+use std::{
+    io::{BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+const URI: &str = "file:///grammar.abs";
+
+const GRAMMAR: &str = r#"// документ
+Doc = Item* End;
+Item { A(Pair<Ident>) B(Num) }
+    Pair<T> = T "="s T;
+Num: u32 = "[0-9]+"
+unit End = ";"s
+"#;
+
+/// Сессия с бинарником: запросы нумеруются по порядку, уведомления сервера копятся.
+struct Server {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    id: i64,
+    notifications: Vec<Value>,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_abstract-parser-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut server = Self {
+            stdin: child.stdin.take().unwrap(),
+            stdout: BufReader::new(child.stdout.take().unwrap()),
+            child,
+            id: 0,
+            notifications: vec![],
+        };
+        let capabilities = server.request("initialize", json!({"capabilities": {}}));
+        assert_eq!(capabilities["capabilities"]["textDocumentSync"], 1);
+        server.notify("initialized", json!({}));
+        server
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        let message = json!({"jsonrpc": "2.0", "method": method, "params": params});
+        rpc::write(&mut self.stdin, &message).unwrap();
+    }
+
+    fn response(&mut self, method: &str, params: Value) -> Value {
+        self.id += 1;
+        let message = json!({"jsonrpc": "2.0", "id": self.id, "method": method, "params": params});
+        rpc::write(&mut self.stdin, &message).unwrap();
+        loop {
+            let message = rpc::read(&mut self.stdout).unwrap().unwrap();
+            if message["id"] == self.id {
+                return message;
+            }
+            self.notifications.push(message);
+        }
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let response = self.response(method, params);
+        assert_eq!(response["error"], Value::Null, "{method}");
+        response["result"].clone()
+    }
+
+    /// Открывает документ и возвращает опубликованные диагностики.
+    fn open(&mut self, uri: &str, text: &str) -> Value {
+        let document = json!({"uri": uri, "languageId": "abs", "version": 1, "text": text});
+        self.notify("textDocument/didOpen", json!({ "textDocument": document }));
+        // запрос без ответа по существу: сервер отвечает по порядку, уведомление уже пришло
+        self.response("$/sync", json!({}));
+        let message = self.notifications.pop().unwrap();
+        assert_eq!(message["method"], "textDocument/publishDiagnostics");
+        assert_eq!(message["params"]["uri"], uri);
+        message["params"]["diagnostics"].clone()
+    }
+
+    /// Запрос к позиции `line:character` документа.
+    fn at(
+        &mut self,
+        method: &str,
+        uri: &str,
+        line: usize,
+        character: usize,
+        extra: Value,
+    ) -> Value {
+        let mut params = json!({
+            "textDocument": {"uri": uri},
+            "position": {"line": line, "character": character},
+        });
+        params
+            .as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        self.request(method, params)
+    }
+
+    fn exit(mut self) -> i32 {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        self.child.wait().unwrap().code().unwrap()
+    }
+}
+
+fn range(line: usize, start: usize, end: usize) -> Value {
+    json!({
+        "start": {"line": line, "character": start},
+        "end": {"line": line, "character": end},
+    })
+}
+
+/// Коды и диапазоны диагностик.
+fn codes(diagnostics: &Value) -> Vec<(Value, Value)> {
+    diagnostics
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| (v["code"].clone(), v["range"].clone()))
+        .collect()
+}
+
+#[test]
+fn diagnostics() {
+    let mut server = Server::start();
+    assert_eq!(server.open(URI, GRAMMAR), json!([]));
+
+    let diagnostics = server.open(
+        "file:///lints.abs",
+        "Doc = Expr Missing;\nExpr = (Expr \"+\"s Num) / Num\nNum = \"[0-9]+\"\n",
+    );
+    assert_eq!(
+        codes(&diagnostics),
+        [
+            (json!("undefined-rule"), range(0, 11, 18)),
+            (json!("left-recursion"), range(1, 0, 4)),
+        ]
+    );
+
+    // строка, которая не разбирается, подсвечивается целиком
+    let diagnostics = server.open("file:///broken.abs", "Doc = Num\nNum = \"[0-9]+\n");
+    assert_eq!(codes(&diagnostics), [(Value::Null, range(1, 0, 13))]);
+    // остальные правила доступны
+    let symbols = server.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": "file:///broken.abs"}}),
+    );
+    assert_eq!(symbols[0]["name"], "Doc");
+    assert_eq!(symbols.as_array().unwrap().len(), 1);
+    assert_eq!(server.exit(), 0);
+}
+
+#[test]
+fn navigation() {
+    let mut server = Server::start();
+    server.open(URI, GRAMMAR);
+
+    // `Pair` в варианте `A` ведёт к определению
+    let definition = server.at("textDocument/definition", URI, 2, 10, json!({}));
+    assert_eq!(definition, json!({"uri": URI, "range": range(3, 4, 8)}));
+
+    // параметр дженерика: объявление и обе ссылки
+    let references = server.at(
+        "textDocument/references",
+        URI,
+        3,
+        14,
+        json!({"context": {"includeDeclaration": true}}),
+    );
+    let ranges = references
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| v["range"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ranges,
+        [range(3, 9, 10), range(3, 14, 15), range(3, 21, 22)]
+    );
+
+    // правило `grammar::core::parser` не определено в документе
+    assert_eq!(
+        server.at("textDocument/definition", URI, 2, 15, json!({})),
+        Value::Null
+    );
+    assert_eq!(server.exit(), 0);
+}
+
+#[test]
+fn hover() {
+    let mut server = Server::start();
+    server.open(URI, GRAMMAR);
+    let mut hover = |line, character| {
+        server.at("textDocument/hover", URI, line, character, json!({}))["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .to_owned()
+    };
+
+    assert_eq!(
+        hover(1, 0),
+        "```rust\n<Doc<'src> as TransferRule<IS>>::Output = \
+         SeqOutput<(Vec<ItemOutput<'src, IS>>, EndToken)>\n```"
+    );
+    assert_eq!(
+        hover(2, 0),
+        "```rust\npub enum ItemOutput<'src, IS> {\n    \
+         A(SeqOutput<(<Ident<'src> as TransferRule<IS>>::Output, &'src str, \
+         <Ident<'src> as TransferRule<IS>>::Output)>),\n    B(u32),\n}\n```"
+    );
+    assert_eq!(
+        hover(3, 9),
+        "```rust\nT::Output\n```\ngeneric parameter of `Pair<T>`"
+    );
+    assert_eq!(hover(2, 15), "rule `Ident` from `grammar::core::parser`");
+    server.exit();
+}
+
+#[test]
+fn rename() {
+    let mut server = Server::start();
+    server.open(URI, GRAMMAR);
+
+    let prepare = server.at("textDocument/prepareRename", URI, 4, 1, json!({}));
+    assert_eq!(
+        prepare,
+        json!({"range": range(4, 0, 3), "placeholder": "Num"})
+    );
+
+    let edit = server.at(
+        "textDocument/rename",
+        URI,
+        4,
+        1,
+        json!({"newName": "Number_"}),
+    );
+    assert_eq!(
+        edit,
+        json!({"changes": {URI: [
+            {"range": range(2, 24, 27), "newText": "Number_"},
+            {"range": range(4, 0, 3), "newText": "Number_"},
+        ]}})
+    );
+
+    let taken = server.response(
+        "textDocument/rename",
+        json!({
+            "textDocument": {"uri": URI},
+            "position": {"line": 4, "character": 1},
+            "newName": "Item",
+        }),
+    );
+    assert_eq!(taken["error"]["message"], "`Item` is already defined");
+    server.exit();
+}
+
+#[test]
+fn symbols_and_completion() {
+    let mut server = Server::start();
+    server.open(URI, GRAMMAR);
+
+    let symbols = server.request(
+        "textDocument/documentSymbol",
+        json!({"textDocument": {"uri": URI}}),
+    );
+    let outline = |v: &Value| {
+        v.as_array()
+            .unwrap()
+            .iter()
+            .map(|v| {
+                (
+                    v["name"].clone(),
+                    v["kind"].clone(),
+                    v["children"].as_array().unwrap().len(),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        outline(&symbols),
+        [
+            (json!("Doc"), json!(12), 0),
+            (json!("Item"), json!(10), 1),
+            (json!("Num"), json!(14), 0),
+            (json!("End"), json!(14), 0),
+        ]
+    );
+    assert_eq!(symbols[1]["children"][0]["name"], "Pair");
+    // определение вместе с вложенными
+    assert_eq!(
+        symbols[1]["range"]["end"],
+        json!({"line": 3, "character": 23})
+    );
+
+    let labels = |v: Value| {
+        v.as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["label"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>()
+    };
+    let inside = labels(server.at("textDocument/completion", URI, 3, 16, json!({})));
+    assert_eq!(inside[..5], ["Doc", "Item", "Pair", "Num", "End"]);
+    assert!(inside.contains(&"Ident".to_owned()));
+    assert_eq!(inside.last().unwrap(), "T");
+    let outside = labels(server.at("textDocument/completion", URI, 1, 8, json!({})));
+    assert!(!outside.contains(&"T".to_owned()));
+    server.exit();
+}
+
+/// Грамматика в строке макроса: позиции — позиции `.rs`, а ссылки на Rust-типы модуля
+/// не ошибка.
+#[test]
+fn embedded() {
+    const URI: &str = "file:///grammar.rs";
+    let mut server = Server::start();
+    let diagnostics = server.open(
+        URI,
+        "use abstract_parser::grammar::core::parser::*;\n\
+         \n\
+         grammar! {r#\"\n\
+         Doc = Item* External;\n\
+         Item = \"[a-z]+\"\n\
+         \"#}\n",
+    );
+    assert_eq!(diagnostics, json!([]));
+    let definition = server.at("textDocument/definition", URI, 3, 7, json!({}));
+    assert_eq!(definition, json!({"uri": URI, "range": range(4, 0, 4)}));
+    server.exit();
+}
+
+#[test]
+fn exit_without_shutdown() {
+    let mut server = Server::start();
+    server.notify("exit", Value::Null);
+    assert_eq!(server.child.wait().unwrap().code(), Some(1));
+}
+
+#[test]
+fn malformed_json() {
+    let mut server = Server::start();
+    let body = r#"{"jsonrpc": "2.0", "id": 7, "method": "#;
+    write!(server.stdin, "Content-Length: {}\r\n\r\n{body}", body.len()).unwrap();
+    server.stdin.flush().unwrap();
+    let response = rpc::read(&mut server.stdout).unwrap().unwrap();
+    assert_eq!(response["id"], Value::Null);
+    assert_eq!(response["error"]["code"], -32700);
+
+    // сессия продолжается
+    assert_eq!(server.open(URI, GRAMMAR), json!([]));
+    assert_eq!(server.exit(), 0);
+}
@@ -15,7 +15,8 @@ use utils::info;
 mod cached_rule_iter;
 
 use crate::{
-    logs::{feature_logs, DebugLog, DisplayLog},
+    debug::{emit, hooked_transfer, instrumented, ParseEvent, HOOKED, TRACED},
+    logs::{rule_log, trace_logs, trace_matches, RuleName},
     BufferIter, Cursorable, Peekab, ProductionError, Promotable, TransferRule,
 };
 use rustc_hash::FxHashMap;
@@ -74,16 +75,23 @@ impl<
 {
     fn parse_cached(&mut self, rule: &Rule) -> Result<Rule::Output, ProductionError<Rule::Error>> {
         let id = (*self.iter.cursor(), TypeId::of::<Rule>());
+        let instrumented = instrumented();
         let a = self.cache.get(&id);
 
         if let Some(v) = a {
-            if cfg!(feature = "logs") {
+            if instrumented & HOOKED != 0 {
+                emit(ParseEvent::Cached {
+                    rule: rule.rule_name(),
+                    pos: id.0,
+                    end: v.as_ref().ok().and_then(|(_, pos)| *pos),
+                    ok: v.is_ok(),
+                });
+            }
+            if instrumented & TRACED != 0 && trace_matches(id.0, rule) {
                 info!(
                     "@{} 🔁Cached {} {}",
                     id.0,
-                    DisplayLog::fmt(rule)
-                        .or_else(|| DebugLog::fmt(rule))
-                        .unwrap_or(format!("Display or Debug not implemented")),
+                    rule_log(rule),
                     if v.is_ok() { "✅Pass" } else { "❌Fail" }
                 );
             }
@@ -101,11 +109,14 @@ impl<
                         ProductionError::Token(e.downcast_ref::<Rule::Error>().unwrap().clone())
                     }
                     ProductionError::EndStream => ProductionError::EndStream,
+                    ProductionError::ZeroProgressLoop(e) => ProductionError::ZeroProgressLoop(e.clone()),
                 })
         } else {
             let old_cursor = *self.iter.cursor();
-            let out = if cfg!(feature = "logs") {
-                feature_logs(old_cursor, rule, || rule.transfer(self))
+            let out = if instrumented & TRACED != 0 {
+                trace_logs(old_cursor, rule, || hooked_transfer(self, rule))
+            } else if instrumented & HOOKED != 0 {
+                hooked_transfer(self, rule)
             } else {
                 rule.transfer(self)
             };
@@ -142,6 +153,11 @@ impl<Iter: Cursorable> Promotable for CachedIter<Iter> {
     ) -> Result<Rule::Output, ProductionError<Rule::Error>> {
         PackratParse::<Rule>::parse_cached(self, rule)
     }
+
+    #[inline]
+    fn position(&mut self) -> Option<usize> {
+        Some(*self.iter.cursor())
+    }
 }
 
 impl<Iter: Cursorable> Cursorable for CachedIter<Iter> {
@@ -20,7 +20,7 @@ use std::{cell::LazyCell, marker::PhantomData, sync::LazyLock};
 use std_reset::prelude::Deref;
 
 #[derive(Debug)]
-pub struct CachedRule<Rule>(Rule);
+pub struct CachedRule<Rule>(pub Rule);
 
 impl<IS: Cursorable, Rule: TransferRule<CachedIter<IS>>> TransferRule<CachedRuleIter<IS>>
     for CachedRule<Rule>
@@ -0,0 +1,180 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+use crate::{logs::RuleName, Cursorable, ProductionError, TransferRule};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEvent {
+    Enter {
+        rule: Cow<'static, str>,
+        pos: usize,
+    },
+    /// `end` — позиция курсора до отката, при неудаче показывает, докуда дошло правило.
+    /// `ok` — правило вернуло `Ok`, `consumed` — ещё и продвинуло курсор: успешные `&`, `!`
+    /// и `Optional` без значения откатываются.
+    Exit {
+        rule: Cow<'static, str>,
+        start: usize,
+        end: usize,
+        ok: bool,
+        consumed: bool,
+    },
+    /// Результат взят из кэша `CachedIter`.
+    Cached {
+        rule: Cow<'static, str>,
+        pos: usize,
+        end: Option<usize>,
+        ok: bool,
+    },
+}
+
+impl ParseEvent {
+    #[inline]
+    pub fn rule(&self) -> &str {
+        match self {
+            Self::Enter { rule, .. } | Self::Exit { rule, .. } | Self::Cached { rule, .. } => rule,
+        }
+    }
+}
+
+/// Хук на каждый вызов `Promotable::impl_parse`: по нему отладчик записывает
+/// вход/выход из правил и попадания в кэш.
+pub trait ParseHook {
+    fn event(&mut self, event: ParseEvent);
+}
+
+impl<F: FnMut(ParseEvent)> ParseHook for F {
+    #[inline]
+    fn event(&mut self, event: ParseEvent) {
+        self(event)
+    }
+}
+
+/// Бит [`instrumented`]: включена трассировка [`crate::logs`].
+pub const TRACED: u8 = 1;
+/// Бит [`instrumented`]: стоит хук.
+pub const HOOKED: u8 = 2;
+
+thread_local! {
+    static HOOK: RefCell<Option<Box<dyn ParseHook>>> = const { RefCell::new(None) };
+    static INSTRUMENTED: Cell<u8> = Cell::new(if crate::logs::trace_configured() { TRACED } else { 0 });
+}
+
+/// Трассировка и хук текущего потока битами [`TRACED`] и [`HOOKED`]: когда оба выключены,
+/// вызов `parse` стоит одного чтения thread-local.
+#[inline(always)]
+pub fn instrumented() -> u8 {
+    INSTRUMENTED.get()
+}
+
+#[inline(always)]
+pub fn hooked() -> bool {
+    instrumented() & HOOKED != 0
+}
+
+pub(crate) fn set_instrumented(bit: u8, on: bool) {
+    INSTRUMENTED.set(if on {
+        INSTRUMENTED.get() | bit
+    } else {
+        INSTRUMENTED.get() & !bit
+    });
+}
+
+/// Ставит хук текущего потока, возвращает прежний.
+pub fn set_hook(hook: Option<Box<dyn ParseHook>>) -> Option<Box<dyn ParseHook>> {
+    set_instrumented(HOOKED, hook.is_some());
+    HOOK.replace(hook)
+}
+
+/// Выполняет разбор, записывая все события.
+pub fn record<O>(f: impl FnOnce() -> O) -> (O, Vec<ParseEvent>) {
+    use std::rc::Rc;
+
+    struct Restore(Option<Box<dyn ParseHook>>);
+    impl Drop for Restore {
+        #[inline]
+        fn drop(&mut self) {
+            set_hook(self.0.take());
+        }
+    }
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let restore = Restore(set_hook(Some(Box::new({
+        let events = events.clone();
+        move |event| events.borrow_mut().push(event)
+    }))));
+    let out = f();
+    drop(restore);
+    let events = events.take();
+    (out, events)
+}
+
+#[inline]
+pub fn emit(event: ParseEvent) {
+    HOOK.with_borrow_mut(|hook| {
+        if let Some(hook) = hook {
+            hook.event(event)
+        }
+    })
+}
+
+#[inline(never)]
+pub fn hooked_transfer<IS: Cursorable, Rule: TransferRule<IS>>(
+    input_stream: &mut IS,
+    rule: &Rule,
+) -> Result<Rule::Output, ProductionError<Rule::Error>> {
+    let start = *input_stream.cursor();
+    let name = rule.rule_name();
+    emit(ParseEvent::Enter {
+        rule: name.clone(),
+        pos: start,
+    });
+    let out = rule.transfer(input_stream);
+    emit(ParseEvent::Exit {
+        rule: name,
+        start,
+        end: *input_stream.cursor(),
+        ok: out.is_ok(),
+        consumed: Rule::is_promotion(&out),
+    });
+    out
+}
+
+/// `parse` с трассировкой или хуком: `instrumented` — значение [`instrumented`] на входе.
+#[inline(never)]
+pub fn instrumented_parse<IS: Cursorable, Rule: TransferRule<IS>>(
+    input_stream: &mut IS,
+    rule: &Rule,
+    instrumented: u8,
+) -> Result<Rule::Output, ProductionError<Rule::Error>> {
+    let start = *input_stream.cursor();
+    let mut parse = || {
+        let out = if instrumented & HOOKED != 0 {
+            hooked_transfer(input_stream, rule)
+        } else {
+            rule.transfer(input_stream)
+        };
+        if !Rule::is_promotion(&out) {
+            *input_stream.cursor() = start;
+        }
+        out
+    };
+    if instrumented & TRACED != 0 {
+        crate::logs::trace_logs(start, rule, parse)
+    } else {
+        parse()
+    }
+}
@@ -0,0 +1,466 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Нетипизированное дерево разбора: одно на любую грамматику, для обобщённых инструментов.
+//! Строится по событиям [`record`] параллельно с обычным разбором, типизированный вывод
+//! при этом не меняется.
+
+use crate::debug::{record, ParseEvent};
+use std::{collections::HashMap, fmt, mem, ops::Range};
+
+/// Вид узла: по варианту на правило и токен грамматики. `grammar!` генерирует его как
+/// `SyntaxKind`, `tree!` — по флагу `kind` с заданным именем.
+pub trait GreenKind: Copy + fmt::Debug {
+    /// Вид по имени правила из события разбора; правила без вида в дерево не попадают,
+    /// их дети поднимаются к родителю.
+    fn from_rule(name: &str) -> Option<Self>;
+
+    fn name(self) -> &'static str;
+
+    /// Пробелы, комментарии и пропущенные поля: в дереве без потерь становятся [`Trivia`].
+    fn is_trivia(self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreenNode<K> {
+    pub kind: K,
+    /// Диапазон курсора: байты для `CharsIter`, токены для `TokenStreamIter`.
+    pub span: Range<usize>,
+    pub children: Vec<GreenNode<K>>,
+}
+
+impl<K: GreenKind> GreenNode<K> {
+    #[inline]
+    pub fn text<'a>(&self, src: &'a str) -> &'a str {
+        &src[self.span.clone()]
+    }
+
+    /// Обход в прямом порядке.
+    pub fn preorder(&self) -> impl Iterator<Item = &GreenNode<K>> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// Дерево с отступами, по узлу `Kind start..end` на строку.
+impl<K: GreenKind> fmt::Display for GreenNode<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn node<K: GreenKind>(
+            f: &mut fmt::Formatter<'_>,
+            v: &GreenNode<K>,
+            depth: usize,
+        ) -> fmt::Result {
+            writeln!(
+                f,
+                "{:w$}{} {}..{}",
+                "",
+                v.kind.name(),
+                v.span.start,
+                v.span.end,
+                w = depth * 2
+            )?;
+            v.children.iter().try_for_each(|v| node(f, v, depth + 1))
+        }
+        node(f, self, 0)
+    }
+}
+
+/// Собирает зелёное дерево из записанных событий: успешные правила с видом становятся
+/// узлами, неудачные ветки отбрасываются, попадания в кэш повторяют уже собранные узлы.
+pub fn build<K: GreenKind>(events: &[ParseEvent]) -> Vec<GreenNode<K>> {
+    // кэш нужен только `CachedIter`: ключ — имя, начало и конец успешного разбора
+    let cached = events
+        .iter()
+        .any(|v| matches!(v, ParseEvent::Cached { ok: true, .. }));
+    let mut memo = HashMap::<(&str, usize, usize), Vec<GreenNode<K>>>::new();
+    let mut stack = vec![vec![]];
+
+    for event in events {
+        match event {
+            ParseEvent::Enter { .. } => stack.push(vec![]),
+            ParseEvent::Exit {
+                rule,
+                start,
+                end,
+                consumed,
+                ..
+            } => {
+                let children = stack.pop().expect("Exit без Enter");
+                // откатившиеся правила, в том числе успешные просмотры вперёд, не в дереве
+                if !consumed {
+                    continue;
+                }
+                let nodes = match K::from_rule(rule) {
+                    Some(kind) => vec![GreenNode {
+                        kind,
+                        span: *start..*end,
+                        children,
+                    }],
+                    None => children,
+                };
+                if cached {
+                    memo.insert((rule, *start, *end), nodes.clone());
+                }
+                stack.last_mut().unwrap().extend(nodes);
+            }
+            ParseEvent::Cached {
+                rule,
+                pos,
+                end: Some(end),
+                ok: true,
+            } => {
+                if let Some(nodes) = memo.get(&(rule.as_ref(), *pos, *end)) {
+                    stack.last_mut().unwrap().extend(nodes.iter().cloned());
+                }
+            }
+            ParseEvent::Cached { .. } => {}
+        }
+    }
+    stack.swap_remove(0)
+}
+
+/// Выполняет разбор и параллельно собирает зелёное дерево.
+///
+/// ```ignore
+/// let (out, tree) = green::<SyntaxKind, _>(|| InputStreamIter::new(src).full_parse(&Grammar::default()));
+/// ```
+#[inline]
+pub fn green<K: GreenKind, O>(parse: impl FnOnce() -> O) -> (O, Vec<GreenNode<K>>) {
+    let (out, events) = record(parse);
+    (out, build(&events))
+}
+
+/// Кусок trivia: узел пропускаемого вида или участок входа, не покрытый ни одним узлом.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Trivia<K> {
+    pub kind: Option<K>,
+    pub span: Range<usize>,
+}
+
+/// Узел дерева без потерь: trivia прикреплены к соседним узлам, а не отброшены.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SyntaxNode<K> {
+    pub kind: K,
+    pub span: Range<usize>,
+    pub leading: Vec<Trivia<K>>,
+    /// Trivia после узла до конца строки включительно, у последнего ребёнка — все оставшиеся.
+    pub trailing: Vec<Trivia<K>>,
+    pub children: Vec<SyntaxNode<K>>,
+}
+
+impl<K: GreenKind> SyntaxNode<K> {
+    /// Диапазон вместе с trivia.
+    #[inline]
+    pub fn full_span(&self) -> Range<usize> {
+        let start = self
+            .leading
+            .first()
+            .map_or(self.span.start, |v| v.span.start);
+        let end = self.trailing.last().map_or(self.span.end, |v| v.span.end);
+        start..end
+    }
+
+    /// Печатает узел вместе с trivia: лист — своим текстом, остальные — детьми.
+    pub fn write(&self, src: &str, f: &mut impl fmt::Write) -> fmt::Result {
+        for v in &self.leading {
+            f.write_str(&src[v.span.clone()])?;
+        }
+        if self.children.is_empty() {
+            f.write_str(&src[self.span.clone()])?;
+        }
+        for v in &self.children {
+            v.write(src, f)?;
+        }
+        for v in &self.trailing {
+            f.write_str(&src[v.span.clone()])?;
+        }
+        Ok(())
+    }
+
+    pub fn preorder(&self) -> impl Iterator<Item = &SyntaxNode<K>> {
+        let mut stack = vec![self];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.children.iter().rev());
+            Some(node)
+        })
+    }
+}
+
+/// Дерево без потерь: [`Display`](fmt::Display) печатает вход байт в байт.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxTree<'src, K> {
+    pub src: &'src str,
+    pub roots: Vec<SyntaxNode<K>>,
+    /// Весь вход, если не разобран ни один узел.
+    pub trivia: Vec<Trivia<K>>,
+}
+
+impl<'src, K: GreenKind> SyntaxTree<'src, K> {
+    pub fn new(src: &'src str, roots: Vec<GreenNode<K>>) -> Self {
+        let (roots, trivia) = match attach(src, pieces(src, 0..src.len(), roots)) {
+            Ok(v) => (v, vec![]),
+            Err(v) => (vec![], v),
+        };
+        Self { src, roots, trivia }
+    }
+}
+
+impl<K: GreenKind> fmt::Display for SyntaxTree<'_, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for v in &self.roots {
+            v.write(self.src, f)?;
+        }
+        for v in &self.trivia {
+            f.write_str(&self.src[v.span.clone()])?;
+        }
+        Ok(())
+    }
+}
+
+enum Piece<K> {
+    Node(SyntaxNode<K>),
+    Trivia(Trivia<K>),
+}
+
+/// Дети узла вперемешку с непокрытыми участками его диапазона.
+fn pieces<K: GreenKind>(
+    src: &str,
+    span: Range<usize>,
+    children: Vec<GreenNode<K>>,
+) -> Vec<Piece<K>> {
+    let mut pos = span.start;
+    let mut out = vec![];
+    for child in children {
+        // повторы из кэша могут перекрываться с уже разобранным
+        if child.span.start < pos {
+            continue;
+        }
+        if pos < child.span.start {
+            out.push(Piece::Trivia(Trivia {
+                kind: None,
+                span: pos..child.span.start,
+            }));
+        }
+        pos = child.span.end;
+        out.push(if child.kind.is_trivia() {
+            Piece::Trivia(Trivia {
+                kind: Some(child.kind),
+                span: child.span,
+            })
+        } else {
+            Piece::Node(syntax_node(src, child))
+        });
+    }
+    if pos < span.end {
+        out.push(Piece::Trivia(Trivia {
+            kind: None,
+            span: pos..span.end,
+        }));
+    }
+    out
+}
+
+/// Прикрепляет trivia к соседним узлам; без узлов возвращает сами trivia.
+fn attach<K: GreenKind>(
+    src: &str,
+    pieces: Vec<Piece<K>>,
+) -> Result<Vec<SyntaxNode<K>>, Vec<Trivia<K>>> {
+    let mut nodes = Vec::<SyntaxNode<K>>::new();
+    let mut pending = vec![];
+    let mut same_line = false;
+    for piece in pieces {
+        match piece {
+            Piece::Node(mut v) => {
+                v.leading = mem::take(&mut pending);
+                nodes.push(v);
+                same_line = true;
+            }
+            Piece::Trivia(v) => match nodes.last_mut() {
+                Some(last) if same_line => {
+                    same_line = !src[v.span.clone()].contains('\n');
+                    last.trailing.push(v);
+                }
+                _ => pending.push(v),
+            },
+        }
+    }
+    match nodes.last_mut() {
+        Some(last) => last.trailing.extend(pending),
+        None => return Err(pending),
+    }
+    Ok(nodes)
+}
+
+fn syntax_node<K: GreenKind>(src: &str, node: GreenNode<K>) -> SyntaxNode<K> {
+    SyntaxNode {
+        kind: node.kind,
+        // узел из одних trivia печатается целиком как лист
+        children: attach(src, pieces(src, node.span.clone(), node.children)).unwrap_or_default(),
+        span: node.span,
+        leading: vec![],
+        trailing: vec![],
+    }
+}
+
+/// Выполняет разбор и параллельно собирает дерево без потерь.
+#[inline]
+pub fn lossless<'src, K: GreenKind, O>(
+    src: &'src str,
+    parse: impl FnOnce() -> O,
+) -> (O, SyntaxTree<'src, K>) {
+    let (out, roots) = green(parse);
+    (out, SyntaxTree::new(src, roots))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Kind {
+        A,
+        B,
+        S,
+    }
+
+    impl GreenKind for Kind {
+        fn from_rule(name: &str) -> Option<Self> {
+            match name {
+                "A" => Some(Self::A),
+                "B" => Some(Self::B),
+                "S" => Some(Self::S),
+                _ => None,
+            }
+        }
+
+        fn name(self) -> &'static str {
+            match self {
+                Self::A => "A",
+                Self::B => "B",
+                Self::S => "S",
+            }
+        }
+
+        fn is_trivia(self) -> bool {
+            matches!(self, Self::S)
+        }
+    }
+
+    fn enter(rule: &'static str, pos: usize) -> ParseEvent {
+        ParseEvent::Enter {
+            rule: rule.into(),
+            pos,
+        }
+    }
+
+    fn exit(rule: &'static str, span: Range<usize>, ok: bool) -> ParseEvent {
+        ParseEvent::Exit {
+            rule: rule.into(),
+            start: span.start,
+            end: span.end,
+            ok,
+            consumed: ok,
+        }
+    }
+
+    #[test]
+    fn build_tree() {
+        let events = [
+            enter("A", 0),
+            // безымянная обёртка: её дети поднимаются к A
+            enter("Seq", 0),
+            enter("B", 0),
+            exit("B", 0..1, true),
+            enter("B", 1),
+            exit("B", 1..1, false),
+            exit("Seq", 0..1, true),
+            // неудачная ветка с успешным ребёнком отбрасывается целиком
+            enter("Choice", 1),
+            enter("B", 1),
+            exit("B", 1..2, true),
+            exit("Choice", 1..2, false),
+            ParseEvent::Cached {
+                rule: "B".into(),
+                pos: 0,
+                end: Some(1),
+                ok: true,
+            },
+            exit("A", 0..2, true),
+        ];
+        let leaf = |span| GreenNode {
+            kind: Kind::B,
+            span,
+            children: vec![],
+        };
+        let tree = build::<Kind>(&events);
+        assert_eq!(
+            tree,
+            [GreenNode {
+                kind: Kind::A,
+                span: 0..2,
+                children: vec![leaf(0..1), leaf(0..1)],
+            }]
+        );
+        assert_eq!(tree[0].to_string(), "A 0..2\n  B 0..1\n  B 0..1\n");
+        assert_eq!(tree[0].preorder().count(), 3);
+    }
+
+    #[test]
+    fn lossless_tree() {
+        // " b  b\n b" — A с тремя B, пробелы S и один непокрытый участок
+        let src = " b  b\n b";
+        let node = |kind, span: Range<usize>, children| GreenNode {
+            kind,
+            span,
+            children,
+        };
+        let tree = SyntaxTree::new(
+            src,
+            vec![node(
+                Kind::A,
+                1..8,
+                vec![
+                    node(Kind::B, 1..2, vec![]),
+                    node(Kind::S, 2..4, vec![]),
+                    node(Kind::B, 4..5, vec![]),
+                    node(Kind::S, 5..6, vec![]),
+                    node(Kind::B, 7..8, vec![]),
+                ],
+            )],
+        );
+        assert_eq!(tree.to_string(), src);
+
+        let root = &tree.roots[0];
+        assert_eq!(root.full_span(), 0..8);
+        let spans = |v: &[Trivia<Kind>]| {
+            v.iter()
+                .map(|v| (v.span.start, v.span.end))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(spans(&root.leading), [(0, 1)]);
+        assert_eq!(spans(&root.children[0].trailing), [(2, 4)]);
+        // перевод строки закрывает trailing, остальное — leading следующего узла
+        assert_eq!(spans(&root.children[1].trailing), [(5, 6)]);
+        assert_eq!(spans(&root.children[2].leading), [(6, 7)]);
+        assert_eq!(root.preorder().count(), 4);
+
+        let empty = SyntaxTree::<Kind>::new(src, vec![]);
+        assert_eq!(empty.to_string(), src);
+    }
+}
@@ -41,6 +41,12 @@ pub trait Promotable<InputStream = Self>: Sized {
         &mut self,
         rule: &Rule,
     ) -> Result<Rule::Output, ProductionError<Rule::Error>>;
+
+    /// Курсор, если поток его знает: по нему повторы замечают итерации без продвижения.
+    #[inline]
+    fn position(&mut self) -> Option<usize> {
+        None
+    }
 }
 
 pub trait Cursorable: Promotable {
@@ -59,18 +65,24 @@ pub trait Cursorable: Promotable {
 }
 
 impl<InputStream: Cursorable> Promotable for InputStream {
+    /// Трассировка и хук идут мимо `impl_parse`: поток со своим `impl_parse` переопределяет
+    /// и `parse`.
     #[inline]
     default fn parse<Rule: TransferRule<InputStream>>(
         &mut self,
         rule: &Rule,
     ) -> Result<Rule::Output, ProductionError<Rule::Error>> {
-        if cfg!(feature = "logs") {
-            crate::logs::feature_logs(*self.cursor(), rule, || self.impl_parse(rule))
-        } else {
-            self.impl_parse(rule)
+        match crate::debug::instrumented() {
+            0 => self.impl_parse(rule),
+            v => crate::debug::instrumented_parse(self, rule, v),
         }
     }
 
+    #[inline]
+    default fn position(&mut self) -> Option<usize> {
+        Some(*self.cursor())
+    }
+
     default fn impl_parse<Rule: TransferRule<Self>>(
         &mut self,
         rule: &Rule,
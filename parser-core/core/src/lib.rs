@@ -134,8 +134,14 @@ pub use input_stream::*;
 mod input_stream;
 pub use rules::production::*;
 pub mod cached;
+pub mod debug;
+pub mod green;
 pub mod logs;
+pub mod progress;
+pub mod property;
 pub mod rules;
+pub mod unparse;
+pub mod visit;
 
 use std::{cell::RefCell, iter::Peekable, marker::PhantomData, rc::Rc};
 use std_reset::prelude::Deref;
@@ -10,7 +10,260 @@
 // THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
 // 
 
-use std::{any::TypeId, borrow::Cow, fmt::Display};
+use std::{
+    any::{type_name, TypeId},
+    borrow::Cow,
+    cell::RefCell,
+    fmt::Display,
+    ops::Range,
+    str::FromStr,
+};
+
+/// Переменная окружения, включающая трассировку без пересборки:
+/// `ABSTRACT_PARSER_TRACE=Rule1,Rule2@100..200`.
+pub const TRACE_ENV: &str = "ABSTRACT_PARSER_TRACE";
+
+/// Runtime-настройка трассировки: фильтр по именам правил и по диапазону курсора.
+/// Пустой фильтр пропускает всё.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TraceConfig {
+    pub rules: Vec<String>,
+    pub range: Option<Range<usize>>,
+}
+
+impl TraceConfig {
+    #[inline]
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn rules<S: Into<String>>(mut self, rules: impl IntoIterator<Item = S>) -> Self {
+        self.rules.extend(rules.into_iter().map(Into::into));
+        self
+    }
+
+    #[inline]
+    pub fn range(mut self, range: Range<usize>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Читает [`TRACE_ENV`], при фиче `logs` без переменной трассируется всё. Неверное
+    /// значение отключает трассировку, о нём один раз пишется в stderr.
+    pub fn from_env() -> Option<Self> {
+        static REPORTED: std::sync::Once = std::sync::Once::new();
+        match std::env::var(TRACE_ENV) {
+            Ok(v) => v
+                .parse()
+                .map_err(|TraceConfigError(e)| {
+                    REPORTED.call_once(|| eprintln!("warning: {TRACE_ENV}=`{v}` is ignored: {e}"))
+                })
+                .ok(),
+            Err(_) if cfg!(feature = "logs") => Some(Self::all()),
+            Err(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn matches(&self, pos: usize, rule: &str) -> bool {
+        self.range.as_ref().is_none_or(|r| r.contains(&pos))
+            && (self.rules.is_empty() || self.rules.iter().any(|v| v == rule))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceConfigError(pub String);
+
+impl FromStr for TraceConfig {
+    type Err = TraceConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (rules, range) = match s.trim().split_once('@') {
+            Some((rules, range)) => (rules, Some(range.trim())),
+            None => (s, None),
+        };
+
+        let range = range
+            .map(|range| {
+                let (start, end) = range.split_once("..").ok_or_else(|| {
+                    TraceConfigError(format!("expected `start..end`, got `{range}`"))
+                })?;
+                let bound = |v: &str, default| {
+                    if v.is_empty() {
+                        Ok(default)
+                    } else {
+                        v.parse::<usize>()
+                            .map_err(|e| TraceConfigError(format!("`{v}`: {e}")))
+                    }
+                };
+                Ok(bound(start, 0)?..bound(end, usize::MAX)?)
+            })
+            .transpose()?;
+
+        let rules = rules
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty() && *v != "*")
+            .map(String::from)
+            .collect();
+
+        Ok(Self { rules, range })
+    }
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<TraceConfig>> = RefCell::new(TraceConfig::from_env());
+}
+
+/// Начальное значение бита [`TRACED`](crate::debug::TRACED).
+#[inline]
+pub(crate) fn trace_configured() -> bool {
+    TRACE.with_borrow(Option::is_some)
+}
+
+/// Включена ли трассировка; бит общего с хуком флага [`crate::debug::instrumented`].
+#[inline(always)]
+pub fn trace_enabled() -> bool {
+    crate::debug::instrumented() & crate::debug::TRACED != 0
+}
+
+/// Заменяет настройку трассировки текущего потока, возвращает прежнюю.
+pub fn set_trace(config: Option<TraceConfig>) -> Option<TraceConfig> {
+    crate::debug::set_instrumented(crate::debug::TRACED, config.is_some());
+    TRACE.replace(config)
+}
+
+/// Трассировка на время одного разбора.
+pub fn with_trace<O>(config: Option<TraceConfig>, f: impl FnOnce() -> O) -> O {
+    struct Restore(Option<TraceConfig>);
+    impl Drop for Restore {
+        #[inline]
+        fn drop(&mut self) {
+            set_trace(self.0.take());
+        }
+    }
+
+    let _restore = Restore(set_trace(config));
+    f()
+}
+
+/// Короткое имя правила: `grammar::__Command::__def::__Rule<..>` -> `Command`,
+/// обёртки токенов раскрываются: `TokenRule<Chars<.. _Plus::Token>>` -> `Plus`.
+pub fn rule_name<Rule: ?Sized>() -> &'static str {
+    short_name(type_name::<Rule>())
+}
+
+fn short_name(name: &'static str) -> &'static str {
+    const TRANSPARENT: [&str; 7] = [
+        "TokenRule",
+        "Chars",
+        "SelfToken",
+        "SubStrToken",
+        "RegExprTokenRule",
+        "CachedRule",
+        "Box",
+    ];
+    const ANONYMOUS: [&str; 4] = ["__Rule", "Rule", "Token", "__def"];
+
+    let name = name.trim_start_matches('&');
+    let (path, args) = name.split_at(name.find('<').unwrap_or(name.len()));
+    let mut segments = path.rsplit("::").skip_while(|v| ANONYMOUS.contains(v));
+    let last = segments.next().unwrap_or(path);
+
+    match args.strip_prefix('<') {
+        Some(args) if TRANSPARENT.contains(&last) => {
+            let mut depth = 0;
+            let end = args
+                .find(|c| {
+                    match c {
+                        '<' | '(' => depth += 1,
+                        '>' | ')' if depth > 0 => depth -= 1,
+                        ',' | '>' if depth == 0 => return true,
+                        _ => {}
+                    }
+                    false
+                })
+                .unwrap_or(args.len());
+            short_name(&args[..end])
+        }
+        _ => last.trim_start_matches('_'),
+    }
+}
+
+/// Имя правила для трассировки и отладчика, рантайм-правила подставляют своё.
+pub trait RuleName {
+    fn rule_name(&self) -> Cow<'static, str>;
+}
+
+impl<T: ?Sized> RuleName for T {
+    #[inline]
+    default fn rule_name(&self) -> Cow<'static, str> {
+        Cow::Borrowed(rule_name::<T>())
+    }
+}
+
+impl<T: RuleName + ?Sized> RuleName for &T {
+    #[inline]
+    fn rule_name(&self) -> Cow<'static, str> {
+        (**self).rule_name()
+    }
+}
+
+/// Имя правила-синонима, см. [`NamedRule`](crate::rules::NamedRule).
+pub trait RuleLabel {
+    const NAME: &'static str;
+}
+
+impl<Label: RuleLabel, Rule> RuleName for crate::rules::NamedRule<Label, Rule> {
+    #[inline]
+    fn rule_name(&self) -> Cow<'static, str> {
+        Cow::Borrowed(Label::NAME)
+    }
+}
+
+impl<T: RuleName + ?Sized> RuleName for Box<T> {
+    #[inline]
+    fn rule_name(&self) -> Cow<'static, str> {
+        (**self).rule_name()
+    }
+}
+
+impl<T: RuleName + ?Sized> RuleName for std::rc::Rc<T> {
+    #[inline]
+    fn rule_name(&self) -> Cow<'static, str> {
+        (**self).rule_name()
+    }
+}
+
+#[inline]
+pub fn trace_matches<Rule>(pos: usize, rule: &Rule) -> bool {
+    TRACE.with_borrow(|v| {
+        v.as_ref()
+            .is_some_and(|v| v.matches(pos, &rule.rule_name()))
+    })
+}
+
+#[inline]
+pub fn rule_log<Rule>(rule: &Rule) -> String {
+    DisplayLog::fmt(rule)
+        .or_else(|| DebugLog::fmt(rule))
+        .unwrap_or_else(|| "Display or Debug not implemented".into())
+}
+
+/// Вход в правило при включённой трассировке; правила вне фильтра разбираются без логов.
+#[inline]
+pub fn trace_logs<O, E, Rule>(
+    pos: usize,
+    rule: &Rule,
+    mut res: impl FnMut() -> Result<O, E>,
+) -> Result<O, E> {
+    if trace_matches(pos, rule) {
+        feature_logs(pos, rule, res)
+    } else {
+        res()
+    }
+}
 
 #[inline]
 pub fn feature_logs<O, E, Rule>(
@@ -22,9 +275,7 @@ pub fn feature_logs<O, E, Rule>(
         format!(
             "{} @{pos} {}",
             utils::stacker::formated_remaining_stack(),
-            DisplayLog::fmt(rule)
-                .or_else(|| DebugLog::fmt(rule))
-                .unwrap_or(format!("Display or Debug not implemented"))
+            rule_log(rule)
         ),
         ("✅Pass", "❌Fail"),
         res,
@@ -66,3 +317,66 @@ impl<T: std::fmt::Debug> DebugLog for T {
         Some(format!("{self:#?}"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_config() {
+        assert_eq!("".parse(), Ok(TraceConfig::all()));
+        assert_eq!("*".parse(), Ok(TraceConfig::all()));
+        assert_eq!(
+            "Rule1, Rule2@100..200".parse(),
+            Ok(TraceConfig::all().rules(["Rule1", "Rule2"]).range(100..200))
+        );
+        assert_eq!("@..20".parse(), Ok(TraceConfig::all().range(0..20)));
+        assert!("Rule@10".parse::<TraceConfig>().is_err());
+
+        let config = TraceConfig::all().rules(["Rule"]).range(5..10);
+        assert!(config.matches(5, "Rule"));
+        assert!(!config.matches(10, "Rule"));
+        assert!(!config.matches(5, "Other"));
+    }
+
+    #[test]
+    fn scoped_trace() {
+        let old = set_trace(None);
+        with_trace(Some(TraceConfig::all()), || assert!(trace_enabled()));
+        assert!(!trace_enabled());
+
+        // трассировка и хук — независимые биты одного флага
+        use crate::debug::{hooked, instrumented, record, HOOKED, TRACED};
+        record(|| {
+            with_trace(Some(TraceConfig::all()), || {
+                assert_eq!(instrumented(), TRACED | HOOKED)
+            });
+            assert!(hooked() && !trace_enabled());
+        });
+        assert_eq!(instrumented(), 0);
+
+        // паника внутри разбора не оставляет хук включённым
+        let panicked = std::panic::catch_unwind(|| record(|| panic!("parse")));
+        assert!(panicked.is_err() && !hooked());
+        set_trace(old);
+    }
+
+    #[test]
+    fn short_rule_name() {
+        #[allow(non_snake_case)]
+        mod __Command {
+            pub struct __Rule<T>(T);
+        }
+        assert_eq!(rule_name::<__Command::__Rule<u8>>(), "Command");
+        assert_eq!(rule_name::<&Box<__Command::__Rule<u8>>>(), "Command");
+        assert_eq!(rule_name::<TraceConfig>(), "TraceConfig");
+
+        struct Label;
+        impl RuleLabel for Label {
+            const NAME: &'static str = "Alias";
+        }
+        let rule = crate::rules::NamedRule::<Label, TraceConfig>(Default::default(), Default::default());
+        assert_eq!(rule.rule_name(), "Alias");
+        assert_eq!(std::rc::Rc::new(&rule).rule_name(), "Alias");
+    }
+}
@@ -0,0 +1,161 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Защита повторов от итераций, которые ничего не разобрали: `Space = "\s*"` под `*`
+//! успешно совпадает с пустой строкой бесконечно. Повторы сверяют курсор до и после
+//! каждой итерации и по настройке потока либо останавливаются, либо возвращают
+//! [`ProductionError::ZeroProgressLoop`].
+
+use crate::{logs::RuleName, ProductionError};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    fmt,
+    str::FromStr,
+};
+
+/// Переменная окружения с поведением по умолчанию: `stop` или `error`.
+pub const ZERO_PROGRESS_ENV: &str = "ABSTRACT_PARSER_ZERO_PROGRESS";
+
+/// Что делает повтор, когда итерация не сдвинула курсор.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZeroProgress {
+    /// Повтор завершается с уже разобранными элементами, пустой — последний.
+    #[default]
+    Stop,
+    /// Повтор возвращает ошибку, которую не глотают выбор, `?` и предикаты.
+    Error,
+}
+
+impl ZeroProgress {
+    /// Читает [`ZERO_PROGRESS_ENV`], без переменной — [`ZeroProgress::Stop`].
+    pub fn from_env() -> Self {
+        std::env::var(ZERO_PROGRESS_ENV)
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for ZeroProgress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "stop" => Ok(Self::Stop),
+            "error" => Ok(Self::Error),
+            v => Err(format!("expected `stop` or `error`, got `{v}`")),
+        }
+    }
+}
+
+/// Повтор `rule` на позиции `pos` разобрал элемент, не сдвинув курсор.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZeroProgressLoop {
+    pub rule: Cow<'static, str>,
+    pub pos: usize,
+}
+
+impl fmt::Display for ZeroProgressLoop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` matched empty input at {} inside a repetition",
+            self.rule, self.pos
+        )
+    }
+}
+
+impl std::error::Error for ZeroProgressLoop {}
+
+thread_local! {
+    static POLICY: Cell<ZeroProgress> = Cell::new(ZeroProgress::from_env());
+    /// Правила, о которых уже сообщено: в цикле разбора одно и то же повторяется.
+    static REPORTED: RefCell<HashSet<Cow<'static, str>>> = RefCell::new(HashSet::new());
+}
+
+#[inline]
+pub fn zero_progress() -> ZeroProgress {
+    POLICY.get()
+}
+
+/// Заменяет поведение текущего потока, возвращает прежнее.
+#[inline]
+pub fn set_zero_progress(policy: ZeroProgress) -> ZeroProgress {
+    POLICY.replace(policy)
+}
+
+/// Поведение на время одного разбора.
+pub fn with_zero_progress<O>(policy: ZeroProgress, f: impl FnOnce() -> O) -> O {
+    struct Restore(ZeroProgress);
+    impl Drop for Restore {
+        #[inline]
+        fn drop(&mut self) {
+            set_zero_progress(self.0);
+        }
+    }
+
+    let _restore = Restore(set_zero_progress(policy));
+    f()
+}
+
+/// Вызывается повтором, когда итерация `rule` на `pos` ничего не разобрала: `Ok` —
+/// остановиться. В отладочной сборке о каждом правиле один раз пишется в stderr.
+#[cold]
+pub(crate) fn stalled<Rule: ?Sized, Error>(
+    rule: &Rule,
+    pos: usize,
+) -> Result<(), ProductionError<Error>> {
+    let error = ZeroProgressLoop {
+        rule: rule.rule_name(),
+        pos,
+    };
+    if cfg!(debug_assertions) && REPORTED.with_borrow_mut(|v| v.insert(error.rule.clone())) {
+        eprintln!(
+            "warning: zero-progress loop: {error}; the repetition would never end, \
+             make the repeated rule consume input"
+        );
+    }
+    match zero_progress() {
+        ZeroProgress::Stop => Ok(()),
+        ZeroProgress::Error => Err(ProductionError::ZeroProgressLoop(error)),
+    }
+}
+
+#[cfg(test)]
+#[macros::parse_test(items: [Token1, Token2] input_stream: [Token1, Token2])]
+#[test]
+fn zero_progress_error(input_stream: abstract_parser::InputStream) {
+    use crate::{
+        rules::{ChoiceRule, OptionalRule, Repeat, RepeatRule, TokenRule},
+        Promotable,
+    };
+
+    let rule = ChoiceRule((
+        RepeatRule {
+            rule: OptionalRule(TokenRule(Token1::default())),
+            marker: Repeat,
+        },
+        TokenRule(Token1::default()),
+    ));
+    let out = with_zero_progress(ZeroProgress::Error, || input_stream.parse(&rule));
+    // выбор не переходит к следующей альтернативе
+    assert_eq!(
+        out.unwrap_err(),
+        ProductionError::ZeroProgressLoop(ZeroProgressLoop {
+            rule: "OptionalRule".into(),
+            pos: 1,
+        })
+    );
+    assert_eq!(zero_progress(), ZeroProgress::Stop);
+}
@@ -0,0 +1,606 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Проверка свойств разбора на сгенерированных входах. Входы строятся по структуре правила
+//! ([`Generate`]) или стратегией пользователя, часть из них портится до почти верных;
+//! упавший вход сокращается до минимального, на котором свойство всё ещё нарушено.
+//! `asserts_parse_test!` принимает их блоком `properties: [..]`.
+
+use crate::{cached::CachedIter, rules::*, InputStreamIter};
+use std::{
+    fmt::Debug,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+/// SplitMix64: воспроизводимая по зерну последовательность без внешних зависимостей.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut v = self.0;
+        v = (v ^ (v >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        v = (v ^ (v >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        v ^ (v >> 31)
+    }
+
+    /// Число из `0..n`, `n > 0`.
+    #[inline]
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Число повторов из `min..=max`, не больше чем на [`MAX_EXTRA`] сверх минимума.
+    #[inline]
+    pub fn repeat(&mut self, min: usize, max: Option<usize>) -> usize {
+        self.repeat_up_to(min, max, MAX_EXTRA)
+    }
+
+    /// Число повторов из `min..=max`, не больше чем на `extra` сверх минимума.
+    #[inline]
+    pub fn repeat_up_to(&mut self, min: usize, max: Option<usize>, extra: usize) -> usize {
+        let limit = min.saturating_add(extra);
+        let max = max.map_or(limit, |v| v.min(limit));
+        min + self.below(max.saturating_sub(min) + 1)
+    }
+}
+
+/// Сколько повторов сверх минимума порождают `*`, `+` и `**`.
+pub const MAX_EXTRA: usize = 3;
+
+/// Вход, который правило разбирает: токены по его структуре.
+pub trait Generate<T> {
+    /// `depth` — сколько ещё раз можно раскрыть [`Rec`]; на нуле выборы берут первый вариант,
+    /// повторы — минимум, а `Rec` не порождает ничего.
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>);
+}
+
+/// Токен входа, см. [`Generate`].
+pub trait GenerateToken<T> {
+    fn generate(&self, rng: &mut Rng, out: &mut Vec<T>);
+}
+
+impl<T, Rule: Generate<T>> Generate<T> for &Rule {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        (*self).generate(rng, depth, out)
+    }
+}
+
+impl<T, Token: GenerateToken<T>> Generate<T> for TokenRule<Token> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, _: usize, out: &mut Vec<T>) {
+        self.0.generate(rng, out)
+    }
+}
+
+impl<T, Label, Rule: Generate<T>> Generate<T> for NamedRule<Label, Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        self.0.generate(rng, depth, out)
+    }
+}
+
+impl<T, Rule: Generate<T> + Default> Generate<T> for Rec<Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        if let Some(depth) = depth.checked_sub(1) {
+            self.as_deref()
+                .unwrap_or(&Rule::default())
+                .generate(rng, depth, out)
+        }
+    }
+}
+
+impl<T, Rule: Generate<T> + Default> Generate<T> for RecB<Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        self.0.generate(rng, depth, out)
+    }
+}
+
+impl<T, Rule: Generate<T>> Generate<T> for OptionalRule<Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        if depth > 0 && rng.below(2) == 1 {
+            self.0.generate(rng, depth, out)
+        }
+    }
+}
+
+/// Предикаты не потребляют ввод.
+impl<T, Rule> Generate<T> for PositiveLookaheadRule<Rule> {
+    #[inline]
+    fn generate(&self, _: &mut Rng, _: usize, _: &mut Vec<T>) {}
+}
+
+impl<T, Rule> Generate<T> for NegativeLookaheadRule<Rule> {
+    #[inline]
+    fn generate(&self, _: &mut Rng, _: usize, _: &mut Vec<T>) {}
+}
+
+impl<T, Rule> Generate<T> for AndPredicateRule<Rule> {
+    #[inline]
+    fn generate(&self, _: &mut Rng, _: usize, _: &mut Vec<T>) {}
+}
+
+impl<T, Rule: Generate<T>> Generate<T> for VecSequenceRule<Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        self.0.iter().for_each(|v| v.generate(rng, depth, out))
+    }
+}
+
+impl<T, Rule: Generate<T>> Generate<T> for VecChoiceRule<Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        let i = match (depth, self.0.len()) {
+            (_, 0) => return,
+            (0, _) => 0,
+            (_, n) => rng.below(n),
+        };
+        self.0[i].generate(rng, depth, out)
+    }
+}
+
+/// Границы числа повторов маркера, `None` — без верхней.
+pub trait RepeatBounds {
+    fn bounds(&self) -> (usize, Option<usize>);
+}
+
+impl RepeatBounds for Repeat {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+}
+
+impl<const MIN: usize> RepeatBounds for Min<MIN> {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (MIN, None)
+    }
+}
+
+impl RepeatBounds for SMin {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (self.min, None)
+    }
+}
+
+impl<const MAX: usize> RepeatBounds for Max<MAX> {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (0, Some(MAX))
+    }
+}
+
+impl RepeatBounds for SMax {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (0, Some(self.max))
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> RepeatBounds for MinMax<MIN, MAX> {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (MIN, Some(MAX))
+    }
+}
+
+impl RepeatBounds for SMinMax {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (*self.range.start(), Some(*self.range.end()))
+    }
+}
+
+impl<const COUNT: usize> RepeatBounds for Count<COUNT> {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (COUNT, Some(COUNT))
+    }
+}
+
+impl RepeatBounds for SCountRepeatRule {
+    #[inline]
+    fn bounds(&self) -> (usize, Option<usize>) {
+        (self.count, Some(self.count))
+    }
+}
+
+#[inline]
+fn count(marker: &impl RepeatBounds, rng: &mut Rng, depth: usize) -> usize {
+    let (min, max) = marker.bounds();
+    if depth == 0 {
+        min
+    } else {
+        rng.repeat(min, max)
+    }
+}
+
+impl<T, Marker: RepeatBounds, Rule: Generate<T>> Generate<T> for RepeatRule<Marker, Rule> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        (0..count(&self.marker, rng, depth)).for_each(|_| self.rule.generate(rng, depth, out))
+    }
+}
+
+fn joined<T>(
+    rule: &impl Generate<T>,
+    join: &impl Generate<T>,
+    count: usize,
+    rng: &mut Rng,
+    depth: usize,
+    out: &mut Vec<T>,
+) {
+    for i in 0..count {
+        if i != 0 {
+            join.generate(rng, depth, out)
+        }
+        rule.generate(rng, depth, out)
+    }
+}
+
+impl<T, Marker: RepeatBounds, Rule: Generate<T>, Join: Generate<T>> Generate<T>
+    for JoinableRule<Marker, Rule, Join>
+{
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        let count = 1 + count(&self.repeat_rule, rng, depth);
+        joined(&self.rule, &self.join, count, rng, depth, out)
+    }
+}
+
+impl<T, Rule: Generate<T>, Join: Generate<T>, const MIN: usize> Generate<T>
+    for MinJoinableRule<MIN, Rule, Join>
+{
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        let count = count(&SMin { min: MIN }, rng, depth);
+        let JoinableRule { rule, join, .. } = &self.join_rule;
+        joined(rule, join, count, rng, depth, out)
+    }
+}
+
+impl<T, Rule: Generate<T>, Join: Generate<T>> Generate<T> for SMinJoinableRule<Rule, Join> {
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        let count = count(&SMin { min: self.min }, rng, depth);
+        let JoinableRule { rule, join, .. } = &self.join_rule;
+        joined(rule, join, count, rng, depth, out)
+    }
+}
+
+impl<T, Start: Generate<T>, Body: Generate<T>, End: Generate<T>> Generate<T>
+    for WrapRule<Start, Body, End>
+{
+    #[inline]
+    fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+        self.0.generate(rng, depth, out);
+        self.1.generate(rng, depth, out);
+        self.2.generate(rng, depth, out);
+    }
+}
+
+macro_rules! impl_generate {
+    ($($a:ident)+) => {
+        impl<T, $($a: Generate<T>),+> Generate<T> for SequenceRule<($($a),+)> {
+            #[inline]
+            fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+                $( self.0.${index()}.generate(rng, depth, out) ${ignore($a)}; )+
+            }
+        }
+
+        impl<T, $($a: Generate<T>),+> Generate<T> for ChoiceRule<($($a),+)> {
+            /// На нулевой глубине — первая альтернатива.
+            #[inline]
+            fn generate(&self, rng: &mut Rng, depth: usize, out: &mut Vec<T>) {
+                let i = if depth == 0 { 0 } else { rng.below(${count($a)}) };
+                $( if i == ${index()} { self.0.${index()}.generate(rng, depth, out) } ${ignore($a)} )+
+            }
+        }
+    };
+}
+
+tuple_impl!(@type_count impl_generate! T T T T T T T T T T T T T T T T T T T T T T T T);
+
+/// Настройки проверки: зерно, число входов, глубина и алфавит для порчи.
+#[derive(Debug, Clone)]
+pub struct Check<T> {
+    seed: u64,
+    cases: usize,
+    depth: usize,
+    alphabet: Vec<T>,
+}
+
+impl<T> Default for Check<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            cases: 256,
+            depth: 4,
+            alphabet: vec![],
+        }
+    }
+}
+
+impl<T: Clone + Debug> Check<T> {
+    #[inline]
+    pub fn seed(mut self, v: u64) -> Self {
+        self.seed = v;
+        self
+    }
+
+    #[inline]
+    pub fn cases(mut self, v: usize) -> Self {
+        self.cases = v;
+        self
+    }
+
+    /// Глубина раскрытия [`Rec`], см. [`Generate`].
+    #[inline]
+    pub fn depth(mut self, v: usize) -> Self {
+        self.depth = v;
+        self
+    }
+
+    /// Токены, которые порча вставляет во вход помимо уже сгенерированных.
+    #[inline]
+    pub fn alphabet(mut self, v: impl IntoIterator<Item = T>) -> Self {
+        self.alphabet = v.into_iter().collect();
+        self
+    }
+
+    /// Входы по структуре `rule`, половина из них испорчена.
+    pub fn run_rule<Rule: Generate<T>>(
+        &self,
+        rule: &Rule,
+        property: impl Fn(&[T]) -> Result<(), String>,
+    ) {
+        self.run(
+            |rng| {
+                let mut input = vec![];
+                rule.generate(rng, self.depth, &mut input);
+                if rng.below(2) == 1 {
+                    self.mutate(rng, &mut input)
+                }
+                input
+            },
+            property,
+        )
+    }
+
+    /// Входы стратегии `strategy`; паникует на первом нарушении с сокращённым входом.
+    pub fn run(
+        &self,
+        mut strategy: impl FnMut(&mut Rng) -> Vec<T>,
+        property: impl Fn(&[T]) -> Result<(), String>,
+    ) {
+        let rng = &mut Rng::new(self.seed);
+        for case in 0..self.cases {
+            let input = strategy(rng);
+            if let Err(message) = property(&input) {
+                let (input, message) = shrink(input, message, &property);
+                panic!(
+                    "property failed (seed {}, case {case}): {message}\nminimal input: {input:?}",
+                    self.seed
+                );
+            }
+        }
+    }
+
+    /// Удаляет, повторяет, переставляет или вставляет один токен.
+    fn mutate(&self, rng: &mut Rng, input: &mut Vec<T>) {
+        let token = match (self.alphabet.len(), input.len()) {
+            (0, 0) => return,
+            (0, n) => input[rng.below(n)].clone(),
+            (n, _) => self.alphabet[rng.below(n)].clone(),
+        };
+        if input.is_empty() {
+            input.push(token);
+            return;
+        }
+        let i = rng.below(input.len());
+        match rng.below(4) {
+            0 => {
+                input.remove(i);
+            }
+            1 => input.insert(i, input[i].clone()),
+            2 => {
+                let j = rng.below(input.len());
+                input.swap(i, j)
+            }
+            _ => input.insert(i + rng.below(2), token),
+        }
+    }
+}
+
+/// Сокращает вход, пока свойство нарушено; сообщение — от последнего нарушения.
+fn shrink<T: Clone>(
+    input: Vec<T>,
+    mut message: String,
+    property: impl Fn(&[T]) -> Result<(), String>,
+) -> (Vec<T>, String) {
+    let input = ddmin(input, |candidate| match property(candidate) {
+        Err(v) => {
+            message = v;
+            true
+        }
+        Ok(()) => false,
+    });
+    (input, message)
+}
+
+/// Удаляет куски входа, пока `fails` держится: сначала половины, затем всё меньшие
+/// части вплоть до одного элемента.
+pub fn ddmin<T: Clone>(mut input: Vec<T>, mut fails: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut chunk = input.len().div_ceil(2).max(1);
+    while !input.is_empty() {
+        let mut start = 0;
+        let mut removed = false;
+        while start < input.len() {
+            let end = (start + chunk).min(input.len());
+            let mut candidate = input[..start].to_vec();
+            candidate.extend_from_slice(&input[end..]);
+            if fails(&candidate) {
+                input = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        if !removed {
+            if chunk == 1 {
+                break;
+            }
+            chunk = chunk.div_ceil(2);
+        }
+    }
+    input
+}
+
+/// Разбор `input` с начала; возвращает и конечную позицию курсора. Вход копируется:
+/// поток отдаёт ссылки со временем жизни правила `'a`, а не среза.
+#[allow(clippy::unnecessary_to_owned)]
+pub fn parse<'a, T: Clone + 'a, IS: Cursorable + 'a, Rule: TransferRule<IS>>(
+    rule: &Rule,
+    input: &[T],
+    new: impl FnOnce(InputStreamIter<'a, T>) -> IS,
+) -> (Result<Rule::Output, ProductionError<Rule::Error>>, usize) {
+    let input_stream = &mut new(InputStreamIter::new(input.to_vec().into_iter()));
+    let out = input_stream.parse(rule);
+    (out, *input_stream.cursor())
+}
+
+/// Разбор не паникует.
+pub fn never_panics<'a, T: Clone + 'a, Rule: TransferRule<InputStreamIter<'a, T>>>(
+    rule: &Rule,
+) -> impl Fn(&[T]) -> Result<(), String> + '_ {
+    move |input| {
+        catch_unwind(AssertUnwindSafe(|| parse(rule, input, |v| v)))
+            .map(|_| ())
+            .map_err(|e| {
+                let message = e
+                    .downcast_ref::<&str>()
+                    .map(|v| v.to_string())
+                    .or_else(|| e.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                format!("parse panicked: {message}")
+            })
+    }
+}
+
+/// Успешный разбор съедает префикс входа, неудачный — ничего.
+pub fn consumes_prefix<'a, T: Clone + 'a, Rule: TransferRule<InputStreamIter<'a, T>>>(
+    rule: &Rule,
+) -> impl Fn(&[T]) -> Result<(), String> + '_ {
+    move |input| match parse(rule, input, |v| v) {
+        (Ok(_), cursor) if cursor > input.len() => Err(format!(
+            "cursor {cursor} past the end of {} tokens",
+            input.len()
+        )),
+        (Err(_), cursor) if cursor != 0 => {
+            Err(format!("failed parse left the cursor at {cursor}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// [`CachedIter`] даёт тот же вывод, ту же ошибку и тот же курсор, что и разбор без кэша.
+pub fn cached_eq<'a, T: Clone + 'a, Output, Error, Rule>(
+    rule: &Rule,
+) -> impl Fn(&[T]) -> Result<(), String> + '_
+where
+    Output: PartialEq + Debug,
+    Error: PartialEq + Debug,
+    Rule: TransferRule<InputStreamIter<'a, T>, Output = Output, Error = Error>
+        + TransferRule<CachedIter<InputStreamIter<'a, T>>, Output = Output, Error = Error>,
+{
+    move |input| {
+        let plain = parse(rule, input, |v| v);
+        let cached = parse(rule, input, CachedIter::new);
+        if plain == cached {
+            Ok(())
+        } else {
+            Err(format!("uncached {plain:?} != cached {cached:?}"))
+        }
+    }
+}
+
+/// Значение, разобранное из входа, печатается и разбирается обратно в то же значение:
+/// `parse(unparse(x)) == x`. Вход и напечатанный текст не освобождаются, чтобы вывод разбора
+/// мог их заимствовать.
+pub fn round_trip<O: PartialEq + Debug>(
+    parse: impl Fn(&'static str) -> Option<O>,
+    unparse: impl Fn(&O) -> String,
+) -> impl Fn(&[char]) -> Result<(), String> {
+    let leak = |v: String| -> &'static str { Box::leak(v.into_boxed_str()) };
+    move |input| {
+        let Some(x) = parse(leak(input.iter().collect())) else {
+            return Ok(());
+        };
+        let text = unparse(&x);
+        match parse(leak(text.clone())) {
+            Some(v) if v == x => Ok(()),
+            Some(v) => Err(format!("{text:?} parsed back as {v:?}, not {x:?}")),
+            None => Err(format!("printed text {text:?} does not parse")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks() {
+        // нарушено, пока во входе есть и 3, и 7
+        let property = |input: &[u8]| match input.contains(&3) && input.contains(&7) {
+            true => Err("3 and 7".to_string()),
+            false => Ok(()),
+        };
+        let (input, _) = shrink(vec![1, 3, 5, 2, 8, 7, 9, 4], String::new(), property);
+        assert_eq!(input, vec![3, 7]);
+    }
+}
+
+// инварианты разбора у комбинаторов ядра проверяются разом, на правиле из всех них
+#[cfg(test)]
+parser_macros::asserts_parse_test! {
+    name: combinators
+    rule: ChoiceRule((
+        SequenceRule((NegativeLookaheadRule(TokenRule(Token1::default())), TokenRule(Token2::default()))),
+        SequenceRule((
+            AndPredicateRule(TokenRule(Token1::default())),
+            RepeatRule { rule: TokenRule(Token1::default()), marker: Repeat },
+        )),
+        JoinableRule {
+            rule: TokenRule(Token3::default()),
+            join: TokenRule(Token2::default()),
+            repeat_rule: Repeat
+        },
+    ))
+    {
+        items: [Token1, Token2, Token3]
+        properties: [never_panics, consumes_prefix, cached_eq]
+    }
+}
@@ -84,6 +84,7 @@ where
                     SeqError2::V1(e) => Err(ProductionError::Token(e)),
                 },
                 ProductionError::EndStream => Ok(vec![]),
+                ProductionError::ZeroProgressLoop(e) => Err(ProductionError::ZeroProgressLoop(e)),
             })
     }
 }
@@ -194,9 +195,10 @@ impl<IS: Promotable, Rule: TransferRule<IS>, Join: TransferRule<IS>> TransferRul
         if self.min == 0 {
             todo!("ошибка что должно быть больше 0")
         }
-        let Ok(reps) = input_stream.parse(&self.join_rule) else {
-            unreachable!()
-        };
+        // у JoinableRule<Repeat, ..> нет других ошибок
+        let reps = input_stream
+            .parse(&self.join_rule)
+            .map_err(|e| e.to(|()| unreachable!()))?;
         (reps.len() >= self.min)
             .then_some(reps)
             .ok_or(ProductionError::Token(LessThanMin(self.min)))
@@ -18,6 +18,50 @@ mod repeat_rules;
 
 use super::*;
 
+/// Правило-синоним со своим именем: `type Name = NamedRule<__Name, Rule>`. Разбирает как `Rule`,
+/// но в трассировке, отладчике и зелёном дереве виден как `Name`.
+#[derive(Debug)]
+pub struct NamedRule<Label, Rule>(pub Rule, pub std::marker::PhantomData<Label>);
+
+impl<Label, Rule: Default> Default for NamedRule<Label, Rule> {
+    #[inline]
+    fn default() -> Self {
+        Self(Rule::default(), std::marker::PhantomData)
+    }
+}
+
+impl<Label, Rule: Clone> Clone for NamedRule<Label, Rule> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), std::marker::PhantomData)
+    }
+}
+
+impl<IS, Label, Rule: TransferRule<IS>> TransferRule<IS> for NamedRule<Label, Rule> {
+    type Output = Rule::Output;
+    type Error = Rule::Error;
+
+    #[inline]
+    fn is_promotion(out: &Result<Self::Output, ProductionError<Self::Error>>) -> bool {
+        Rule::is_promotion(out)
+    }
+
+    #[inline]
+    fn transfer(
+        &self,
+        input_stream: InputStream<IS>,
+    ) -> Result<Self::Output, ProductionError<Self::Error>> {
+        self.0.transfer(input_stream)
+    }
+}
+
+impl<Label: crate::logs::RuleLabel, Rule> std::fmt::Display for NamedRule<Label, Rule> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", ::utils::logs::SaveLevel::colored(Label::NAME))
+    }
+}
+
 #[derive(Debug, std_reset::prelude::Default, Clone)]
 pub struct OptionalRule<Rule>(pub Rule);
 
@@ -28,7 +72,7 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for OptionalRule<R
 
     #[inline]
     fn is_promotion(out: &Result<Self::Output, ProductionError<Self::Error>>) -> bool {
-        out.as_ref().unwrap().is_some()
+        matches!(out, Ok(Some(_)))
     }
 
     #[inline]
@@ -36,7 +80,11 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for OptionalRule<R
         &self,
         input_stream: InputStream<IS>,
     ) -> Result<Self::Output, ProductionError<Self::Error>> {
-        Ok(input_stream.parse(&self.0).ok())
+        match input_stream.parse(&self.0) {
+            Ok(v) => Ok(Some(v)),
+            Err(ProductionError::ZeroProgressLoop(e)) => Err(ProductionError::ZeroProgressLoop(e)),
+            Err(_) => Ok(None),
+        }
     }
 }
 
@@ -75,6 +123,8 @@ parser_macros::asserts_parse_test! {
     }
 }
 
+/// Пробует `Rule`, не потребляя ввод, и сообщает результат, но сам не падает. Предикат `&e`
+/// с семантикой PEG — [`AndPredicateRule`].
 #[derive(Debug, std_reset::prelude::Default, Clone)]
 pub struct PositiveLookaheadRule<Rule>(pub Rule);
 
@@ -85,7 +135,7 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for PositiveLookah
 
     #[inline]
     fn is_promotion(out: &Result<Self::Output, ProductionError<Self::Error>>) -> bool {
-        out.as_ref().unwrap().is_none()
+        matches!(out, Ok(None))
     }
 
     #[inline]
@@ -96,6 +146,7 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for PositiveLookah
         match input_stream.parse(&self.0) {
             Ok(v) => Ok(Some(v)),
             Err(ProductionError::EndStream) => Err(ProductionError::EndStream),
+            Err(ProductionError::ZeroProgressLoop(e)) => Err(ProductionError::ZeroProgressLoop(e)),
             Err(..) => Ok(None),
         }
     }
@@ -153,6 +204,7 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for NegativeLookah
     ) -> Result<Self::Output, ProductionError<Self::Error>> {
         match input_stream.parse(&self.0) {
             Ok(_) => Err(ProductionError::Token(LookaheadMatched)),
+            Err(ProductionError::ZeroProgressLoop(e)) => Err(ProductionError::ZeroProgressLoop(e)),
             Err(_) => Ok(()),
         }
     }
@@ -252,9 +304,73 @@ parser_macros::asserts_parse_test! {
     }
 }
 
+/// Предикат `&e`: успех без потребления ввода, если `Rule` разбирается, иначе ошибка `Rule`.
+#[derive(Debug, std_reset::prelude::Default, Clone)]
+pub struct AndPredicateRule<Rule>(pub Rule);
+
+impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for AndPredicateRule<Rule> {
+    type Output = ();
+    type Error = Rule::Error;
+
+    #[inline]
+    fn is_promotion(_: &Result<Self::Output, ProductionError<Self::Error>>) -> bool {
+        false
+    }
+
+    #[inline]
+    fn transfer(
+        &self,
+        input_stream: InputStream<IS>,
+    ) -> Result<Self::Output, ProductionError<Self::Error>> {
+        input_stream.parse(&self.0).map(|_| ())
+    }
+}
+
+impl<Rule> std::fmt::Display for AndPredicateRule<Rule> {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", ::utils::logs::SaveLevel::colored("AndPredicate"))
+    }
+}
+
+#[cfg(test)]
+parser_macros::asserts_parse_test! {
+    name: and_predicate_rule
+    rule: AndPredicateRule(TokenRule(Token1::default()))
+    {
+        input_stream: [Token1]
+        right_assert: Ok(())
+    }
+    {
+        input_stream: [Token2, Token1]
+        right_assert: Err(ProductionError::Token(()))
+    }
+    rule: SequenceRule((AndPredicateRule(TokenRule(Token1::default())), TokenRule(Token1::default())))
+    {
+        input_stream: [Token1, Token2]
+        right_assert: Ok(SeqOutput(((), Token1::default())))
+    }
+    {
+        input_stream: [Token2, Token1]
+        right_assert: Err(ProductionError::Token(SeqError2::V0(())))
+    }
+    rule: SequenceRule((TokenRule(Token2::default()), AndPredicateRule(TokenRule(Token1::default()))))
+    {
+        items: [Token1, Token2]
+        input_stream: [Token2]
+        right_assert: Err(ProductionError::EndStream)
+    }
+}
+
 pub struct SequenceRule<Tuple>(pub Tuple);
 
+/// С фичей `serde` сериализуется как кортеж.
 #[derive(Deref)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct SeqOutput<Tuple>(pub Tuple);
 
 use paste::paste;
@@ -49,8 +49,41 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for RepeatRule<Rep
         &self,
         input_stream: InputStream<IS>,
     ) -> Result<Self::Output, ProductionError<Self::Error>> {
-        Ok(from_fn(|| input_stream.parse(&self.rule).ok()).collect())
+        repeat(input_stream, &self.rule, usize::MAX).map(|(reps, _)| reps)
+    }
+}
+
+/// Разбирает `rule`, пока он успешен, но не больше `max` раз. Итерация, не сдвинувшая
+/// курсор, последняя (`true` во втором поле) или ошибка по [`crate::progress`];
+/// такую же ошибку из вложенного повтора `rule` повтор не глотает. Элемент пустой
+/// итерации остаётся в выходе, но в минимум не засчитывается, см. [`progressed`].
+fn repeat<IS: Promotable, Rule: TransferRule<IS>, Error>(
+    input_stream: InputStream<IS>,
+    rule: &Rule,
+    max: usize,
+) -> Result<(Vec<Rule::Output>, bool), ProductionError<Error>> {
+    let mut reps = vec![];
+    while reps.len() < max {
+        let start = input_stream.position();
+        match input_stream.parse(rule) {
+            Ok(v) => reps.push(v),
+            Err(ProductionError::ZeroProgressLoop(e)) => {
+                return Err(ProductionError::ZeroProgressLoop(e))
+            }
+            Err(_) => break,
+        }
+        if let Some(pos) = start.filter(|_| input_stream.position() == start) {
+            crate::progress::stalled(rule, pos)?;
+            return Ok((reps, true));
+        }
     }
+    Ok((reps, false))
+}
+
+/// Число итераций [`repeat`], сдвинувших курсор.
+#[inline]
+fn progressed<T>((reps, stalled): &(Vec<T>, bool)) -> usize {
+    reps.len() - *stalled as usize
 }
 
 impl std::fmt::Display for Repeat {
@@ -75,6 +108,24 @@ parser_macros::asserts_parse_test! {
         input_stream: [Token1, Token1, Token2, Token1, Token1, Token1]
         right_assert: Ok(vec![Token1::default(), Token1::default()])
     }
+    // пустая итерация завершает повтор
+    rule: RepeatRule {
+        rule: OptionalRule(TokenRule(Token1::default())),
+        marker: Repeat
+    }
+    {
+        input_stream: [Token1, Token1, Token2]
+        right_assert: Ok(vec![Some(Token1::default()), Some(Token1::default()), None])
+    }
+    rule: RepeatRule {
+        rule: OptionalRule(TokenRule(Token1::default())),
+        marker: Max::<3>
+    }
+    {
+        items: [Token1, Token2]
+        input_stream: [Token2]
+        right_assert: Ok(vec![None])
+    }
 }
 
 #[derive(std_reset::prelude::Default, Clone)]
@@ -135,6 +186,19 @@ parser_macros::asserts_parse_test! {
         input_stream: [Token1]
         right_assert: Err(ProductionError::Token(LessThanMin(2)))
     }
+    // пустая итерация в минимум не засчитывается
+    rule: RepeatRule {
+        rule: OptionalRule(TokenRule(Token1::default())),
+        marker: Min::<2>
+    }
+    {
+        input_stream: [Token1, Token2]
+        right_assert: Err(ProductionError::Token(LessThanMin(2)))
+    }
+    {
+        input_stream: [Token1, Token1, Token2]
+        right_assert: Ok(vec![Some(Token1::default()), Some(Token1::default()), None])
+    }
 }
 
 #[derive(Debug, std_reset::prelude::Default, Clone)]
@@ -156,14 +220,9 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for RepeatRule<SMi
         if self.min == 0 {
             todo!("ошибка что должно быть больше 0")
         }
-        let Ok(reps) = input_stream.parse(&RepeatRule {
-            rule: &self.rule,
-            marker: Repeat,
-        }) else {
-            unreachable!()
-        };
-        (reps.len() >= self.min)
-            .then_some(reps)
+        let reps = repeat(input_stream, &self.rule, usize::MAX)?;
+        (progressed(&reps) >= self.min)
+            .then_some(reps.0)
             .ok_or(ProductionError::Token(LessThanMin(self.min)))
     }
 }
@@ -253,12 +312,10 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for RepeatRule<SMa
         if self.max == 0 {
             todo!("ошибка что должно быть больше 0")
         }
-        let reps = (0..self.max)
-            .map_while(|_| input_stream.parse(&self.rule).ok())
-            .collect::<Vec<_>>();
+        let (reps, stalled) = repeat(input_stream, &self.rule, self.max)?;
 
         // если число Rule равно MAX, то парсим еще раз, чтобы проаверить чтобы Rule не было больше MAX
-        if reps.len() == self.max {
+        if reps.len() == self.max && !stalled {
             if input_stream.parse(&self.rule).is_ok() {
                 input_stream
                     .parse(&self.rule)
@@ -343,6 +400,14 @@ parser_macros::asserts_parse_test! {
         input_stream: [Token1, Token1, Token1, Token1]
         right_assert: Ok(vec![Token1::default(), Token1::default(), Token1::default(), Token1::default()])
     }
+    rule: RepeatRule {
+        rule: OptionalRule(TokenRule(Token1::default())),
+        marker: MinMax::<2, 5>
+    }
+    {
+        input_stream: [Token1, Token2]
+        right_assert: Err(ProductionError::Token(MinMaxRepeatError::LessThanMin(2)))
+    }
 }
 
 #[derive(Debug, std_reset::prelude::Default, Clone)]
@@ -369,17 +434,17 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS> for RepeatRule<SMi
             todo!("написать ошибку")
         }
 
-        let reps = (0..*self.range.end())
-            .map_while(|_| input_stream.parse(&self.rule).ok())
-            .collect::<Vec<_>>();
+        let reps = repeat(input_stream, &self.rule, *self.range.end())?;
+        let progressed = progressed(&reps);
+        let (reps, stalled) = reps;
 
-        if reps.len() < *self.range.start() {
+        if progressed < *self.range.start() {
             Err(ProductionError::Token(MinMaxRepeatError::LessThanMin(
                 *self.range.start(),
             )))
         } else {
             // если число Rule равно MAX, то парсим еще раз, чтобы проаверить чтобы Rule не было больше MAX
-            if reps.len() == *self.range.end() {
+            if reps.len() == *self.range.end() && !stalled {
                 input_stream
                     .parse(&self.rule)
                     .is_err()
@@ -490,6 +555,9 @@ impl<IS: Promotable, Rule: TransferRule<IS>> TransferRule<IS>
                 Ok(v) => vec.push(v),
                 Err(ProductionError::EndStream) => return Err(ProductionError::EndStream),
                 Err(ProductionError::Token(..)) => break,
+                Err(ProductionError::ZeroProgressLoop(e)) => {
+                    return Err(ProductionError::ZeroProgressLoop(e))
+                }
             }
         }
         if vec.len() != self.count {
@@ -62,6 +62,8 @@ pub(super) mod production {
     pub enum ProductionError<Error> {
         Token(Error),
         EndStream,
+        /// Повтор не сдвинулся с места, см. [`crate::progress`].
+        ZeroProgressLoop(crate::progress::ZeroProgressLoop),
     }
 
     impl<Error> ProductionError<Error> {
@@ -70,6 +72,7 @@ pub(super) mod production {
             match self {
                 ProductionError::Token(e) => ProductionError::Token(f(e)),
                 ProductionError::EndStream => ProductionError::EndStream,
+                ProductionError::ZeroProgressLoop(e) => ProductionError::ZeroProgressLoop(e),
             }
         }
     }
@@ -120,10 +120,16 @@ impl<IS, Rule: TransferRule<IS>> TransferRule<IS> for VecChoiceRule<Rule> {
         input_stream: InputStream<IS>,
     ) -> Result<Self::Output, ProductionError<Self::Error>> {
         let mut errs = Vec::default();
-        self.0
-            .iter()
-            .find_map(|v| v.transfer(input_stream).map_err(|e| errs.push(e)).ok())
-            .ok_or(ProductionError::Token(errs))
+        for v in &self.0 {
+            match v.transfer(input_stream) {
+                Ok(v) => return Ok(v),
+                Err(ProductionError::ZeroProgressLoop(e)) => {
+                    return Err(ProductionError::ZeroProgressLoop(e))
+                }
+                Err(e) => errs.push(e),
+            }
+        }
+        Err(ProductionError::Token(errs))
     }
 }
 
@@ -146,6 +152,7 @@ macro_rules! impl_seq {
         paste! {
             match $is.parse(&$s.0.$i) {
                 Ok(v) => return Ok(Self::Output::[<V $i>](v)),
+                Err(ProductionError::ZeroProgressLoop(e)) => return Err(ProductionError::ZeroProgressLoop(e)),
                 Err([<e $i>]) => impl_seq!(@arm $s $is {$($oth_i)*} {$($e_i)* [<e $i>]})
             }
         }
@@ -156,6 +163,7 @@ macro_rules! impl_seq {
     (@impl $($a:ident)+) => {
         paste! {
             #[derive(Debug, Clone, PartialEq)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             pub enum [<ChoiceOutput ${count($a)}>]<$($a),+> {
                 $( [<V ${index()}>]($a) ),+
             }
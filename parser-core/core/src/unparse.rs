@@ -0,0 +1,365 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Печать вывода обратно в текст. Правило знает то, чего нет в выводе: литералы пропущенных
+//! полей, разделители повторов и пробелы, поэтому текст печатает правило, а не вывод.
+//! `grammar!` с `#[derive(Unparse)]` реализует [`Unparse`] для сгенерированных правил и
+//! `Display` для их `*Output`.
+
+use crate::rules::*;
+
+pub trait Unparse<IS>: TransferRule<IS> {
+    /// Текст значения, которое вернул бы разбор этим правилом.
+    fn unparse(&self, output: &Self::Output, out: &mut String);
+
+    /// Текст без значения, для пропущенных полей: литералы как есть, повторы — минимальное
+    /// число раз, пробелы — один.
+    fn canonical(&self, out: &mut String);
+}
+
+/// Текст токена, см. [`Unparse`].
+pub trait UnparseToken<IS>: TokenRuleTrait<IS> {
+    fn unparse(&self, output: &Self::Output, out: &mut String);
+
+    fn canonical(&self, out: &mut String);
+}
+
+#[inline]
+pub fn unparse<IS, Rule: Unparse<IS>>(rule: &Rule, output: &Rule::Output) -> String {
+    let mut out = String::new();
+    rule.unparse(output, &mut out);
+    out
+}
+
+impl<IS, Rule: Unparse<IS>> Unparse<IS> for &Rule {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        (*self).unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        (*self).canonical(out)
+    }
+}
+
+impl<IS, T: UnparseToken<IS>> Unparse<IS> for TokenRule<T> {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0.unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out)
+    }
+}
+
+impl<IS, Label, Rule: Unparse<IS>> Unparse<IS> for NamedRule<Label, Rule> {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0.unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out)
+    }
+}
+
+impl<IS: Promotable, Rule: Unparse<IS> + Default> Unparse<IS> for Rec<Rule> {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.as_deref()
+            .unwrap_or(&Rule::default())
+            .unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.as_deref().unwrap_or(&Rule::default()).canonical(out)
+    }
+}
+
+impl<IS: Promotable, Rule: Unparse<IS, Output: Clone, Error: Clone> + Default> Unparse<IS>
+    for RecB<Rule>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0.unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out)
+    }
+}
+
+/// Опциональное значение без вывода печатается целиком: так пропущенный `Space` становится
+/// пробелом.
+impl<IS: Promotable, Rule: Unparse<IS>> Unparse<IS> for OptionalRule<Rule> {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        if let Some(output) = output {
+            self.0.unparse(output, out)
+        }
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out)
+    }
+}
+
+/// Предикаты не потребляют ввод.
+impl<IS: Promotable, Rule: TransferRule<IS>> Unparse<IS> for PositiveLookaheadRule<Rule> {
+    #[inline]
+    fn unparse(&self, _: &Self::Output, _: &mut String) {}
+
+    #[inline]
+    fn canonical(&self, _: &mut String) {}
+}
+
+impl<IS: Promotable, Rule: TransferRule<IS>> Unparse<IS> for NegativeLookaheadRule<Rule> {
+    #[inline]
+    fn unparse(&self, _: &Self::Output, _: &mut String) {}
+
+    #[inline]
+    fn canonical(&self, _: &mut String) {}
+}
+
+impl<IS: Promotable, Rule: TransferRule<IS>> Unparse<IS> for AndPredicateRule<Rule> {
+    #[inline]
+    fn unparse(&self, _: &Self::Output, _: &mut String) {}
+
+    #[inline]
+    fn canonical(&self, _: &mut String) {}
+}
+
+impl<IS: Promotable, Rule: Unparse<IS>> Unparse<IS> for VecSequenceRule<Rule> {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0
+            .iter()
+            .zip(output)
+            .for_each(|(rule, output)| rule.unparse(output, out))
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.iter().for_each(|rule| rule.canonical(out))
+    }
+}
+
+/// Минимальное число повторов, для [`Unparse::canonical`].
+pub trait MinRepeats {
+    fn min_repeats(&self) -> usize;
+}
+
+impl MinRepeats for Repeat {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        0
+    }
+}
+
+impl<const MIN: usize> MinRepeats for Min<MIN> {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        MIN
+    }
+}
+
+impl MinRepeats for SMin {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        self.min
+    }
+}
+
+impl<const MAX: usize> MinRepeats for Max<MAX> {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        0
+    }
+}
+
+impl MinRepeats for SMax {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        0
+    }
+}
+
+impl<const MIN: usize, const MAX: usize> MinRepeats for MinMax<MIN, MAX> {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        MIN
+    }
+}
+
+impl MinRepeats for SMinMax {
+    #[inline]
+    fn min_repeats(&self) -> usize {
+        *self.range.start()
+    }
+}
+
+impl<IS: Promotable, Marker: MinRepeats, Rule: Unparse<IS>> Unparse<IS> for RepeatRule<Marker, Rule>
+where
+    Self: TransferRule<IS, Output = Vec<Rule::Output>>,
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        output.iter().for_each(|v| self.rule.unparse(v, out))
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        (0..self.marker.min_repeats()).for_each(|_| self.rule.canonical(out))
+    }
+}
+
+/// Элементы через канонический разделитель: его значение в выводе не сохраняется.
+fn joined<IS, Rule: Unparse<IS>, Join: Unparse<IS>>(
+    rule: &Rule,
+    join: &Join,
+    output: &[Rule::Output],
+    out: &mut String,
+) {
+    for (i, v) in output.iter().enumerate() {
+        if i != 0 {
+            join.canonical(out)
+        }
+        rule.unparse(v, out)
+    }
+}
+
+fn joined_canonical<IS, Rule: Unparse<IS>, Join: Unparse<IS>>(
+    rule: &Rule,
+    join: &Join,
+    count: usize,
+    out: &mut String,
+) {
+    for i in 0..count {
+        if i != 0 {
+            join.canonical(out)
+        }
+        rule.canonical(out)
+    }
+}
+
+impl<IS: Promotable, Marker: MinRepeats + Clone, Rule: Unparse<IS>, Join: Unparse<IS>> Unparse<IS>
+    for JoinableRule<Marker, Rule, Join>
+where
+    Self: TransferRule<IS, Output = Vec<Rule::Output>>,
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        joined(&self.rule, &self.join, output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        let count = 1 + self.repeat_rule.min_repeats();
+        joined_canonical(&self.rule, &self.join, count, out)
+    }
+}
+
+impl<IS: Promotable, Rule: Unparse<IS>, Join: Unparse<IS>, const MIN: usize> Unparse<IS>
+    for MinJoinableRule<MIN, Rule, Join>
+where
+    Self: TransferRule<IS, Output = Vec<Rule::Output>>,
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        joined(&self.join_rule.rule, &self.join_rule.join, output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        joined_canonical(&self.join_rule.rule, &self.join_rule.join, MIN, out)
+    }
+}
+
+impl<IS: Promotable, Rule: Unparse<IS>, Join: Unparse<IS>> Unparse<IS>
+    for SMinJoinableRule<Rule, Join>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        joined(&self.join_rule.rule, &self.join_rule.join, output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        joined_canonical(&self.join_rule.rule, &self.join_rule.join, self.min, out)
+    }
+}
+
+impl<IS: Promotable, Start: Unparse<IS>, Body: Unparse<IS>, End: Unparse<IS>> Unparse<IS>
+    for WrapRule<Start, Body, End>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0.canonical(out);
+        self.1.unparse(output, out);
+        self.2.canonical(out);
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out);
+        self.1.canonical(out);
+        self.2.canonical(out);
+    }
+}
+
+use paste::paste;
+macro_rules! impl_unparse {
+    (@impl $($a:ident)+) => {
+        impl<IS: Promotable, $($a: Unparse<IS>),+> Unparse<IS> for SequenceRule<($($a),+)> {
+            #[inline]
+            fn unparse(&self, output: &Self::Output, out: &mut String) {
+                $( self.0.${index()}.unparse(&output.0.${index()}, out) ${ignore($a)}; )+
+            }
+
+            #[inline]
+            fn canonical(&self, out: &mut String) {
+                $( self.0.${index()}.canonical(out) ${ignore($a)}; )+
+            }
+        }
+
+        paste! {
+            impl_unparse!(@choice [<ChoiceOutput ${count($a)}>] $($a)+);
+        }
+    };
+    (@choice $output:ident $($a:ident)+) => {
+        paste! {
+            impl<IS: Promotable, $($a: Unparse<IS>),+> Unparse<IS> for ChoiceRule<($($a),+)> {
+                #[inline]
+                fn unparse(&self, output: &Self::Output, out: &mut String) {
+                    match output {
+                        $( $output::[<V ${index()}>](v) => self.0.${index()}.unparse(v, out) ${ignore($a)} ),+
+                    }
+                }
+
+                /// Первая альтернатива.
+                #[inline]
+                fn canonical(&self, out: &mut String) {
+                    self.0.0.canonical(out)
+                }
+            }
+        }
+    };
+}
+
+tuple_impl!(@type_count impl_unparse! @impl T T T T T T T T T T T T T T T T T T T T T T T T);
@@ -0,0 +1,316 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Обход типизированного вывода в духе `syn::visit`. `grammar!` с `#[derive(Visit)]`,
+//! `#[derive(VisitMut)]` или `#[derive(Fold)]` генерирует одноимённые трейты с методом на
+//! каждое правило-дерево; здесь — структурный обход того, что между ними: кортежей
+//! (и последовательностей, и кортежных структур), вариантов выбора, повторов и листьев.
+
+use crate::rules::*;
+
+/// Обход детей по ссылке. Узлы правил вызывают метод посетителя `V`, остальное — обход
+/// своих детей.
+pub trait Walk<V: ?Sized> {
+    fn walk(&self, v: &mut V);
+}
+
+pub trait WalkMut<V: ?Sized> {
+    fn walk_mut(&mut self, v: &mut V);
+}
+
+/// Пересборка значения: узлы правил отдаются методу `fold_*`, остальное собирается заново
+/// из свёрнутых детей.
+pub trait FoldWith<F: ?Sized>: Sized {
+    fn fold_with(self, f: &mut F) -> Self;
+}
+
+macro_rules! leaf {
+    ($($t:ty),* $(,)?) => {$(
+        impl<V: ?Sized> Walk<V> for $t {
+            #[inline]
+            fn walk(&self, _: &mut V) {}
+        }
+
+        impl<V: ?Sized> WalkMut<V> for $t {
+            #[inline]
+            fn walk_mut(&mut self, _: &mut V) {}
+        }
+
+        impl<F: ?Sized> FoldWith<F> for $t {
+            #[inline]
+            fn fold_with(self, _: &mut F) -> Self {
+                self
+            }
+        }
+    )*};
+}
+
+leaf!(
+    (),
+    bool,
+    char,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    f32,
+    f64,
+    String
+);
+
+impl<V: ?Sized> Walk<V> for &str {
+    #[inline]
+    fn walk(&self, _: &mut V) {}
+}
+
+impl<V: ?Sized> WalkMut<V> for &str {
+    #[inline]
+    fn walk_mut(&mut self, _: &mut V) {}
+}
+
+impl<F: ?Sized> FoldWith<F> for &str {
+    #[inline]
+    fn fold_with(self, _: &mut F) -> Self {
+        self
+    }
+}
+
+impl<V: ?Sized, T: Walk<V>> Walk<V> for Vec<T> {
+    #[inline]
+    fn walk(&self, v: &mut V) {
+        self.iter().for_each(|item| item.walk(v))
+    }
+}
+
+impl<V: ?Sized, T: WalkMut<V>> WalkMut<V> for Vec<T> {
+    #[inline]
+    fn walk_mut(&mut self, v: &mut V) {
+        self.iter_mut().for_each(|item| item.walk_mut(v))
+    }
+}
+
+impl<F: ?Sized, T: FoldWith<F>> FoldWith<F> for Vec<T> {
+    #[inline]
+    fn fold_with(self, f: &mut F) -> Self {
+        self.into_iter().map(|item| item.fold_with(f)).collect()
+    }
+}
+
+impl<V: ?Sized, T: Walk<V>, const N: usize> Walk<V> for [T; N] {
+    #[inline]
+    fn walk(&self, v: &mut V) {
+        self.iter().for_each(|item| item.walk(v))
+    }
+}
+
+impl<V: ?Sized, T: WalkMut<V>, const N: usize> WalkMut<V> for [T; N] {
+    #[inline]
+    fn walk_mut(&mut self, v: &mut V) {
+        self.iter_mut().for_each(|item| item.walk_mut(v))
+    }
+}
+
+impl<F: ?Sized, T: FoldWith<F>, const N: usize> FoldWith<F> for [T; N] {
+    #[inline]
+    fn fold_with(self, f: &mut F) -> Self {
+        self.map(|item| item.fold_with(f))
+    }
+}
+
+impl<V: ?Sized, T: Walk<V>> Walk<V> for Option<T> {
+    #[inline]
+    fn walk(&self, v: &mut V) {
+        if let Some(item) = self {
+            item.walk(v)
+        }
+    }
+}
+
+impl<V: ?Sized, T: WalkMut<V>> WalkMut<V> for Option<T> {
+    #[inline]
+    fn walk_mut(&mut self, v: &mut V) {
+        if let Some(item) = self {
+            item.walk_mut(v)
+        }
+    }
+}
+
+impl<F: ?Sized, T: FoldWith<F>> FoldWith<F> for Option<T> {
+    #[inline]
+    fn fold_with(self, f: &mut F) -> Self {
+        self.map(|item| item.fold_with(f))
+    }
+}
+
+impl<V: ?Sized, T: Walk<V>> Walk<V> for Box<T> {
+    #[inline]
+    fn walk(&self, v: &mut V) {
+        (**self).walk(v)
+    }
+}
+
+impl<V: ?Sized, T: WalkMut<V>> WalkMut<V> for Box<T> {
+    #[inline]
+    fn walk_mut(&mut self, v: &mut V) {
+        (**self).walk_mut(v)
+    }
+}
+
+impl<F: ?Sized, T: FoldWith<F>> FoldWith<F> for Box<T> {
+    #[inline]
+    fn fold_with(self, f: &mut F) -> Self {
+        Box::new((*self).fold_with(f))
+    }
+}
+
+use paste::paste;
+macro_rules! impl_walk {
+    (@impl $($a:ident)+) => {
+        impl<V: ?Sized, $($a: Walk<V>),+> Walk<V> for SeqOutput<($($a),+)> {
+            #[inline]
+            fn walk(&self, v: &mut V) {
+                $( self.0.${index()}.walk(v) ${ignore($a)}; )+
+            }
+        }
+
+        impl<V: ?Sized, $($a: WalkMut<V>),+> WalkMut<V> for SeqOutput<($($a),+)> {
+            #[inline]
+            fn walk_mut(&mut self, v: &mut V) {
+                $( self.0.${index()}.walk_mut(v) ${ignore($a)}; )+
+            }
+        }
+
+        impl<F: ?Sized, $($a: FoldWith<F>),+> FoldWith<F> for SeqOutput<($($a),+)> {
+            #[inline]
+            fn fold_with(self, f: &mut F) -> Self {
+                SeqOutput(($(self.0.${index()}.fold_with(f) ${ignore($a)}),+))
+            }
+        }
+
+        impl<V: ?Sized, $($a: Walk<V>),+> Walk<V> for ($($a,)+) {
+            #[inline]
+            fn walk(&self, v: &mut V) {
+                $( self.${index()}.walk(v) ${ignore($a)}; )+
+            }
+        }
+
+        impl<V: ?Sized, $($a: WalkMut<V>),+> WalkMut<V> for ($($a,)+) {
+            #[inline]
+            fn walk_mut(&mut self, v: &mut V) {
+                $( self.${index()}.walk_mut(v) ${ignore($a)}; )+
+            }
+        }
+
+        impl<F: ?Sized, $($a: FoldWith<F>),+> FoldWith<F> for ($($a,)+) {
+            #[inline]
+            fn fold_with(self, f: &mut F) -> Self {
+                ($(self.${index()}.fold_with(f) ${ignore($a)},)+)
+            }
+        }
+
+        paste! {
+            impl<V: ?Sized, $($a: Walk<V>),+> Walk<V> for [<ChoiceOutput ${count($a)}>]<$($a),+> {
+                #[inline]
+                fn walk(&self, v: &mut V) {
+                    match self {
+                        $( Self::[<V ${index()}>](item) => item.walk(v) ${ignore($a)} ),+
+                    }
+                }
+            }
+
+            impl<V: ?Sized, $($a: WalkMut<V>),+> WalkMut<V> for [<ChoiceOutput ${count($a)}>]<$($a),+> {
+                #[inline]
+                fn walk_mut(&mut self, v: &mut V) {
+                    match self {
+                        $( Self::[<V ${index()}>](item) => item.walk_mut(v) ${ignore($a)} ),+
+                    }
+                }
+            }
+
+            impl<F: ?Sized, $($a: FoldWith<F>),+> FoldWith<F> for [<ChoiceOutput ${count($a)}>]<$($a),+> {
+                #[inline]
+                fn fold_with(self, f: &mut F) -> Self {
+                    match self {
+                        $( Self::[<V ${index()}>](item) => Self::[<V ${index()}>](item.fold_with(f)) ${ignore($a)} ),+
+                    }
+                }
+            }
+        }
+    };
+}
+
+tuple_impl!(@type_count impl_walk! @impl T T T T T T T T T T T T T T T T T T T T T T T T);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Count(usize);
+
+    struct Item(usize);
+
+    impl Walk<Count> for Item {
+        fn walk(&self, v: &mut Count) {
+            v.0 += self.0
+        }
+    }
+
+    impl WalkMut<Count> for Item {
+        fn walk_mut(&mut self, v: &mut Count) {
+            v.0 += 1;
+            self.0 *= 10;
+        }
+    }
+
+    impl FoldWith<Count> for Item {
+        fn fold_with(self, f: &mut Count) -> Self {
+            f.0 += 1;
+            Item(self.0 + f.0)
+        }
+    }
+
+    #[test]
+    fn structural() {
+        let mut out = SeqOutput((
+            "a",
+            vec![Item(1), Item(2)],
+            Some(Box::new(ChoiceOutput2::<Item, &str>::V0(Item(3)))),
+            [Item(4)],
+        ));
+
+        let mut count = Count::default();
+        out.walk(&mut count);
+        assert_eq!(count.0, 10);
+
+        let mut count = Count::default();
+        out.walk_mut(&mut count);
+        assert_eq!(count.0, 4);
+        assert_eq!(out.0 .1[1].0, 20);
+
+        let out = out.fold_with(&mut Count::default());
+        let SeqOutput((_, items, Some(choice), [last])) = out else {
+            unreachable!()
+        };
+        assert_eq!(items.iter().map(|v| v.0).collect::<Vec<_>>(), [11, 22]);
+        assert!(matches!(*choice, ChoiceOutput2::V0(Item(33))));
+        assert_eq!(last.0, 44);
+    }
+}
@@ -15,7 +15,7 @@ use parser_core::{
     Promotable,
     rules::{OptionalRule, SeqOutput, SequenceRule},
 };
-use syn::LitStr;
+use syn::{LitBool, LitStr};
 use syn_parser::{InputStreamIter, field_token, rules::SynToken};
 
 pub fn choice_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
@@ -118,9 +118,10 @@ pub fn choice_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
         output_attrs,
         error_attrs,
         output_generics,
+        is_unparse,
     )) = {
         use crate::fields::Field;
-        field_token!(TransferRuleBound InputStreamBound OutputAttrs ErrorAttrs OutputGenerics);
+        field_token!(TransferRuleBound InputStreamBound OutputAttrs ErrorAttrs OutputGenerics Unparse);
         InputStreamIter::new(attr.clone())
             .parse(&<SequenceRule<(
                 OptionalRule<Field<TransferRuleBound, SynToken<TypeParamBound>>>,
@@ -128,6 +129,7 @@ pub fn choice_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
                 OptionalRule<Field<OutputAttrs, SynToken<VecAttrs>>>,
                 OptionalRule<Field<ErrorAttrs, SynToken<VecAttrs>>>,
                 OptionalRule<Field<OutputGenerics, SynToken<Generics>>>,
+                OptionalRule<Field<Unparse, SynToken<LitBool>>>,
             )>>::default())
             .unwrap()
     };
@@ -169,6 +171,53 @@ pub fn choice_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
+    let unparse_impl = is_unparse.filter(LitBool::value).map(|_| {
+        let unparse = quote!(abstract_parser::unparse::Unparse<__IS>);
+        let arms = non_only_rules_vars.clone().map(|(i, (var_ident, ty))| {
+            let i = Index::from(i);
+            quote!(__Output::#var_ident(v) => <#ty as #unparse>::unparse(&self.#i, v, out))
+        });
+        let canonical = non_only_rules_vars.clone().next().map(|(i, (_, ty))| {
+            let i = Index::from(i);
+            quote!(<#ty as #unparse>::canonical(&self.#i, out))
+        });
+        let type_params = generics.type_params().map(|v| &v.ident);
+
+        // `Display` только у негенерических правил: у них правило выводится из типа вывода
+        let display =
+            (generics.type_params().next().is_none() && output_generics.is_some()).then(|| {
+                quote! {
+                    impl #output_impl std::fmt::Display for __Output #output_type #output_where {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            f.write_str(&abstract_parser::unparse::unparse::<__IS, _>(
+                                &<__Rule #type_ as std::default::Default>::default(),
+                                self,
+                            ))
+                        }
+                    }
+                }
+            });
+
+        quote! {
+            impl<#(#bounded_generics),*> #unparse for __Rule #type_
+            where
+                #(#type_params: #unparse,)*
+            {
+                fn unparse(&self, output: &Self::Output, out: &mut String) {
+                    match output {
+                        #(#arms),*
+                    }
+                }
+
+                fn canonical(&self, out: &mut String) {
+                    #canonical
+                }
+            }
+
+            #display
+        }
+    });
+
     quote! {
         #vis use self::#mod_name::{Rule as #ident, Output as #output_name, Error as #error_name};
         #[allow(non_snake_case)]
@@ -204,6 +253,8 @@ pub fn choice_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
                         ))
                     }
                 }
+
+                #unparse_impl
             }
         }
 
@@ -11,7 +11,7 @@
 // 
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::quote;
 use syn::{
     Data, DataEnum, DataStruct, DataUnion, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed,
@@ -24,8 +24,18 @@ pub fn derive_bounds(attr: TokenStream, input: TokenStream) -> TokenStream {
         ident, data, attrs, ..
     } = &derive_intput;
 
+    let tmps = parse_macro_input!(attr as Tmps).0;
+    let serde_attrs = serde_attrs(&derive_intput, &tmps);
+    // чужие derive выводятся как обычно, без границ на поля
+    let forwarded = tmps
+        .iter()
+        .map(|v| &v.macros)
+        .filter(|v| !KNOWN.iter().any(|known| v == known))
+        .collect::<Vec<_>>();
+    let forwarded = (!forwarded.is_empty()).then(|| quote!(#[derive(#(#forwarded),*)]));
+
     let impl_ = {
-        parse_macro_input!(attr as Tmps).0.into_iter().map(
+        tmps.into_iter().map(
             |Tmp {
                  macros,
                  impl_,
@@ -166,10 +176,13 @@ pub fn derive_bounds(attr: TokenStream, input: TokenStream) -> TokenStream {
                             }
                         }
                     }
-                    v => {
-                        syn::Error::new_spanned(macros, format!("Not expect {v}"))
-                            .to_compile_error()
-                    }
+                    // выводятся через `#[derive]` в `serde_attrs`
+                    "Serialize" | "Deserialize" => quote!(),
+                    // у правила, а не у вывода: `sequence_struct` и `choice_rule`
+                    "Unparse" => quote!(),
+                    "Visit" | "VisitMut" | "Fold" => walk_impl(&derive_intput, &macros),
+                    "Hash" | "Eq" => hash_impl(&derive_intput, &macros),
+                    _ => quote!(),
                 }
             },
         )
@@ -177,6 +190,8 @@ pub fn derive_bounds(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     quote! {
         #(#attrs)*
+        #serde_attrs
+        #forwarded
         #derive_intput
 
         #(#impl_)*
@@ -184,6 +199,221 @@ pub fn derive_bounds(attr: TokenStream, input: TokenStream) -> TokenStream {
     .into()
 }
 
+/// Derive, которые `derive_bounds` выводит сам; остальные передаются `#[derive]`.
+const KNOWN: [&str; 11] = [
+    "Debug",
+    "PartialEq",
+    "Clone",
+    "Serialize",
+    "Deserialize",
+    "Unparse",
+    "Visit",
+    "VisitMut",
+    "Fold",
+    "Hash",
+    "Eq",
+];
+
+/// `Serialize` и `Deserialize` выводит сам serde, но с границами на типы полей: у выходных
+/// типов это проекции `<Rule as TransferRule<__IS>>::Output`, а не параметры.
+fn serde_attrs(input: &DeriveInput, tmps: &[Tmp]) -> TokenStream2 {
+    let derives = tmps
+        .iter()
+        .map(|v| &v.macros)
+        .filter(|v| *v == "Serialize" || *v == "Deserialize")
+        .collect::<Vec<_>>();
+    if derives.is_empty() {
+        return quote!();
+    }
+
+    let fields: Vec<_> = match &input.data {
+        Data::Struct(DataStruct { fields, .. }) => fields.iter().map(|v| &v.ty).collect(),
+        Data::Enum(DataEnum { variants, .. }) => variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|v| &v.ty))
+            .collect(),
+        Data::Union(..) => vec![],
+    };
+    let mut types = vec![];
+    for v in fields {
+        let v = quote!(#v).to_string();
+        if !types.contains(&v) {
+            types.push(v);
+        }
+    }
+
+    let bounds = derives.iter().map(|v| {
+        let bound = if *v == "Serialize" {
+            types
+                .iter()
+                .map(|v| format!("{v}: abstract_parser::serde::Serialize"))
+                .collect::<Vec<_>>()
+        } else {
+            input
+                .generics
+                .lifetimes()
+                .map(|v| format!("'de: {}", v.lifetime))
+                .chain(
+                    types
+                        .iter()
+                        .map(|v| format!("{v}: abstract_parser::serde::Deserialize<'de>")),
+                )
+                .collect()
+        };
+        let kind = Ident::new(&v.to_string().to_lowercase(), Span::call_site());
+        let bound = LitStr::new(&bound.join(", "), Span::call_site());
+        quote!(#kind = #bound)
+    });
+    quote! {
+        #[derive(#(abstract_parser::serde::#derives),*)]
+        #[serde(crate = "abstract_parser::serde", bound(#(#bounds),*))]
+    }
+}
+
+/// Структурный обход из `abstract_parser::visit` для типов без своего метода у посетителя:
+/// анонимных выборов и обобщённых деревьев. Границы — на типы полей, как у serde.
+fn walk_impl(input: &DeriveInput, macros: &Ident) -> TokenStream2 {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+
+    let (trait_, method, ref_) = match macros.to_string().as_str() {
+        "Visit" => (quote!(Walk), quote!(walk), quote!(&)),
+        "VisitMut" => (quote!(WalkMut), quote!(walk_mut), quote!(&mut)),
+        _ => (quote!(FoldWith), quote!(fold_with), quote!()),
+    };
+    let trait_ = quote!(abstract_parser::visit::#trait_);
+
+    let mut impl_generics = generics.clone();
+    impl_generics.params.push(syn::parse_quote!(__V: ?Sized));
+    let (impl_generics, ..) = impl_generics.split_for_impl();
+    let (_, type_generics, where_clause) = generics.split_for_impl();
+
+    let (types, body): (Vec<_>, _) = match data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            let members = fields.members().collect::<Vec<_>>();
+            (
+                fields.iter().map(|v| &v.ty).collect(),
+                if ref_.is_empty() {
+                    quote!(Self { #(#members: #trait_::#method(self.#members, v)),* })
+                } else {
+                    quote!(#(#trait_::#method(#ref_ self.#members, v);)*)
+                },
+            )
+        }
+        Data::Enum(DataEnum { variants, .. }) => {
+            let arms = variants.iter().map(|Variant { ident, fields, .. }| {
+                let args = (0..fields.len())
+                    .map(|i| Ident::new(&format!("arg{i}"), Span::call_site()))
+                    .collect::<Vec<_>>();
+                if ref_.is_empty() {
+                    quote!(Self::#ident(#(#args),*) => Self::#ident(#(#trait_::#method(#args, v)),*))
+                } else {
+                    quote!(Self::#ident(#(#args),*) => { #(#trait_::#method(#args, v);)* })
+                }
+            });
+            (
+                variants
+                    .iter()
+                    .flat_map(|v| v.fields.iter().map(|v| &v.ty))
+                    .collect(),
+                quote!(match self { #(#arms),* }),
+            )
+        }
+        Data::Union(..) => unreachable!(),
+    };
+    let predicates = where_clause
+        .map(|v| v.predicates.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let signature = if ref_.is_empty() {
+        quote!(fn #method(self, v: &mut __V) -> Self)
+    } else {
+        quote!(fn #method(#ref_ self, v: &mut __V))
+    };
+
+    quote! {
+        impl #impl_generics #trait_<__V> for #ident #type_generics
+        where
+            #(#predicates,)*
+            #(#types: #trait_<__V>,)*
+        {
+            #[inline]
+            #signature {
+                #body
+            }
+        }
+    }
+}
+
+/// `Hash` и `Eq` с границами на типы полей, как у serde: `#[derive]` потребовал бы их от
+/// `IS`.
+fn hash_impl(input: &DeriveInput, macros: &Ident) -> TokenStream2 {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        ..
+    } = input;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+
+    let (types, body): (Vec<_>, _) = match data {
+        Data::Struct(DataStruct { fields, .. }) => {
+            let members = fields.members();
+            (
+                fields.iter().map(|v| &v.ty).collect(),
+                quote!(#(std::hash::Hash::hash(&self.#members, state);)*),
+            )
+        }
+        Data::Enum(DataEnum { variants, .. }) => {
+            let arms = variants.iter().map(|Variant { ident, fields, .. }| {
+                let args = (0..fields.len())
+                    .map(|i| Ident::new(&format!("arg{i}"), Span::call_site()))
+                    .collect::<Vec<_>>();
+                quote!(Self::#ident(#(#args),*) => { #(std::hash::Hash::hash(#args, state);)* })
+            });
+            (
+                variants
+                    .iter()
+                    .flat_map(|v| v.fields.iter().map(|v| &v.ty))
+                    .collect(),
+                quote! {
+                    std::hash::Hash::hash(&std::mem::discriminant(self), state);
+                    match self { #(#arms),* }
+                },
+            )
+        }
+        Data::Union(..) => unreachable!(),
+    };
+    let predicates = where_clause
+        .map(|v| v.predicates.iter().collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    if macros == "Eq" {
+        return quote! {
+            impl #impl_generics std::cmp::Eq for #ident #type_generics
+            where
+                #(#predicates,)*
+                #(#types: std::cmp::Eq,)*
+            {}
+        };
+    }
+    quote! {
+        impl #impl_generics std::hash::Hash for #ident #type_generics
+        where
+            #(#predicates,)*
+            #(#types: std::hash::Hash,)*
+        {
+            #[inline]
+            fn hash<__H: std::hash::Hasher>(&self, state: &mut __H) {
+                #body
+            }
+        }
+    }
+}
+
 struct Tmps(Vec<Tmp>);
 
 impl Parse for Tmps {
@@ -31,7 +31,7 @@ pub fn test_token_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
 
     let token = parse_macro_input!(attr as Ident);
 
-    token_rule(
+    let rule: TokenStream2 = token_rule(
         quote! {
             InputStreamBound: abstract_parser::InputStreamTrait<&'a #token>
             Output: Self
@@ -43,6 +43,19 @@ pub fn test_token_rule(attr: TokenStream, input: TokenStream) -> TokenStream {
         }.into(),
         item_struct.to_token_stream().into()
     )
+    .into();
+
+    quote! {
+        #rule
+
+        impl abstract_parser::property::GenerateToken<#token> for #ident<'_> {
+            #[inline]
+            fn generate(&self, _: &mut abstract_parser::property::Rng, out: &mut Vec<#token>) {
+                out.push(#token::#ident)
+            }
+        }
+    }
+    .into()
 }
 
 /// ```rust,ignore
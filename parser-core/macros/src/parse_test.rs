@@ -157,18 +157,16 @@ pub fn assert_parse_test(input: TokenStream) -> TokenStream {
     assert_parse_test_quote(parse_macro_input!(input)).into()
 }
 
-fn assert_parse_test_quote(
-    AssertTest {
-        name,
-        rule,
-        assert:
-            TestAssert {
-                items,
-                input_stream: Items(input_stream_items),
-                right_assert,
-            },
-    }: AssertTest,
-) -> proc_macro2::TokenStream {
+fn assert_parse_test_quote(AssertTest { name, rule, assert }: AssertTest) -> proc_macro2::TokenStream {
+    let (items, Items(input_stream_items), right_assert) = match assert {
+        TestAssert::Assert {
+            items,
+            input_stream,
+            right_assert,
+        } => (items, input_stream, right_assert),
+        TestAssert::Property(property) => return property_test_quote(name, rule, property),
+    };
+
     let attr_content = items
         .map(|Items(items)| {
             let input_stream_items = input_stream_items.clone();
@@ -191,6 +189,48 @@ fn assert_parse_test_quote(
     }
 }
 
+/// Входы строятся по правилу или стратегией `strategy`; голое имя в `properties` —
+/// функция из `abstract_parser::property`, остальное — `Fn(&[Token]) -> Result<(), String>`.
+fn property_test_quote(
+    name: Ident,
+    rule: Expr,
+    PropertyAssert {
+        items: Items(items),
+        properties,
+        strategy,
+        cases,
+    }: PropertyAssert,
+) -> proc_macro2::TokenStream {
+    let items = items.collect::<Vec<_>>();
+    let properties = properties.into_iter().map(|v| match &v {
+        Expr::Path(path) if path.path.get_ident().is_some() => {
+            quote!(abstract_parser::property::#v(&rule))
+        }
+        _ => quote!(#v),
+    });
+    let cases = cases.map(|v| quote!(.cases(#v)));
+    let run = match strategy {
+        Some(strategy) => quote!(check.run(#strategy, property)),
+        None => quote!(check.run_rule(&rule, property)),
+    };
+
+    quote! {
+        #[abstract_parser::macros::parse_test(items: [#(#items),*] input_stream: [])]
+        #[test]
+        fn #name(input_stream: abstract_parser::InputStream) {
+            let _: &mut abstract_parser::InputStreamIter<Token> = input_stream;
+            let rule = #rule;
+            let properties: Vec<Box<dyn Fn(&[Token]) -> Result<(), String> + '_>> =
+                vec![#(Box::new(#properties)),*];
+            let property = |input: &[Token]| properties.iter().try_for_each(|v| v(input));
+            let check = abstract_parser::property::Check::default()
+                .alphabet([#(Token::#items),*])
+                #cases;
+            #run
+        }
+    }
+}
+
 struct AssertTest {
     name: Ident,
     rule: syn::Expr,
@@ -268,19 +308,54 @@ impl Parse for RuleTestAssert {
     }
 }
 
-struct TestAssert {
-    items: Option<Items>,
-    input_stream: Items,
-    right_assert: Expr,
+enum TestAssert {
+    Assert {
+        items: Option<Items>,
+        input_stream: Items,
+        right_assert: Expr,
+    },
+    Property(PropertyAssert),
 }
 
 impl Parse for TestAssert {
     #[inline]
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Ok(Self {
-            items: Field::opt_parse(input, "items")?,
-            input_stream: Field::strict_parse(input, "input_stream")?,
-            right_assert: Field::strict_parse(input, "right_assert")?,
-        })
+        let items = Field::opt_parse(input, "items")?;
+        match (items, Field::opt_parse(input, "properties")?) {
+            (Some(items), Some(Properties(properties))) => Ok(Self::Property(PropertyAssert {
+                items,
+                properties,
+                strategy: Field::opt_parse(input, "strategy")?,
+                cases: Field::opt_parse(input, "cases")?,
+            })),
+            (None, Some(_)) => Err(input.error("`properties` need `items` to build inputs from")),
+            (items, None) => Ok(Self::Assert {
+                items,
+                input_stream: Field::strict_parse(input, "input_stream")?,
+                right_assert: Field::strict_parse(input, "right_assert")?,
+            }),
+        }
+    }
+}
+
+struct PropertyAssert {
+    items: Items,
+    properties: Vec<Expr>,
+    strategy: Option<Expr>,
+    cases: Option<Expr>,
+}
+
+struct Properties(Vec<Expr>);
+
+impl Parse for Properties {
+    #[inline]
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        bracketed!(content in input);
+        Ok(Self(
+            <Punctuated<Expr, Token![,]>>::parse_terminated(&content)?
+                .into_iter()
+                .collect(),
+        ))
     }
 }
@@ -15,7 +15,7 @@ use parser_core::{
     Promotable,
     rules::{OptionalRule, SeqOutput, SequenceRule},
 };
-use syn::{LitStr, Token, TypeParamBound};
+use syn::{LitBool, LitStr, Token, TypeParamBound};
 use syn_parser::{
     InputStreamIter, field_token,
     rules::{IdentRule, SynToken},
@@ -70,15 +70,22 @@ pub fn sequence_struct(attr: TokenStream, input: TokenStream) -> TokenStream {
                 }
             });
 
-    let SeqOutput((tranfer_rule_bound, input_stream_bound, output_generics, tranfer_rule_generics)) = {
+    let SeqOutput((
+        tranfer_rule_bound,
+        input_stream_bound,
+        output_generics,
+        tranfer_rule_generics,
+        is_unparse,
+    )) = {
         use crate::fields::Field;
-        field_token!(TransferRuleBound InputStreamBound OutputGenerics TransferRuleGenerics);
+        field_token!(TransferRuleBound InputStreamBound OutputGenerics TransferRuleGenerics Unparse);
         InputStreamIter::new(attr.clone())
             .parse(&<SequenceRule<(
                 OptionalRule<Field<TransferRuleBound, SynToken<TypeParamBound>>>,
                 Field<InputStreamBound, SynToken<TypeParamBound>>,
                 OptionalRule<Field<OutputGenerics, SynToken<Generics>>>,
                 OptionalRule<Field<TransferRuleGenerics, SynToken<Generics>>>,
+                OptionalRule<Field<Unparse, SynToken<LitBool>>>,
             )>>::default())
             .unwrap()
     };
@@ -225,6 +232,79 @@ pub fn sequence_struct(attr: TokenStream, input: TokenStream) -> TokenStream {
         }
     };
 
+    let unparse_impl = is_unparse.filter(LitBool::value).map(|_| {
+        let unparse = quote!(abstract_parser::unparse::Unparse<__IS>);
+        let non_ignored = non_ignored_fields
+            .clone()
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        let parts = non_only_rule_fields
+            .clone()
+            .map(|(i, Field { ident, ty, .. })| {
+                let index = Index::from(i);
+                match non_ignored.iter().position(|v| *v == i) {
+                    None => quote!(<#ty as #unparse>::canonical(&self.#index, out);),
+                    Some(j) => {
+                        let output = match (ident, non_ignored.len()) {
+                            (Some(ident), _) => quote!(&output.#ident),
+                            (None, 1) => quote!(output),
+                            (None, _) => {
+                                let j = Index::from(j);
+                                quote!(&output.#j)
+                            }
+                        };
+                        quote!(<#ty as #unparse>::unparse(&self.#index, #output, out);)
+                    }
+                }
+            });
+        let canonical = non_only_rule_fields.clone().map(|(i, Field { ty, .. })| {
+            let i = Index::from(i);
+            quote!(<#ty as #unparse>::canonical(&self.#i, out);)
+        });
+        let type_params = generics.type_params().map(|v| &v.ident);
+        let predicates = where_
+            .map(|v| v.predicates.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        // `Display` только у негенерических правил: у них правило выводится из типа вывода
+        let display = match (&fields, &output_generics) {
+            (Fields::Named(..), Some(output_generics))
+                if generics.type_params().next().is_none() =>
+            {
+                let (output_impl, output_type, output_where) = output_generics.split_for_impl();
+                Some(quote! {
+                    impl #output_impl std::fmt::Display for __Output #output_type #output_where {
+                        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                            f.write_str(&abstract_parser::unparse::unparse::<__IS, _>(
+                                &<__Rule #type_ as std::default::Default>::default(),
+                                self,
+                            ))
+                        }
+                    }
+                })
+            }
+            _ => None,
+        };
+
+        quote! {
+            impl<#(#bounded_generics),*> #unparse for __Rule #type_
+            where
+                #(#predicates,)*
+                #(#type_params: #unparse,)*
+            {
+                fn unparse(&self, output: &Self::Output, out: &mut String) {
+                    #(#parts)*
+                }
+
+                fn canonical(&self, out: &mut String) {
+                    #(#canonical)*
+                }
+            }
+
+            #display
+        }
+    });
+
     quote! {
         #vis use self::#mod_name::{Rule as #ident, Output as #output_name};
         #[allow(non_snake_case)]
@@ -241,6 +321,8 @@ pub fn sequence_struct(attr: TokenStream, input: TokenStream) -> TokenStream {
 
                 #output_items
 
+                #unparse_impl
+
                 impl<#(#bounded_generics),*> abstract_parser::TransferRule<__IS> for __Rule #type_ #where_ {
                     type Output = #output;
                     type Error = #error;
@@ -0,0 +1,248 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Какие токены могут стоять на позиции входа — для дополнения в редакторе и подсказок
+//! «возможно, имелось в виду». Вход обрезается на позиции, и каждый токен, который разбор
+//! пробует на её месте, записывается вместе с путём правил и считается несовпавшим, а не
+//! концом входа, поэтому разбор проходит все альтернативы `ChoiceRule`/`VecChoiceRule`,
+//! необязательные ветви и продолжения повторов. Токены перехватываются в `sub_str` и
+//! `reg_expr`, так что работают и токены рантайм-грамматик.
+//!
+//! ```ignore
+//! let expected = expected(&Doc::default(), "a = ", 4);
+//! assert!(expected.iter().any(|v| v.name.as_deref() == Some("Number")));
+//! ```
+
+use crate::{
+    iter::{CharsIter, CharsIterTrait},
+    TransferRule,
+};
+use parser::{
+    logs::RuleName,
+    rules::{NamedRule, TokenRule},
+    Cursorable, Peekab, ProductionError, Promotable,
+};
+use std::{borrow::Cow, fmt::Display};
+
+/// Токен, ожидаемый на позиции.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    /// Имя правила токена: `Ident`, `Plus`, у литералов грамматики — `Token0`, ..
+    /// `None`, если токен разобран не отдельным правилом, а напрямую внутри другого.
+    pub name: Option<Cow<'static, str>>,
+    pub pattern: Pattern,
+    /// Правила от корня до токена, сам токен не входит.
+    pub path: Vec<Cow<'static, str>>,
+}
+
+/// Чему должен соответствовать токен.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    SubStr(Cow<'static, str>),
+    RegExpr(Cow<'static, str>),
+}
+
+impl Display for Pattern {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SubStr(v) => write!(f, r#""{v}""#),
+            Self::RegExpr(v) => write!(f, r#"r"{v}""#),
+        }
+    }
+}
+
+/// Токены, которые разбор `rule` пробует на `offset` в `src`, без повторов, в порядке
+/// попыток. Всё после `offset` отбрасывается; `offset` должен быть границей символа.
+pub fn expected<'src, Rule: TransferRule<'src, CompletionIter<'src>>>(
+    rule: &Rule,
+    src: &'src str,
+    offset: usize,
+) -> Vec<Expected> {
+    let mut iter = CompletionIter::new(&src[..offset]);
+    let _ = iter.parse(rule);
+    iter.expected
+}
+
+/// Вход, обрезанный на позиции дополнения: токены на его конце записываются и не
+/// совпадают, см. [`expected`].
+#[derive(Debug)]
+pub struct CompletionIter<'src> {
+    iter: CharsIter<'src>,
+    /// Открытые правила и токен ли правило.
+    path: Vec<(Cow<'static, str>, bool)>,
+    expected: Vec<Expected>,
+}
+
+impl<'src> CompletionIter<'src> {
+    #[inline]
+    pub fn new(src: &'src str) -> Self {
+        Self {
+            iter: CharsIter::new(src),
+            path: vec![],
+            expected: vec![],
+        }
+    }
+
+    /// Записанные на конце входа токены.
+    #[inline]
+    pub fn expected(&self) -> &[Expected] {
+        &self.expected
+    }
+}
+
+impl<'src> Promotable for CompletionIter<'src> {
+    #[inline]
+    fn parse<Rule: parser::TransferRule<Self>>(
+        &mut self,
+        rule: &Rule,
+    ) -> Result<Rule::Output, ProductionError<Rule::Error>> {
+        if parser::logs::trace_enabled() {
+            parser::logs::trace_logs(*self.cursor(), rule, || self.impl_parse(rule))
+        } else {
+            self.impl_parse(rule)
+        }
+    }
+
+    fn impl_parse<Rule: parser::TransferRule<Self>>(
+        &mut self,
+        rule: &Rule,
+    ) -> Result<Rule::Output, ProductionError<Rule::Error>> {
+        let old_cursor = *self.cursor();
+        self.path.push((rule.rule_name(), rule.is_token()));
+        let out = rule.transfer(self);
+        self.path.pop();
+        if !Rule::is_promotion(&out) {
+            *self.cursor() = old_cursor;
+        }
+        out
+    }
+}
+
+impl<'src> CharsIterTrait<'src> for CompletionIter<'src> {
+    #[inline]
+    fn as_str(&self) -> &'src str {
+        self.iter.as_str()
+    }
+
+    fn token_at_end(&mut self, pattern: impl FnOnce() -> Pattern) -> bool {
+        let (name, path) = match self.path.split_last() {
+            Some(((name, true), path)) => (Some(name.clone()), path),
+            _ => (None, &self.path[..]),
+        };
+        let expected = Expected {
+            name,
+            pattern: pattern(),
+            path: path.iter().map(|v| v.0.clone()).collect(),
+        };
+        if !self.expected.contains(&expected) {
+            self.expected.push(expected);
+        }
+        true
+    }
+}
+
+impl<'src> Cursorable for CompletionIter<'src> {
+    #[inline]
+    fn cursor(&mut self) -> &mut usize {
+        self.iter.cursor()
+    }
+}
+
+impl<'src> Iterator for CompletionIter<'src> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<'src> Peekab for CompletionIter<'src> {
+    #[inline]
+    fn peek_n<Error>(&mut self, byte_offset: usize) -> Result<Self::Item, ProductionError<Error>> {
+        self.iter.peek_n(byte_offset)
+    }
+}
+
+/// Правило — токен: его имя становится [`Expected::name`], а не частью пути.
+trait IsToken {
+    fn is_token(&self) -> bool;
+}
+
+impl<T> IsToken for T {
+    #[inline]
+    default fn is_token(&self) -> bool {
+        false
+    }
+}
+
+impl<T> IsToken for TokenRule<T> {
+    #[inline]
+    fn is_token(&self) -> bool {
+        true
+    }
+}
+
+impl<Label, Rule> IsToken for NamedRule<Label, Rule> {
+    #[inline]
+    fn is_token(&self) -> bool {
+        self.0.is_token()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{Chars, SSubStrToken};
+    use parser::rules::{OptionalRule, SequenceRule, VecChoiceRule};
+
+    fn sub_str<'src>(v: &str) -> TokenRule<Chars<'src, SSubStrToken>> {
+        TokenRule(Chars::new(SSubStrToken(v.into())))
+    }
+
+    fn patterns(expected: &[Expected]) -> Vec<(String, Vec<&str>)> {
+        expected
+            .iter()
+            .map(|v| {
+                let path = v.path.iter().map(|v| v.as_ref()).collect();
+                (v.pattern.to_string(), path)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn alternatives_and_optional() {
+        let rule = SequenceRule((
+            OptionalRule(sub_str("a")),
+            VecChoiceRule(vec![sub_str("x"), sub_str("y")]),
+        ));
+        let seq = "SequenceRule";
+        assert_eq!(
+            patterns(&expected(&rule, "a", 0)),
+            [
+                (r#""a""#.into(), vec![seq, "OptionalRule"]),
+                (r#""x""#.into(), vec![seq, "VecChoiceRule"]),
+                (r#""y""#.into(), vec![seq, "VecChoiceRule"]),
+            ]
+        );
+        assert_eq!(
+            patterns(&expected(&rule, "ab", 1)),
+            [
+                (r#""x""#.into(), vec![seq, "VecChoiceRule"]),
+                (r#""y""#.into(), vec![seq, "VecChoiceRule"]),
+            ]
+        );
+        // после удачного выбора ждать нечего
+        assert_eq!(expected(&rule, "ay", 2), []);
+    }
+}
@@ -0,0 +1,303 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Разностная проверка кэширования: один и тот же вход разбирается без кэша, через
+//! [`CachedIter`], через [`CachedRuleIter`] и правилом в [`CachedRule`]. Вывод, ошибка и
+//! конечный курсор должны совпасть; при расхождении по записанным событиям разбора
+//! ищется первое правило, чей результат на той же позиции отличается.
+
+use crate::{iter::CharsIter, InputStreamTrait, TransferRule};
+use parser::{
+    cached::{CachedIter, CachedRule, CachedRuleIter},
+    debug::{record, ParseEvent},
+};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug},
+};
+
+/// Способ разбора, который сравнивается с разбором без кэша.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Plain,
+    Cached,
+    /// `CachedRuleIter` без `CachedRule`: кэш не задействован, только проброс разбора.
+    RuleIter,
+    /// Всё правило в `CachedRule` поверх `CachedRuleIter`.
+    CachedRule,
+}
+
+/// Результат одного разбора: `Debug` вывода или ошибки (типы вывода у потоков разные),
+/// курсор и события.
+#[derive(Debug, Clone)]
+pub struct Run {
+    pub result: String,
+    pub cursor: usize,
+    pub events: Vec<ParseEvent>,
+}
+
+/// Итог вызова правила: `end` — только у успешного, неудачный откатывает курсор.
+pub type Outcome = (bool, Option<usize>);
+
+/// Первое правило, результат которого на позиции `pos` у бэкендов разный.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivergingRule {
+    pub rule: Cow<'static, str>,
+    pub pos: usize,
+    pub expected: Outcome,
+    /// `None` — у другого бэкенда на этом месте вызвано другое правило.
+    pub found: Option<Outcome>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub backend: Backend,
+    pub plain: Run,
+    pub other: Run,
+    pub rule: Option<DivergingRule>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:?} diverges from {:?}", self.backend, Backend::Plain)?;
+        if let Some(v) = &self.rule {
+            writeln!(
+                f,
+                "first diverging rule: {} @{}: {:?} != {:?}",
+                v.rule, v.pos, v.expected, v.found
+            )?;
+        }
+        writeln!(
+            f,
+            "plain cursor {}: {}",
+            self.plain.cursor, self.plain.result
+        )?;
+        write!(
+            f,
+            "{:?} cursor {}: {}",
+            self.backend, self.other.cursor, self.other.result
+        )
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Разбирает `src` всеми [`Backend`] и сравнивает с разбором без кэша. События
+/// записываются, только когда результаты уже разошлись: на больших входах их миллионы.
+pub fn differential<'src, Rule>(rule: &Rule, src: &'src str) -> Result<(), Box<Divergence>>
+where
+    Rule: TransferRule<'src, CharsIter<'src>, Output: Debug, Error: Debug>
+        + TransferRule<'src, CachedIter<CharsIter<'src>>, Output: Debug, Error: Debug>
+        + TransferRule<'src, CachedRuleIter<CharsIter<'src>>, Output: Debug, Error: Debug>,
+{
+    let plain = |events| run(CharsIter::new(src), rule, events);
+    let backends: [(Backend, &dyn Fn(bool) -> Run); 3] = [
+        (Backend::Cached, &|events| {
+            run(CachedIter::new(CharsIter::new(src)), rule, events)
+        }),
+        (Backend::RuleIter, &|events| {
+            run(CachedRuleIter::new(CharsIter::new(src)), rule, events)
+        }),
+        (Backend::CachedRule, &|events| {
+            run(
+                CachedRuleIter::new(CharsIter::new(src)),
+                &CachedRule(rule),
+                events,
+            )
+        }),
+    ];
+    let expected = plain(false);
+    for (backend, other) in IntoIterator::into_iter(backends) {
+        let found = other(false);
+        if expected.result == found.result && expected.cursor == found.cursor {
+            continue;
+        }
+        let (plain, other) = (plain(true), other(true));
+        return Err(Box::new(Divergence {
+            backend,
+            rule: diverging_rule(&plain.events, &other.events),
+            plain,
+            other,
+        }));
+    }
+    Ok(())
+}
+
+fn run<
+    'src,
+    IS: InputStreamTrait<'src>,
+    Rule: TransferRule<'src, IS, Output: Debug, Error: Debug>,
+>(
+    mut input_stream: IS,
+    rule: &Rule,
+    events: bool,
+) -> Run {
+    let (result, events) = if events {
+        record(|| input_stream.parse(rule))
+    } else {
+        (input_stream.parse(rule), vec![])
+    };
+    Run {
+        result: format!("{result:?}"),
+        cursor: *input_stream.cursor(),
+        events,
+    }
+}
+
+/// Вызов правила, восстановленный из событий. Попадание в кэш — лист без детей.
+pub(crate) struct Call<'e> {
+    pub(crate) rule: &'e str,
+    pub(crate) pos: usize,
+    pub(crate) outcome: Outcome,
+    pub(crate) cached: bool,
+    pub(crate) children: Vec<Call<'e>>,
+}
+
+pub(crate) fn calls(events: &[ParseEvent]) -> Vec<Call<'_>> {
+    let mut stack = vec![Vec::new()];
+    for event in events {
+        let call = match event {
+            ParseEvent::Enter { .. } => {
+                stack.push(Vec::new());
+                continue;
+            }
+            ParseEvent::Exit {
+                rule,
+                start,
+                end,
+                ok,
+                consumed,
+            } => Call {
+                rule,
+                pos: *start,
+                outcome: (*ok, consumed.then_some(*end)),
+                cached: false,
+                children: stack.pop().unwrap_or_default(),
+            },
+            ParseEvent::Cached { rule, pos, end, ok } => Call {
+                rule,
+                pos: *pos,
+                outcome: (*ok, *end),
+                cached: true,
+                children: vec![],
+            },
+        };
+        match stack.last_mut() {
+            Some(siblings) => siblings.push(call),
+            None => stack.push(vec![call]),
+        }
+    }
+    stack.into_iter().next().unwrap_or_default()
+}
+
+/// Деревья вызовов сравниваются попарно: до первого расхождения разборы делают одни и
+/// те же вызовы, кэш лишь заменяет поддерево листом. Дети проверяются раньше родителя,
+/// поэтому находится самое глубокое правило.
+fn diverging_call<'e>(plain: &[Call<'e>], other: &[Call<'e>]) -> Option<DivergingRule> {
+    let diverging = |call: &Call, found| DivergingRule {
+        rule: Cow::Owned(call.rule.to_owned()),
+        pos: call.pos,
+        expected: call.outcome,
+        found,
+    };
+    for (i, expected) in plain.iter().enumerate() {
+        let Some(mut found) = other.get(i) else {
+            return Some(diverging(expected, None));
+        };
+        // `CachedRule` оборачивает правило ещё одним вызовом.
+        while (found.rule, found.pos) != (expected.rule, expected.pos) {
+            match &found.children[..] {
+                [inner] => found = inner,
+                _ => return Some(diverging(expected, None)),
+            }
+        }
+        if !found.cached {
+            if let Some(v) = diverging_call(&expected.children, &found.children) {
+                return Some(v);
+            }
+        }
+        if found.outcome != expected.outcome {
+            return Some(diverging(expected, Some(found.outcome)));
+        }
+    }
+    None
+}
+
+fn diverging_rule(plain: &[ParseEvent], other: &[ParseEvent]) -> Option<DivergingRule> {
+    diverging_call(&calls(plain), &calls(other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parser::{
+        rules::{ChoiceRule, SequenceRule},
+        InputStream, ProductionError,
+    };
+    use std::cell::Cell;
+
+    thread_local!(static CALLS: Cell<usize> = const { Cell::new(0) });
+
+    /// Разбирает `a` только при первом вызове за разбор: кэш вернёт прежний успех.
+    #[derive(Debug, Default)]
+    struct Once;
+
+    impl<'src, IS: InputStreamTrait<'src>> parser::TransferRule<IS> for Once {
+        type Output = char;
+        type Error = ();
+
+        fn transfer(&self, input_stream: InputStream<IS>) -> Result<char, ProductionError<()>> {
+            let first = CALLS.with(|v| v.replace(v.get() + 1)) == 0;
+            input_stream
+                .next()
+                .filter(|&c| first && c == 'a')
+                .ok_or(ProductionError::Token(()))
+        }
+    }
+
+    /// Сбрасывает счётчик `Once` перед каждым разбором.
+    #[derive(Debug, Default)]
+    struct Fresh<Rule>(Rule);
+
+    impl<IS, Rule: parser::TransferRule<IS>> parser::TransferRule<IS> for Fresh<Rule> {
+        type Output = Rule::Output;
+        type Error = Rule::Error;
+
+        fn transfer(
+            &self,
+            input_stream: InputStream<IS>,
+        ) -> Result<Self::Output, ProductionError<Self::Error>> {
+            CALLS.with(|v| v.set(0));
+            self.0.transfer(input_stream)
+        }
+    }
+
+    #[test]
+    fn stateful_rule() {
+        let rule = Fresh(ChoiceRule((SequenceRule((Once, Once)), Once)));
+
+        let divergence = differential(&rule, "a").unwrap_err();
+        assert_eq!(divergence.backend, Backend::Cached);
+        assert_eq!((divergence.plain.cursor, divergence.other.cursor), (0, 1));
+        assert_eq!(
+            divergence.rule,
+            Some(DivergingRule {
+                rule: "Once".into(),
+                pos: 0,
+                expected: (false, None),
+                found: Some((true, Some(1))),
+            })
+        );
+
+        differential(&Fresh(Once), "a").unwrap();
+    }
+}
@@ -11,7 +11,10 @@
 // 
 
 use crate::{iter::CharsIterTrait, CharParser, InputStreamTrait, ParseError, TransferRule};
-use parser::{cached::CachedIter, Promotable};
+use parser::{
+    cached::{CachedIter, CachedRuleIter},
+    Promotable,
+};
 
 impl<'src, IS: InputStreamTrait<'src>> CharParser<'src> for CachedIter<IS> {}
 
@@ -21,3 +24,12 @@ impl<'src, IS: InputStreamTrait<'src>> CharsIterTrait<'src> for CachedIter<IS> {
         self.iter.as_str()
     }
 }
+
+impl<'src, IS: InputStreamTrait<'src>> CharParser<'src> for CachedRuleIter<IS> {}
+
+impl<'src, IS: InputStreamTrait<'src>> CharsIterTrait<'src> for CachedRuleIter<IS> {
+    #[inline]
+    fn as_str(&self) -> &'src str {
+        self.0.as_str()
+    }
+}
@@ -12,6 +12,7 @@
 
 mod cached;
 
+use crate::completion::Pattern;
 use parser::{Cursorable, Peekab, ProductionError};
 
 #[derive(Debug)]
@@ -32,6 +33,14 @@ impl<'src> CharsIter<'src> {
 
 pub trait CharsIterTrait<'src> {
     fn as_str(&self) -> &'src str;
+
+    /// Токен `pattern` пробуется на конце входа. `true` — вернуть несовпадение вместо
+    /// `EndStream`, чтобы разбор пробовал остальные ветви, см. [`completion`](crate::completion).
+    #[inline]
+    fn token_at_end(&mut self, pattern: impl FnOnce() -> Pattern) -> bool {
+        let _ = pattern;
+        false
+    }
 }
 
 impl<'src> CharsIterTrait<'src> for CharsIter<'src> {
@@ -15,12 +15,17 @@
     macro_metavar_expr_concat,
     trait_alias,
     const_trait_impl,
-    const_default
+    const_default,
+    specialization
 )]
+#![allow(incomplete_features)]
 
 pub extern crate macros;
 
+pub mod completion;
+pub mod differential;
 pub mod iter;
+pub mod minimize;
 pub mod rules;
 
 use crate::iter::{CharsIter, CharsIterTrait};
@@ -0,0 +1,253 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Сокращение входа, на котором разбор сбоит, до минимального воспроизведения. Сначала
+//! по структуре разбора удаляются целые элементы повторов (элемент вместе со следующим
+//! разделителем у `JoinableRule`), затем строки и отдельные символы.
+//!
+//! Тип правила грамматики зависит от времени жизни входа, поэтому правило создаётся
+//! заново для каждого кандидата — через [`minimize!`](crate::minimize!) или замыкания
+//! в [`minimize`].
+
+use crate::{
+    differential::{calls, differential, Call},
+    iter::CharsIter,
+    CharParser, TransferRule,
+};
+use parser::{
+    cached::{CachedIter, CachedRuleIter},
+    debug::{record, ParseEvent},
+    property::ddmin,
+};
+use std::{
+    cell::Cell,
+    fmt::Debug,
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
+
+/// Какой сбой должен сохраняться при сокращении.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// Разбор не удался; с `Some` — `Debug` ошибки содержит подстроку.
+    Error(Option<String>),
+    /// Разбор паникует; с `Some` — сообщение паники содержит подстроку.
+    Panic(Option<String>),
+    /// Вложенность правил больше предела. Настоящее переполнение стека обрывает процесс,
+    /// поэтому ищется вход, на котором оно близко.
+    Depth(usize),
+    /// Разбор с кэшем расходится с разбором без него, см. [`differential`].
+    Divergence,
+}
+
+impl Failure {
+    /// Воспроизводится ли сбой на `src`. Паника, кроме [`Failure::Panic`], сбоем не считается.
+    pub fn check<'src, Rule>(&self, rule: &Rule, src: &'src str) -> bool
+    where
+        Rule: TransferRule<'src, CharsIter<'src>, Output: Debug, Error: Debug>
+            + TransferRule<'src, CachedIter<CharsIter<'src>>, Output: Debug, Error: Debug>
+            + TransferRule<'src, CachedRuleIter<CharsIter<'src>>, Output: Debug, Error: Debug>,
+    {
+        match self {
+            Self::Divergence => quiet(|| differential(rule, src).is_err()).unwrap_or(false),
+            _ => self.check_parse(|| {
+                CharsIter::new(src)
+                    .full_parse(rule)
+                    .map(|_| ())
+                    .map_err(|e| format!("{:?}", e.parse_result))
+            }),
+        }
+    }
+
+    /// То же для любого разбора, например рантайм-грамматики: `parse` возвращает текст
+    /// ошибки. [`Failure::Divergence`] так не проверяется.
+    pub fn check_parse(&self, parse: impl Fn() -> Result<(), String>) -> bool {
+        let result = quiet(|| match self {
+            Self::Error(pattern) => parse().is_err_and(|e| contains(pattern, &e)),
+            Self::Panic(_) => {
+                let _ = parse();
+                false
+            }
+            Self::Depth(limit) => depth(&record(&parse).1) > *limit,
+            Self::Divergence => false,
+        });
+        match (self, result) {
+            (_, Ok(v)) => v,
+            (Self::Panic(pattern), Err(message)) => contains(pattern, &message),
+            (_, Err(_)) => false,
+        }
+    }
+}
+
+thread_local! {
+    /// Паники этого потока не печатаются, см. [`quiet`].
+    static SILENCED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Запуск без вывода паники; при панике — её сообщение. Хук паники процесса подменяется
+/// один раз и молчит только в потоках внутри `quiet`, паники остальных потоков (например,
+/// параллельных тестов) печатаются прежним хуком.
+fn quiet<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !SILENCED.get() {
+                hook(info)
+            }
+        }));
+    });
+    let silenced = SILENCED.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    SILENCED.set(silenced);
+    result.map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|v| v.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    })
+}
+
+#[inline]
+fn contains(pattern: &Option<String>, v: &str) -> bool {
+    pattern.as_deref().is_none_or(|p| v.contains(p))
+}
+
+/// Наибольшая вложенность правил в событиях разбора.
+pub fn depth(events: &[ParseEvent]) -> usize {
+    let (mut depth, mut max) = (0usize, 0);
+    for event in events {
+        match event {
+            ParseEvent::Enter { .. } => {
+                depth += 1;
+                max = max.max(depth);
+            }
+            ParseEvent::Exit { .. } => depth = depth.saturating_sub(1),
+            ParseEvent::Cached { .. } => {}
+        }
+    }
+    max
+}
+
+/// События разбора `src` без кэша: по ним строится структура для сокращения.
+pub fn events<'src, Rule: TransferRule<'src, CharsIter<'src>>>(
+    rule: &Rule,
+    src: &'src str,
+) -> Vec<ParseEvent> {
+    record(|| quiet(|| CharsIter::new(src).full_parse(rule).is_ok())).1
+}
+
+/// ```rust,ignore
+/// let input = std::fs::read_to_string("label.zpl")?;
+/// let minimal = minimize!(Grammar, &input, Failure::Error(None)).expect("input parses");
+/// ```
+#[macro_export]
+macro_rules! minimize {
+    ($rule:ty, $input:expr, $failure:expr) => {{
+        let failure: &$crate::minimize::Failure = &$failure;
+        $crate::minimize::minimize(
+            $input,
+            |src| failure.check(&<$rule>::default(), src),
+            |src| $crate::minimize::events(&<$rule>::default(), src),
+        )
+    }};
+}
+
+/// Минимальный вход, на котором `fails` ещё держится; `None`, если сбоя нет и на `input`.
+/// `events` даёт события разбора кандидата для структурного сокращения.
+pub fn minimize(
+    input: &str,
+    mut fails: impl FnMut(&str) -> bool,
+    mut events: impl FnMut(&str) -> Vec<ParseEvent>,
+) -> Option<String> {
+    if !fails(input) {
+        return None;
+    }
+    let mut input = input.to_string();
+    loop {
+        let len = input.len();
+        input = by_structure(input, &mut fails, &mut events);
+        input = by_parts(input.split_inclusive('\n').collect(), &mut fails);
+        input = by_parts(input.split_inclusive(|_: char| true).collect(), &mut fails);
+        if input.len() == len {
+            return Some(input);
+        }
+    }
+}
+
+/// Строки или символы по отдельности.
+fn by_parts(parts: Vec<&str>, fails: &mut impl FnMut(&str) -> bool) -> String {
+    ddmin(parts, |parts| fails(&parts.concat())).concat()
+}
+
+/// Для каждой группы соседних успешных вызовов, от самых многочисленных, ищется
+/// наименьшее подмножество элементов, которое сохраняет сбой. После удачного сокращения
+/// структура строится заново.
+fn by_structure(
+    mut input: String,
+    fails: &mut impl FnMut(&str) -> bool,
+    events: &mut impl FnMut(&str) -> Vec<ParseEvent>,
+) -> String {
+    'tree: loop {
+        let events = events(&input);
+        let mut groups = vec![];
+        units(&calls(&events), &mut groups);
+        groups.sort_by_key(|v| std::cmp::Reverse(v.len()));
+        for group in groups {
+            let kept = ddmin(group.clone(), |kept| fails(&without(&input, &group, kept)));
+            if kept.len() < group.len() {
+                input = without(&input, &group, &kept);
+                continue 'tree;
+            }
+        }
+        return input;
+    }
+}
+
+/// Элементы группы: от начала вызова до начала следующего соседа, последний — до своего
+/// конца. Так разделитель между элементами уходит вместе с элементом.
+fn units(calls: &[Call], groups: &mut Vec<Vec<Range<usize>>>) {
+    let ok = calls
+        .iter()
+        .filter_map(|v| Some((v.pos, v.outcome.1.filter(|_| v.outcome.0)?)))
+        .filter(|(start, end)| start < end)
+        .collect::<Vec<_>>();
+    let group = ok
+        .iter()
+        .enumerate()
+        .map(|(i, &(start, end))| match ok.get(i + 1) {
+            Some(&(next, _)) if next >= end => start..next,
+            _ => start..end,
+        })
+        .collect::<Vec<_>>();
+    if group.len() > 1 && group.windows(2).all(|v| v[0].end <= v[1].start) {
+        groups.push(group);
+    }
+    calls.iter().for_each(|v| units(&v.children, groups));
+}
+
+/// `input` без тех элементов `group`, которых нет в `kept`.
+fn without(input: &str, group: &[Range<usize>], kept: &[Range<usize>]) -> String {
+    let mut out = String::with_capacity(input.len());
+    let (mut pos, mut kept) = (0, kept.iter().peekable());
+    for range in group {
+        if kept.next_if(|v| *v == range).is_some() {
+            continue;
+        }
+        out.push_str(&input[pos..range.start]);
+        pos = range.end;
+    }
+    out.push_str(&input[pos..]);
+    out
+}
@@ -14,8 +14,10 @@ pub extern crate fancy_regex;
 
 pub use reg_expr_error::*;
 mod reg_expr_error;
+pub use unparse::*;
+mod unparse;
 
-use crate::InputStreamTrait;
+use crate::{completion::Pattern, InputStreamTrait};
 use fancy_regex::Regex;
 use parser::ProductionError;
 use std::{
@@ -29,6 +31,13 @@ pub trait TransferRule<'src, IS: InputStreamTrait<'src>> = parser::TransferRule<
 #[derive(Debug, std_reset::prelude::Default, Clone, PartialEq)]
 pub struct Chars<'src, Rule>(Rule, PhantomContravariantLifetime<'src>);
 
+impl<'src, Rule> Chars<'src, Rule> {
+    #[inline]
+    pub fn new(rule: Rule) -> Self {
+        Self(rule, Default::default())
+    }
+}
+
 impl<'src, IS: InputStreamTrait<'src>, Rule: TransferRule<'src, IS>> parser::TransferRule<IS>
     for Chars<'src, Rule>
 {
@@ -202,6 +211,23 @@ macro_rules! token {
     () => {};
 }
 
+/// Токен на конце входа: `EndStream`, если вход не просит иного, см.
+/// [`CharsIterTrait::token_at_end`](crate::iter::CharsIterTrait::token_at_end).
+#[allow(clippy::result_large_err)]
+fn at_end<'src, IS: InputStreamTrait<'src>>(
+    input_stream: parser::InputStream<IS>,
+    pattern: impl FnOnce() -> Pattern,
+) -> Result<&'src str, ProductionError<RegExprError<'src>>> {
+    if input_stream.token_at_end(pattern) {
+        Err(ProductionError::Token(RegExprError::Span {
+            src: input_stream.as_str(),
+            byte_range: 0..0,
+        }))
+    } else {
+        Err(ProductionError::EndStream)
+    }
+}
+
 pub use sub_str::*;
 mod sub_str {
     use super::*;
@@ -319,27 +345,53 @@ mod sub_str {
         type Output = &'src str;
         type Error = RegExprError<'src>;
 
+        #[inline]
         fn transfer(
             &self,
             input_stream: parser::InputStream<IS>,
         ) -> Result<Self::Output, ProductionError<Self::Error>> {
-            let src = input_stream.as_str();
-            if src.is_empty() {
-                Err(ProductionError::EndStream)
-            } else {
-                src.starts_with(self)
-                    .then(|| {
-                        *input_stream.cursor() += self.len();
-                        &src[..self.len()]
-                    })
-                    .ok_or(ProductionError::Token(RegExprError::Span {
-                        src,
-                        byte_range: {
-                            let (i, ch) = src.char_indices().next().unwrap();
-                            i..i + ch.len_utf8()
-                        },
-                    }))
-            }
+            sub_str_handle(input_stream, self)
+        }
+    }
+
+    /// Подстрока, известная только в рантайме.
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct SSubStrToken(pub String);
+
+    impl<'src, IS: InputStreamTrait<'src>> TokenRuleTrait<'src, IS> for SSubStrToken {
+        type Output = &'src str;
+        type Error = RegExprError<'src>;
+
+        #[inline]
+        fn transfer(
+            &self,
+            input_stream: parser::InputStream<IS>,
+        ) -> Result<Self::Output, ProductionError<Self::Error>> {
+            sub_str_handle(input_stream, &self.0)
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn sub_str_handle<'src, IS: InputStreamTrait<'src>>(
+        input_stream: parser::InputStream<IS>,
+        sub_str: &str,
+    ) -> Result<&'src str, ProductionError<RegExprError<'src>>> {
+        let src = input_stream.as_str();
+        if src.is_empty() {
+            at_end(input_stream, || Pattern::SubStr(sub_str.to_owned().into()))
+        } else {
+            src.starts_with(sub_str)
+                .then(|| {
+                    *input_stream.cursor() += sub_str.len();
+                    &src[..sub_str.len()]
+                })
+                .ok_or(ProductionError::Token(RegExprError::Span {
+                    src,
+                    byte_range: {
+                        let (i, ch) = src.char_indices().next().unwrap();
+                        i..i + ch.len_utf8()
+                    },
+                }))
         }
     }
 }
@@ -415,7 +467,19 @@ mod reg_expr {
     impl SRegExprToken {
         #[inline]
         pub fn new<'src>(reg_expr: &'src str) -> Self {
-            Self(Regex::new(&format!("^{}", reg_expr)).unwrap())
+            Self::try_new(reg_expr).unwrap()
+        }
+
+        #[inline]
+        #[allow(clippy::result_large_err)]
+        pub fn try_new(reg_expr: &str) -> Result<Self, fancy_regex::Error> {
+            Regex::new(&format!("^{}", reg_expr)).map(Self)
+        }
+
+        /// Выражение без якоря `^`.
+        #[inline]
+        pub fn reg_expr(&self) -> &str {
+            &self.0.as_str()[1..]
         }
     }
 
@@ -438,7 +502,9 @@ mod reg_expr {
     ) -> Result<&'src str, ProductionError<RegExprError<'src>>> {
         let src = input_stream.as_str();
         if src.is_empty() {
-            Err(ProductionError::EndStream)
+            at_end(input_stream, || {
+                Pattern::RegExpr(reg_expr.as_str()[1..].to_owned().into())
+            })
         } else {
             reg_expr
                 .find(src)
@@ -0,0 +1,438 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! [`parser::unparse`] для токенов: захваченный текст как есть, литералы из грамматики, для
+//! регулярных выражений — простейшая подходящая строка.
+
+use super::*;
+use parser::unparse::{self, Unparse};
+use std::{fmt::Display, iter::Peekable, str::Chars as StrChars};
+
+/// Текст токена, см. [`parser::unparse::Unparse`].
+pub trait UnparseToken<'src, IS>: TokenRuleTrait<'src, IS> {
+    fn unparse(&self, output: &Self::Output, out: &mut String);
+
+    fn canonical(&self, out: &mut String);
+}
+
+impl<'src, IS: InputStreamTrait<'src>, Rule: Unparse<IS>> Unparse<IS> for Chars<'src, Rule> {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0.unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out)
+    }
+}
+
+impl<'src, IS: InputStreamTrait<'src>, Rule: UnparseToken<'src, IS>> unparse::UnparseToken<IS>
+    for Chars<'src, Rule>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        self.0.unparse(output, out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.0.canonical(out)
+    }
+}
+
+impl<
+        'src,
+        IS: InputStreamTrait<'src>,
+        Token: UnparseToken<'src, IS, Output = &'src str>,
+        T: FromStr + Display,
+    > UnparseToken<'src, IS> for ParseToken<Token, T>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        out.push_str(&output.to_string())
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.token.canonical(out)
+    }
+}
+
+/// Значение unit-токена — он сам, текст берётся из грамматики.
+impl<
+        'src,
+        IS: InputStreamTrait<'src>,
+        Token: UnparseToken<'src, IS, Output = &'src str>,
+        T: SelfTokenTrait,
+    > UnparseToken<'src, IS> for SelfToken<Token, T>
+{
+    #[inline]
+    fn unparse(&self, _: &Self::Output, out: &mut String) {
+        self.token.canonical(out)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        self.token.canonical(out)
+    }
+}
+
+impl<'src, IS: InputStreamTrait<'src>, T: SubStrTokenTrait> UnparseToken<'src, IS>
+    for SubStrToken<T>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        out.push_str(output)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        out.push_str(T::SUB_STR)
+    }
+}
+
+impl<'src, IS: InputStreamTrait<'src>> UnparseToken<'src, IS> for &'static str {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        out.push_str(output)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        out.push_str(self)
+    }
+}
+
+impl<'src, IS: InputStreamTrait<'src>> UnparseToken<'src, IS> for SSubStrToken {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        out.push_str(output)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        out.push_str(&self.0)
+    }
+}
+
+impl<'src, IS: InputStreamTrait<'src>, T: RegExprTokenTrait> UnparseToken<'src, IS>
+    for RegExprTokenRule<T>
+{
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        out.push_str(output)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        out.push_str(&reg_expr_sample(T::REG_EXPR))
+    }
+}
+
+impl<'src, IS: InputStreamTrait<'src>> UnparseToken<'src, IS> for SRegExprToken {
+    #[inline]
+    fn unparse(&self, output: &Self::Output, out: &mut String) {
+        out.push_str(output)
+    }
+
+    #[inline]
+    fn canonical(&self, out: &mut String) {
+        out.push_str(&reg_expr_sample(self.reg_expr()))
+    }
+}
+
+/// Простейшая строка под регулярное выражение: квантификаторы — минимальное число раз,
+/// альтернативы и классы — первый вариант, `\s` — пробел. Предикаты пропускаются.
+#[inline]
+pub fn reg_expr_sample(reg_expr: &str) -> String {
+    reg_expr_pick(reg_expr, &mut First)
+}
+
+/// Строка под регулярное выражение, варианты и число повторов в которой выбирает `pick`.
+/// Предикаты пропускаются, поэтому результат может и не подойти.
+pub fn reg_expr_pick(reg_expr: &str, pick: &mut impl Pick) -> String {
+    let mut out = String::new();
+    alternation(&mut reg_expr.chars().peekable(), &mut out, pick);
+    out
+}
+
+/// Выбор при построении строки под регулярное выражение.
+pub trait Pick {
+    /// Номер варианта из `n > 0`.
+    fn pick(&mut self, n: usize) -> usize;
+
+    /// Число повторов из `min..=max`, `None` — без верхней границы.
+    fn repeat(&mut self, min: usize, max: Option<usize>) -> usize;
+}
+
+/// Первый вариант и наименьшее число повторов, см. [`reg_expr_sample`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct First;
+
+impl Pick for First {
+    #[inline]
+    fn pick(&mut self, _: usize) -> usize {
+        0
+    }
+
+    #[inline]
+    fn repeat(&mut self, min: usize, _: Option<usize>) -> usize {
+        min
+    }
+}
+
+fn alternation(chars: &mut Peekable<StrChars>, out: &mut String, pick: &mut impl Pick) {
+    let mut variants = vec![String::new()];
+    sequence(chars, &mut variants[0], pick);
+    while chars.next_if_eq(&'|').is_some() {
+        let mut variant = String::new();
+        sequence(chars, &mut variant, pick);
+        variants.push(variant);
+    }
+    let i = pick.pick(variants.len());
+    out.push_str(&variants[i]);
+}
+
+fn sequence(chars: &mut Peekable<StrChars>, out: &mut String, pick: &mut impl Pick) {
+    while let Some(&c) = chars.peek() {
+        if c == '|' || c == ')' {
+            return;
+        }
+        // каждый повтор строится заново, чтобы `[a-z]+` не давал одну букву подряд
+        let start = chars.clone();
+        let mut atom = String::new();
+        self::atom(chars, &mut atom, pick);
+        let (min, max) = quantifier(chars);
+        let count = pick.repeat(min, max);
+        if count > 0 {
+            out.push_str(&atom);
+        }
+        for _ in 1..count {
+            self::atom(&mut start.clone(), out, pick);
+        }
+    }
+}
+
+fn atom(chars: &mut Peekable<StrChars>, out: &mut String, pick: &mut impl Pick) {
+    let Some(c) = chars.next() else { return };
+    match c {
+        '(' => {
+            let mut group = String::new();
+            let is_lookaround = if chars.next_if_eq(&'?').is_some() {
+                // `(?:..)`, `(?=..)`, `(?!..)`, `(?<=..)`, `(?<!..)`, `(?<name>..)`
+                match chars.next() {
+                    Some('<') if matches!(chars.peek(), Some('=' | '!')) => {
+                        chars.next();
+                        true
+                    }
+                    Some('<') => {
+                        while chars.next().is_some_and(|c| c != '>') {}
+                        false
+                    }
+                    Some('=' | '!') => true,
+                    _ => false,
+                }
+            } else {
+                false
+            };
+            alternation(chars, &mut group, pick);
+            chars.next_if_eq(&')');
+            if !is_lookaround {
+                out.push_str(&group)
+            }
+        }
+        '[' => out.push(class(chars).pick(pick)),
+        '\\' => match chars.next().map(escape) {
+            Some(Some(Escape::Char(c))) => out.push(c),
+            Some(Some(Escape::Class(v))) => out.push(v.pick(pick)),
+            _ => (),
+        },
+        '.' => out.push(Class::negated(vec![]).pick(pick)),
+        '^' | '$' => (),
+        c => out.push(c),
+    }
+}
+
+/// Набор символов `[..]`, `\d`, `.` в виде диапазонов.
+struct Class {
+    negated: bool,
+    items: Vec<(char, char)>,
+}
+
+/// Кандидаты для отрицаний: сначала «удобные», затем остальной печатный ASCII.
+const ANY: &str = "a0 _x.bcdefghijklmnopqrstuvwyzABCDEFGHIJKLMNOPQRSTUVWXYZ123456789\
+                   !\"#$%&'()*+,-/:;<=>?@[\\]^`{|}~";
+
+impl Class {
+    #[inline]
+    fn new(items: Vec<(char, char)>) -> Self {
+        Self {
+            negated: false,
+            items,
+        }
+    }
+
+    #[inline]
+    fn negated(items: Vec<(char, char)>) -> Self {
+        Self {
+            negated: true,
+            items,
+        }
+    }
+
+    /// Первый диапазон и его первый символ у [`First`]; у отрицания — первый подходящий
+    /// из [`ANY`].
+    fn pick(&self, pick: &mut impl Pick) -> char {
+        if self.negated {
+            let candidates = ANY
+                .chars()
+                .filter(|c| !self.items.iter().any(|(start, end)| (start..=end).contains(&c)))
+                .collect::<Vec<_>>();
+            return match candidates.len() {
+                0 => 'a',
+                n => candidates[pick.pick(n)],
+            };
+        }
+        let Some(&(start, end)) = (match self.items.len() {
+            0 => None,
+            n => self.items.get(pick.pick(n)),
+        }) else {
+            return 'a';
+        };
+        let len = (end as u32).saturating_sub(start as u32) as usize + 1;
+        char::from_u32(start as u32 + pick.pick(len) as u32).unwrap_or(start)
+    }
+}
+
+enum Escape {
+    Char(char),
+    Class(Class),
+}
+
+fn escape(c: char) -> Option<Escape> {
+    const SPACE: [(char, char); 4] = [(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')];
+    const DIGIT: [(char, char); 1] = [('0', '9')];
+    const WORD: [(char, char); 4] = [('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+    Some(match c {
+        's' => Escape::Class(Class::new(SPACE.to_vec())),
+        'd' => Escape::Class(Class::new(DIGIT.to_vec())),
+        'w' => Escape::Class(Class::new(WORD.to_vec())),
+        'S' => Escape::Class(Class::negated(SPACE.to_vec())),
+        'D' => Escape::Class(Class::negated(DIGIT.to_vec())),
+        'W' => Escape::Class(Class::negated(WORD.to_vec())),
+        'n' => Escape::Char('\n'),
+        't' => Escape::Char('\t'),
+        'r' => Escape::Char('\r'),
+        'b' | 'B' | 'A' | 'z' | 'Z' | 'G' => return None,
+        c => Escape::Char(c),
+    })
+}
+
+fn class(chars: &mut Peekable<StrChars>) -> Class {
+    let negated = chars.next_if_eq(&'^').is_some();
+    let mut items: Vec<(char, char)> = vec![];
+    let mut first = true;
+    while let Some(c) = chars.next() {
+        if c == ']' && !first {
+            break;
+        }
+        first = false;
+        let c = match c {
+            '\\' => match chars.next().map(|c| (c, escape(c))) {
+                Some((_, Some(Escape::Class(v)))) if !v.negated => {
+                    items.extend(v.items);
+                    continue;
+                }
+                Some((_, Some(Escape::Char(c)))) | Some((c, _)) => c,
+                None => break,
+            },
+            c => c,
+        };
+        if chars.peek() == Some(&'-') {
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            if let Some(end) = lookahead.next().filter(|&v| v != ']') {
+                *chars = lookahead;
+                items.push((c, end));
+                continue;
+            }
+        }
+        items.push((c, c));
+    }
+    Class { negated, items }
+}
+
+fn quantifier(chars: &mut Peekable<StrChars>) -> (usize, Option<usize>) {
+    let range = match chars.peek() {
+        Some('*') => (0, None),
+        Some('?') => (0, Some(1)),
+        Some('+') => (1, None),
+        Some('{') => {
+            let mut lookahead = chars.clone();
+            lookahead.next();
+            let range = match (number(&mut lookahead), lookahead.next()) {
+                (Some(min), Some('}')) => (min, Some(min)),
+                (Some(min), Some(',')) => match (number(&mut lookahead), lookahead.next()) {
+                    (max, Some('}')) => (min, max),
+                    _ => return (1, Some(1)),
+                },
+                _ => return (1, Some(1)),
+            };
+            *chars = lookahead;
+            return lazy(chars, range);
+        }
+        _ => return (1, Some(1)),
+    };
+    chars.next();
+    lazy(chars, range)
+}
+
+fn number(chars: &mut Peekable<StrChars>) -> Option<usize> {
+    let mut v = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        v.push(c)
+    }
+    v.parse().ok()
+}
+
+/// Ленивые и сверхжадные квантификаторы выбирают из того же диапазона.
+#[inline]
+fn lazy(chars: &mut Peekable<StrChars>, range: (usize, Option<usize>)) -> (usize, Option<usize>) {
+    chars.next_if(|&c| c == '?' || c == '+');
+    range
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample() {
+        for (reg_expr, sample) in [
+            (r"\s+", " "),
+            (r",\s*", ","),
+            (r"[0-9]+", "0"),
+            (r"\^[A-Z]{2}", "^AA"),
+            (r#""[^"]*""#, r#""""#),
+            (r"[^a0 ]", "_"),
+            (r"(?:ab|cd)?e", "e"),
+            (r"(ab|cd)+", "ab"),
+            (r"x(?=y)", "x"),
+            (r"\(", "("),
+            (r"[a-z_][a-z0-9_]*", "a"),
+            (r"-?\d+(\.\d+)?", "0"),
+        ] {
+            assert_eq!(reg_expr_sample(reg_expr), sample, "{}", reg_expr);
+        }
+    }
+}
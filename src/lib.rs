@@ -18,5 +18,7 @@ pub use parser::*;
 #[cfg(feature = "grammar")]
 pub extern crate grammar;
 pub extern crate parsers;
+#[cfg(feature = "serde")]
+pub extern crate serde;
 pub extern crate shared_macros;
 pub extern crate utils;
@@ -0,0 +1,82 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(macro_metavar_expr_concat, phantom_variance_markers)]
+
+#[path = "../parser-core/core/benches/features/grammar.rs"]
+mod features;
+#[path = "../benches/zpl/grammar.rs"]
+mod zpl;
+
+use abstract_parser::parsers::chars::differential::differential;
+use grammar_feature_parsing::default_feature_rule;
+
+const ZPL: &str = include_str!("../benches/zpl/grammar.abs");
+/// Все `.abs` репозитория, кроме ZPL: их разбирают грамматика бенчмарка `features`
+/// и `default_feature_rule`. Типы, описанные в самих файлах, здесь не сравниваются.
+const ABS: [&str; 6] = [
+    include_str!("../grammar/feature/src/parsing/benches/cpcl/grammar.abs"),
+    include_str!("../grammar/feature/tests/grammar.abs"),
+    include_str!("../grammar/core/tree/tests/grammar.abs"),
+    include_str!("../examples/parser/grammar.abs"),
+    include_str!("../grammar/runtime/src/prelude.abs"),
+    include_str!("../cli/tests/data/list.abs"),
+];
+
+/// Вход целиком, с другими переводами строк и обрезанный в нескольких местах,
+/// чтобы сравнить и ошибки.
+fn inputs(src: &str) -> Vec<&str> {
+    let step = (src.len() / 7).max(1);
+    let mut out = vec![src];
+    out.extend(
+        (step..src.len())
+            .step_by(step)
+            .filter(|&i| src.is_char_boundary(i))
+            .map(|i| &src[..i]),
+    );
+    out
+}
+
+macro_rules! differential {
+    ($grammar:ident, $src:expr) => {
+        differential!(@rule $grammar::Grammar::default(), $src)
+    };
+    (@rule $rule:expr, $src:expr) => {{
+        let src: &str = $src;
+        let crlf = src.replace('\n', "\r\n");
+        let rule = $rule;
+        for input in inputs(src).into_iter().chain(inputs(&crlf)) {
+            if let Err(e) = differential(&rule, input) {
+                panic!("{}\ninput: {:?}", e, input);
+            }
+        }
+    }};
+}
+
+#[test]
+fn zpl() {
+    differential!(zpl, ZPL);
+}
+
+#[test]
+fn abs() {
+    for src in ABS {
+        differential!(features, src);
+    }
+}
+
+#[test]
+fn feature_rule() {
+    for src in ABS {
+        differential!(@rule default_feature_rule(), src);
+    }
+}
@@ -0,0 +1,104 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(macro_metavar_expr_concat, phantom_variance_markers)]
+
+#[path = "../parser-core/core/benches/features/grammar.rs"]
+mod features;
+#[path = "../benches/zpl/grammar.rs"]
+mod zpl;
+
+use abstract_parser::{
+    cached::CachedIter,
+    green::{lossless, GreenKind, SyntaxTree},
+    parsers::chars::{CharParser, InputStreamIter},
+};
+
+const ZPL: &str = include_str!("../benches/zpl/grammar.abs");
+const CPCL: &str = include_str!("../grammar/feature/src/parsing/benches/cpcl/grammar.abs");
+const FEATURES: &str = include_str!("../grammar/feature/tests/grammar.abs");
+
+/// Свойства дерева без потерь: печать совпадает со входом, дети вместе с trivia покрывают
+/// диапазон родителя без пропусков и наложений, в trivia только пропускаемые виды.
+fn check<K: GreenKind>(src: &str, tree: &SyntaxTree<K>) {
+    assert_eq!(tree.to_string(), src);
+    assert!(!tree.roots.is_empty());
+    for node in tree.roots.iter().flat_map(|v| v.preorder()) {
+        let mut text = String::new();
+        node.write(src, &mut text).unwrap();
+        assert_eq!(text, src[node.full_span()], "{:?}", node.kind);
+
+        if let (Some(first), Some(last)) = (node.children.first(), node.children.last()) {
+            assert_eq!(first.full_span().start, node.span.start, "{:?}", node.kind);
+            assert_eq!(last.full_span().end, node.span.end, "{:?}", node.kind);
+        }
+        for v in node.children.windows(2) {
+            assert_eq!(
+                v[0].full_span().end,
+                v[1].full_span().start,
+                "{:?}",
+                node.kind
+            );
+        }
+        for v in node.leading.iter().chain(&node.trailing) {
+            assert!(v.kind.is_none_or(GreenKind::is_trivia), "{:?}", v.kind);
+        }
+    }
+}
+
+macro_rules! round_trip {
+    ($grammar:path, $src:expr) => {{
+        use $grammar::{Grammar, SyntaxKind};
+
+        let src: &str = $src;
+        let (out, tree) = lossless::<SyntaxKind, _>(src, || {
+            CachedIter::new(InputStreamIter::new(src)).full_parse(&Grammar::default())
+        });
+        assert!(out.is_ok(), "{}", src);
+        check(src, &tree);
+        tree
+    }};
+}
+
+/// Те же файлы с другими пробелами: переводы строк `\r\n` и лишние пробелы в конце строк.
+fn respaced(src: &str) -> [String; 2] {
+    [
+        src.replace('\n', "\r\n"),
+        src.lines().map(|v| format!("{}  \n", v)).collect(),
+    ]
+}
+
+#[test]
+fn zpl() {
+    let tree = round_trip!(zpl, ZPL);
+    assert!(tree.roots.iter().flat_map(|v| v.preorder()).any(|v| v
+        .leading
+        .iter()
+        .chain(&v.trailing)
+        .any(|v| v.kind.is_some())));
+}
+
+#[test]
+fn cpcl() {
+    round_trip!(features, CPCL);
+    for src in respaced(CPCL) {
+        round_trip!(features, &src);
+    }
+}
+
+#[test]
+fn features() {
+    round_trip!(features, FEATURES);
+    for src in respaced(FEATURES) {
+        round_trip!(features, &src);
+    }
+}
@@ -0,0 +1,21 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+#![feature(macro_metavar_expr_concat, phantom_variance_markers)]
+
+#[path = "../parser-core/core/benches/features/grammar.rs"]
+mod features;
+
+use abstract_parser::grammar::feature::grammar::snapshot_tests;
+use features::Grammar;
+
+snapshot_tests!("corpus/*.abs", Grammar);
@@ -14,6 +14,7 @@
 #![feature(specialization)]
 
 pub mod logs;
+pub mod snapshot;
 
 pub mod stacker {
     pub use stacker::*;
@@ -0,0 +1,166 @@
+// 
+// abstract-parser — proprietary, source-available software (not open-source).    
+// Copyright (c) 2025 Abakar Letifov
+// (Летифов Абакар Замединович). All rights reserved.
+// 
+// Use of this Work is permitted only for viewing and internal evaluation,        
+// under the terms of the LICENSE file in the repository root.
+// If you do not or cannot agree to those terms, do not use this Work.
+// 
+// THE WORK IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND.
+// 
+
+//! Сравнение вывода с файлом-снимком. `UPDATE_SNAPSHOTS=1` перезаписывает снимки,
+//! отсутствующий снимок создаётся при первом запуске.
+
+use colored::Colorize;
+use std::{env, fmt::Write, fs, path::Path};
+
+/// Переменная окружения, при которой снимки перезаписываются вместо сравнения.
+pub const UPDATE_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Строк контекста вокруг изменений в диффе.
+const CONTEXT: usize = 2;
+
+#[track_caller]
+pub fn assert_snapshot(snap: impl AsRef<Path>, actual: &str) {
+    let snap = snap.as_ref();
+    let update = env::var_os(UPDATE_ENV).is_some_and(|v| v != "0");
+    match fs::read_to_string(snap) {
+        Ok(expected) if !update => {
+            if expected != actual {
+                panic!(
+                    "snapshot {} differs, run with {UPDATE_ENV}=1 to accept\n{}",
+                    snap.display(),
+                    diff(&expected, actual)
+                );
+            }
+        }
+        _ => {
+            if let Some(dir) = snap.parent() {
+                fs::create_dir_all(dir).unwrap_or_else(|e| panic!("{e} Path: {}.", dir.display()));
+            }
+            fs::write(snap, actual).unwrap_or_else(|e| panic!("{e} Path: {}.", snap.display()));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Построчный дифф с `CONTEXT` строками контекста, как у `diff -u`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let lines = diff_lines(
+        &expected.lines().collect::<Vec<_>>(),
+        &actual.lines().collect::<Vec<_>>(),
+    );
+    let changed = |i: usize| !matches!(lines.get(i), Some(Line::Same(_)) | None);
+    let mut out = String::new();
+    let (mut old, mut new) = (1, 1);
+    let mut skipped = true;
+    for (i, line) in lines.iter().enumerate() {
+        let near = (i.saturating_sub(CONTEXT)..=i + CONTEXT).any(changed);
+        if near {
+            if skipped {
+                writeln!(out, "{}", format!("@@ -{old} +{new} @@").cyan()).unwrap();
+            }
+            match line {
+                Line::Same(v) => writeln!(out, "  {v}"),
+                Line::Removed(v) => writeln!(out, "{}", format!("- {v}").red()),
+                Line::Added(v) => writeln!(out, "{}", format!("+ {v}").green()),
+            }
+            .unwrap();
+        }
+        skipped = !near;
+        match line {
+            Line::Same(_) => (old, new) = (old + 1, new + 1),
+            Line::Removed(_) => old += 1,
+            Line::Added(_) => new += 1,
+        }
+    }
+    out
+}
+
+/// Общие начало и конец отрезаются, середина сравнивается по наибольшей общей
+/// подпоследовательности: снимки меняются локально, и таблица остаётся маленькой.
+fn diff_lines<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = expected
+        .iter()
+        .zip(actual)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = expected[prefix..]
+        .iter()
+        .rev()
+        .zip(actual[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let (a, b) = (
+        &expected[prefix..expected.len() - suffix],
+        &actual[prefix..actual.len() - suffix],
+    );
+
+    // lcs[i][j] — длина общей подпоследовательности `a[i..]` и `b[j..]`
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out: Vec<_> = expected[..prefix].iter().map(|v| Line::Same(v)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            out.push(Line::Same(a[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(Line::Removed(a[i]));
+            i += 1;
+        } else {
+            out.push(Line::Added(b[j]));
+            j += 1;
+        }
+    }
+    out.extend(
+        expected[expected.len() - suffix..]
+            .iter()
+            .map(|v| Line::Same(v)),
+    );
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines() {
+        use Line::*;
+
+        assert_eq!(
+            diff_lines(&["a", "b", "c", "d"], &["a", "c", "x", "d"]),
+            [Same("a"), Removed("b"), Same("c"), Added("x"), Same("d")]
+        );
+        assert_eq!(diff_lines(&["a"], &[]), [Removed("a")]);
+    }
+
+    #[test]
+    fn context() {
+        colored::control::set_override(false);
+        let expected = (0..10).map(|i| format!("{i}\n")).collect::<String>();
+        let actual = expected.replace("5\n", "five\n");
+        assert_eq!(
+            diff(&expected, &actual),
+            "@@ -4 +4 @@\n  3\n  4\n- 5\n+ five\n  6\n  7\n"
+        );
+    }
+}